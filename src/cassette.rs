@@ -0,0 +1,355 @@
+//! Record/replay of API responses for deterministic offline tests.
+//!
+//! Hidden test mode: set `AXIOM_RECORD=<file>` to append every API response to
+//! a cassette, or `AXIOM_REPLAY=<file>` to serve responses from one instead of
+//! the network (replay wins if both are set). A cassette is JSON Lines, one
+//! `Interaction` per request, holding the raw response body so both plain and
+//! SSE responses replay through the same parsers as live traffic. Responses
+//! replay strictly in order; the recorded request is kept for debugging only.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::env_var;
+use crate::logging;
+use crate::models::MessageRequest;
+
+/// Cassette chosen by the environment, shared by every client in the process
+/// so a recording isn't truncated each time a client is created.
+static ENV_CASSETTE: OnceLock<Option<Arc<Cassette>>> = OnceLock::new();
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Whether the response was an SSE stream
+    pub stream: bool,
+    pub request: serde_json::Value,
+    /// Raw response body
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// A cassette file being recorded to or replayed from.
+#[derive(Debug)]
+pub struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+    state: Mutex<CassetteState>,
+}
+
+#[derive(Debug)]
+struct CassetteState {
+    /// Open file in record mode
+    file: Option<File>,
+    /// Loaded interactions in replay mode
+    interactions: Vec<Interaction>,
+    next: usize,
+}
+
+impl Cassette {
+    /// Cassette selected by `AXIOM_REPLAY` or `AXIOM_RECORD`, if either is set.
+    pub fn from_env() -> Result<Option<Arc<Self>>> {
+        if let Some(cassette) = ENV_CASSETTE.get() {
+            return Ok(cassette.clone());
+        }
+        let cassette = if let Ok(path) = env_var("AXIOM_REPLAY") {
+            Some(Arc::new(Self::replay(path)?))
+        } else if let Ok(path) = env_var("AXIOM_RECORD") {
+            Some(Arc::new(Self::record(path)?))
+        } else {
+            None
+        };
+        Ok(ENV_CASSETTE.get_or_init(|| cassette).clone())
+    }
+
+    /// Start a new recording at `path`, replacing any existing cassette.
+    pub fn record(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create cassette {}", path.display()))?;
+        logging::info(format!("Recording API responses to {}", path.display()));
+        Ok(Self {
+            mode: CassetteMode::Record,
+            path,
+            state: Mutex::new(CassetteState {
+                file: Some(file),
+                interactions: Vec::new(),
+                next: 0,
+            }),
+        })
+    }
+
+    /// Load a cassette from `path` for replay.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let interactions = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(idx, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Failed to parse cassette {} line {}",
+                        path.display(),
+                        idx + 1
+                    )
+                })
+            })
+            .collect::<Result<Vec<Interaction>>>()?;
+        logging::info(format!(
+            "Replaying {} API responses from {}",
+            interactions.len(),
+            path.display()
+        ));
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path,
+            state: Mutex::new(CassetteState {
+                file: None,
+                interactions,
+                next: 0,
+            }),
+        })
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Body of the next recorded response, checking it has the expected kind.
+    pub fn next_body(&self, stream: bool) -> Result<String> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to lock cassette"))?;
+        let index = state.next;
+        let Some(interaction) = state.interactions.get(index).cloned() else {
+            bail!(
+                "Failed to replay API response: cassette {} has no interaction #{}",
+                self.path.display(),
+                index + 1
+            );
+        };
+        if interaction.stream != stream {
+            bail!(
+                "Failed to replay API response: interaction #{} in {} is {}, expected {}",
+                index + 1,
+                self.path.display(),
+                kind_name(interaction.stream),
+                kind_name(stream)
+            );
+        }
+        state.next += 1;
+        Ok(interaction.body)
+    }
+
+    /// Append an interaction to the cassette file.
+    pub fn append(&self, request: &MessageRequest, stream: bool, body: String) -> Result<()> {
+        let interaction = Interaction {
+            stream,
+            request: serde_json::to_value(request)?,
+            body,
+        };
+        let line = serde_json::to_string(&interaction)?;
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to lock cassette"))?;
+        let file = state
+            .file
+            .as_mut()
+            .context("Failed to record API response: cassette is not recording")?;
+        writeln!(file, "{line}")
+            .with_context(|| format!("Failed to write cassette {}", self.path.display()))?;
+        state.interactions.push(interaction);
+        Ok(())
+    }
+}
+
+fn kind_name(stream: bool) -> &'static str {
+    if stream { "a stream" } else { "a message" }
+}
+
+/// Collects a streamed body as it is consumed and records it once the stream
+/// is dropped, so partially consumed streams are still captured.
+pub struct StreamRecorder {
+    cassette: Arc<Cassette>,
+    request: MessageRequest,
+    body: Vec<u8>,
+}
+
+impl StreamRecorder {
+    pub fn new(cassette: Arc<Cassette>, request: MessageRequest) -> Self {
+        Self {
+            cassette,
+            request,
+            body: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.body.extend_from_slice(chunk);
+    }
+}
+
+impl Drop for StreamRecorder {
+    fn drop(&mut self) {
+        let body = String::from_utf8_lossy(&self.body).into_owned();
+        if let Err(err) = self.cassette.append(&self.request, true, body) {
+            logging::warn(format!("{err:#}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AnthropicClient;
+    use crate::config::Config;
+    use crate::models::{ContentBlock, Delta, Message, StreamEvent};
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use tempfile::tempdir;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn request() -> MessageRequest {
+        MessageRequest {
+            model: "test-model".to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "hi".to_string(),
+                    cache_control: None,
+                }],
+            }],
+            max_tokens: 16,
+            ..MessageRequest::default()
+        }
+    }
+
+    fn message_text(content: &[ContentBlock]) -> String {
+        content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn stream_text(client: &AnthropicClient) -> String {
+        let mut stream = client
+            .create_message_stream(request())
+            .await
+            .expect("stream");
+        let mut text = String::new();
+        while let Some(event) = stream.next().await {
+            if let StreamEvent::ContentBlockDelta {
+                delta: Delta::TextDelta { text: chunk },
+                ..
+            } = event.expect("event")
+            {
+                text.push_str(&chunk);
+            }
+        }
+        text
+    }
+
+    #[tokio::test]
+    async fn recorded_responses_replay_offline() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(body_partial_json(json!({"stream": false})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "hello from the API"}],
+                "model": "test-model",
+                "stop_reason": "end_turn",
+                "stop_sequence": null,
+                "usage": {"input_tokens": 3, "output_tokens": 4}
+            })))
+            .mount(&server)
+            .await;
+        let sse = concat!(
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"streamed \"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"reply\"}}\n\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(body_partial_json(json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let tmp = tempdir().expect("tempdir");
+        let cassette_path = tmp.path().join("cassette.jsonl");
+        let live_config = Config {
+            api_key: Some("test-key".to_string()),
+            base_url: Some(server.uri()),
+            ..Config::default()
+        };
+        let recording = Cassette::record(&cassette_path).expect("record");
+        let client = AnthropicClient::with_cassette(
+            &live_config,
+            "test-model".to_string(),
+            Some(Arc::new(recording)),
+        )
+        .expect("client");
+        let live = client.create_message(request()).await.expect("message");
+        assert_eq!(message_text(&live.content), "hello from the API");
+        assert_eq!(stream_text(&client).await, "streamed reply");
+        drop(server);
+
+        // Nothing listens on the base URL now; every response must come from disk.
+        let offline_config = Config {
+            base_url: Some("http://127.0.0.1:9".to_string()),
+            ..Config::default()
+        };
+        let replay = Cassette::replay(&cassette_path).expect("replay");
+        let client = AnthropicClient::with_cassette(
+            &offline_config,
+            "test-model".to_string(),
+            Some(Arc::new(replay)),
+        )
+        .expect("client");
+        let replayed = client.create_message(request()).await.expect("message");
+        assert_eq!(message_text(&replayed.content), "hello from the API");
+        assert_eq!(stream_text(&client).await, "streamed reply");
+        let err = client.create_message(request()).await.unwrap_err();
+        assert!(err.to_string().contains("no interaction #3"), "{err}");
+    }
+
+    #[test]
+    fn replay_rejects_mismatched_kind() {
+        let tmp = tempdir().expect("tempdir");
+        let cassette_path = tmp.path().join("cassette.jsonl");
+        let recording = Cassette::record(&cassette_path).expect("record");
+        recording
+            .append(&request(), false, "{}".to_string())
+            .expect("append");
+
+        let replay = Cassette::replay(&cassette_path).expect("replay");
+        let err = replay.next_body(true).unwrap_err().to_string();
+        assert!(err.contains("is a message, expected a stream"), "{err}");
+        assert_eq!(replay.next_body(false).expect("body"), "{}");
+    }
+}
@@ -1,23 +1,74 @@
 //! HTTP clients for LLM providers.
 //!
 //! This module centralizes retry behavior, base URLs, and streaming helpers
-//! for the Axiom CLI's network requests.
+//! for the Axiom CLI's network requests. The client speaks either the Anthropic
+//! Messages API or, with `api_style = "openai"`, the `OpenAI` Chat Completions
+//! API (see `openai_compat`). Message requests can be recorded to
+//! or replayed from a cassette (see `cassette`) for offline tests, or served
+//! by the scripted mock backend (see `mock_backend`) for offline development.
 
+use std::fmt;
 use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Result;
 use futures_util::StreamExt;
-use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use serde_json::Value;
 
+use crate::cassette::{Cassette, CassetteMode, StreamRecorder};
 use crate::config::{Config, RetryPolicy};
-use crate::llm_client::{LlmClient, StreamEventBox};
+use crate::llm_client::{ApiStyle, LlmClient, StreamEventBox};
 use crate::logging;
+use crate::mock_backend::{self, MockBackend};
 use crate::models::{
-    ContentBlock, Message, MessageRequest, MessageResponse, ModelListResponse, StreamEvent,
+    ChatCompletionResponse, ContentBlock, Message, MessageRequest, MessageResponse,
+    ModelListResponse, StreamEvent,
 };
+use crate::openai_compat::{self, ChatStreamTranslator};
 
 // === Types ===
 
+/// Where message requests are sent, chosen by the `backend` config key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The Anthropic-compatible HTTP API.
+    #[default]
+    Api,
+    /// Scripted responses from `mock_script`; never touches the network.
+    Mock,
+}
+
+impl Backend {
+    /// Config value for this backend.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Mock => "mock",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "api" => Ok(Self::Api),
+            "mock" => Ok(Self::Mock),
+            other => Err(format!("Unknown backend '{other}' (expected api or mock)")),
+        }
+    }
+}
+
 pub fn test_connection_sync(base_url: &str, api_key: &str) -> Result<()> {
     let url = format!("{}/v1/messages", base_url.trim_end_matches('/'));
 
@@ -60,15 +111,20 @@ pub fn test_connection_sync(base_url: &str, api_key: &str) -> Result<()> {
     Ok(())
 }
 
-/// Client for Anthropic-compatible API requests.
+/// Client for Anthropic-compatible (or, per `api_style`, OpenAI-compatible) API requests.
 #[derive(Clone)]
 #[must_use]
 pub struct AnthropicClient {
     http_client: reqwest::Client,
     base_url: String,
+    api_style: ApiStyle,
     retry: RetryPolicy,
     #[allow(dead_code)]
     default_model: String,
+    /// Set by `AXIOM_RECORD`/`AXIOM_REPLAY`
+    cassette: Option<Arc<Cassette>>,
+    /// Set by `backend = "mock"`; answers every request instead of the API
+    mock: Option<Arc<MockBackend>>,
 }
 
 // === AnthropicClient ===
@@ -85,11 +141,44 @@ impl AnthropicClient {
 
     /// Create an Anthropic-compatible client pinned to a specific model.
     pub fn with_model(config: &Config, model: String) -> Result<Self> {
-        let base_url = config.anthropic_base_url();
-        let api_key = config.anthropic_api_key()?;
+        Self::with_cassette(config, model, Cassette::from_env()?)
+    }
+
+    /// Create a client that records to or replays from `cassette`. Replay and
+    /// the mock backend never touch the network, so they work without an API key.
+    pub fn with_cassette(
+        config: &Config,
+        model: String,
+        cassette: Option<Arc<Cassette>>,
+    ) -> Result<Self> {
+        let api_style = config.api_style();
+        let base_url = match api_style {
+            ApiStyle::Anthropic => config.anthropic_base_url(),
+            ApiStyle::OpenAi => config.openai_base_url(),
+        };
+        let mock = match config.backend() {
+            Backend::Mock => {
+                let script = config.mock_script_path();
+                logging::info(match &script {
+                    Some(path) => format!("Mock backend: serving {}", path.display()),
+                    None => "Mock backend: echoing messages".to_string(),
+                });
+                Some(Arc::new(MockBackend::load(script.as_deref())?))
+            }
+            Backend::Api => None,
+        };
+        let offline = mock.is_some()
+            || cassette
+                .as_ref()
+                .is_some_and(|c| c.mode() == CassetteMode::Replay);
+        let api_key = if offline {
+            config.anthropic_api_key().unwrap_or_default()
+        } else {
+            config.anthropic_api_key()?
+        };
         let retry = config.retry_policy();
 
-        logging::info(format!("Compatible base URL: {base_url}"));
+        logging::info(format!("Compatible base URL ({api_style}): {base_url}"));
         logging::info(format!(
             "Retry policy: enabled={}, max_retries={}, initial_delay={}s, max_delay={}s",
             retry.enabled, retry.max_retries, retry.initial_delay, retry.max_delay
@@ -97,8 +186,18 @@ impl AnthropicClient {
 
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert("x-api-key", HeaderValue::from_str(&api_key)?);
-        headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+        match api_style {
+            ApiStyle::Anthropic => {
+                headers.insert("x-api-key", HeaderValue::from_str(&api_key)?);
+                headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+            }
+            ApiStyle::OpenAi => {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {api_key}"))?,
+                );
+            }
+        }
 
         let http_client = reqwest::Client::builder()
             .default_headers(headers)
@@ -107,46 +206,119 @@ impl AnthropicClient {
         Ok(Self {
             http_client,
             base_url,
+            api_style,
             retry,
             default_model: model,
+            cassette,
+            mock,
+        })
+    }
+
+    fn cassette(&self, mode: CassetteMode) -> Option<&Arc<Cassette>> {
+        self.cassette.as_ref().filter(|c| c.mode() == mode)
+    }
+
+    /// Endpoint and JSON body for `request` in the configured wire format.
+    fn wire_request(&self, request: &MessageRequest) -> Result<(String, Value)> {
+        Ok(match self.api_style {
+            ApiStyle::Anthropic => (
+                format!("{}/v1/messages", self.base_url),
+                serde_json::to_value(request)?,
+            ),
+            ApiStyle::OpenAi => (
+                format!("{}/chat/completions", self.base_url),
+                openai_compat::chat_request(request),
+            ),
         })
     }
 
+    /// Parse a non-streaming response body in the configured wire format.
+    fn parse_message(&self, body: &str) -> Result<MessageResponse> {
+        Ok(match self.api_style {
+            ApiStyle::Anthropic => serde_json::from_str(body)?,
+            ApiStyle::OpenAi => openai_compat::message_response(serde_json::from_str::<
+                ChatCompletionResponse,
+            >(body)?),
+        })
+    }
+
+    /// Parse a streamed response body in the configured wire format.
+    fn event_stream(
+        &self,
+        chunks: impl futures_util::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin + Send + 'static,
+    ) -> StreamEventBox {
+        match self.api_style {
+            ApiStyle::Anthropic => Pin::from(Box::new(parse_sse_stream(chunks))),
+            ApiStyle::OpenAi => Pin::from(Box::new(parse_chat_stream(chunks))),
+        }
+    }
+
     /// Get the default model name
     #[allow(dead_code)] // For future model selection
     pub fn default_model(&self) -> &str {
         &self.default_model
     }
 
-    /// Create a non-streaming Anthropic-compatible message request.
+    /// Create a non-streaming message request.
     pub async fn create_message(&self, request: MessageRequest) -> Result<MessageResponse> {
-        let url = format!("{}/v1/messages", self.base_url);
         let mut request = request;
         request.stream = Some(false);
 
+        if let Some(mock) = &self.mock {
+            return Ok(mock.respond(&request));
+        }
+        if let Some(cassette) = self.cassette(CassetteMode::Replay) {
+            let body = cassette.next_body(false)?;
+            return self.parse_message(&body);
+        }
+
+        let (url, wire) = self.wire_request(&request)?;
         let response =
-            send_with_retry(&self.retry, || self.http_client.post(&url).json(&request)).await?;
-        Ok(response.json::<MessageResponse>().await?)
+            send_with_retry(&self.retry, || self.http_client.post(&url).json(&wire)).await?;
+        let body = response.text().await?;
+        if let Some(cassette) = self.cassette(CassetteMode::Record) {
+            cassette.append(&request, false, body.clone())?;
+        }
+        self.parse_message(&body)
     }
 
-    /// Create a streaming Anthropic-compatible message request.
-    pub async fn create_message_stream(
-        &self,
-        request: MessageRequest,
-    ) -> Result<impl futures_util::Stream<Item = Result<StreamEvent>>> {
-        let url = format!("{}/v1/messages", self.base_url);
+    /// Create a streaming message request.
+    pub async fn create_message_stream(&self, request: MessageRequest) -> Result<StreamEventBox> {
         let mut request = request;
         request.stream = Some(true);
 
+        // The mock always answers in the Anthropic format.
+        if let Some(mock) = &self.mock {
+            let body = mock_backend::sse_body(&mock.respond(&request));
+            return Ok(Pin::from(Box::new(parse_sse_stream(single_chunk(body)))));
+        }
+        if let Some(cassette) = self.cassette(CassetteMode::Replay) {
+            return Ok(self.event_stream(single_chunk(cassette.next_body(true)?)));
+        }
+
+        let (url, wire) = self.wire_request(&request)?;
         let response =
-            send_with_retry(&self.retry, || self.http_client.post(&url).json(&request)).await?;
+            send_with_retry(&self.retry, || self.http_client.post(&url).json(&wire)).await?;
 
-        Ok(parse_sse_stream(response.bytes_stream()))
+        if let Some(cassette) = self.cassette(CassetteMode::Record) {
+            let mut recorder = StreamRecorder::new(Arc::clone(cassette), request);
+            let chunks = response.bytes_stream().map(move |chunk| {
+                if let Ok(bytes) = &chunk {
+                    recorder.push(bytes);
+                }
+                chunk
+            });
+            return Ok(self.event_stream(chunks));
+        }
+        Ok(self.event_stream(response.bytes_stream()))
     }
 
     /// List available models from the API
     pub async fn list_models(&self) -> Result<Vec<String>> {
-        let url = format!("{}/v1/models", self.base_url);
+        let url = match self.api_style {
+            ApiStyle::Anthropic => format!("{}/v1/models", self.base_url),
+            ApiStyle::OpenAi => format!("{}/models", self.base_url),
+        };
 
         let response = self.http_client.get(&url).send().await?;
 
@@ -158,7 +330,12 @@ impl AnthropicClient {
 
         let result: ModelListResponse = response.json().await?;
 
-        Ok(result.models.into_iter().map(|m| m.id).collect())
+        Ok(result
+            .models
+            .into_iter()
+            .chain(result.data)
+            .map(|m| m.id)
+            .collect())
     }
 }
 
@@ -216,11 +393,18 @@ where
     }
 }
 
-/// Parse an SSE stream into structured stream events.
-fn parse_sse_stream(
+/// A whole response body as a one-chunk byte stream.
+fn single_chunk(
+    body: String,
+) -> impl futures_util::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin + Send + 'static {
+    futures_util::stream::iter([Ok(bytes::Bytes::from(body))])
+}
+
+/// Payloads of the `data:` lines in an SSE stream, ending at `[DONE]`.
+fn sse_data(
     stream: impl futures_util::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin,
-) -> impl futures_util::Stream<Item = Result<StreamEvent>> {
-    async_stream::try_stream! {
+) -> impl futures_util::Stream<Item = String> {
+    async_stream::stream! {
         let mut buffer = String::new();
         let mut stream = stream;
 
@@ -244,17 +428,7 @@ fn parse_sse_stream(
                         if data == "[DONE]" {
                             return;
                         }
-                        // Log raw SSE data for debugging
-                        if data.contains("tool_use") || data.contains("input_json") {
-                            logging::info(format!("SSE tool event: {}", data));
-                        }
-                        match serde_json::from_str::<StreamEvent>(data) {
-                            Ok(event) => yield event,
-                            Err(err) => {
-                                logging::warn(format!("Failed to parse SSE event: {err}"));
-                                logging::warn(format!("Raw SSE data: {data}"));
-                            }
-                        }
+                        yield data.to_string();
                     }
                 }
             }
@@ -262,11 +436,65 @@ fn parse_sse_stream(
     }
 }
 
+/// Parse an SSE stream into structured stream events.
+fn parse_sse_stream(
+    stream: impl futures_util::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin,
+) -> impl futures_util::Stream<Item = Result<StreamEvent>> {
+    async_stream::try_stream! {
+        let mut data = std::pin::pin!(sse_data(stream));
+
+        while let Some(data) = data.next().await {
+            // Log raw SSE data for debugging
+            if data.contains("tool_use") || data.contains("input_json") {
+                logging::info(format!("SSE tool event: {}", data));
+            }
+            match serde_json::from_str::<StreamEvent>(&data) {
+                Ok(event) => yield event,
+                Err(err) => {
+                    logging::warn(format!("Failed to parse SSE event: {err}"));
+                    logging::warn(format!("Raw SSE data: {data}"));
+                }
+            }
+        }
+    }
+}
+
+/// Parse an `OpenAI` chat completion SSE stream into Anthropic-style events.
+fn parse_chat_stream(
+    stream: impl futures_util::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin,
+) -> impl futures_util::Stream<Item = Result<StreamEvent>> {
+    async_stream::try_stream! {
+        let mut data = std::pin::pin!(sse_data(stream));
+        let mut translator = ChatStreamTranslator::default();
+
+        while let Some(data) = data.next().await {
+            match serde_json::from_str::<ChatCompletionResponse>(&data) {
+                Ok(chunk) => {
+                    for event in translator.push(chunk) {
+                        yield event;
+                    }
+                }
+                Err(err) => {
+                    logging::warn(format!("Failed to parse SSE chunk: {err}"));
+                    logging::warn(format!("Raw SSE data: {data}"));
+                }
+            }
+        }
+        for event in translator.finish() {
+            yield event;
+        }
+    }
+}
+
 // === Trait Implementations ===
 
 impl LlmClient for AnthropicClient {
     fn provider_name(&self) -> &'static str {
-        "anthropic"
+        if self.mock.is_some() {
+            Backend::Mock.as_str()
+        } else {
+            self.api_style.as_str()
+        }
     }
 
     fn model(&self) -> &str {
@@ -279,14 +507,6 @@ impl LlmClient for AnthropicClient {
     }
 
     async fn create_message_stream(&self, request: MessageRequest) -> Result<StreamEventBox> {
-        let url = format!("{}/v1/messages", self.base_url);
-        let mut request = request;
-        request.stream = Some(true);
-
-        let response =
-            send_with_retry(&self.retry, || self.http_client.post(&url).json(&request)).await?;
-
-        let stream = parse_sse_stream(response.bytes_stream());
-        Ok(Pin::from(Box::new(stream)))
+        AnthropicClient::create_message_stream(self, request).await
     }
 }
@@ -1,15 +1,51 @@
 //! Config commands: config, set, settings, yolo, trust, logout
 
 use super::CommandResult;
-use crate::config::clear_api_key;
+use crate::config::{clear_api_key, default_config_path, project_config_path};
 use crate::palette;
-use crate::settings::Settings;
-use crate::tui::app::{App, AppMode, OnboardingState};
-use crate::tui::approval::ApprovalMode;
+use crate::settings::{SettingSpec, Settings};
+use crate::tui::app::{App, AppAction, AppMode, OnboardingState};
+use crate::tui::approval::{ApprovalMode, ApprovalPolicy};
+use crate::tui::paste_burst::PasteBurstConfig;
+
+/// Dispatch `/config` and `/config edit`
+pub fn config(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg {
+        None | Some("") => show_config(app),
+        Some("edit") => edit_config(app),
+        Some(other) => CommandResult::error(format!(
+            "Unknown /config subcommand '{other}'. Usage: /config [edit]"
+        )),
+    }
+}
+
+/// Open config.toml in `$VISUAL`/`$EDITOR`, or print its path when neither is set
+fn edit_config(app: &App) -> CommandResult {
+    let Some(path) = app.config_path.clone().or_else(default_config_path) else {
+        return CommandResult::error("Failed to locate config file: no home directory");
+    };
+    match editor_command() {
+        Some(editor) => CommandResult::action(AppAction::EditConfig { editor, path }),
+        None => CommandResult::message(format!(
+            "No editor configured (set $VISUAL or $EDITOR). Config file:\n  {}\n\n\
+             Edit it directly, then run /reload.",
+            path.display()
+        )),
+    }
+}
+
+fn editor_command() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
 
 /// Display current configuration
 pub fn show_config(app: &mut App) -> CommandResult {
     let has_project_doc = app.project_doc.is_some();
+    let project_config = project_config_path(&app.workspace);
     let config_info = format!(
         "Session Configuration:\n\
          ─────────────────────────────\n\
@@ -22,7 +58,8 @@ pub fn show_config(app: &mut App) -> CommandResult {
          Trust mode:     {}\n\
          Auto-compact:   {}\n\
          Total tokens:   {}\n\
-         Project doc:    {}",
+         Project doc:    {}\n\
+         Project config: {}",
         app.mode.label(),
         app.model,
         app.workspace.display(),
@@ -37,15 +74,104 @@ pub fn show_config(app: &mut App) -> CommandResult {
         } else {
             "not found"
         },
+        if project_config.is_file() {
+            project_config.display().to_string()
+        } else {
+            "none".to_string()
+        },
     );
     CommandResult::message(config_info)
 }
 
-/// Show persistent settings
-pub fn show_settings(_app: &mut App) -> CommandResult {
-    match Settings::load() {
-        Ok(settings) => CommandResult::message(settings.display()),
-        Err(e) => CommandResult::error(format!("Failed to load settings: {e}")),
+/// Show persistent settings; `--all` lists every key with its type and default
+pub fn show_settings(_app: &mut App, arg: Option<&str>) -> CommandResult {
+    let settings = match Settings::load() {
+        Ok(settings) => settings,
+        Err(e) => return CommandResult::error(format!("Failed to load settings: {e}")),
+    };
+    match arg {
+        None | Some("") => CommandResult::message(settings.display()),
+        Some("--all") => CommandResult::message(settings.schema_table()),
+        Some(other) => CommandResult::error(format!(
+            "Unknown /settings option '{other}'. Usage: /settings [--all]"
+        )),
+    }
+}
+
+/// List themes, or switch to one and persist it to settings
+pub fn theme(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let name = match arg.map(str::trim) {
+        None | Some("") => {
+            let list = palette::THEME_NAMES
+                .iter()
+                .map(|name| {
+                    if *name == app.ui_theme.name {
+                        format!("  {name} (current)")
+                    } else {
+                        format!("  {name}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return CommandResult::message(format!(
+                "Themes:\n{list}\n\nUsage: /theme <name> or /theme toggle"
+            ));
+        }
+        Some("toggle") => toggled_theme(app.ui_theme.name),
+        Some(name) => {
+            let name = name.to_ascii_lowercase();
+            match palette::THEME_NAMES.iter().find(|t| **t == name) {
+                Some(name) => name,
+                None => {
+                    return CommandResult::error(format!(
+                        "Unknown theme '{name}'. Available: {}",
+                        palette::THEME_NAMES.join(", ")
+                    ));
+                }
+            }
+        }
+    };
+
+    app.ui_theme = palette::ui_theme(name);
+    app.mark_history_updated();
+
+    let saved = Settings::load().and_then(|mut settings| {
+        settings.set("theme", name)?;
+        settings.save()
+    });
+    match saved {
+        Ok(()) => CommandResult::message(format!("Theme set to {name}")),
+        Err(e) => CommandResult::error(format!(
+            "Theme set to {name} for this session, but failed to save it: {e}"
+        )),
+    }
+}
+
+/// Light flips to dark; anything else flips to light
+fn toggled_theme(current: &str) -> &'static str {
+    if current == "light" { "dark" } else { "light" }
+}
+
+/// Collapse or expand long output in every tool cell for this session
+pub fn collapse(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let collapsed = match arg.map(str::trim) {
+        None | Some("") => app.toggle_tool_output_collapse(),
+        Some(value) => {
+            let Ok(collapsed) = crate::settings::parse_bool(value) else {
+                return CommandResult::error(format!(
+                    "Unknown /collapse option '{value}'. Usage: /collapse [on|off]"
+                ));
+            };
+            if collapsed != app.collapse_tool_output {
+                app.toggle_tool_output_collapse();
+            }
+            collapsed
+        }
+    };
+    if collapsed {
+        CommandResult::message("Tool output collapsed (Ctrl+O to expand)")
+    } else {
+        CommandResult::message("Tool output expanded (Ctrl+O to collapse)")
     }
 }
 
@@ -69,7 +195,15 @@ pub fn set_config(app: &mut App, args: Option<&str>) -> CommandResult {
 
     let parts: Vec<&str> = args.splitn(2, ' ').collect();
     if parts.len() < 2 {
-        return CommandResult::error("Usage: /set <key> <value>");
+        let key = parts[0].to_lowercase();
+        return match SettingSpec::find(&key) {
+            Some(spec) => CommandResult::error(format!(
+                "Usage: /set {} <value> ({})",
+                spec.key,
+                spec.kind.hint()
+            )),
+            None => CommandResult::error("Usage: /set <key> <value>"),
+        };
     }
 
     let key = parts[0].to_lowercase();
@@ -114,19 +248,30 @@ pub fn set_config(app: &mut App, args: Option<&str>) -> CommandResult {
     }
 
     // Apply to current session
-    match key.as_str() {
-        "auto_compact" | "compact" => {
+    let canonical = SettingSpec::find(&key).map_or(key.as_str(), |spec| spec.key);
+    match canonical {
+        "auto_compact" => {
             app.auto_compact = settings.auto_compact;
         }
-        "show_thinking" | "thinking" => {
+        "auto_compact_threshold" => {
+            app.auto_compact_threshold = settings.auto_compact_threshold;
+        }
+        "compact_keep_pairs" => {
+            app.compact_keep_pairs = settings.compact_keep_pairs;
+        }
+        "show_thinking" => {
             app.show_thinking = settings.show_thinking;
             app.mark_history_updated();
         }
-        "show_tool_details" | "tool_details" => {
+        "show_tool_details" => {
             app.show_tool_details = settings.show_tool_details;
             app.mark_history_updated();
         }
-        "default_mode" | "mode" => {
+        "collapse_tool_output" => {
+            app.collapse_tool_output = settings.collapse_tool_output;
+            app.mark_history_updated();
+        }
+        "default_mode" => {
             let mode = match settings.default_mode.as_str() {
                 "agent" => AppMode::Agent,
                 "plan" => AppMode::Plan,
@@ -137,9 +282,25 @@ pub fn set_config(app: &mut App, args: Option<&str>) -> CommandResult {
             };
             app.set_mode(mode);
         }
-        "max_history" | "history" => {
+        "max_history" => {
             app.max_input_history = settings.max_input_history;
         }
+        "vim_mode" => {
+            app.vim_mode = settings.vim_mode;
+        }
+        "aggregate_subagents" => {
+            app.aggregate_subagents = settings.aggregate_subagents;
+        }
+        "redact_on_save" => {
+            app.redact_on_save = settings.redact_on_save;
+        }
+        "max_composer_lines" => {
+            app.max_composer_lines = settings.max_composer_lines;
+        }
+        "paste_burst_min_chars" | "paste_burst_interval_ms" | "paste_burst_idle_ms" => {
+            app.paste_burst
+                .set_config(PasteBurstConfig::from_settings(&settings));
+        }
         "default_model" => {
             if let Some(ref model) = settings.default_model {
                 app.model.clone_from(model);
@@ -192,6 +353,74 @@ pub fn trust(app: &mut App) -> CommandResult {
     CommandResult::message("Trust mode enabled - can access files outside workspace")
 }
 
+const APPROVALS_MODE_USAGE: &str = "Usage: /approvals mode <manual | auto-safe | yolo | deny>";
+
+/// List tools approved "for session", clear them so they prompt again, or set the policy
+pub fn approvals(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim).filter(|a| !a.is_empty()) {
+        None => {
+            if app.approval_session_approved.is_empty()
+                && app.approval_session_categories.is_empty()
+            {
+                return CommandResult::message("No tools are approved for this session.");
+            }
+            let mut categories: Vec<&str> = app
+                .approval_session_categories
+                .iter()
+                .map(|category| category.label())
+                .collect();
+            categories.sort_unstable();
+            let mut tools: Vec<&String> = app.approval_session_approved.iter().collect();
+            tools.sort();
+            let mut output = String::from("Approved for this session:\n");
+            for category in categories {
+                output.push_str(&format!("  all {category} tools\n"));
+            }
+            for tool in tools {
+                output.push_str(&format!("  {tool}\n"));
+            }
+            output.push_str("\nUse /approvals clear to ask again.");
+            CommandResult::message(output)
+        }
+        Some("clear") => {
+            let count = app.approval_session_approved.len();
+            let categories = app.approval_session_categories.len();
+            app.approval_session_approved.clear();
+            app.approval_session_categories.clear();
+            match (count, categories) {
+                (0, 0) => CommandResult::message("No session approvals to clear."),
+                (count, 0) => CommandResult::message(format!(
+                    "Cleared session approvals for {count} tool(s); they will ask again."
+                )),
+                (count, categories) => CommandResult::message(format!(
+                    "Cleared session approvals for {count} tool(s) and {categories} category(ies); they will ask again."
+                )),
+            }
+        }
+        Some(rest) if rest.split_whitespace().next() == Some("mode") => {
+            let value = rest["mode".len()..].trim();
+            if value.is_empty() {
+                return CommandResult::message(format!(
+                    "Approval policy: {}\n{APPROVALS_MODE_USAGE}",
+                    app.approval_policy().label()
+                ));
+            }
+            match ApprovalPolicy::parse(value) {
+                Some(policy) => {
+                    app.set_approval_policy(policy);
+                    CommandResult::message(format!("Approval policy: {}", policy.label()))
+                }
+                None => CommandResult::error(format!(
+                    "Unknown approval policy: {value}. {APPROVALS_MODE_USAGE}"
+                )),
+            }
+        }
+        Some(other) => CommandResult::error(format!(
+            "Unknown /approvals option: {other}. Usage: /approvals [clear | mode <policy>]"
+        )),
+    }
+}
+
 /// Logout - clear API key and return to onboarding
 pub fn logout(app: &mut App) -> CommandResult {
     match clear_api_key() {
@@ -211,24 +440,108 @@ mod tests {
     use crate::config::Config;
     use crate::tui::app::{App, TuiOptions};
     use crate::tui::approval::ApprovalMode;
-    use std::path::PathBuf;
 
     fn create_test_app() -> App {
-        let options = TuiOptions {
-            model: "test-model".to_string(),
-            workspace: PathBuf::from("."),
-            allow_shell: false,
-            max_subagents: 1,
-            skills_dir: PathBuf::from("."),
-            memory_path: PathBuf::from("memory.md"),
-            notes_path: PathBuf::from("notes.txt"),
-            mcp_config_path: PathBuf::from("mcp.json"),
-            use_memory: false,
-            start_in_agent_mode: false,
-            yolo: false,
-            resume_session_id: None,
-        };
-        App::new(options, &Config::default())
+        App::new(TuiOptions::for_test("."), &Config::default())
+    }
+
+    #[test]
+    fn approvals_lists_and_clears_session_approvals() {
+        let mut app = create_test_app();
+        let result = approvals(&mut app, None);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("No tools are approved for this session.")
+        );
+
+        app.approval_session_approved
+            .insert("write_file".to_string());
+        app.approval_session_approved
+            .insert("exec_shell".to_string());
+        app.approval_session_categories
+            .insert(crate::tui::approval::ToolCategory::Safe);
+        let listed = approvals(&mut app, None).message.expect("message");
+        assert!(listed.contains("  all Safe tools\n  exec_shell\n  write_file\n"));
+
+        let result = approvals(&mut app, Some("clear"));
+        assert_eq!(
+            result.message.as_deref(),
+            Some(
+                "Cleared session approvals for 2 tool(s) and 1 category(ies); they will ask again."
+            )
+        );
+        assert!(app.approval_session_approved.is_empty());
+        assert!(app.approval_session_categories.is_empty());
+
+        let result = approvals(&mut app, Some("revoke"));
+        assert!(
+            result
+                .message
+                .unwrap()
+                .contains("Unknown /approvals option")
+        );
+    }
+
+    #[test]
+    fn collapse_toggles_and_accepts_explicit_state() {
+        let mut app = create_test_app();
+        app.collapse_tool_output = true;
+
+        let result = collapse(&mut app, None);
+        assert!(!app.collapse_tool_output);
+        assert!(result.message.unwrap().contains("expanded"));
+
+        collapse(&mut app, Some("off"));
+        assert!(!app.collapse_tool_output);
+        collapse(&mut app, Some("on"));
+        assert!(app.collapse_tool_output);
+
+        let result = collapse(&mut app, Some("sideways"));
+        assert!(result.message.unwrap().contains("Unknown /collapse option"));
+        assert!(app.collapse_tool_output);
+    }
+
+    #[test]
+    fn approvals_mode_sets_the_policy() {
+        let mut app = create_test_app();
+        assert_eq!(app.approval_policy(), ApprovalPolicy::Manual);
+
+        let result = approvals(&mut app, Some("mode auto-safe"));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Approval policy: auto-safe")
+        );
+        assert_eq!(app.approval_mode, ApprovalMode::Suggest);
+        assert_eq!(app.approval_policy(), ApprovalPolicy::AutoSafe);
+        assert_eq!(app.auto_approval("read_file"), Some(true));
+        assert_eq!(app.auto_approval("git_commit"), None);
+        assert_eq!(app.auto_approval("mcp_github_create_issue"), None);
+
+        // Deny wins over approvals granted earlier in the session
+        app.approval_session_approved
+            .insert("git_commit".to_string());
+        approvals(&mut app, Some("mode deny"));
+        assert_eq!(app.auto_approval("git_commit"), Some(false));
+        assert_eq!(app.auto_approval("read_file"), Some(false));
+        app.approval_session_approved.clear();
+
+        approvals(&mut app, Some("mode yolo"));
+        assert_eq!(app.approval_mode, ApprovalMode::Auto);
+        assert_eq!(app.approval_policy(), ApprovalPolicy::Yolo);
+
+        approvals(&mut app, Some("mode manual"));
+        assert_eq!(app.approval_policy(), ApprovalPolicy::Manual);
+        assert!(app.approval_session_categories.is_empty());
+
+        let shown = approvals(&mut app, Some("mode")).message.expect("message");
+        assert!(shown.starts_with("Approval policy: manual"));
+        let result = approvals(&mut app, Some("mode reckless"));
+        assert!(
+            result
+                .message
+                .unwrap()
+                .contains("Unknown approval policy: reckless")
+        );
     }
 
     #[test]
@@ -260,4 +573,53 @@ mod tests {
         assert!(!app.trust_mode);
         assert_eq!(app.approval_mode, ApprovalMode::Suggest);
     }
+
+    #[test]
+    fn test_config_subcommands() {
+        let mut app = create_test_app();
+        let shown = config(&mut app, None);
+        assert!(shown.message.unwrap().starts_with("Session Configuration:"));
+
+        let unknown = config(&mut app, Some("bogus"));
+        assert!(unknown.action.is_none());
+        assert!(unknown.message.unwrap().contains("Usage: /config [edit]"));
+    }
+
+    #[test]
+    fn test_theme_lists_and_rejects_unknown_names() {
+        let mut app = create_test_app();
+        let listed = theme(&mut app, None).message.unwrap();
+        assert!(listed.contains("default (current)"));
+        assert!(listed.contains("light"));
+
+        let unknown = theme(&mut app, Some("neon")).message.unwrap();
+        assert!(unknown.contains("Unknown theme 'neon'"));
+        assert!(unknown.contains("default, dark, light"));
+        assert_eq!(app.ui_theme.name, "default");
+    }
+
+    #[test]
+    fn test_theme_toggle_flips_light_and_dark() {
+        assert_eq!(toggled_theme("light"), "dark");
+        assert_eq!(toggled_theme("dark"), "light");
+        assert_eq!(toggled_theme("default"), "light");
+    }
+
+    #[test]
+    fn test_set_validates_values() {
+        let mut app = create_test_app();
+        let result = set_config(&mut app, Some("show_thinking maybe"));
+        assert!(result.message.unwrap().contains("Expected on/off"));
+
+        let result = set_config(&mut app, Some("theme neon"));
+        assert!(result.message.unwrap().contains("default, dark, light"));
+
+        let result = set_config(&mut app, Some("sidebar_width"));
+        assert!(
+            result
+                .message
+                .unwrap()
+                .contains("/set sidebar_width <value> (10-50)")
+        );
+    }
 }
@@ -27,7 +27,7 @@ pub fn help(app: &mut App, topic: Option<&str>) -> CommandResult {
 
     // Show help overlay
     if app.view_stack.top_kind() != Some(ModalKind::Help) {
-        app.view_stack.push(HelpView::new());
+        app.view_stack.push(HelpView::new(app.mode, app.vim_mode));
     }
     CommandResult::ok()
 }
@@ -44,6 +44,7 @@ pub fn clear(app: &mut App) -> CommandResult {
         *plan = PlanState::default();
     }
     app.tool_log.clear();
+    app.subagent_batch.clear();
     CommandResult::message("Conversation cleared")
 }
 
@@ -174,6 +175,14 @@ pub fn copy(app: &mut App, arg: Option<&str>) -> CommandResult {
     }
 }
 
+/// Buffer the next input as a paste, for terminals without bracketed paste
+pub fn paste(app: &mut App) -> CommandResult {
+    app.paste_burst.force_next_paste();
+    CommandResult::message(
+        "Paste mode: paste now; Enter inserts newlines until input pauses (any other key cancels)",
+    )
+}
+
 fn extract_text_from_cell(cell: &crate::tui::history::HistoryCell) -> String {
     use crate::tui::history::HistoryCell;
     match cell {
@@ -1,11 +1,15 @@
-//! Debug commands: tokens, cost, system, context, undo, retry, debug
+//! Debug commands: tokens, cost, budget, stats, system, context, undo, retry, clearcache, debug
+
+use std::fmt::Write;
 
 use super::CommandResult;
-use crate::models::{SystemPrompt, context_window_for_model};
+use crate::lifetime_usage::UsageLedger;
+use crate::models::{ContentBlock, SystemPrompt, context_window_for_model};
 use crate::pricing;
+use crate::tools::web_search::clear_fetch_cache;
 use crate::tui::app::{App, AppAction, AppMode};
-use crate::tui::history::HistoryCell;
-use crate::utils::estimate_message_chars;
+use crate::tui::history::{HistoryCell, format_tool_duration};
+use crate::utils::{estimate_message_chars, truncate_with_ellipsis};
 
 /// Show token usage for session
 pub fn tokens(app: &mut App) -> CommandResult {
@@ -20,26 +24,55 @@ pub fn tokens(app: &mut App) -> CommandResult {
          API messages:     {}\n\
          Chat messages:    {}\n\
          Model:            {}",
-        app.total_tokens, app.session_cost, message_count, chat_count, app.model,
+        app.total_tokens,
+        app.session_cost(),
+        message_count,
+        chat_count,
+        app.model,
     ))
 }
 
-/// Show session cost breakdown
-pub fn cost(app: &mut App) -> CommandResult {
+const COST_USAGE: &str = "Usage: /cost [--lifetime | --reset]";
+
+/// Show session cost breakdown, or lifetime totals with `--lifetime`
+pub fn cost(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim).filter(|a| !a.is_empty()) {
+        None => {}
+        Some("--lifetime") => {
+            return match UsageLedger::default_location() {
+                Ok(ledger) => lifetime_cost(&ledger),
+                Err(err) => CommandResult::error(format!("{err:#}")),
+            };
+        }
+        Some("--reset") => {
+            return match UsageLedger::default_location() {
+                Ok(ledger) => reset_lifetime_cost(&ledger),
+                Err(err) => CommandResult::error(format!("{err:#}")),
+            };
+        }
+        Some(_) => return CommandResult::error(COST_USAGE),
+    }
+
     CommandResult::message(format!(
         "Session Cost:\n\
          ─────────────────────────────\n\
-         Total spent:      ${:.4}\n\n\
+         Total spent:      ${:.4}\n\
+         Budget:           {}\n\n\
                    API Pricing:\n\
          ─────────────────────────────\n\
+         Text input:       ${:.2}/1M tokens\n\
+         Text output:      ${:.2}/1M tokens\n\
          Image generation: ${:.4}/image\n\
          Audio TTS (HD):   ${:.5}/char\n\
          Video (768P 6s):  ${:.2}\n\
          Video (1080P 6s): ${:.2}\n\
          Music (per 5min): ${:.2}\n\
          Voice cloning:    ${:.2}/voice\n\n\
-         Cost is tracked when paid tools are executed.",
-        app.session_cost,
+         Cost is tracked per API call and when paid tools are executed.",
+        app.session_cost(),
+        budget_limit(app),
+        pricing::prices::TEXT_INPUT_PER_MTOK,
+        pricing::prices::TEXT_OUTPUT_PER_MTOK,
         pricing::prices::IMAGE_PER_UNIT,
         pricing::prices::AUDIO_HD_PER_CHAR,
         pricing::prices::VIDEO_768P_6S,
@@ -49,6 +82,180 @@ pub fn cost(app: &mut App) -> CommandResult {
     ))
 }
 
+/// Tools listed under "Slowest tools" in `/stats`
+const SLOWEST_TOOLS_SHOWN: usize = 5;
+
+/// Show session duration, message counts, tool calls, response times, and slow tools
+pub fn stats(app: &mut App) -> CommandResult {
+    let (mut user, mut assistant, mut tool_results) = (0usize, 0usize, 0usize);
+    for message in &app.api_messages {
+        match message.role.as_str() {
+            "assistant" => assistant += 1,
+            "user" => {
+                let results = message
+                    .content
+                    .iter()
+                    .filter(|block| matches!(block, ContentBlock::ToolResult { .. }))
+                    .count();
+                tool_results += results;
+                if results < message.content.len() {
+                    user += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let stats = &app.stats;
+    let average = stats
+        .average_turn_time()
+        .map_or_else(|| "n/a".to_string(), |d| format!("{:.1}s", d.as_secs_f64()));
+    let mut out = format!(
+        "Session Stats:\n\
+         ─────────────────────────────\n\
+         Duration:         {}\n\
+         User messages:    {user}\n\
+         Assistant msgs:   {assistant}\n\
+         Tool results:     {tool_results}\n\
+         Turns completed:  {}\n\
+         Avg response:     {average}\n\
+         Tool calls:       {}",
+        format_duration(stats.started_at.elapsed()),
+        stats.turns,
+        stats.tool_calls.values().sum::<usize>(),
+    );
+    let mut by_count: Vec<(&String, &usize)> = stats.tool_calls.iter().collect();
+    by_count.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (name, count) in by_count {
+        let _ = write!(out, "\n  {name:<16}{count}");
+    }
+    let slowest = stats.slowest_tools();
+    if !slowest.is_empty() {
+        out.push_str("\nSlowest tools (avg / max):");
+        for (name, timing) in slowest.into_iter().take(SLOWEST_TOOLS_SHOWN) {
+            let _ = write!(
+                out,
+                "\n  {name:<16}{} / {}",
+                format_tool_duration(duration_ms(timing.average())),
+                format_tool_duration(duration_ms(timing.slowest)),
+            );
+        }
+    }
+    CommandResult::message(out)
+}
+
+fn duration_ms(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn format_duration(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn lifetime_cost(ledger: &UsageLedger) -> CommandResult {
+    let lifetime = match ledger.load() {
+        Ok(lifetime) => lifetime,
+        Err(err) => return CommandResult::error(format!("{err:#}")),
+    };
+    if lifetime.models.is_empty() {
+        return CommandResult::message("No lifetime usage recorded yet.");
+    }
+
+    let mut out = String::from("Lifetime Cost:\n─────────────────────────────\n");
+    if let Some(since) = lifetime.since {
+        let _ = writeln!(
+            out,
+            "Since:            {}",
+            since.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+    for (model, totals) in &lifetime.models {
+        let _ = writeln!(
+            out,
+            "{model}: {} turns, {} in / {} out tokens, ${:.4}",
+            totals.turns, totals.input_tokens, totals.output_tokens, totals.cost_usd
+        );
+    }
+    let total = lifetime.total();
+    let _ = write!(
+        out,
+        "─────────────────────────────\n\
+         Total:            ${:.4} over {} turns\n\n\
+         Stored in {}. Use /cost --reset to start over.",
+        total.cost_usd,
+        total.turns,
+        ledger.path().display()
+    );
+    CommandResult::message(out)
+}
+
+fn reset_lifetime_cost(ledger: &UsageLedger) -> CommandResult {
+    match ledger.reset() {
+        Ok(()) => CommandResult::message("Lifetime cost totals reset."),
+        Err(err) => CommandResult::error(format!("{err:#}")),
+    }
+}
+
+fn budget_limit(app: &App) -> String {
+    match app.cost_budget.lock().ok().and_then(|b| b.limit_usd) {
+        Some(limit) => format!("${limit:.2}"),
+        None => "none".to_string(),
+    }
+}
+
+const BUDGET_USAGE: &str = "Usage: /budget [raise <usd> | set <usd> | off]";
+
+/// Show or change the session spending cap
+pub fn budget(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let mut parts = arg.unwrap_or("").split_whitespace();
+    let sub = parts.next();
+    let amount = match (sub, parts.next().map(str::parse::<f64>)) {
+        (Some("raise" | "set"), Some(Ok(amount))) if amount.is_finite() && amount > 0.0 => {
+            Some(amount)
+        }
+        (Some("raise" | "set"), _) => {
+            return CommandResult::error(format!(
+                "Amount must be a positive number. {BUDGET_USAGE}"
+            ));
+        }
+        _ => None,
+    };
+
+    let summary = {
+        let Ok(mut budget) = app.cost_budget.lock() else {
+            return CommandResult::error("Failed to read session budget");
+        };
+        match (sub, amount) {
+            (None, _) => return CommandResult::message(format!("Budget: {}", budget.summary())),
+            (Some("raise"), Some(amount)) => budget.raise(amount),
+            (Some("set"), Some(amount)) => budget.limit_usd = Some(amount),
+            (Some("off"), _) => budget.limit_usd = None,
+            (Some(other), _) => {
+                return CommandResult::error(format!(
+                    "Unknown /budget option: {other}. {BUDGET_USAGE}"
+                ));
+            }
+        }
+        budget.summary()
+    };
+
+    if app.budget_blocked {
+        app.budget_blocked = false;
+        return CommandResult::with_message_and_action(
+            format!("Budget updated: {summary}. Resuming the stopped turn."),
+            AppAction::SendMessage("Continue.".to_string()),
+        );
+    }
+    CommandResult::message(format!("Budget updated: {summary}"))
+}
+
 /// Show current system prompt
 pub fn system_prompt(app: &mut App) -> CommandResult {
     let prompt_text = match &app.system_prompt {
@@ -182,6 +389,101 @@ pub fn retry(app: &mut App) -> CommandResult {
     }
 }
 
+const EDIT_USAGE: &str = "Usage: /edit [n|cancel]";
+
+/// Pull user prompt `n` back into the composer; sending it drops that prompt
+/// and everything after, then continues from the edited text. With no
+/// argument, lists the prompts with their numbers.
+pub fn edit(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let prompts = app.user_prompts();
+    let n = match arg.map(str::trim).filter(|a| !a.is_empty()) {
+        None => {
+            if prompts.is_empty() {
+                return CommandResult::message("No prompts to edit yet");
+            }
+            let mut out = String::from("Prompts:");
+            for (i, prompt) in prompts.iter().enumerate() {
+                let first_line = prompt.lines().next().unwrap_or_default();
+                let _ = write!(
+                    out,
+                    "\n  {}. {}",
+                    i + 1,
+                    truncate_with_ellipsis(first_line, 70, "...")
+                );
+            }
+            out.push_str("\nRun /edit <n> to rewrite one.");
+            return CommandResult::message(out);
+        }
+        Some("cancel") => {
+            return if app.editing_prompt.take().is_some() {
+                app.clear_input();
+                CommandResult::message("Edit cancelled")
+            } else {
+                CommandResult::message("Not editing a prompt")
+            };
+        }
+        Some(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => return CommandResult::error(EDIT_USAGE),
+        },
+    };
+    if app.is_loading {
+        return CommandResult::error("Wait for the current turn to finish before editing");
+    }
+    let Some(text) = prompts.get(n - 1).map(|p| (*p).to_string()) else {
+        return CommandResult::error(format!(
+            "No prompt {n}; this conversation has {}",
+            prompts.len()
+        ));
+    };
+
+    if !app.prompt_in_context(n) {
+        return CommandResult::error(format!(
+            "Prompt {n} is no longer in the model's context (it was compacted or trimmed), \
+             so it can't be edited"
+        ));
+    }
+
+    let later = prompts.len() - n;
+    app.input = text;
+    app.cursor_position = app.input.chars().count();
+    app.paste_burst.clear_after_explicit_paste();
+    app.editing_prompt = Some(n);
+    app.status_message = Some(format!("Editing prompt {n}"));
+    CommandResult::message(format!(
+        "Editing prompt {n}. Press Enter to resend it; the conversation from that prompt on \
+         ({later} later prompt(s) and their replies) is replaced. /edit cancel to abort."
+    ))
+}
+
+/// Flush the `web_fetch` page cache
+pub fn clear_cache(_app: &mut App) -> CommandResult {
+    match clear_fetch_cache() {
+        0 => CommandResult::message("Fetch cache is already empty"),
+        n => CommandResult::message(format!("Cleared {n} cached page(s)")),
+    }
+}
+
+/// `/debug` shows session details; `/debug render` toggles the layout overlay
+pub fn debug(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim) {
+        None | Some("") => debug_info(app),
+        Some("render") => {
+            app.debug_render = !app.debug_render;
+            if app.debug_render {
+                CommandResult::message(
+                    "Render debug on: layout regions are outlined with their size and the transcript shows scroll state. Run /debug render again to hide.",
+                )
+            } else {
+                CommandResult::message("Render debug off")
+            }
+        }
+        Some(other) => CommandResult::error(format!(
+            "Unknown /debug option '{other}'. Usage: /debug [render]"
+        )),
+    }
+}
+
 /// Show comprehensive debug information
 pub fn debug_info(app: &mut App) -> CommandResult {
     let mut output = String::new();
@@ -199,7 +501,7 @@ pub fn debug_info(app: &mut App) -> CommandResult {
     output.push_str(&format!("  Messages:       {}\n", app.api_messages.len()));
     output.push_str(&format!("  History cells:  {}\n", app.history.len()));
     output.push_str(&format!("  Total tokens:   {}\n", app.total_tokens));
-    output.push_str(&format!("  Session cost:   ${:.4}\n", app.session_cost));
+    output.push_str(&format!("  Session cost:   ${:.4}\n", app.session_cost()));
     output.push_str(&format!("  Mode:           {:?}\n", app.mode));
     output.push_str(&format!("  Model:          {}\n\n", app.model));
 
@@ -217,9 +519,13 @@ pub fn debug_info(app: &mut App) -> CommandResult {
 
     // Settings
     output.push_str("Settings:\n");
-    output.push_str(&format!("  Auto-compact:   {}\n", app.auto_compact));
+    output.push_str(&format!(
+        "  Auto-compact:   {} (at {}%)\n",
+        app.auto_compact, app.auto_compact_threshold
+    ));
     output.push_str(&format!("  Show thinking:  {}\n", app.show_thinking));
     output.push_str(&format!("  Show tools:     {}\n", app.show_tool_details));
+    output.push_str(&format!("  Render debug:   {}\n", app.debug_render));
     output.push_str(&format!("  Shell allowed:  {}\n", app.allow_shell));
     output.push_str(&format!("  Trust mode:     {}\n", app.trust_mode));
     output.push_str(&format!("  Approval mode:  {:?}\n", app.approval_mode));
@@ -228,7 +534,13 @@ pub fn debug_info(app: &mut App) -> CommandResult {
     // Workspace
     output.push_str("Workspace:\n");
     output.push_str(&format!("  Path:           {}\n", app.workspace.display()));
-    output.push_str(&format!("  Skills dir:     {}\n", app.skills_dir.display()));
+    for (source, dir) in app.skill_search_path() {
+        output.push_str(&format!(
+            "  Skills dir:     {} ({})\n",
+            dir.display(),
+            source.label()
+        ));
+    }
     output.push_str(&format!(
         "  Project doc:    {}\n\n",
         if app.project_doc.is_some() {
@@ -286,3 +598,208 @@ pub fn debug_info(app: &mut App) -> CommandResult {
 
     CommandResult::message(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+
+    fn create_test_app(max_session_cost_usd: Option<f64>) -> App {
+        let options = TuiOptions::for_test(".");
+        let config = Config {
+            max_session_cost_usd,
+            ..Config::default()
+        };
+        App::new(options, &config)
+    }
+
+    #[test]
+    fn budget_shows_and_updates_the_cap() {
+        let mut app = create_test_app(Some(1.0));
+        let result = budget(&mut app, None);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Budget: $0.0000 of $1.00 spent")
+        );
+
+        budget(&mut app, Some("raise 0.5"));
+        assert_eq!(app.cost_budget.lock().unwrap().limit_usd, Some(1.5));
+        budget(&mut app, Some("set 3"));
+        assert_eq!(app.cost_budget.lock().unwrap().limit_usd, Some(3.0));
+        let result = budget(&mut app, Some("off"));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Budget updated: $0.0000 spent (no budget)")
+        );
+
+        let result = budget(&mut app, Some("raise -2"));
+        assert!(result.message.unwrap().contains("positive number"));
+        let result = budget(&mut app, Some("lower 1"));
+        assert!(result.message.unwrap().contains("Unknown /budget option"));
+    }
+
+    #[test]
+    fn stats_counts_messages_and_tool_calls() {
+        let mut app = create_test_app(None);
+        let text = |role: &str| crate::models::Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: "hi".to_string(),
+                cache_control: None,
+            }],
+        };
+        app.api_messages = vec![
+            text("user"),
+            text("assistant"),
+            crate::models::Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "t1".to_string(),
+                    content: "ok".to_string(),
+                }],
+            },
+            text("assistant"),
+        ];
+        app.stats.record_tool_call("read_file");
+        app.stats.record_tool_call("grep_files");
+        app.stats.record_tool_call("read_file");
+        app.stats
+            .record_tool_duration("read_file", std::time::Duration::from_millis(20));
+        app.stats
+            .record_tool_duration("read_file", std::time::Duration::from_millis(40));
+        app.stats
+            .record_tool_duration("grep_files", std::time::Duration::from_millis(2500));
+        app.stats.record_turn(std::time::Duration::from_secs(3));
+        app.stats.record_turn(std::time::Duration::from_secs(5));
+
+        let out = stats(&mut app).message.unwrap();
+        assert!(out.contains("User messages:    1\n"), "{out}");
+        assert!(out.contains("Assistant msgs:   2\n"), "{out}");
+        assert!(out.contains("Tool results:     1\n"), "{out}");
+        assert!(out.contains("Avg response:     4.0s\n"), "{out}");
+        assert!(out.contains("Tool calls:       3\n  read_file       2\n  grep_files      1"));
+        assert!(
+            out.ends_with(
+                "Slowest tools (avg / max):\n  grep_files      2.5s / 2.5s\n  read_file       30ms / 40ms"
+            ),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn edit_loads_prompt_and_rewinds_on_send() {
+        let mut app = create_test_app(None);
+        let message = |role: &str, text: &str| crate::models::Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        };
+        for (prompt, reply) in [("first", "one"), ("secnod", "two"), ("third", "three")] {
+            app.history.push(HistoryCell::User {
+                content: prompt.to_string(),
+            });
+            app.history.push(HistoryCell::Assistant {
+                content: reply.to_string(),
+                streaming: false,
+            });
+            app.api_messages.push(message("user", prompt));
+            app.api_messages.push(message("assistant", reply));
+        }
+
+        let listing = edit(&mut app, None).message.unwrap();
+        assert!(listing.contains("  2. secnod\n  3. third"), "{listing}");
+        assert!(
+            edit(&mut app, Some("4"))
+                .message
+                .unwrap()
+                .contains("No prompt 4")
+        );
+
+        edit(&mut app, Some("2"));
+        assert_eq!(app.input, "secnod");
+        assert_eq!(app.editing_prompt, Some(2));
+
+        assert!(app.rewind_to_prompt(2));
+        assert_eq!(app.user_prompts(), vec!["first"]);
+        assert_eq!(app.api_messages.len(), 2);
+        assert!(!app.rewind_to_prompt(2));
+
+        // After /trim drops the oldest messages, prompts still map from the
+        // newest, and prompts that left the context are refused
+        for (prompt, reply) in [("fourth", "four"), ("fifth", "five")] {
+            app.history.push(HistoryCell::User {
+                content: prompt.to_string(),
+            });
+            app.api_messages.push(message("user", prompt));
+            app.api_messages.push(message("assistant", reply));
+        }
+        app.api_messages.drain(..2);
+        assert!(
+            edit(&mut app, Some("1"))
+                .message
+                .unwrap()
+                .contains("no longer in the model's context")
+        );
+        assert!(!app.rewind_to_prompt(1));
+        assert!(app.rewind_to_prompt(3));
+        assert_eq!(app.user_prompts(), vec!["first", "fourth"]);
+        let kept: Vec<&str> = app
+            .api_messages
+            .iter()
+            .filter_map(|message| match &message.content[0] {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kept, vec!["fourth", "four"]);
+
+        edit(&mut app, Some("cancel"));
+        assert_eq!(app.editing_prompt, None);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn lifetime_cost_lists_models_and_resets() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let ledger = UsageLedger::new(tmp.path().join("usage.json"));
+        let result = lifetime_cost(&ledger);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("No lifetime usage recorded yet.")
+        );
+
+        let usage = crate::models::Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+        };
+        ledger.record("test-model", &usage).expect("record");
+        let message = lifetime_cost(&ledger).message.unwrap();
+        assert!(message.contains("test-model: 1 turns, 1000000 in / 0 out tokens, $0.2000"));
+        assert!(message.contains("Total:            $0.2000 over 1 turns"));
+
+        reset_lifetime_cost(&ledger);
+        assert!(ledger.load().expect("load").models.is_empty());
+
+        let mut app = create_test_app(None);
+        let result = cost(&mut app, Some("--forever"));
+        assert!(result.message.unwrap().contains(COST_USAGE));
+    }
+
+    #[test]
+    fn raising_a_blocked_budget_resumes_the_turn() {
+        let mut app = create_test_app(Some(0.01));
+        app.cost_budget.lock().unwrap().record(0.01);
+        app.budget_blocked = true;
+
+        let result = budget(&mut app, Some("raise 1"));
+        assert!(matches!(
+            result.action,
+            Some(AppAction::SendMessage(ref text)) if text == "Continue."
+        ));
+        assert!(!app.budget_blocked);
+        assert!((app.session_cost() - 0.01).abs() < f64::EPSILON);
+    }
+}
@@ -94,7 +94,7 @@ fn check_api_key(_app: &App) -> Vec<CheckResult> {
     let mut results = Vec::new();
 
     // Check environment variable first
-    if std::env::var("AXIOM_API_KEY").is_ok() {
+    if crate::config::env_var("AXIOM_API_KEY").is_ok() {
         results.push(CheckResult::ok("AXIOM_API_KEY environment variable is set"));
     } else {
         // Check config file
@@ -119,7 +119,7 @@ fn check_api_key(_app: &App) -> Vec<CheckResult> {
     }
 
     // Check API key format if available
-    if let Ok(key) = std::env::var("AXIOM_API_KEY") {
+    if let Ok(key) = crate::config::env_var("AXIOM_API_KEY") {
         if is_valid_api_key_format(&key) {
             results.push(CheckResult::ok("API key format is valid"));
         } else {
@@ -598,7 +598,7 @@ fn format_diagnostic_output(groups: &[(&'static str, Vec<CheckResult>)]) -> Stri
 
 /// Get config file path
 fn get_config_path() -> PathBuf {
-    if let Ok(path) = std::env::var("AXIOM_CONFIG_PATH") {
+    if let Ok(path) = crate::config::env_var("AXIOM_CONFIG_PATH") {
         return PathBuf::from(path);
     }
     dirs::home_dir()
@@ -0,0 +1,143 @@
+//! Git commands: diff
+
+use std::path::Path;
+
+use super::CommandResult;
+use crate::tui::app::App;
+use crate::tui::history::{DiffCell, HistoryCell, ToolCell};
+use crate::utils::{git_diff, truncate_diff};
+
+/// Largest diff kept in history; matches the `axiom review` default.
+const DIFF_MAX_CHARS: usize = 200_000;
+
+const DIFF_USAGE: &str = "Usage: /diff [--staged] [path]";
+
+/// Show the working-tree (or staged) diff as a highlighted history cell
+pub fn diff(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let mut staged = false;
+    let mut path = None;
+    for token in arg.unwrap_or("").split_whitespace() {
+        match token {
+            "--staged" | "--cached" => staged = true,
+            flag if flag.starts_with("--") => {
+                return CommandResult::error(format!("Unknown /diff option: {flag}. {DIFF_USAGE}"));
+            }
+            _ if path.is_some() => {
+                return CommandResult::error(format!("/diff takes one path. {DIFF_USAGE}"));
+            }
+            _ => path = Some(token),
+        }
+    }
+
+    let diff = match git_diff(&app.workspace, staged, None, path.map(Path::new)) {
+        Ok(diff) => diff,
+        Err(err) => return CommandResult::error(err.to_string()),
+    };
+    let (label, kind) = if staged {
+        ("Staged Diff", "staged changes")
+    } else {
+        ("Working Tree Diff", "unstaged changes")
+    };
+    let scope = path.map(|p| format!(" in {p}")).unwrap_or_default();
+    if diff.trim().is_empty() {
+        return CommandResult::message(format!("No {kind}{scope}."));
+    }
+
+    let title = format!("{label}{scope} · {}", diff_stat(&diff));
+    app.add_message(HistoryCell::Tool(ToolCell::Diff(DiffCell {
+        title,
+        diff: truncate_diff(diff, DIFF_MAX_CHARS),
+    })));
+    CommandResult::ok()
+}
+
+/// One-line summary like "2 files, +10 -3"
+fn diff_stat(diff: &str) -> String {
+    let mut files = 0;
+    let mut added = 0;
+    let mut removed = 0;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            files += 1;
+        } else if line.starts_with('+') && !line.starts_with("+++") {
+            added += 1;
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            removed += 1;
+        }
+    }
+    let noun = if files == 1 { "file" } else { "files" };
+    format!("{files} {noun}, +{added} -{removed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::tui::app::TuiOptions;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn create_test_app(workspace: &Path) -> App {
+        App::new(TuiOptions::for_test(workspace), &Config::default())
+    }
+
+    fn last_diff(app: &App) -> &DiffCell {
+        match app.history.last() {
+            Some(HistoryCell::Tool(ToolCell::Diff(cell))) => cell,
+            other => panic!("expected a diff cell, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn diff_shows_working_tree_and_staged_changes() {
+        let tmp = tempdir().expect("tempdir");
+        git(tmp.path(), &["init", "-q"]);
+        std::fs::write(tmp.path().join("a.txt"), "one\n").expect("write");
+        std::fs::write(tmp.path().join("b.txt"), "two\n").expect("write");
+        git(tmp.path(), &["add", "."]);
+        git(tmp.path(), &["commit", "-q", "-m", "init"]);
+        let mut app = create_test_app(tmp.path());
+
+        let result = diff(&mut app, None);
+        assert_eq!(result.message.as_deref(), Some("No unstaged changes."));
+
+        std::fs::write(tmp.path().join("a.txt"), "uno\n").expect("write");
+        std::fs::write(tmp.path().join("b.txt"), "dos\n").expect("write");
+        diff(&mut app, None);
+        let cell = last_diff(&app);
+        assert_eq!(cell.title, "Working Tree Diff · 2 files, +2 -2");
+        assert!(cell.diff.contains("+uno"));
+
+        diff(&mut app, Some("b.txt"));
+        let cell = last_diff(&app);
+        assert_eq!(cell.title, "Working Tree Diff in b.txt · 1 file, +1 -1");
+        assert!(!cell.diff.contains("+uno"));
+
+        let result = diff(&mut app, Some("--staged"));
+        assert_eq!(result.message.as_deref(), Some("No staged changes."));
+        git(tmp.path(), &["add", "a.txt"]);
+        diff(&mut app, Some("--staged"));
+        let cell = last_diff(&app);
+        assert!(cell.title.starts_with("Staged Diff · 1 file"));
+        assert!(cell.diff.contains("+uno"));
+    }
+
+    #[test]
+    fn diff_rejects_unknown_options() {
+        let mut app = create_test_app(Path::new("."));
+        let result = diff(&mut app, Some("--stat"));
+        assert!(result.message.unwrap().contains("Unknown /diff option"));
+        let result = diff(&mut app, Some("a b"));
+        assert!(result.message.unwrap().contains("one path"));
+    }
+}
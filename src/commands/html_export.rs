@@ -0,0 +1,257 @@
+//! Self-contained HTML transcript for `/export --html`.
+//!
+//! Everything (styles included) is inlined so the file can be shared as-is.
+//! Code fences go through the TUI syntax highlighter and tool calls render as
+//! collapsible `<details>` sections.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use ratatui::style::Color;
+use ratatui::text::Line;
+
+use crate::palette;
+use crate::tui::history::HistoryCell;
+use crate::tui::syntax::{extract_code_blocks, highlight_code};
+
+/// Render the conversation as a standalone HTML document.
+pub fn render(history: &[HistoryCell], model: &str, workspace: &Path, date: &str) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>Chat Export</title>\n<style>\n{}</style>\n</head>\n<body>\n<main>\n",
+        stylesheet()
+    );
+    let _ = write!(
+        html,
+        "<header>\n<h1>Chat Export</h1>\n<dl>\
+         <dt>Model</dt><dd>{}</dd><dt>Workspace</dt><dd>{}</dd><dt>Date</dt><dd>{}</dd>\
+         </dl>\n</header>\n",
+        escape(model),
+        escape(&workspace.display().to_string()),
+        escape(date)
+    );
+
+    for cell in history {
+        match cell {
+            HistoryCell::User { content } => message(&mut html, "user", "You", content),
+            HistoryCell::Assistant { content, .. } => {
+                message(&mut html, "assistant", "Assistant", content);
+            }
+            HistoryCell::System { content } => message(&mut html, "system", "System", content),
+            HistoryCell::ThinkingSummary { summary } => {
+                let _ = write!(
+                    html,
+                    "<details class=\"thinking\"><summary>Thinking</summary>\n{}</details>\n",
+                    render_markdownish(summary.trim())
+                );
+            }
+            HistoryCell::Tool(tool) => {
+                let lines: Vec<String> = tool.lines(100).into_iter().map(line_text).collect();
+                let (title, body) = lines
+                    .split_first()
+                    .map_or(("Tool", &[][..]), |(t, b)| (t.as_str(), b));
+                let _ = write!(
+                    html,
+                    "<details class=\"tool\"><summary>{}</summary>\n<pre>{}</pre></details>\n",
+                    escape(title.trim()),
+                    escape(body.join("\n").trim_end())
+                );
+            }
+            HistoryCell::Error {
+                message: text,
+                suggestion,
+            } => {
+                let mut content = text.clone();
+                if let Some(hint) = suggestion {
+                    let _ = write!(content, "\n\n{hint}");
+                }
+                message(&mut html, "error", "Error", &content);
+            }
+        }
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+fn message(html: &mut String, class: &str, role: &str, content: &str) {
+    let _ = write!(
+        html,
+        "<section class=\"msg {class}\">\n<div class=\"role\">{role}</div>\n{}</section>\n",
+        render_markdownish(content.trim())
+    );
+}
+
+/// Plain paragraphs plus highlighted code fences; other markdown is left as text.
+fn render_markdownish(text: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let flush = |out: &mut String, paragraph: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", escape(&paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    for (is_code, block) in extract_code_blocks(text) {
+        if is_code {
+            flush(&mut out, &mut paragraph);
+            let (lang, code) = block.split_once('\n').unwrap_or((block.as_str(), ""));
+            out.push_str(&highlighted_block(code, lang));
+        } else if block.trim().is_empty() {
+            flush(&mut out, &mut paragraph);
+        } else {
+            paragraph.push(block);
+        }
+    }
+    flush(&mut out, &mut paragraph);
+    out
+}
+
+fn highlighted_block(code: &str, lang: &str) -> String {
+    let mut out = String::from("<pre class=\"code\">");
+    if !lang.is_empty() {
+        let _ = write!(out, "<span class=\"lang\">{}</span>", escape(lang));
+    }
+    out.push_str("<code>");
+    let lines: Vec<String> = highlight_code(code, lang)
+        .into_iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| match span.style.fg.and_then(css_color) {
+                    Some(color) => format!(
+                        "<span style=\"color:{color}\">{}</span>",
+                        escape(&span.content)
+                    ),
+                    None => escape(&span.content),
+                })
+                .collect::<String>()
+        })
+        .collect();
+    out.push_str(&lines.join("\n"));
+    out.push_str("</code></pre>\n");
+    out
+}
+
+fn line_text(line: Line<'static>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+fn css_color(color: Color) -> Option<String> {
+    match color {
+        Color::Rgb(r, g, b) => Some(rgb(r, g, b)),
+        _ => None,
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn stylesheet() -> String {
+    let c = |(r, g, b): (u8, u8, u8)| rgb(r, g, b);
+    format!(
+        ":root {{ --bg: {black}; --panel: {ink}; --border: {slate}; --text: {snow}; \
+         --muted: {silver}; --blue: {blue}; --green: {green}; --red: {red}; --orange: {orange}; }}\n\
+         body {{ margin: 0; background: var(--bg); color: var(--text); \
+         font: 15px/1.55 -apple-system, BlinkMacSystemFont, \"Segoe UI\", sans-serif; }}\n\
+         main {{ max-width: 920px; margin: 0 auto; padding: 32px 20px; }}\n\
+         header {{ border-bottom: 1px solid var(--border); margin-bottom: 24px; }}\n\
+         h1 {{ color: var(--blue); font-size: 22px; margin: 0 0 8px; }}\n\
+         dl {{ display: grid; grid-template-columns: max-content 1fr; gap: 2px 12px; color: var(--muted); }}\n\
+         dt {{ font-weight: 600; }} dd {{ margin: 0; }}\n\
+         .msg {{ background: var(--panel); border-left: 3px solid var(--border); \
+         border-radius: 6px; padding: 10px 16px; margin: 14px 0; }}\n\
+         .msg.user {{ border-left-color: var(--blue); }}\n\
+         .msg.assistant {{ border-left-color: var(--green); }}\n\
+         .msg.system {{ border-left-color: var(--muted); color: var(--muted); }}\n\
+         .msg.error {{ border-left-color: var(--red); }}\n\
+         .role {{ font-weight: 700; font-size: 13px; color: var(--muted); text-transform: uppercase; }}\n\
+         p {{ white-space: pre-wrap; margin: 8px 0; }}\n\
+         pre {{ background: var(--bg); border: 1px solid var(--border); border-radius: 6px; \
+         padding: 10px 12px; overflow-x: auto; font: 13px/1.45 ui-monospace, Menlo, Consolas, monospace; }}\n\
+         pre.code .lang {{ display: block; color: var(--muted); font-size: 11px; margin-bottom: 6px; }}\n\
+         details {{ background: var(--panel); border-radius: 6px; padding: 6px 14px; margin: 10px 0; }}\n\
+         details.tool {{ border-left: 3px solid var(--orange); }}\n\
+         details.thinking {{ border-left: 3px solid var(--border); color: var(--muted); }}\n\
+         summary {{ cursor: pointer; font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 13px; }}\n",
+        black = c(palette::BLACK_RGB),
+        ink = c(palette::INK_RGB),
+        slate = c(palette::SLATE_RGB),
+        snow = c(palette::SNOW_RGB),
+        silver = c(palette::SILVER_RGB),
+        blue = c(palette::BLUE_RGB),
+        green = c(palette::GREEN_RGB),
+        red = c(palette::RED_RGB),
+        orange = c(palette::ORANGE_RGB),
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> String {
+        let history = vec![
+            HistoryCell::User {
+                content: "Show me <main>".to_string(),
+            },
+            HistoryCell::Assistant {
+                content: "Here:\n\n```rust\nfn main() {}\n```\nDone.".to_string(),
+                streaming: false,
+            },
+        ];
+        render(
+            &history,
+            "model-01",
+            Path::new("/tmp/ws"),
+            "2026-01-01 00:00:00",
+        )
+    }
+
+    #[test]
+    fn escapes_user_content() {
+        let html = sample();
+        assert!(html.contains("Show me &lt;main&gt;"));
+        assert!(!html.contains("Show me <main>"));
+    }
+
+    #[test]
+    fn highlights_code_fences_inline() {
+        let html = sample();
+        assert!(html.contains("<pre class=\"code\"><span class=\"lang\">rust</span><code>"));
+        assert!(html.contains("<span style=\"color:#"));
+        assert!(html.contains("<p>Done.</p>"));
+    }
+
+    #[test]
+    fn document_is_self_contained() {
+        let html = sample();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("<script src"));
+    }
+}
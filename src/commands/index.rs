@@ -0,0 +1,36 @@
+//! Index command: index
+
+use crate::tools::index::FileIndex;
+use crate::tui::app::{App, AppAction};
+
+use super::CommandResult;
+
+/// Show the investigator's file index, or rebuild it from scratch with `rebuild`
+pub fn index(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let path = FileIndex::path(&app.workspace);
+    match arg.map(str::trim) {
+        None | Some("") | Some("status") => {
+            let index = FileIndex::load(&app.workspace);
+            if index.is_empty() {
+                return CommandResult::message(format!(
+                    "No file index yet at {}.\n\
+                     It is built on the first codebase investigation, or run /index rebuild.",
+                    path.display()
+                ));
+            }
+            CommandResult::message(format!(
+                "File index: {} files at {}\n\
+                 Changed files are re-read on the next investigation; /index rebuild re-reads all.",
+                index.len(),
+                path.display()
+            ))
+        }
+        Some("rebuild") => CommandResult::with_message_and_action(
+            format!("Rebuilding file index at {}...", path.display()),
+            AppAction::RebuildIndex,
+        ),
+        Some(other) => CommandResult::error(format!(
+            "Unknown /index argument '{other}'. Usage: /index [status|rebuild]"
+        )),
+    }
+}
@@ -65,9 +65,9 @@ pub fn mcp_status(app: &mut App) -> CommandResult {
 
                 // Configuration details
                 output.push_str(&format!(
-                    "    Command: {} {}\n",
-                    server_config.command,
-                    server_config.args.join(" ")
+                    "    {}: {}\n",
+                    server_config.effective_transport(),
+                    server_config.describe()
                 ));
 
                 if !server_config.env.is_empty() {
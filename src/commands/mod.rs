@@ -7,11 +7,14 @@ mod config;
 mod core;
 mod debug;
 mod doctor;
+mod git;
+mod html_export;
+mod index;
 mod init;
 mod mcp;
 mod pins;
 mod queue;
-mod reload;
+pub mod reload;
 pub mod rlm;
 mod session;
 mod setup;
@@ -146,12 +149,36 @@ pub const COMMANDS: &[CommandInfo] = &[
         description: "Load session from file (or RLM context in RLM mode)",
         usage: "/load [path]",
     },
+    CommandInfo {
+        name: "recover",
+        aliases: &[],
+        description: "Continue the session left unfinished by a crash",
+        usage: "/recover",
+    },
     CommandInfo {
         name: "save-session",
         aliases: &["save_session"],
         description: "Save RLM session to file",
         usage: "/save-session [path]",
     },
+    CommandInfo {
+        name: "rename",
+        aliases: &[],
+        description: "Rename the current saved session",
+        usage: "/rename <title>",
+    },
+    CommandInfo {
+        name: "fork",
+        aliases: &["branch"],
+        description: "Branch the conversation into a new session",
+        usage: "/fork [title]",
+    },
+    CommandInfo {
+        name: "tag",
+        aliases: &[],
+        description: "Add or remove a tag on the current session",
+        usage: "/tag [name]",
+    },
     CommandInfo {
         name: "sessions",
         aliases: &["resume"],
@@ -180,20 +207,26 @@ pub const COMMANDS: &[CommandInfo] = &[
         name: "compact",
         aliases: &[],
         description: "Toggle auto-compaction or trigger manual compaction",
-        usage: "/compact [now]",
+        usage: "/compact [now|--target <pct>|--preview]",
+    },
+    CommandInfo {
+        name: "trim",
+        aliases: &[],
+        description: "Drop the oldest messages until the context fits the model's window",
+        usage: "/trim [tokens]",
     },
     CommandInfo {
         name: "export",
         aliases: &[],
-        description: "Export conversation to markdown",
-        usage: "/export [path]",
+        description: "Export conversation to markdown or HTML",
+        usage: "/export [--html] [path]",
     },
     // Config commands
     CommandInfo {
         name: "config",
         aliases: &[],
-        description: "Display current configuration",
-        usage: "/config",
+        description: "Display current configuration, or edit config.toml in $EDITOR",
+        usage: "/config [edit]",
     },
     CommandInfo {
         name: "set",
@@ -213,6 +246,18 @@ pub const COMMANDS: &[CommandInfo] = &[
         description: "Enable trust mode (access files outside workspace)",
         usage: "/trust",
     },
+    CommandInfo {
+        name: "approvals",
+        aliases: &[],
+        description: "List or clear session approvals, or set the approval policy",
+        usage: "/approvals [clear | mode <manual|auto-safe|yolo|deny>]",
+    },
+    CommandInfo {
+        name: "reset-approvals",
+        aliases: &[],
+        description: "Clear session approvals so tools ask again",
+        usage: "/reset-approvals",
+    },
     CommandInfo {
         name: "logout",
         aliases: &[],
@@ -256,6 +301,24 @@ pub const COMMANDS: &[CommandInfo] = &[
         description: "Retry the last request",
         usage: "/retry",
     },
+    CommandInfo {
+        name: "edit",
+        aliases: &[],
+        description: "Edit an earlier prompt and continue from it, dropping later messages",
+        usage: "/edit [n|cancel]",
+    },
+    CommandInfo {
+        name: "diff",
+        aliases: &[],
+        description: "Show the working-tree (or staged) git diff",
+        usage: "/diff [--staged] [path]",
+    },
+    CommandInfo {
+        name: "index",
+        aliases: &[],
+        description: "Show or rebuild the codebase investigator's file index",
+        usage: "/index [status|rebuild]",
+    },
     CommandInfo {
         name: "init",
         aliases: &[],
@@ -265,34 +328,64 @@ pub const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "settings",
         aliases: &[],
-        description: "Show persistent settings",
-        usage: "/settings",
+        description: "Show persistent settings (--all for every key, type, and default)",
+        usage: "/settings [--all]",
+    },
+    CommandInfo {
+        name: "theme",
+        aliases: &[],
+        description: "List UI themes, switch to one, or toggle light/dark",
+        usage: "/theme [name|toggle]",
+    },
+    CommandInfo {
+        name: "collapse",
+        aliases: &[],
+        description: "Collapse or expand long tool output in the transcript",
+        usage: "/collapse [on|off]",
     },
     // Skills commands
     CommandInfo {
         name: "skills",
         aliases: &[],
-        description: "List available skills",
-        usage: "/skills",
+        description: "List available skills, or rescan skill directories",
+        usage: "/skills [reload]",
     },
     CommandInfo {
         name: "skill",
         aliases: &[],
         description: "Activate a skill for next message",
-        usage: "/skill <name>",
+        usage: "/skill <name> [key=value ...]",
     },
     // Debug/cost command
+    CommandInfo {
+        name: "stats",
+        aliases: &[],
+        description: "Show session duration, message counts, tool calls, and response times",
+        usage: "/stats",
+    },
     CommandInfo {
         name: "cost",
         aliases: &[],
-        description: "Show session cost breakdown",
-        usage: "/cost",
+        description: "Show session cost, or lifetime totals across sessions",
+        usage: "/cost [--lifetime | --reset]",
+    },
+    CommandInfo {
+        name: "budget",
+        aliases: &[],
+        description: "Show, raise, or remove the session spending cap",
+        usage: "/budget [raise <usd> | set <usd> | off]",
     },
     CommandInfo {
         name: "debug",
         aliases: &[],
-        description: "Show debug information",
-        usage: "/debug",
+        description: "Show debug information, or outline TUI layout regions",
+        usage: "/debug [render]",
+    },
+    CommandInfo {
+        name: "clearcache",
+        aliases: &[],
+        description: "Flush cached web_fetch pages",
+        usage: "/clearcache",
     },
     CommandInfo {
         name: "reload",
@@ -348,7 +441,7 @@ pub const COMMANDS: &[CommandInfo] = &[
         name: "snippet",
         aliases: &[],
         description: "Insert a snippet template into input",
-        usage: "/snippet <name>",
+        usage: "/snippet <name> [args...] | save <name> [text, default: last prompt sent] | delete <name>",
     },
     CommandInfo {
         name: "snippets",
@@ -368,6 +461,12 @@ pub const COMMANDS: &[CommandInfo] = &[
         description: "Copy last assistant message (or Nth message) to clipboard",
         usage: "/copy [n]",
     },
+    CommandInfo {
+        name: "paste",
+        aliases: &[],
+        description: "Insert the next input as pasted text (Enter adds newlines)",
+        usage: "/paste",
+    },
 ];
 
 /// Execute a slash command
@@ -398,39 +497,54 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
                 session::load(app, arg)
             }
         }
+        "recover" => session::recover(app),
         "save-session" | "save_session" => rlm::save_session(app, arg),
         "sessions" | "resume" => CommandResult::action(AppAction::OpenSessionPicker),
         "history" => CommandResult::action(AppAction::OpenHistoryPicker),
         "status" => rlm::status(app),
         "repl" => rlm::repl(app),
         "compact" => session::compact(app, arg),
+        "trim" => session::trim(app, arg),
         "export" => session::export(app, arg),
+        "rename" => session::rename(app, arg),
+        "tag" => session::tag(app, arg),
+        "fork" | "branch" => session::fork(app, arg),
 
         // Config commands
-        "config" => config::show_config(app),
-        "settings" => config::show_settings(app),
+        "config" => config::config(app, arg),
+        "settings" => config::show_settings(app, arg),
         "set" => config::set_config(app, arg),
+        "theme" => config::theme(app, arg),
+        "collapse" => config::collapse(app, arg),
         "yolo" => config::yolo(app),
         "trust" => config::trust(app),
+        "approvals" => config::approvals(app, arg),
+        "reset-approvals" => config::approvals(app, Some("clear")),
         "logout" => config::logout(app),
         "reload" => reload::reload(app),
         "setup" => setup::setup(app),
 
         // Debug commands
         "tokens" => debug::tokens(app),
-        "cost" => debug::cost(app),
-        "debug" => debug::debug_info(app),
+        "cost" => debug::cost(app, arg),
+        "budget" => debug::budget(app, arg),
+        "stats" => debug::stats(app),
+        "debug" => debug::debug(app, arg),
+        "clearcache" => debug::clear_cache(app),
         "system" => debug::system_prompt(app),
         "context" => debug::context(app),
         "undo" => debug::undo(app),
         "retry" => debug::retry(app),
+        "edit" => debug::edit(app, arg),
         "usage" => usage::usage(app),
 
         // Project commands
         "init" => init::init(app),
+        "diff" => git::diff(app, arg),
+        "index" => index::index(app, arg),
 
         // Skills commands
-        "skills" => skills::list_skills(app),
+        "skills" => skills::list_skills(app, arg),
         "skill" => skills::run_skill(app, arg),
 
         // MCP command
@@ -463,6 +577,7 @@ pub fn execute(cmd: &str, app: &mut App) -> CommandResult {
 
         // Copy command
         "copy" => core::copy(app, arg),
+        "paste" => core::paste(app),
 
         _ => CommandResult::error(format!(
             "Unknown command: /{command}. Type /help for available commands."
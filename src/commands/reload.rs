@@ -5,14 +5,14 @@ use crate::tui::app::App;
 
 use super::CommandResult;
 
-/// Reload configuration from disk
+/// Reload configuration from disk, using the config file and profile the
+/// session started with
 pub fn reload(app: &mut App) -> CommandResult {
-    let profile = std::env::var("AXIOM_PROFILE").ok();
-    let config_path = std::env::var("AXIOM_CONFIG_PATH")
-        .ok()
-        .map(std::path::PathBuf::from);
-
-    match Config::load(config_path, profile.as_deref()) {
+    match Config::load_for_workspace(
+        app.config_path.clone(),
+        app.config_profile.as_deref(),
+        Some(&app.workspace),
+    ) {
         Ok(config) => {
             // Apply relevant config changes to the app
             if let Some(model) = &config.default_model {
@@ -21,6 +21,7 @@ pub fn reload(app: &mut App) -> CommandResult {
             app.allow_shell = config.allow_shell();
             app.max_subagents = config.max_subagents();
             app.skills_dir = config.skills_dir();
+            app.reload_skills();
 
             // Reload settings
             match crate::settings::Settings::load() {
@@ -28,8 +29,14 @@ pub fn reload(app: &mut App) -> CommandResult {
                     app.auto_compact = settings.auto_compact;
                     app.show_thinking = settings.show_thinking;
                     app.show_tool_details = settings.show_tool_details;
+                    app.collapse_tool_output = settings.collapse_tool_output;
+                    app.vim_mode = settings.vim_mode;
+                    app.redact_on_save = settings.redact_on_save;
                     app.max_input_history = settings.max_input_history;
                     app.ui_theme = crate::palette::ui_theme(&settings.theme);
+                    app.paste_burst.set_config(
+                        crate::tui::paste_burst::PasteBurstConfig::from_settings(&settings),
+                    );
                 }
                 Err(e) => {
                     return CommandResult::error(format!(
@@ -45,3 +52,31 @@ pub fn reload(app: &mut App) -> CommandResult {
         Err(e) => CommandResult::error(format!("Failed to reload config: {e}")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::TuiOptions;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reload_uses_startup_config_path_and_profile() {
+        let tmp = tempdir().expect("tempdir");
+        let config_path = tmp.path().join("custom.toml");
+        std::fs::write(
+            &config_path,
+            "default_model = \"base-model\"\n\n[profiles.work]\ndefault_model = \"work-model\"\n",
+        )
+        .expect("write config");
+
+        let options = TuiOptions {
+            config_path: Some(config_path),
+            profile: Some("work".to_string()),
+            ..TuiOptions::for_test(tmp.path())
+        };
+        let mut app = App::new(options, &Config::default());
+
+        let _ = reload(&mut app);
+        assert_eq!(app.model, "work-model");
+    }
+}
@@ -200,21 +200,7 @@ mod tests {
     use std::fs;
 
     fn make_app(workspace: PathBuf) -> App {
-        let options = TuiOptions {
-            model: "test-model".to_string(),
-            workspace,
-            allow_shell: false,
-            max_subagents: 1,
-            skills_dir: PathBuf::from("."),
-            memory_path: PathBuf::from("memory.md"),
-            notes_path: PathBuf::from("notes.txt"),
-            mcp_config_path: PathBuf::from("mcp.json"),
-            use_memory: false,
-            start_in_agent_mode: false,
-            yolo: false,
-            resume_session_id: None,
-        };
-        App::new(options, &Config::default())
+        App::new(TuiOptions::for_test(workspace), &Config::default())
     }
 
     #[test]
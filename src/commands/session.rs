@@ -3,11 +3,12 @@
 use std::fmt::Write;
 use std::path::PathBuf;
 
-use crate::session_manager::create_saved_session;
-use crate::tui::app::App;
+use crate::session_manager::{SavedSession, SessionManager, create_saved_session, format_tags};
+use crate::tui::app::{App, AppAction};
 use crate::tui::history::{HistoryCell, history_cells_from_message};
 
 use super::CommandResult;
+use super::html_export;
 use crate::tools::plan::PlanState;
 
 /// Save session to file
@@ -35,20 +36,29 @@ pub fn save(app: &mut App, path: Option<&str>) -> CommandResult {
         .filter(|p| !p.as_os_str().is_empty())
         .map_or_else(|| app.workspace.clone(), std::path::Path::to_path_buf);
 
+    // The file gets the redacted copy; the live session keeps the original text
+    let on_disk = if app.redact_on_save {
+        crate::session_manager::redact_session(&session)
+    } else {
+        session.clone()
+    };
+    let content = match serde_json::to_string_pretty(&on_disk) {
+        Ok(content) => content,
+        Err(e) => return CommandResult::error(format!("Failed to serialize session: {e}")),
+    };
+
     match std::fs::create_dir_all(&sessions_dir) {
-        Ok(()) => {
-            match std::fs::write(&save_path, serde_json::to_string_pretty(&session).unwrap()) {
-                Ok(()) => {
-                    app.current_session_id = Some(session.metadata.id.clone());
-                    CommandResult::message(format!(
-                        "Session saved to {} (ID: {})",
-                        save_path.display(),
-                        &session.metadata.id[..8]
-                    ))
-                }
-                Err(e) => CommandResult::error(format!("Failed to save session: {e}")),
+        Ok(()) => match std::fs::write(&save_path, content) {
+            Ok(()) => {
+                app.current_session_id = Some(session.metadata.id.clone());
+                CommandResult::message(format!(
+                    "Session saved to {} (ID: {})",
+                    save_path.display(),
+                    &session.metadata.id[..8]
+                ))
             }
-        }
+            Err(e) => CommandResult::error(format!("Failed to save session: {e}")),
+        },
         Err(e) => CommandResult::error(format!("Failed to create directory: {e}")),
     }
 }
@@ -72,13 +82,49 @@ pub fn load(app: &mut App, path: Option<&str>) -> CommandResult {
         }
     };
 
-    let session: crate::session_manager::SavedSession = match serde_json::from_str(&content) {
+    let session: SavedSession = match serde_json::from_str(&content) {
         Ok(s) => s,
         Err(e) => {
             return CommandResult::error(format!("Failed to parse session file: {e}"));
         }
     };
 
+    let message = format!(
+        "Session loaded from {} (ID: {}, {} messages)",
+        load_path.display(),
+        &session.metadata.id[..8],
+        session.metadata.message_count
+    );
+    let action = restore_session(app, session);
+    CommandResult::with_message_and_action(message, action)
+}
+
+/// Continue the session recovered at startup from a run that died mid-turn
+pub fn recover(app: &mut App) -> CommandResult {
+    let Some(id) = app.recoverable_session_id.clone() else {
+        return CommandResult::message("No unfinished session to recover.");
+    };
+    let manager = match app.session_manager() {
+        Ok(manager) => manager,
+        Err(e) => return CommandResult::error(format!("Failed to open sessions: {e}")),
+    };
+    let session = match manager.load_session(&id) {
+        Ok(session) => session,
+        Err(e) => return CommandResult::error(format!("Failed to load session: {e}")),
+    };
+    app.recoverable_session_id = None;
+
+    let message = format!(
+        "Recovered session {} ({} messages). Any reply cut off by the crash ends with [interrupted].",
+        &id[..8],
+        session.metadata.message_count
+    );
+    let action = restore_session(app, session);
+    CommandResult::with_message_and_action(message, action)
+}
+
+/// Replace the conversation with a saved session and sync it to the engine
+fn restore_session(app: &mut App, session: SavedSession) -> AppAction {
     app.api_messages.clone_from(&session.messages);
     app.history.clear();
     for msg in &app.api_messages {
@@ -97,50 +143,328 @@ pub fn load(app: &mut App, path: Option<&str>) -> CommandResult {
     app.recalculate_context_tokens();
     app.scroll_to_bottom();
 
-    CommandResult::with_message_and_action(
-        format!(
-            "Session loaded from {} (ID: {}, {} messages)",
-            load_path.display(),
-            &session.metadata.id[..8],
-            session.metadata.message_count
-        ),
-        crate::tui::app::AppAction::SyncSession {
-            messages: app.api_messages.clone(),
-            system_prompt: app.system_prompt.clone(),
-            model: app.model.clone(),
-            workspace: app.workspace.clone(),
-        },
-    )
+    AppAction::SyncSession {
+        messages: app.api_messages.clone(),
+        system_prompt: app.system_prompt.clone(),
+        model: app.model.clone(),
+        workspace: app.workspace.clone(),
+    }
+}
+
+/// Rename the current session, saving it first if it hasn't been saved yet
+pub fn rename(app: &mut App, title: Option<&str>) -> CommandResult {
+    let Some(title) = title.map(str::trim).filter(|t| !t.is_empty()) else {
+        return CommandResult::error("Usage: /rename <title>");
+    };
+    let manager = match app.session_manager() {
+        Ok(manager) => manager,
+        Err(e) => return CommandResult::error(format!("Failed to open sessions: {e}")),
+    };
+
+    let id = match ensure_saved_session(app, &manager, "rename") {
+        Ok(id) => id,
+        Err(e) => return CommandResult::error(e),
+    };
+
+    match manager.rename_session(&id, title) {
+        Ok(metadata) => CommandResult::message(format!(
+            "Session {} renamed to \"{}\"",
+            &metadata.id[..8],
+            metadata.title
+        )),
+        Err(e) => CommandResult::error(format!("Failed to rename session: {e}")),
+    }
+}
+
+/// Branch the conversation into a new saved session and switch to it.
+/// The original session file is left as it was.
+pub fn fork(app: &mut App, title: Option<&str>) -> CommandResult {
+    let manager = match app.session_manager() {
+        Ok(manager) => manager,
+        Err(e) => return CommandResult::error(format!("Failed to open sessions: {e}")),
+    };
+    let original_id = match ensure_saved_session(app, &manager, "fork") {
+        Ok(id) => id,
+        Err(e) => return CommandResult::error(e),
+    };
+    let original = manager.load_session(&original_id).ok();
+
+    let mut forked = create_saved_session(
+        &app.api_messages,
+        &app.model,
+        &app.workspace,
+        u64::from(app.total_tokens),
+        app.system_prompt.as_ref(),
+        app.pinned_messages.clone(),
+    );
+    if let Some(original) = &original {
+        forked.metadata.title = format!("{} (fork)", original.metadata.title);
+        forked.metadata.tags.clone_from(&original.metadata.tags);
+    }
+    if let Some(title) = title.map(str::trim).filter(|t| !t.is_empty()) {
+        forked.metadata.title = title.to_string();
+    }
+
+    if let Err(e) = manager.save_session(&forked) {
+        return CommandResult::error(format!("Failed to save fork: {e}"));
+    }
+    app.current_session_id = Some(forked.metadata.id.clone());
+    CommandResult::message(format!(
+        "Forked session {} into {} (\"{}\"). Resume the original with: axiom --resume {}",
+        &original_id[..8],
+        &forked.metadata.id[..8],
+        forked.metadata.title,
+        &original_id[..8]
+    ))
+}
+
+/// Add or remove a tag on the current session; with no argument, list its tags
+pub fn tag(app: &mut App, tag: Option<&str>) -> CommandResult {
+    let manager = match app.session_manager() {
+        Ok(manager) => manager,
+        Err(e) => return CommandResult::error(format!("Failed to open sessions: {e}")),
+    };
+
+    let Some(tag) = tag.map(str::trim).filter(|t| !t.is_empty()) else {
+        let tags = app
+            .current_session_id
+            .as_deref()
+            .and_then(|id| manager.load_session(id).ok())
+            .map(|session| session.metadata.tags)
+            .unwrap_or_default();
+        return if tags.is_empty() {
+            CommandResult::message("No tags on this session. Usage: /tag <name>")
+        } else {
+            CommandResult::message(format!("Tags: {}", format_tags(&tags)))
+        };
+    };
+
+    let id = match ensure_saved_session(app, &manager, "tag") {
+        Ok(id) => id,
+        Err(e) => return CommandResult::error(e),
+    };
+
+    match manager.toggle_tag(&id, tag) {
+        Ok((metadata, added)) => {
+            let verb = if added { "Added" } else { "Removed" };
+            let tags = if metadata.tags.is_empty() {
+                "none".to_string()
+            } else {
+                format_tags(&metadata.tags)
+            };
+            CommandResult::message(format!(
+                "{verb} tag #{}. Tags: {tags}",
+                tag.trim_start_matches('#')
+            ))
+        }
+        Err(e) => CommandResult::error(format!("Failed to tag session: {e}")),
+    }
+}
+
+/// ID of the current saved session, saving the conversation first if needed.
+/// Refuses when persistence is off (`--no-save`), since any of the callers
+/// would write the session to disk.
+fn ensure_saved_session(
+    app: &mut App,
+    manager: &SessionManager,
+    action: &str,
+) -> Result<String, String> {
+    if !app.auto_save {
+        return Err(format!(
+            "Can't {action} this session: persistence is off for this run (--no-save)"
+        ));
+    }
+    if let Some(id) = &app.current_session_id {
+        return Ok(id.clone());
+    }
+    if app.api_messages.is_empty() {
+        return Err(format!("Nothing to {action} yet: send a message first"));
+    }
+    let session = create_saved_session(
+        &app.api_messages,
+        &app.model,
+        &app.workspace,
+        u64::from(app.total_tokens),
+        app.system_prompt.as_ref(),
+        app.pinned_messages.clone(),
+    );
+    manager
+        .save_session(&session)
+        .map_err(|e| format!("Failed to save session: {e}"))?;
+    app.current_session_id = Some(session.metadata.id.clone());
+    Ok(session.metadata.id)
 }
 
-/// Toggle auto-compaction or trigger manual compaction
+/// Toggle auto-compaction, trigger manual compaction, or compact down to a
+/// share of the context window with `--target <pct>`
 pub fn compact(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let arg = arg.map(str::trim).unwrap_or_default();
+    if let Some(value) = arg.strip_prefix("--target") {
+        return compact_to_target(app, value);
+    }
     match arg {
-        Some("now") => {
+        "now" => {
             // Trigger manual compaction via engine
-            CommandResult::action(crate::tui::app::AppAction::CompactContext)
+            CommandResult::action(AppAction::CompactContext {
+                target_tokens: None,
+            })
         }
+        "--preview" | "preview" => CommandResult::action(AppAction::PreviewCompaction),
         _ => {
             // Toggle auto-compact setting
             app.auto_compact = !app.auto_compact;
             CommandResult::message(format!(
-                "Auto-compact: {}\n\nTip: Use '/compact now' to trigger manual compaction",
-                if app.auto_compact { "ON" } else { "OFF" }
+                "Auto-compact: {} (at {}% of the context window)\n\n\
+                 Tip: Use '/compact now' to trigger manual compaction, \
+                 '/compact --target <pct>' to compact down to a share of the window, or \
+                 '/compact --preview' to see what would be summarized first",
+                if app.auto_compact { "ON" } else { "OFF" },
+                app.auto_compact_threshold
             ))
         }
     }
 }
 
-/// Export conversation to markdown
-pub fn export(app: &mut App, path: Option<&str>) -> CommandResult {
-    let export_path = path.map_or_else(
-        || {
-            let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-            PathBuf::from(format!("chat_export_{timestamp}.md"))
+/// Summarize older messages so the context ends up near `pct` percent of the window
+fn compact_to_target(app: &mut App, value: &str) -> CommandResult {
+    let value = value.trim().trim_start_matches('=').trim();
+    let pct = match value.trim_end_matches('%').parse::<u32>() {
+        Ok(pct) if (1..=100).contains(&pct) => pct,
+        _ => return CommandResult::error("Usage: /compact --target <pct> (1-100)"),
+    };
+    let Some(window) = app.context_window() else {
+        return CommandResult::error(format!(
+            "Unknown context window for {}; use /compact now instead",
+            app.model
+        ));
+    };
+
+    let target = window / 100 * pct;
+    let before = app.total_conversation_tokens;
+    if before <= target {
+        return CommandResult::message(format!(
+            "Context is ~{before} tokens, already within {pct}% of the window (~{target} tokens)."
+        ));
+    }
+    let message_tokens = crate::compaction::estimate_tokens(&app.api_messages);
+    let overhead = usize::try_from(before)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(message_tokens)
+        + crate::compaction::SUMMARY_TOKEN_ALLOWANCE;
+    let budget = usize::try_from(target)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(overhead);
+
+    CommandResult::with_message_and_action(
+        format!(
+            "Compacting ~{before} tokens down to about {pct}% of the window (~{target} tokens)"
+        ),
+        AppAction::CompactContext {
+            target_tokens: Some(budget),
+        },
+    )
+}
+
+/// Share of the context window `/trim` fills by default, leaving room to reply
+const TRIM_TARGET_PERCENT: u32 = 75;
+
+/// Drop the oldest messages so the context fits in `[tokens]`, by default
+/// three quarters of the model's window
+pub fn trim(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let target = match arg.map(str::trim).filter(|a| !a.is_empty()) {
+        Some(value) => match value.replace('_', "").parse::<u32>() {
+            Ok(tokens) if tokens > 0 => tokens,
+            _ => return CommandResult::error("Usage: /trim [tokens]"),
         },
-        PathBuf::from,
-    );
+        None => match app.context_window() {
+            Some(window) => window / 100 * TRIM_TARGET_PERCENT,
+            None => {
+                return CommandResult::error(format!(
+                    "Unknown context window for {}; pass a token budget: /trim <tokens>",
+                    app.model
+                ));
+            }
+        },
+    };
+
+    let before = app.total_conversation_tokens;
+    let message_tokens = crate::compaction::estimate_tokens(&app.api_messages);
+    let overhead = usize::try_from(before)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(message_tokens);
+    let budget = usize::try_from(target)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(overhead);
+    let dropped = crate::compaction::trim_to_budget(&mut app.api_messages, budget);
+    if dropped == 0 {
+        return CommandResult::message(format!(
+            "Context is ~{before} tokens; nothing to trim for a {target}-token budget."
+        ));
+    }
+    app.recalculate_context_tokens();
+
+    CommandResult::with_message_and_action(
+        format!(
+            "Dropped the {dropped} oldest messages from the context (~{before} -> ~{} tokens). \
+             They stay in the transcript but are no longer sent to the model.",
+            app.total_conversation_tokens
+        ),
+        AppAction::SyncSession {
+            messages: app.api_messages.clone(),
+            system_prompt: app.system_prompt.clone(),
+            model: app.model.clone(),
+            workspace: app.workspace.clone(),
+        },
+    )
+}
 
+/// Export conversation to markdown, or to a self-contained HTML file with `--html`
+pub fn export(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let arg = arg.unwrap_or_default();
+    let Some(tokens) = shlex::split(arg) else {
+        return CommandResult::error(format!(
+            "Failed to parse export arguments: unbalanced quotes in '{arg}'"
+        ));
+    };
+    let mut html = false;
+    let mut path = None;
+    for token in tokens {
+        match token.as_str() {
+            "--html" => html = true,
+            other if other.starts_with("--") => {
+                return CommandResult::error(format!(
+                    "Unknown export option: {other}. Usage: /export [--html] [path]"
+                ));
+            }
+            _ if path.is_none() => path = Some(PathBuf::from(token)),
+            _ => return CommandResult::error("Usage: /export [--html] [path]"),
+        }
+    }
+    let html = html
+        || path
+            .as_ref()
+            .and_then(|p| p.extension())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+    let export_path = path.unwrap_or_else(|| {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let extension = if html { "html" } else { "md" };
+        PathBuf::from(format!("chat_export_{timestamp}.{extension}"))
+    });
+    let date = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let content = if html {
+        html_export::render(&app.history, &app.model, &app.workspace, &date)
+    } else {
+        markdown_export(app, &date)
+    };
+
+    match std::fs::write(&export_path, content) {
+        Ok(()) => CommandResult::message(format!("Exported to {}", export_path.display())),
+        Err(e) => CommandResult::error(format!("Failed to export: {e}")),
+    }
+}
+
+fn markdown_export(app: &App, date: &str) -> String {
     let mut content = String::new();
     content.push_str("# Chat Export\n\n");
     let _ = write!(
@@ -148,7 +472,7 @@ pub fn export(app: &mut App, path: Option<&str>) -> CommandResult {
         "**Model:** {}\n**Workspace:** {}\n**Date:** {}\n\n---\n\n",
         app.model,
         app.workspace.display(),
-        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        date
     );
 
     for cell in &app.history {
@@ -163,11 +487,7 @@ pub fn export(app: &mut App, path: Option<&str>) -> CommandResult {
 
         let _ = write!(content, "{}\n\n{}\n\n---\n\n", role, body.trim());
     }
-
-    match std::fs::write(&export_path, content) {
-        Ok(()) => CommandResult::message(format!("Exported to {}", export_path.display())),
-        Err(e) => CommandResult::error(format!("Failed to export: {e}")),
-    }
+    content
 }
 
 /// Hard reset of the session - clears everything to fresh state
@@ -191,12 +511,17 @@ pub fn reset(app: &mut App) -> CommandResult {
     // 4. Clear any queued messages
     app.queued_messages.clear();
     app.queued_draft = None;
+    app.editing_prompt = None;
 
     // 4b. Clear any active suggestions
     app.suggestion_engine.clear();
 
     // 5. Reset context/compaction state
-    app.session_cost = 0.0;
+    if let Ok(mut budget) = app.cost_budget.lock() {
+        budget.spent_usd = 0.0;
+    }
+    app.budget_blocked = false;
+    app.stats = crate::tui::app::SessionStats::new();
     app.tool_log.clear();
     app.last_prompt_tokens = None;
     app.last_completion_tokens = None;
@@ -243,3 +568,79 @@ fn line_to_string(line: ratatui::text::Line<'static>) -> String {
         .map(|span| span.content.to_string())
         .collect::<String>()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{ContentBlock, Message};
+    use crate::tui::app::TuiOptions;
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_redacts_secrets_when_enabled() {
+        let tmp = tempdir().expect("tempdir");
+        let mut app = App::new(TuiOptions::for_test(tmp.path()), &Config::default());
+        app.api_messages.push(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "my key is sk-api-abcdefghijklmnop1234".to_string(),
+                cache_control: None,
+            }],
+        });
+
+        let plain = tmp.path().join("plain.json");
+        save(&mut app, Some(plain.to_str().expect("utf-8 path")));
+        let content = std::fs::read_to_string(&plain).expect("read");
+        assert!(content.contains("sk-api-abcdefghijklmnop1234"));
+
+        app.redact_on_save = true;
+        let redacted = tmp.path().join("redacted.json");
+        save(&mut app, Some(redacted.to_str().expect("utf-8 path")));
+        let content = std::fs::read_to_string(&redacted).expect("read");
+        assert!(!content.contains("sk-api-abcdefghijklmnop1234"));
+        assert!(content.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn export_accepts_a_quoted_path_with_spaces() {
+        let tmp = tempdir().expect("tempdir");
+        let mut app = App::new(TuiOptions::for_test(tmp.path()), &Config::default());
+        let path = tmp.path().join("my notes.md");
+
+        let result = export(&mut app, Some(&format!("'{}'", path.display())));
+        let message = result.message.expect("message");
+        assert!(message.starts_with("Exported to"), "{message}");
+        assert!(path.exists());
+
+        let result = export(&mut app, Some("'unterminated"));
+        assert!(result.message.expect("message").starts_with("Error:"));
+    }
+
+    #[test]
+    fn no_save_refuses_commands_that_write_the_session() {
+        let tmp = tempdir().expect("tempdir");
+        let options = TuiOptions {
+            no_save: true,
+            ..TuiOptions::for_test(tmp.path())
+        };
+        let mut app = App::new(options, &Config::default());
+        app.api_messages.push(Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "hello".to_string(),
+                cache_control: None,
+            }],
+        });
+
+        for result in [
+            rename(&mut app, Some("title")),
+            tag(&mut app, Some("wip")),
+            fork(&mut app, None),
+        ] {
+            let message = result.message.expect("message");
+            assert!(message.contains("--no-save"), "{message}");
+        }
+        assert!(app.current_session_id.is_none());
+    }
+}
@@ -2,31 +2,47 @@
 
 use std::fmt::Write;
 
-use crate::skills::SkillRegistry;
+use crate::skills::{parse_skill_args, skill_origin};
 use crate::tui::app::App;
 use crate::tui::history::HistoryCell;
 
 use super::CommandResult;
 
-/// List all available skills
-pub fn list_skills(app: &mut App) -> CommandResult {
+/// List all available skills, or rescan the skill directories with `reload`
+pub fn list_skills(app: &mut App, arg: Option<&str>) -> CommandResult {
+    match arg.map(str::trim) {
+        None | Some("") => {}
+        Some("reload") => return reload_skills(app),
+        Some(other) => {
+            return CommandResult::error(format!(
+                "Unknown /skills argument '{other}'. Usage: /skills [reload]"
+            ));
+        }
+    }
+    let search_path = app.skill_search_path();
+    let registry = &app.skills;
     let skills_dir = app.skills_dir.clone();
-    let registry = SkillRegistry::discover(&skills_dir);
+    let locations = search_path
+        .iter()
+        .map(|(source, dir)| format!("  {} ({})", dir.display(), source.label()))
+        .collect::<Vec<_>>()
+        .join("\n");
 
     if registry.is_empty() {
         let msg = format!(
             "No skills found.\n\n\
-             Skills location: {}\n\n\
+             Skills locations:\n{}\n\n\
              To add skills, create directories with SKILL.md files:\n  \
              {}/my-skill/SKILL.md\n\n\
              Format:\n  \
              ---\n  \
              name: my-skill\n  \
              description: What this skill does\n  \
-             allowed-tools: read_file, list_dir\n  \
+             tools: [read_file, list_dir]\n  \
+             params: framework, style=unit\n  \
              ---\n\n  \
-             <instructions here>",
-            skills_dir.display(),
+             <instructions here, using {{{{framework}}}} placeholders>",
+            locations,
             skills_dir.display()
         );
         return CommandResult::message(msg);
@@ -35,33 +51,56 @@ pub fn list_skills(app: &mut App) -> CommandResult {
     let mut output = format!("Available skills ({}):\n", registry.len());
     output.push_str("─────────────────────────────\n");
     for skill in registry.list() {
-        let _ = writeln!(output, "  /{} - {}", skill.name, skill.description);
+        let _ = writeln!(
+            output,
+            "  /{} - {} [{}]",
+            skill.name,
+            skill.description,
+            skill_origin(skill)
+        );
     }
     let _ = write!(
         output,
-        "\nUse /skill <name> to run a skill\nSkills location: {}",
-        skills_dir.display()
+        "\nUse /skill <name> [key=value ...] to run a skill\nSkills locations (workspace wins on name conflicts):\n{locations}"
     );
 
     CommandResult::message(output)
 }
 
-/// Run a specific skill - activates skill for next user message
-pub fn run_skill(app: &mut App, name: Option<&str>) -> CommandResult {
-    let name = match name {
-        Some(n) => n.trim(),
-        None => {
-            return CommandResult::error("Usage: /skill <name>");
+/// Rescan the skill search path and report what changed
+fn reload_skills(app: &mut App) -> CommandResult {
+    let changes = app.reload_skills();
+    let count = app.skills.len();
+    if changes.is_empty() {
+        CommandResult::message(format!("Skills reloaded: no changes ({count} loaded)."))
+    } else {
+        CommandResult::message(format!(
+            "Skills reloaded ({count} loaded):\n{}",
+            changes.summary()
+        ))
+    }
+}
+
+/// Run a specific skill - activates skill for next user message.
+/// Arguments after the name are `key=value` parameters for the skill.
+pub fn run_skill(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let (name, args) = match arg.map(str::trim) {
+        Some(arg) if !arg.is_empty() => arg
+            .split_once(char::is_whitespace)
+            .map_or((arg, ""), |(name, rest)| (name, rest.trim())),
+        _ => {
+            return CommandResult::error("Usage: /skill <name> [key=value ...]");
         }
     };
 
-    let skills_dir = app.skills_dir.clone();
-    let registry = SkillRegistry::discover(&skills_dir);
-
-    if let Some(skill) = registry.get(name) {
+    if let Some(skill) = app.skills.get(name).cloned() {
+        let body = match parse_skill_args(args).and_then(|args| skill.render(&args)) {
+            Ok(body) => body,
+            Err(e) => return CommandResult::error(e.to_string()),
+        };
         let instruction = format!(
             "You are now using a skill. Follow these instructions:\n\n# Skill: {}\n\n{}\n\n---\n\nNow respond to the user's request following the above skill instructions.",
-            skill.name, skill.body
+            skill.name, body
         );
 
         app.add_message(HistoryCell::System {
@@ -69,13 +108,19 @@ pub fn run_skill(app: &mut App, name: Option<&str>) -> CommandResult {
         });
 
         app.active_skill = Some(instruction);
+        app.active_skill_tools = (!skill.tools.is_empty()).then(|| skill.tools.clone());
 
+        let tools = if skill.tools.is_empty() {
+            String::new()
+        } else {
+            format!("\n\nTools for that turn: {}", skill.tools.join(", "))
+        };
         CommandResult::message(format!(
-            "Skill '{}' activated.\n\nDescription: {}\n\nType your request and the skill instructions will be applied.",
+            "Skill '{}' activated.\n\nDescription: {}{tools}\n\nType your request and the skill instructions will be applied.",
             skill.name, skill.description
         ))
     } else {
-        let available: Vec<String> = registry.list().iter().map(|s| s.name.clone()).collect();
+        let available: Vec<String> = app.skills.list().iter().map(|s| s.name.clone()).collect();
 
         if available.is_empty() {
             CommandResult::error(format!(
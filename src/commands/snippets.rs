@@ -2,7 +2,7 @@
 
 use std::fmt::Write;
 
-use crate::snippets::SnippetRegistry;
+use crate::snippets::{SnippetRegistry, delete_user_snippet, save_user_snippet};
 use crate::tui::app::{App, AppAction};
 
 use super::CommandResult;
@@ -19,45 +19,70 @@ pub fn list_snippets(_app: &mut App) -> CommandResult {
         );
     }
 
-    let mut output = format!("Available snippets ({}):\n", registry.len());
-    output.push_str("─────────────────────────────\n");
-
-    for snippet in registry.list() {
-        let _ = writeln!(
-            output,
-            "  /snippet {:12} - {}",
-            snippet.name, snippet.description
-        );
+    let (builtin, user): (Vec<_>, Vec<_>) = registry
+        .list()
+        .into_iter()
+        .partition(|snippet| snippet.builtin);
+    let mut output = String::new();
+    for (title, snippets) in [("Your snippets", user), ("Built-in snippets", builtin)] {
+        if snippets.is_empty() {
+            continue;
+        }
+        let _ = writeln!(output, "{title} ({}):", snippets.len());
+        output.push_str("─────────────────────────────\n");
+        for snippet in snippets {
+            let _ = writeln!(
+                output,
+                "  /snippet {:12} - {}",
+                snippet.name, snippet.description
+            );
+        }
+        output.push('\n');
     }
 
     let _ = write!(
         output,
-        "\nUse /snippet <name> to insert a snippet\nSnippets location: {}",
+        "Use /snippet <name> to insert a snippet, /snippet save <name> to save your last prompt as one\nSnippets location: {}",
         SnippetRegistry::default_path().display()
     );
 
     if registry.is_using_defaults() {
-        output
-            .push_str("\n\n(Using built-in defaults - create ~/.axiom/snippets.toml to customize)");
+        output.push_str(
+            "\n\n(No user snippets yet - use /snippet save <name> or edit ~/.axiom/snippets.toml)",
+        );
     }
 
     CommandResult::message(output)
 }
 
-/// Insert a snippet into the input field
-pub fn insert_snippet(_app: &mut App, name: Option<&str>) -> CommandResult {
-    let name = match name {
-        Some(n) => n.trim(),
-        None => {
-            return CommandResult::error("Usage: /snippet <name>");
+/// Insert a snippet into the input field. Words after the name fill the
+/// template's `${1}`, `${2}`, ... placeholders; quote them to include spaces.
+pub fn insert_snippet(app: &mut App, arg: Option<&str>) -> CommandResult {
+    let (name, rest) = match arg.map(str::trim) {
+        Some(arg) if !arg.is_empty() => arg
+            .split_once(char::is_whitespace)
+            .map_or((arg, ""), |(name, rest)| (name, rest.trim())),
+        _ => {
+            return CommandResult::error("Usage: /snippet <name> [args...]");
         }
     };
+    match name {
+        "save" => return save_snippet(app, rest),
+        "delete" => return delete_snippet(rest),
+        _ => {}
+    }
+    let Some(args) = shlex::split(rest) else {
+        return CommandResult::error(format!(
+            "Failed to parse snippet arguments: unbalanced quotes in '{rest}'"
+        ));
+    };
 
     let registry = SnippetRegistry::load();
 
     if let Some(snippet) = registry.get(name) {
         // Return action to set the input text
-        CommandResult::action(AppAction::SetInput(snippet.template.clone()))
+        let (text, cursor) = snippet.expand(&args);
+        CommandResult::action(AppAction::SetInput { text, cursor })
     } else {
         // Not found - suggest similar names
         let similar = registry.find_similar(name, 2);
@@ -85,3 +110,56 @@ pub fn snippet_names() -> Vec<String> {
     let registry = SnippetRegistry::load();
     registry.list().iter().map(|s| s.name.clone()).collect()
 }
+
+/// `/snippet save <name> [text]`: store the text, or the last prompt sent,
+/// as a user snippet
+fn save_snippet(app: &App, arg: &str) -> CommandResult {
+    let (name, text) = arg
+        .split_once(char::is_whitespace)
+        .map_or((arg, ""), |(name, text)| (name, text.trim()));
+    if name.is_empty() {
+        return CommandResult::error(
+            "Usage: /snippet save <name> [text] (without text, saves the last prompt you sent)",
+        );
+    }
+    let template = if text.is_empty() {
+        match app.input_history.last() {
+            Some(last) => last.clone(),
+            None => {
+                return CommandResult::error(
+                    "Nothing to save yet: send a prompt first, or use /snippet save <name> <text>",
+                );
+            }
+        }
+    } else {
+        text.to_string()
+    };
+
+    let path = SnippetRegistry::default_path();
+    match save_user_snippet(&path, name, &template) {
+        Ok(replaced) => CommandResult::message(format!(
+            "{} snippet '{name}' in {}. Insert it with /snippet {name}",
+            if replaced { "Updated" } else { "Saved" },
+            path.display()
+        )),
+        Err(e) => CommandResult::error(format!("{e:#}")),
+    }
+}
+
+/// `/snippet delete <name>`: remove a user snippet
+fn delete_snippet(name: &str) -> CommandResult {
+    if name.is_empty() {
+        return CommandResult::error("Usage: /snippet delete <name>");
+    }
+    let path = SnippetRegistry::default_path();
+    match delete_user_snippet(&path, name) {
+        Ok(true) => CommandResult::message(format!("Deleted snippet '{name}'.")),
+        Ok(false) if SnippetRegistry::load().get(name).is_some_and(|s| s.builtin) => {
+            CommandResult::error(format!(
+                "'{name}' is a built-in snippet; save one with the same name to override it"
+            ))
+        }
+        Ok(false) => CommandResult::error(format!("No user snippet named '{name}'.")),
+        Err(e) => CommandResult::error(format!("{e:#}")),
+    }
+}
@@ -17,7 +17,7 @@ pub fn usage(app: &mut App) -> CommandResult {
     // Current session
     output.push_str("Current Session:\n");
     output.push_str(&format!("  Tokens used:     {}\n", app.total_tokens));
-    output.push_str(&format!("  Est. cost:       ${:.4}\n", app.session_cost));
+    output.push_str(&format!("  Est. cost:       ${:.4}\n", app.session_cost()));
     output.push_str(&format!(
         "  Messages sent:   {}\n",
         app.api_messages.len() / 2
@@ -26,19 +26,51 @@ pub struct CompactionConfig {
     pub message_threshold: usize,
     pub model: String,
     pub cache_summary: bool,
-    /// Keep this many recent messages unsummarized
+    /// Keep at least this many recent messages unsummarized; the kept tail is
+    /// extended back to the start of its turn so tool calls keep their results
     pub keep_recent: usize,
+    /// Text of pinned messages; the turns containing them are never summarized
+    pub pinned: Vec<String>,
+}
+
+/// Exchanges kept verbatim by default when compacting.
+pub const DEFAULT_KEEP_PAIRS: usize = 3;
+
+/// Context size that triggers auto-compaction when the model's window is unknown.
+pub const DEFAULT_AUTO_COMPACT_TOKENS: usize = 80_000;
+
+/// What the user wants a compaction to keep, sent along with each turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeepPolicy {
+    /// Recent user/assistant exchanges kept as-is
+    pub recent_pairs: usize,
+    /// Text of pinned messages
+    pub pinned: Vec<String>,
+    /// Estimated context size that compacts the conversation before the next
+    /// request; `None` turns auto-compaction off
+    pub auto_compact_tokens: Option<usize>,
+}
+
+impl Default for KeepPolicy {
+    fn default() -> Self {
+        Self {
+            recent_pairs: DEFAULT_KEEP_PAIRS,
+            pinned: Vec::new(),
+            auto_compact_tokens: None,
+        }
+    }
 }
 
 impl Default for CompactionConfig {
     fn default() -> Self {
         Self {
-            enabled: true,          // Enable by default for better UX
-            token_threshold: 80000, // 80K tokens ~ 320K chars
-            message_threshold: 30,  // After 30 messages
+            enabled: true,                                // Enable by default for better UX
+            token_threshold: DEFAULT_AUTO_COMPACT_TOKENS, // 80K tokens ~ 320K chars
+            message_threshold: 30,                        // After 30 messages
             model: "anthropic/claude-3-5-sonnet-20241022".to_string(),
             cache_summary: true,
-            keep_recent: 6, // Keep last 6 messages as-is
+            keep_recent: DEFAULT_KEEP_PAIRS * 2,
+            pinned: Vec::new(),
         }
     }
 }
@@ -118,6 +150,62 @@ pub fn estimate_request_tokens(
     estimate_tokens(messages) + estimate_system_tokens(system) + estimate_tools_tokens(tools)
 }
 
+/// Drop the oldest messages until the rest fit in `budget_tokens`.
+///
+/// The kept history always starts on a plain user message, so no tool result
+/// is left without its tool use, and the latest message is never dropped.
+/// Returns how many messages were removed.
+pub fn trim_to_budget(messages: &mut Vec<Message>, budget_tokens: usize) -> usize {
+    let Some(last) = messages.len().checked_sub(1) else {
+        return 0;
+    };
+    let mut total = estimate_tokens(messages);
+    let mut start = 0;
+    while total > budget_tokens && start < last {
+        total -= estimate_tokens(std::slice::from_ref(&messages[start]));
+        start += 1;
+    }
+    if start == 0 {
+        return 0;
+    }
+    while start < last && !starts_exchange(&messages[start]) {
+        start += 1;
+    }
+    messages.drain(..start);
+    start
+}
+
+/// Tokens set aside for the summary a compaction adds to the system prompt
+pub const SUMMARY_TOKEN_ALLOWANCE: usize = 1_000;
+
+/// How many of the most recent messages fit in `budget_tokens`.
+///
+/// Like `trim_to_budget`, the kept tail starts on a plain user message where
+/// possible, and at least the latest message is always kept.
+pub fn keep_recent_within(messages: &[Message], budget_tokens: usize) -> usize {
+    let mut total = 0;
+    let mut keep = 0;
+    for message in messages.iter().rev() {
+        total += estimate_tokens(std::slice::from_ref(message));
+        if total > budget_tokens && keep > 0 {
+            break;
+        }
+        keep += 1;
+    }
+    while keep > 1 && !starts_exchange(&messages[messages.len() - keep]) {
+        keep -= 1;
+    }
+    keep
+}
+
+fn starts_exchange(message: &Message) -> bool {
+    message.role == "user"
+        && !message
+            .content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::ToolResult { .. }))
+}
+
 #[allow(dead_code)]
 pub fn should_compact(messages: &[Message], config: &CompactionConfig) -> bool {
     if !config.enabled {
@@ -130,28 +218,100 @@ pub fn should_compact(messages: &[Message], config: &CompactionConfig) -> bool {
     token_estimate > config.token_threshold || message_count > config.message_threshold
 }
 
-pub async fn compact_messages(
+/// Which messages a compaction folds into the summary and which it keeps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionPlan {
+    /// Indices of messages to summarize, in order
+    pub summarized: Vec<usize>,
+    /// Indices of messages kept verbatim, in order
+    pub kept: Vec<usize>,
+}
+
+/// Decide what a compaction keeps. Whole turns (a plain user message and
+/// everything up to the next one) are kept or summarized together, so a
+/// `tool_use` is never separated from its `tool_result`. The recent turns
+/// covering `keep_recent` messages and any turn containing a pinned message
+/// are kept.
+pub fn plan_compaction(messages: &[Message], config: &CompactionConfig) -> CompactionPlan {
+    // With nothing to keep the split sits past the last message
+    let mut split = messages.len().saturating_sub(config.keep_recent);
+    while split > 0 && split < messages.len() && !starts_exchange(&messages[split]) {
+        split -= 1;
+    }
+
+    let pinned: Vec<&str> = config
+        .pinned
+        .iter()
+        .map(|pin| pin.trim())
+        .filter(|pin| !pin.is_empty())
+        .collect();
+    let mut plan = CompactionPlan::default();
+    let mut turn_start = 0;
+    while turn_start < split {
+        let mut turn_end = turn_start + 1;
+        while turn_end < split && !starts_exchange(&messages[turn_end]) {
+            turn_end += 1;
+        }
+        let turn = turn_start..turn_end;
+        let is_pinned = messages[turn.clone()]
+            .iter()
+            .any(|message| contains_pin(message, &pinned));
+        if is_pinned {
+            plan.kept.extend(turn);
+        } else {
+            plan.summarized.extend(turn);
+        }
+        turn_start = turn_end;
+    }
+    plan.kept.extend(split..messages.len());
+    plan
+}
+
+fn contains_pin(message: &Message, pinned: &[&str]) -> bool {
+    message.content.iter().any(|block| match block {
+        ContentBlock::Text { text, .. } => pinned.iter().any(|pin| text.contains(pin)),
+        _ => false,
+    })
+}
+
+/// A planned compaction and its summary, computed without changing the
+/// conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionPreview {
+    pub plan: CompactionPlan,
+    pub summary: String,
+}
+
+impl CompactionPreview {
+    /// Estimated tokens in the messages the summary replaces.
+    pub fn summarized_tokens(&self, messages: &[Message]) -> usize {
+        estimate_tokens(&pick(messages, &self.plan.summarized))
+    }
+}
+
+/// Plan a compaction and write its summary. `None` when every message is
+/// recent or pinned.
+pub async fn preview_compaction(
     client: &AnthropicClient,
     messages: &[Message],
     config: &CompactionConfig,
-) -> Result<(Vec<Message>, Option<SystemPrompt>)> {
-    if messages.is_empty() {
-        return Ok((Vec::new(), None));
+) -> Result<Option<CompactionPreview>> {
+    let plan = plan_compaction(messages, config);
+    if plan.summarized.is_empty() {
+        return Ok(None);
     }
+    let summary = create_summary(client, &pick(messages, &plan.summarized), &config.model).await?;
+    Ok(Some(CompactionPreview { plan, summary }))
+}
 
-    // Keep the last few messages as-is (use config setting)
-    let keep_recent = config.keep_recent;
-    let (to_summarize, recent) = if messages.len() <= keep_recent {
-        return Ok((messages.to_vec(), None));
-    } else {
-        let split_point = messages.len() - keep_recent;
-        (&messages[..split_point], &messages[split_point..])
-    };
-
-    // Create a summary of older messages
-    let summary = create_summary(client, to_summarize, &config.model).await?;
-
-    // Build new message list with summary as system block
+/// Replace the summarized messages with `preview`'s summary, returning the
+/// kept messages and the summary as a system prompt.
+pub fn apply_compaction(
+    messages: &[Message],
+    preview: &CompactionPreview,
+    config: &CompactionConfig,
+) -> (Vec<Message>, SystemPrompt) {
+    let summary = &preview.summary;
     let summary_block = SystemBlock {
         block_type: "text".to_string(),
         text: format!(
@@ -165,13 +325,33 @@ pub async fn compact_messages(
             None
         },
     };
+    (
+        pick(messages, &preview.plan.kept),
+        SystemPrompt::Blocks(vec![summary_block]),
+    )
+}
 
-    Ok((
-        recent.to_vec(),
-        Some(SystemPrompt::Blocks(vec![summary_block])),
-    ))
+fn pick(messages: &[Message], indices: &[usize]) -> Vec<Message> {
+    indices.iter().map(|&i| messages[i].clone()).collect()
 }
 
+pub async fn compact_messages(
+    client: &AnthropicClient,
+    messages: &[Message],
+    config: &CompactionConfig,
+) -> Result<(Vec<Message>, Option<SystemPrompt>)> {
+    match preview_compaction(client, messages, config).await? {
+        Some(preview) => {
+            let (kept, summary) = apply_compaction(messages, &preview, config);
+            Ok((kept, Some(summary)))
+        }
+        None => Ok((messages.to_vec(), None)),
+    }
+}
+
+/// Transcript characters sent for summarization (~60K tokens)
+const MAX_SUMMARY_INPUT_CHARS: usize = 240_000;
+
 async fn create_summary(
     client: &AnthropicClient,
     messages: &[Message],
@@ -207,6 +387,19 @@ async fn create_summary(
         }
     }
 
+    // A resumed session can be larger than the model's window; summarize its
+    // most recent part rather than sending a request that cannot fit
+    if conversation_text.len() > MAX_SUMMARY_INPUT_CHARS {
+        let mut cut = conversation_text.len() - MAX_SUMMARY_INPUT_CHARS;
+        while !conversation_text.is_char_boundary(cut) {
+            cut += 1;
+        }
+        conversation_text = format!(
+            "[Earlier conversation omitted]\n\n{}",
+            &conversation_text[cut..]
+        );
+    }
+
     let request = MessageRequest {
         model: model.to_string(),
         messages: vec![Message {
@@ -336,6 +529,10 @@ pub async fn maybe_compact(
 
     // Perform compaction
     let (compacted_messages, summary_prompt) = compact_messages(client, messages, config).await?;
+    if summary_prompt.is_none() {
+        // Everything left is recent or pinned
+        return Ok((messages.to_vec(), system.clone(), false));
+    }
 
     // Merge with original system prompt
     let merged_system = merge_system_prompts(system.as_ref(), summary_prompt);
@@ -351,3 +548,137 @@ pub async fn maybe_compact(
 
     Ok((compacted_messages, merged_system, true))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(role: &str, text: &str) -> Message {
+        Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+        }
+    }
+
+    fn tool_use(id: &str) -> Message {
+        Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: id.to_string(),
+                name: "read_file".to_string(),
+                input: serde_json::json!({}),
+            }],
+        }
+    }
+
+    fn tool_result(id: &str) -> Message {
+        Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: id.to_string(),
+                content: "ok".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn pinned_turns_and_tool_pairs_survive_compaction() {
+        let messages = vec![
+            text("user", "first question"),
+            text("assistant", "first answer"),
+            text("user", "remember: deploy on Fridays only"),
+            text("assistant", "noted"),
+            text("user", "third question"),
+            tool_use("t1"),
+            tool_result("t1"),
+            text("assistant", "third answer"),
+            text("user", "latest question"),
+            text("assistant", "latest answer"),
+        ];
+        let config = CompactionConfig {
+            keep_recent: 3,
+            pinned: vec!["deploy on Fridays only".to_string()],
+            ..CompactionConfig::default()
+        };
+
+        let plan = plan_compaction(&messages, &config);
+        // The pinned turn stays; the recent tail widens to a whole turn so the
+        // tool result is not separated from its tool use
+        assert_eq!(plan.summarized, vec![0, 1]);
+        assert_eq!(plan.kept, vec![2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let config = CompactionConfig {
+            keep_recent: 2,
+            ..config
+        };
+        let plan = plan_compaction(&messages, &config);
+        assert_eq!(plan.summarized, vec![0, 1, 4, 5, 6, 7]);
+        assert_eq!(plan.kept, vec![2, 3, 8, 9]);
+
+        let config = CompactionConfig {
+            keep_recent: 0,
+            ..config
+        };
+        let plan = plan_compaction(&messages, &config);
+        assert_eq!(plan.summarized, vec![0, 1, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(plan.kept, vec![2, 3]);
+    }
+
+    #[test]
+    fn keep_recent_within_fits_budget_and_starts_on_user_message() {
+        let big = "x".repeat(400);
+        let messages = vec![
+            text("user", &big),
+            text("assistant", &big),
+            text("user", &big),
+            text("assistant", &big),
+        ];
+        let one = estimate_tokens(&messages[..1]);
+        assert_eq!(keep_recent_within(&messages, one * 2), 2);
+        // Three would fit, but the tail would start on an assistant message
+        assert_eq!(keep_recent_within(&messages, one * 3), 2);
+        assert_eq!(keep_recent_within(&messages, one * 10), 4);
+        assert_eq!(keep_recent_within(&messages, 0), 1);
+    }
+
+    #[test]
+    fn trim_to_budget_drops_oldest_and_restarts_on_user_message() {
+        let big = "x".repeat(400);
+        let mut messages = vec![
+            text("user", &big),
+            Message {
+                role: "assistant".to_string(),
+                content: vec![ContentBlock::ToolUse {
+                    id: "t1".to_string(),
+                    name: "read_file".to_string(),
+                    input: serde_json::json!({}),
+                }],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "t1".to_string(),
+                    content: big.clone(),
+                }],
+            },
+            text("assistant", &big),
+            text("user", "next question"),
+            text("assistant", "answer"),
+        ];
+
+        assert_eq!(trim_to_budget(&mut messages.clone(), 10_000), 0);
+
+        // Dropping the first message alone would leave an orphaned tool result
+        let dropped = trim_to_budget(&mut messages, 250);
+        assert_eq!(dropped, 4);
+        assert_eq!(messages.len(), 2);
+        assert!(starts_exchange(&messages[0]));
+
+        let mut last_only = vec![text("user", &big)];
+        assert_eq!(trim_to_budget(&mut last_only, 1), 0);
+        assert_eq!(last_only.len(), 1);
+    }
+}
@@ -0,0 +1,377 @@
+//! Shell completion generation.
+//!
+//! Static scripts for bash, elvish, fish, powershell, and zsh come from
+//! `clap_complete`. Nushell has no generator there, so a small `extern`
+//! generator lives here. Fish and Nushell scripts also call back into the
+//! hidden `complete-values` subcommand to offer live session IDs and model names.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, Command, ValueEnum, ValueHint};
+use clap_complete::{Generator, Shell};
+
+use crate::config::Config;
+
+/// Shells that `completions` can generate scripts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    #[value(name = "powershell")]
+    PowerShell,
+    Zsh,
+}
+
+impl std::fmt::Display for CompletionShell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Bash => "bash",
+            Self::Elvish => "elvish",
+            Self::Fish => "fish",
+            Self::Nushell => "nushell",
+            Self::PowerShell => "powershell",
+            Self::Zsh => "zsh",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Values the hidden `complete-values` subcommand can list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DynamicCompletion {
+    /// Saved session IDs (for `--resume`)
+    Sessions,
+    /// Known model names (for `--model`)
+    Models,
+}
+
+impl DynamicCompletion {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Sessions => "sessions",
+            Self::Models => "models",
+        }
+    }
+
+    /// Which dynamic completion applies to an argument, keyed by its id
+    fn for_arg(arg: &Arg) -> Option<Self> {
+        match arg.get_id().as_str() {
+            "resume" => Some(Self::Sessions),
+            "model" => Some(Self::Models),
+            _ => None,
+        }
+    }
+}
+
+/// Write the completion script for `shell` to `buf`.
+pub fn generate(shell: CompletionShell, cmd: &mut Command, name: &str, buf: &mut dyn Write) {
+    match shell {
+        CompletionShell::Bash => clap_complete::generate(Shell::Bash, cmd, name, buf),
+        CompletionShell::Elvish => clap_complete::generate(Shell::Elvish, cmd, name, buf),
+        CompletionShell::PowerShell => clap_complete::generate(Shell::PowerShell, cmd, name, buf),
+        CompletionShell::Zsh => clap_complete::generate(Shell::Zsh, cmd, name, buf),
+        CompletionShell::Fish => {
+            clap_complete::generate(Shell::Fish, cmd, name, buf);
+            let _ = buf.write_all(fish_dynamic_completions(name).as_bytes());
+        }
+        CompletionShell::Nushell => clap_complete::generate(Nushell, cmd, name, buf),
+    }
+}
+
+/// Print candidates for the hidden `complete-values` subcommand, as `value\tdescription` lines.
+pub fn dynamic_candidates(kind: DynamicCompletion, config: &Config) -> Vec<String> {
+    let default_model = config.default_model.as_deref();
+    match kind {
+        DynamicCompletion::Sessions => {
+            crate::session_manager::SessionManager::new(config.session_dir())
+                .and_then(|manager| manager.list_sessions())
+                .map(|sessions| {
+                    sessions
+                        .into_iter()
+                        .map(|session| format!("{}\t{}", session.id, one_line(&session.title)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        DynamicCompletion::Models => {
+            let mut models: Vec<String> = crate::tui::model_picker::available_models()
+                .into_iter()
+                .map(|model| format!("{}\t{}", model.id, model.name))
+                .collect();
+            if let Some(default) = default_model
+                && !models.iter().any(|m| m.split('\t').next() == Some(default))
+            {
+                models.insert(0, format!("{default}\tconfigured default"));
+            }
+            models
+        }
+    }
+}
+
+/// Conventional per-user completion file location for a shell
+pub fn default_install_path(shell: CompletionShell, name: &str) -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let xdg = |var: &str, fallback: PathBuf| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .unwrap_or(fallback)
+    };
+    match shell {
+        CompletionShell::Bash => Some(
+            xdg("XDG_DATA_HOME", home.join(".local").join("share"))
+                .join("bash-completion")
+                .join("completions")
+                .join(name),
+        ),
+        CompletionShell::Zsh => Some(home.join(".zfunc").join(format!("_{name}"))),
+        CompletionShell::Fish => Some(
+            xdg("XDG_CONFIG_HOME", home.join(".config"))
+                .join("fish")
+                .join("completions")
+                .join(format!("{name}.fish")),
+        ),
+        CompletionShell::Nushell => Some(
+            xdg("XDG_CONFIG_HOME", home.join(".config"))
+                .join("nushell")
+                .join("autoload")
+                .join(format!("{name}.nu")),
+        ),
+        CompletionShell::Elvish | CompletionShell::PowerShell => None,
+    }
+}
+
+/// Extra fish completions that query `complete-values` at completion time
+fn fish_dynamic_completions(name: &str) -> String {
+    let mut out = String::from("\n# Dynamic values\n");
+    for (kind, flags) in [
+        (DynamicCompletion::Sessions, "-r --resume"),
+        (DynamicCompletion::Models, "--model"),
+    ] {
+        out.push_str(&format!(
+            "complete -c {name} -f -n \"__fish_prev_arg_in {flags}\" -a \"({name} complete-values {})\"\n",
+            kind.as_str()
+        ));
+    }
+    out
+}
+
+fn one_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Nushell `extern` completion generator
+#[derive(Debug, Clone, Copy)]
+pub struct Nushell;
+
+impl Generator for Nushell {
+    fn file_name(&self, name: &str) -> String {
+        format!("{name}.nu")
+    }
+
+    fn generate(&self, cmd: &Command, buf: &mut dyn Write) {
+        self.try_generate(cmd, buf)
+            .expect("failed to write completion file");
+    }
+
+    fn try_generate(&self, cmd: &Command, buf: &mut dyn Write) -> std::io::Result<()> {
+        let bin = cmd.get_bin_name().unwrap_or_else(|| cmd.get_name());
+        let mut defs = String::new();
+        let mut externs = String::new();
+
+        for kind in [DynamicCompletion::Sessions, DynamicCompletion::Models] {
+            defs.push_str(&format!(
+                "  def \"nu-complete {bin} {kind}\" [] {{\n    \
+                 ^{bin} complete-values {kind} | lines | split column \"\\t\" value description\n  }}\n\n",
+                kind = kind.as_str()
+            ));
+        }
+        nushell_command(cmd, bin, &mut defs, &mut externs);
+
+        write!(
+            buf,
+            "module {bin}-completions {{\n\n{defs}{externs}}}\n\nexport use {bin}-completions *\n"
+        )
+    }
+}
+
+fn nushell_command(cmd: &Command, path: &str, defs: &mut String, externs: &mut String) {
+    if let Some(about) = cmd.get_about() {
+        externs.push_str(&format!("  # {}\n", one_line(&about.to_string())));
+    }
+    externs.push_str(&format!("  export extern \"{path}\" [\n"));
+
+    for arg in cmd.get_arguments().filter(|arg| !arg.is_hide_set()) {
+        let completer = nushell_completer(arg, path, defs);
+        let line = if arg.is_positional() {
+            let name = arg.get_id().as_str().replace('_', "-");
+            let multiple = matches!(arg.get_action(), ArgAction::Append)
+                || arg
+                    .get_num_args()
+                    .is_some_and(|range| range.max_values() > 1);
+            let (prefix, suffix) = match (multiple, arg.is_required_set()) {
+                (true, _) => ("...", ""),
+                (false, true) => ("", ""),
+                (false, false) => ("", "?"),
+            };
+            format!("{prefix}{name}{suffix}: {}{completer}", nushell_type(arg))
+        } else {
+            let mut flag = match (arg.get_long(), arg.get_short()) {
+                (Some(long), Some(short)) => format!("--{long}(-{short})"),
+                (Some(long), None) => format!("--{long}"),
+                (None, Some(short)) => format!("-{short}"),
+                (None, None) => continue,
+            };
+            if arg.get_action().takes_values() {
+                flag.push_str(&format!(": {}{completer}", nushell_type(arg)));
+            }
+            flag
+        };
+        match arg.get_help() {
+            Some(help) => {
+                externs.push_str(&format!("    {line}  # {}\n", one_line(&help.to_string())))
+            }
+            None => externs.push_str(&format!("    {line}\n")),
+        }
+    }
+    externs.push_str("  ]\n\n");
+
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let sub_path = format!("{path} {}", sub.get_name());
+        nushell_command(sub, &sub_path, defs, externs);
+    }
+}
+
+fn nushell_type(arg: &Arg) -> &'static str {
+    match arg.get_value_hint() {
+        ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath => "path",
+        _ => "string",
+    }
+}
+
+/// `@"completer"` annotation for args with fixed or dynamic values
+fn nushell_completer(arg: &Arg, path: &str, defs: &mut String) -> String {
+    if let Some(kind) = DynamicCompletion::for_arg(arg) {
+        let bin = path.split(' ').next().unwrap_or(path);
+        return format!("@\"nu-complete {bin} {}\"", kind.as_str());
+    }
+    let values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| format!("\"{}\"", value.get_name()))
+        .collect();
+    if values.is_empty() || !arg.get_action().takes_values() {
+        return String::new();
+    }
+    let def_name = format!("nu-complete {path} {}", arg.get_id());
+    defs.push_str(&format!(
+        "  def \"{def_name}\" [] {{\n    [ {} ]\n  }}\n\n",
+        values.join(" ")
+    ));
+    format!("@\"{def_name}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_command() -> Command {
+        Command::new("tool")
+            .about("Sample tool")
+            .arg(
+                Arg::new("resume")
+                    .long("resume")
+                    .short('r')
+                    .help("Resume a session"),
+            )
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .action(ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("run")
+                    .about("Run things")
+                    .arg(
+                        Arg::new("level")
+                            .long("level")
+                            .value_parser(["low", "high"]),
+                    )
+                    .arg(Arg::new("target").required(true)),
+            )
+            .subcommand(Command::new("secret").hide(true))
+    }
+
+    fn render(shell: CompletionShell) -> String {
+        let mut cmd = sample_command();
+        let mut buf = Vec::new();
+        generate(shell, &mut cmd, "tool", &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn nushell_emits_externs_for_subcommands() {
+        let out = render(CompletionShell::Nushell);
+        assert!(out.contains("export extern \"tool\" ["));
+        assert!(out.contains("export extern \"tool run\" ["));
+        assert!(out.contains("--verbose\n") || out.contains("--verbose  #"));
+        assert!(out.contains("target: string"));
+        assert!(!out.contains("tool secret"));
+    }
+
+    #[test]
+    fn nushell_wires_fixed_and_dynamic_values() {
+        let out = render(CompletionShell::Nushell);
+        assert!(out.contains("--resume(-r): string@\"nu-complete tool sessions\""));
+        assert!(out.contains("^tool complete-values sessions"));
+        assert!(out.contains("--level: string@\"nu-complete tool run level\""));
+        assert!(out.contains("[ \"low\" \"high\" ]"));
+    }
+
+    #[test]
+    fn fish_includes_dynamic_values() {
+        let out = render(CompletionShell::Fish);
+        assert!(out.contains("-l level"));
+        assert!(out.contains("(tool complete-values sessions)"));
+        assert!(out.contains("(tool complete-values models)"));
+    }
+
+    #[test]
+    fn default_paths_cover_common_shells() {
+        for shell in [
+            CompletionShell::Bash,
+            CompletionShell::Zsh,
+            CompletionShell::Fish,
+            CompletionShell::Nushell,
+        ] {
+            if dirs::home_dir().is_some() {
+                assert!(default_install_path(shell, "tool").is_some(), "{shell}");
+            }
+        }
+        assert!(default_install_path(CompletionShell::PowerShell, "tool").is_none());
+    }
+
+    #[test]
+    fn session_candidates_come_from_the_configured_session_dir() {
+        use crate::session_manager::{SessionManager, create_saved_session};
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config = Config {
+            session_dir: Some(tmp.path().display().to_string()),
+            ..Config::default()
+        };
+        let session = create_saved_session(&[], "model", tmp.path(), 0, None, Vec::new());
+        SessionManager::new(config.session_dir())
+            .expect("manager")
+            .save_session(&session)
+            .expect("save");
+
+        let candidates = dynamic_candidates(DynamicCompletion::Sessions, &config);
+        assert_eq!(candidates.len(), 1);
+        assert!(candidates[0].starts_with(&session.metadata.id));
+    }
+}
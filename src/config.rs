@@ -8,8 +8,18 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 
-use crate::features::{Features, FeaturesToml, is_known_feature_key};
+use crate::client::Backend;
+use crate::features::{
+    FEATURES, FeatureToggle, Features, FeaturesToml, dependents, feature_from_key,
+    is_known_feature_key, prerequisites,
+};
 use crate::hooks::HooksConfig;
+use crate::llm_client::ApiStyle;
+use crate::tools::media::{
+    DEFAULT_AUDIO_MODEL, DEFAULT_IMAGE_MODEL, DEFAULT_MUSIC_MODEL, DEFAULT_VIDEO_MODEL, MediaConfig,
+};
+use crate::tools::shell::DEFAULT_MAX_OUTPUT_BYTES;
+use crate::tools::web_search::{SearchBackend, WebSearchConfig};
 
 // === Types ===
 
@@ -76,6 +86,12 @@ pub struct Config {
     pub default_video_model: Option<String>,
     pub default_audio_model: Option<String>,
     pub default_music_model: Option<String>,
+    /// Where message requests go: `api` (default) or `mock` for scripted offline replies
+    pub backend: Option<String>,
+    /// TOML script of canned replies for the mock backend (echoes when unset)
+    pub mock_script: Option<String>,
+    /// Wire format of the endpoint: `anthropic` (default) or `openai` (Chat Completions)
+    pub api_style: Option<String>,
 
     // === Coding API Configuration ===
     /// Second API key (optional, falls back to primary)
@@ -98,20 +114,116 @@ pub struct Config {
     pub mcp_config_path: Option<String>,
     pub notes_path: Option<String>,
     pub memory_path: Option<String>,
+    /// Where sessions are saved (default `~/.axiom/sessions`)
+    pub session_dir: Option<String>,
     pub allow_shell: Option<bool>,
     pub max_subagents: Option<usize>,
+    /// Per-stream byte cap for `exec_shell` output (default 100000)
+    pub shell_max_output_bytes: Option<usize>,
+    /// Block API calls once estimated session spend would pass this many USD
+    pub max_session_cost_usd: Option<f64>,
     pub retry: Option<RetryConfig>,
     pub features: Option<FeaturesToml>,
 
+    // === Web Search Configuration ===
+    /// Backend for `web_search`: `duckduckgo` (default), `brave`, or `searxng`
+    pub search_backend: Option<String>,
+    /// API key for the search backend (required by `brave`)
+    pub search_api_key: Option<String>,
+    /// Base URL for the search backend (required by `searxng`)
+    pub search_base_url: Option<String>,
+    /// Seconds `web_fetch` reuses a fetched page (default 300, 0 disables)
+    pub web_fetch_cache_ttl_secs: Option<u64>,
+
     /// Lifecycle hooks configuration
     #[serde(default)]
     pub hooks: Option<HooksConfig>,
 
     /// Custom model context windows (model name -> context size in tokens)
-    #[serde(default)]
+    #[serde(
+        default,
+        rename = "model_context_windows",
+        alias = "model_context_windows_raw"
+    )]
     pub model_context_windows_raw: Option<std::collections::HashMap<String, u32>>,
 }
 
+/// Where an effective config value came from, as shown by `config show`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Global(PathBuf),
+    Profile(String),
+    Project(PathBuf),
+    Env(&'static str),
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global(path) => write!(f, "global ({})", path.display()),
+            Self::Profile(name) => write!(f, "profile '{name}'"),
+            Self::Project(path) => write!(f, "project ({})", path.display()),
+            Self::Env(var) => write!(f, "env {var}"),
+        }
+    }
+}
+
+/// One effective top-level config key with its value and source.
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// Project overlay location, relative to the workspace root.
+pub const PROJECT_CONFIG_PATH: &str = ".axiom/config.toml";
+
+/// Keys a checked-in project config may not set: credentials, endpoints, and
+/// anything that runs commands or points tools at paths outside the repo.
+const PROJECT_IGNORED_KEYS: &[&str] = &[
+    "api_key",
+    "api_key_2",
+    "base_url",
+    "base_url_2",
+    "search_api_key",
+    "search_base_url",
+    "hooks",
+    "mcp_config_path",
+    "tools_file",
+    "skills_dir",
+    "notes_path",
+    "memory_path",
+    "session_dir",
+    "output_dir",
+    "profiles",
+];
+
+/// Environment variables applied by `apply_env_overrides`, with the key each sets.
+const ENV_OVERRIDE_KEYS: &[(&str, &str)] = &[
+    ("AXIOM_API_KEY", "api_key"),
+    ("AXIOM_API_KEY_2", "api_key_2"),
+    ("AXIOM_BASE_URL", "base_url"),
+    ("AXIOM_BASE_URL_2", "base_url_2"),
+    ("AXIOM_DEFAULT_CODING_MODEL", "default_coding_model"),
+    ("AXIOM_OUTPUT_DIR", "output_dir"),
+    ("AXIOM_SKILLS_DIR", "skills_dir"),
+    ("AXIOM_MCP_CONFIG", "mcp_config_path"),
+    ("AXIOM_NOTES_PATH", "notes_path"),
+    ("AXIOM_MEMORY_PATH", "memory_path"),
+    ("AXIOM_SESSION_DIR", "session_dir"),
+    ("AXIOM_ALLOW_SHELL", "allow_shell"),
+    ("AXIOM_MAX_SUBAGENTS", "max_subagents"),
+    ("AXIOM_MAX_SESSION_COST_USD", "max_session_cost_usd"),
+    ("AXIOM_MODEL_CONTEXT_WINDOWS", "model_context_windows"),
+    ("AXIOM_SEARCH_BACKEND", "search_backend"),
+    ("AXIOM_BACKEND", "backend"),
+    ("AXIOM_API_STYLE", "api_style"),
+    ("AXIOM_MOCK_SCRIPT", "mock_script"),
+    ("AXIOM_SEARCH_API_KEY", "search_api_key"),
+    ("AXIOM_SEARCH_BASE_URL", "search_base_url"),
+];
+
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ConfigFile {
     #[serde(flatten)]
@@ -132,26 +244,114 @@ impl Config {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn load(path: Option<PathBuf>, profile: Option<&str>) -> Result<Self> {
+        let workspace = std::env::current_dir().ok();
+        Self::load_for_workspace(path, profile, workspace.as_deref())
+    }
+
+    /// Load configuration, overlaying `<workspace>/.axiom/config.toml` when present.
+    ///
+    /// Precedence, lowest first: global file, selected profile, project file,
+    /// environment variables.
+    pub fn load_for_workspace(
+        path: Option<PathBuf>,
+        profile: Option<&str>,
+        workspace: Option<&Path>,
+    ) -> Result<Self> {
         let path = path.or_else(default_config_path);
         let mut config = if let Some(path) = path.as_ref() {
             if path.exists() {
                 let contents = fs::read_to_string(path)
                     .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-                let parsed: ConfigFile = toml::from_str(&contents)
+                let mut table: toml::Table = toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+                let renamed = migrate_table(&mut table);
+                if !renamed.is_empty() {
+                    warn_deprecated_once(&format!(
+                        "{} uses deprecated keys ({}); run `axiom config migrate` to update it",
+                        path.display(),
+                        renamed.join(", ")
+                    ));
+                }
+                let parsed: ConfigFile = toml::Value::Table(table)
+                    .try_into()
                     .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
                 apply_profile(parsed, profile)?
             } else {
+                require_no_profile(profile)?;
                 Config::default()
             }
         } else {
+            require_no_profile(profile)?;
             Config::default()
         };
 
+        if let Some((_, table)) = load_project_table(workspace, path.as_deref())? {
+            let project: Config = toml::Value::Table(table)
+                .try_into()
+                .context("Failed to parse project config")?;
+            config = merge_config(config, project);
+        }
+
         apply_env_overrides(&mut config);
         config.validate()?;
         Ok(config)
     }
 
+    /// Every top-level key that ends up set, with the layer that set it.
+    pub fn effective_entries(
+        path: Option<PathBuf>,
+        profile: Option<&str>,
+        workspace: Option<&Path>,
+    ) -> Result<Vec<ConfigEntry>> {
+        let path = path.or_else(default_config_path);
+        let mut entries: std::collections::BTreeMap<String, (String, ConfigSource)> =
+            std::collections::BTreeMap::new();
+        let mut apply = |table: &toml::Table, source: &ConfigSource| {
+            for (key, value) in table {
+                entries.insert(key.clone(), (display_value(key, value), source.clone()));
+            }
+        };
+
+        if let Some(path) = path.as_ref().filter(|p| p.exists()) {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+            let mut table: toml::Table = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+            migrate_table(&mut table);
+            let profiles = table.remove("profiles");
+            apply(&table, &ConfigSource::Global(path.clone()));
+            if let Some(name) = profile {
+                let Some(toml::Value::Table(profile_table)) =
+                    profiles.as_ref().and_then(|p| p.get(name))
+                else {
+                    let names = profiles
+                        .as_ref()
+                        .and_then(toml::Value::as_table)
+                        .map(|p| p.keys().cloned().collect())
+                        .unwrap_or_default();
+                    return Err(profile_not_found(name, names));
+                };
+                apply(profile_table, &ConfigSource::Profile(name.to_string()));
+            }
+        } else {
+            require_no_profile(profile)?;
+        }
+        if let Some((project_path, table)) = load_project_table(workspace, path.as_deref())? {
+            apply(&table, &ConfigSource::Project(project_path));
+        }
+        for (var, key) in ENV_OVERRIDE_KEYS {
+            if let Ok(value) = env_var(var) {
+                let value = display_value(key, &toml::Value::String(value));
+                entries.insert((*key).to_string(), (value, ConfigSource::Env(var)));
+            }
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, (value, source))| ConfigEntry { key, value, source })
+            .collect())
+    }
+
     /// Validate that critical config fields are present.
     pub fn validate(&self) -> Result<()> {
         if let Some(ref key) = self.api_key
@@ -166,6 +366,26 @@ impl Config {
                 }
             }
         }
+        if let Some(backend) = &self.backend {
+            backend
+                .parse::<Backend>()
+                .map_err(|e| anyhow::anyhow!("Invalid backend: {e}"))?;
+        }
+        if let Some(style) = &self.api_style {
+            style
+                .parse::<ApiStyle>()
+                .map_err(|e| anyhow::anyhow!("Invalid api_style: {e}"))?;
+        }
+        if let Some(backend) = &self.search_backend {
+            backend
+                .parse::<SearchBackend>()
+                .map_err(|e| anyhow::anyhow!("Invalid search_backend: {e}"))?;
+        }
+        if let Some(limit) = self.max_session_cost_usd
+            && !(limit.is_finite() && limit > 0.0)
+        {
+            anyhow::bail!("max_session_cost_usd must be a positive number, got {limit}");
+        }
         Ok(())
     }
 
@@ -179,6 +399,23 @@ impl Config {
         normalize_base_url(&base)
     }
 
+    /// Return the OpenAI-compatible base URL (normalized, ending in `/v1`).
+    #[must_use]
+    pub fn openai_base_url(&self) -> String {
+        let root = normalize_base_url(
+            &self
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://api.axiom.io".to_string()),
+        );
+        let root = root.trim_end_matches('/');
+        if root.ends_with("/v1") {
+            root.to_string()
+        } else {
+            format!("{root}/v1")
+        }
+    }
+
     /// Return the Anthropic-compatible base URL (normalized).
     #[must_use]
     pub fn anthropic_base_url(&self) -> String {
@@ -296,16 +533,35 @@ impl Config {
         windows
     }
 
-    pub fn set_feature(&mut self, key: &str, enabled: bool) -> Result<()> {
+    /// Set a feature flag. Enabling also enables its prerequisites; disabling
+    /// leaves dependents alone but reports the ones still enabled.
+    pub fn set_feature(&mut self, key: &str, enabled: bool) -> Result<FeatureToggle> {
         if !is_known_feature_key(key) {
             anyhow::bail!("Unknown feature flag: {key}");
         }
+        let current = self.features();
+        let is_enabled = |key: &str| feature_from_key(key).is_some_and(|f| current.enabled(f));
+        let mut toggle = FeatureToggle::default();
+        if enabled {
+            toggle.enabled_prerequisites = prerequisites(key)
+                .into_iter()
+                .filter(|required| !is_enabled(required))
+                .collect();
+        } else {
+            toggle.broken_dependents = dependents(key)
+                .into_iter()
+                .filter(|dependent| is_enabled(dependent))
+                .collect();
+        }
+
         let table = self.features.get_or_insert_with(FeaturesToml::default);
+        for required in &toggle.enabled_prerequisites {
+            table.entries.insert((*required).to_string(), true);
+        }
         table.entries.insert(key.to_string(), enabled);
-        Ok(())
+        Ok(toggle)
     }
 
-    #[allow(dead_code)]
     pub fn output_dir(&self) -> PathBuf {
         self.output_dir
             .as_deref()
@@ -353,16 +609,26 @@ impl Config {
             .unwrap_or_else(|| PathBuf::from("./memory.md"))
     }
 
+    /// Resolve the directory sessions are saved in.
+    #[must_use]
+    pub fn session_dir(&self) -> PathBuf {
+        self.session_dir
+            .as_deref()
+            .map(expand_path)
+            .or_else(default_session_dir)
+            .unwrap_or_else(|| PathBuf::from("./sessions"))
+    }
+
     /// Return whether shell execution is allowed.
     #[must_use]
     pub fn allow_shell(&self) -> bool {
         self.allow_shell.unwrap_or(false)
     }
 
-    /// Return the maximum number of concurrent sub-agents.
+    /// Return the maximum number of concurrent sub-agents (0 disables them).
     #[must_use]
     pub fn max_subagents(&self) -> usize {
-        self.max_subagents.unwrap_or(5).clamp(1, 5)
+        self.max_subagents.unwrap_or(5).min(5)
     }
 
     // === RLM Configuration Methods ===
@@ -483,6 +749,75 @@ impl Config {
         self.duo_config().player_temperature.unwrap_or(0.7)
     }
 
+    /// Backend that serves message requests; an unknown value falls back to
+    /// the API (`validate` reports it at load time).
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend
+            .as_deref()
+            .and_then(|backend| backend.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Wire format of the configured endpoint; an unknown value falls back to
+    /// Anthropic (`validate` reports it at load time).
+    #[must_use]
+    pub fn api_style(&self) -> ApiStyle {
+        self.api_style
+            .as_deref()
+            .and_then(|style| style.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Script of canned replies for the mock backend, if configured.
+    #[must_use]
+    pub fn mock_script_path(&self) -> Option<PathBuf> {
+        self.mock_script.as_deref().map(expand_path)
+    }
+
+    /// Per-stream byte cap for `exec_shell` output.
+    #[must_use]
+    pub fn shell_max_output_bytes(&self) -> usize {
+        self.shell_max_output_bytes
+            .unwrap_or(DEFAULT_MAX_OUTPUT_BYTES)
+    }
+
+    /// Session spending cap in USD, if one is set.
+    #[must_use]
+    pub fn max_session_cost_usd(&self) -> Option<f64> {
+        self.max_session_cost_usd
+    }
+
+    /// Resolve `web_search` settings; an unknown backend falls back to DuckDuckGo
+    /// (`validate` reports it at load time).
+    #[must_use]
+    pub fn web_search_config(&self) -> WebSearchConfig {
+        WebSearchConfig {
+            backend: self
+                .search_backend
+                .as_deref()
+                .and_then(|backend| backend.parse().ok())
+                .unwrap_or_default(),
+            api_key: self.search_api_key.clone(),
+            base_url: self.search_base_url.clone(),
+            fetch_cache_ttl_secs: self.web_fetch_cache_ttl_secs,
+        }
+    }
+
+    /// Endpoint, API key, and output directory for the multimedia tools.
+    #[must_use]
+    pub fn media_config(&self) -> MediaConfig {
+        MediaConfig {
+            api_base: self.openai_base_url(),
+            api_key: self.anthropic_api_key().ok(),
+            output_dir: self.output_dir(),
+            image_model: media_model(&self.default_image_model, DEFAULT_IMAGE_MODEL),
+            audio_model: media_model(&self.default_audio_model, DEFAULT_AUDIO_MODEL),
+            music_model: media_model(&self.default_music_model, DEFAULT_MUSIC_MODEL),
+            video_model: media_model(&self.default_video_model, DEFAULT_VIDEO_MODEL),
+        }
+    }
+
     /// Get hooks configuration, returning default if not configured.
     pub fn hooks_config(&self) -> HooksConfig {
         self.hooks.clone().unwrap_or_default()
@@ -515,8 +850,8 @@ impl Config {
 
 // === Defaults ===
 
-fn default_config_path() -> Option<PathBuf> {
-    if let Ok(path) = std::env::var("AXIOM_CONFIG_PATH")
+pub fn default_config_path() -> Option<PathBuf> {
+    if let Ok(path) = env_var("AXIOM_CONFIG_PATH")
         && !path.trim().is_empty()
     {
         return Some(PathBuf::from(path));
@@ -545,50 +880,890 @@ fn default_memory_path() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".axiom").join("memory.md"))
 }
 
+fn default_session_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".axiom").join("sessions"))
+}
+
+// === Project Overlay ===
+
+/// Path of the project config for `workspace`.
+pub fn project_config_path(workspace: &Path) -> PathBuf {
+    workspace.join(PROJECT_CONFIG_PATH)
+}
+
+/// Read the workspace's project config, dropping keys a project may not set.
+/// Returns `None` when there is no project file or it is the global file itself
+/// (e.g. when the workspace is the home directory).
+fn load_project_table(
+    workspace: Option<&Path>,
+    global_path: Option<&Path>,
+) -> Result<Option<(PathBuf, toml::Table)>> {
+    let Some(workspace) = workspace else {
+        return Ok(None);
+    };
+    let path = project_config_path(workspace);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    if let Some(global) = global_path
+        && fs::canonicalize(global).ok() == fs::canonicalize(&path).ok()
+    {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read project config: {}", path.display()))?;
+    let mut table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+    migrate_table(&mut table);
+    for key in PROJECT_IGNORED_KEYS {
+        if table.remove(*key).is_some() {
+            crate::logging::warn(format!(
+                "Ignoring `{key}` in {}: set it in the global config instead",
+                path.display()
+            ));
+        }
+    }
+    // A project may turn the shell off for everyone, but not on.
+    if table.get("allow_shell").and_then(toml::Value::as_bool) == Some(true) {
+        table.remove("allow_shell");
+        crate::logging::warn(format!(
+            "Ignoring `allow_shell = true` in {}: projects can only disable the shell",
+            path.display()
+        ));
+    }
+    Ok(Some((path, table)))
+}
+
+fn display_value(key: &str, value: &toml::Value) -> String {
+    if key.starts_with("api_key") || key.ends_with("api_key") {
+        return "********".to_string();
+    }
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// === Migration ===
+
+/// Config keys that were renamed, as `(old, new)`.
+const RENAMED_KEYS: &[(&str, &str)] = &[("default_text_model", "default_model")];
+
+/// Prefix the environment variables used before the rename to Axiom.
+const LEGACY_ENV_PREFIX: &str = "MINIMAX_";
+
+static DEPRECATION_WARNED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Print a deprecation warning the first time one comes up in this process.
+/// Later config reloads (e.g. `/reload` inside the TUI) stay quiet.
+fn warn_deprecated_once(message: &str) {
+    if !DEPRECATION_WARNED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        eprintln!("warning: {message}");
+    }
+}
+
+/// Read an `AXIOM_*` variable, falling back to its legacy `MINIMAX_*` name.
+pub fn env_var(name: &str) -> std::result::Result<String, std::env::VarError> {
+    match std::env::var(name) {
+        Err(std::env::VarError::NotPresent) => {
+            let legacy = legacy_env_name(name).ok_or(std::env::VarError::NotPresent)?;
+            let value = std::env::var(&legacy)?;
+            warn_deprecated_once(&format!(
+                "{legacy} is deprecated; rename it to {name} (run `axiom config migrate` for details)"
+            ));
+            Ok(value)
+        }
+        result => result,
+    }
+}
+
+fn legacy_env_name(name: &str) -> Option<String> {
+    name.strip_prefix("AXIOM_")
+        .map(|rest| format!("{LEGACY_ENV_PREFIX}{rest}"))
+}
+
+/// Legacy environment variables currently set, as `(old, new)`.
+pub fn legacy_env_vars_in_use() -> Vec<(String, String)> {
+    ENV_OVERRIDE_KEYS
+        .iter()
+        .map(|(var, _)| *var)
+        .map(|var| (*var).to_string())
+        .chain(["AXIOM_CONFIG_PATH".to_string(), "AXIOM_PROFILE".to_string()])
+        .chain(FEATURES.iter().map(|spec| feature_env_var(spec.key)))
+        .filter_map(|var| {
+            let legacy = legacy_env_name(&var)?;
+            std::env::var_os(&legacy).map(|_| (legacy, var))
+        })
+        .collect()
+}
+
+/// Rename deprecated keys in a parsed config (including inside profiles).
+/// If both names are present the new one wins. Returns the old names found.
+fn migrate_table(table: &mut toml::Table) -> Vec<&'static str> {
+    let mut renamed = Vec::new();
+    for (old, new) in RENAMED_KEYS {
+        if let Some(value) = table.remove(*old) {
+            table.entry(*new).or_insert(value);
+            renamed.push(*old);
+        }
+    }
+    if let Some(toml::Value::Table(profiles)) = table.get_mut("profiles") {
+        for (_, profile) in profiles.iter_mut() {
+            if let toml::Value::Table(profile) = profile {
+                for old in migrate_table(profile) {
+                    if !renamed.contains(&old) {
+                        renamed.push(old);
+                    }
+                }
+            }
+        }
+    }
+    renamed
+}
+
+/// Rewrite deprecated keys in a config file in place, keeping comments and
+/// layout. The original is saved next to it with a `.bak` suffix.
+/// Returns a description of each change; empty when the file is current.
+pub fn migrate_config_file(path: &Path) -> Result<Vec<String>> {
+    let mut doc = read_config_document(path)?;
+    let changes = migrate_document(&mut doc);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    let backup = path.with_extension("toml.bak");
+    fs::copy(path, &backup)
+        .with_context(|| format!("Failed to back up config to {}", backup.display()))?;
+    fs::write(path, doc.to_string())
+        .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+    Ok(changes)
+}
+
+/// Rename deprecated keys in an editable config (including inside profiles).
+/// If both names are present the new one wins.
+fn migrate_document(doc: &mut toml_edit::DocumentMut) -> Vec<String> {
+    let mut changes = rename_table_keys(doc.as_table_mut(), "");
+    if let Some(profiles) = doc
+        .get_mut("profiles")
+        .and_then(toml_edit::Item::as_table_mut)
+    {
+        for (name, profile) in profiles.iter_mut() {
+            if let Some(profile) = profile.as_table_mut() {
+                let prefix = format!("profiles.{}.", name.get());
+                changes.extend(rename_table_keys(profile, &prefix));
+            }
+        }
+    }
+    changes
+}
+
+/// Rename the `RENAMED_KEYS` found directly in `table`, keeping key order
+/// and each key's comments.
+fn rename_table_keys(table: &mut toml_edit::Table, prefix: &str) -> Vec<String> {
+    let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+    let mut changes = Vec::new();
+    // Re-insert every entry so a renamed key stays where it was
+    for key in &keys {
+        let Some((old, item)) = table.remove_entry(key) else {
+            continue;
+        };
+        match RENAMED_KEYS.iter().find(|(from, _)| from == key) {
+            Some((_, new)) if keys.iter().any(|k| k == new) => {
+                changes.push(format!(
+                    "{prefix}{key}: removed, {prefix}{new} is already set"
+                ));
+            }
+            Some((_, new)) => {
+                let renamed = toml_edit::Key::new(*new).with_leaf_decor(old.leaf_decor().clone());
+                table.insert_formatted(&renamed, item);
+                changes.push(format!("{prefix}{key} -> {prefix}{new}"));
+            }
+            None => {
+                table.insert_formatted(&old, item);
+            }
+        }
+    }
+    changes
+}
+
+// === Doctor Checks ===
+
+/// How serious a `doctor` config finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckLevel {
+    Warning,
+    Error,
+}
+
+/// One problem found while checking a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub level: CheckLevel,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: CheckLevel::Warning,
+            message: message.into(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: CheckLevel::Error,
+            message: message.into(),
+        }
+    }
+}
+
+/// Check a config file for `axiom doctor`: TOML syntax (with line and
+/// column), unknown or deprecated keys, whether it loads, whether the
+/// default model is known, and whether configured paths are usable.
+/// Returns no issues for a missing file, since defaults apply.
+pub fn check_config_file(path: &Path) -> Vec<ConfigIssue> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return if path.exists() {
+            vec![ConfigIssue::error(format!(
+                "Failed to read {}",
+                path.display()
+            ))]
+        } else {
+            Vec::new()
+        };
+    };
+    let mut table: toml::Table = match toml::from_str(&contents) {
+        Ok(table) => table,
+        Err(err) => return vec![ConfigIssue::error(describe_toml_error(&contents, &err))],
+    };
+
+    let mut issues = Vec::new();
+    for old in migrate_table(&mut table) {
+        let new = RENAMED_KEYS
+            .iter()
+            .find_map(|(from, to)| (*from == old).then_some(*to))
+            .unwrap_or_default();
+        issues.push(ConfigIssue::warning(format!(
+            "`{old}` is deprecated, use `{new}` (run `axiom config migrate`)"
+        )));
+    }
+    check_unknown_keys(&table, "", &mut issues);
+    if let Some(toml::Value::Table(profiles)) = table.get("profiles") {
+        for (name, profile) in profiles {
+            if let toml::Value::Table(profile) = profile {
+                check_unknown_keys(profile, &format!("profiles.{name}."), &mut issues);
+            }
+        }
+    }
+
+    let config = match Config::load(Some(path.to_path_buf()), None) {
+        Ok(config) => config,
+        Err(err) => {
+            issues.push(ConfigIssue::error(format!("{err:#}")));
+            return issues;
+        }
+    };
+    if let Some(model) = config.default_model.as_deref()
+        && !is_known_model(model, config.model_context_windows_raw.as_ref())
+    {
+        issues.push(ConfigIssue::warning(format!(
+            "default_model `{model}` is not a known model; add its context window under [model_context_windows] if it is valid"
+        )));
+    }
+    let paths = [
+        (
+            "skills_dir",
+            config.skills_dir.is_some(),
+            config.skills_dir(),
+            true,
+        ),
+        (
+            "mcp_config_path",
+            config.mcp_config_path.is_some(),
+            config.mcp_config_path(),
+            false,
+        ),
+        (
+            "notes_path",
+            config.notes_path.is_some(),
+            config.notes_path(),
+            false,
+        ),
+        (
+            "memory_path",
+            config.memory_path.is_some(),
+            config.memory_path(),
+            false,
+        ),
+        (
+            "session_dir",
+            config.session_dir.is_some(),
+            config.session_dir(),
+            true,
+        ),
+    ];
+    for (key, configured, path, is_dir) in paths {
+        if configured && let Err(problem) = check_config_path(&path, is_dir) {
+            issues.push(ConfigIssue::warning(format!(
+                "{key} {}: {problem}",
+                path.display()
+            )));
+        }
+    }
+    issues
+}
+
+/// Render a TOML parse error as `line L, column C: message`.
+fn describe_toml_error(contents: &str, err: &toml::de::Error) -> String {
+    let message = err.message().trim();
+    let Some(span) = err.span() else {
+        return message.to_string();
+    };
+    let before = &contents[..span.start.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(1, |last| last.chars().count() + 1);
+    format!("line {line}, column {column}: {message}")
+}
+
+fn check_unknown_keys(table: &toml::Table, prefix: &str, issues: &mut Vec<ConfigIssue>) {
+    let top_level = prefix.is_empty();
+    let known = struct_fields::<Config>();
+    for (key, value) in table {
+        if top_level && key == "profiles" {
+            continue;
+        }
+        if !known.contains(&key.as_str()) {
+            issues.push(ConfigIssue::warning(unknown_key_message(
+                &format!("{prefix}{key}"),
+                key,
+                known,
+            )));
+            continue;
+        }
+        let nested = match key.as_str() {
+            "retry" => struct_fields::<RetryConfig>(),
+            "rlm" => struct_fields::<RlmConfig>(),
+            "duo" => struct_fields::<DuoConfig>(),
+            _ => continue,
+        };
+        if let toml::Value::Table(section) = value {
+            for inner in section.keys() {
+                if !nested.contains(&inner.as_str()) {
+                    issues.push(ConfigIssue::warning(unknown_key_message(
+                        &format!("{prefix}{key}.{inner}"),
+                        inner,
+                        nested,
+                    )));
+                }
+            }
+        }
+    }
+}
+
+fn unknown_key_message(path: &str, key: &str, known: &[&str]) -> String {
+    match closest_key(key, known) {
+        Some(candidate) => format!("Unknown key `{path}` (did you mean `{candidate}`?)"),
+        None => format!("Unknown key `{path}`"),
+    }
+}
+
+/// The known key closest to `key`, if it is within two edits.
+fn closest_key<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Field names a `Deserialize` struct accepts, read from the derive's
+/// `deserialize_struct` call so the list never drifts from the struct.
+fn struct_fields<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    use serde::de::{self, Visitor};
+
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            Err(de::Error::custom("only struct field names are collected"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("only struct field names are collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Whether `model` is one Axiom knows the context window for.
+fn is_known_model(model: &str, custom: Option<&HashMap<String, u32>>) -> bool {
+    crate::models::context_window_for_model(model, custom).is_some()
+        || crate::tui::model_picker::available_models()
+            .iter()
+            .any(|info| info.id.eq_ignore_ascii_case(model))
+}
+
+/// Check that `path` exists with the right kind, or that its nearest existing
+/// ancestor is a writable directory so it can be created.
+fn check_config_path(path: &Path, is_dir: bool) -> std::result::Result<(), String> {
+    if path.exists() {
+        return match (is_dir, path.is_dir()) {
+            (true, false) => Err("exists but is not a directory".to_string()),
+            (false, true) => Err("is a directory, expected a file".to_string()),
+            _ => Ok(()),
+        };
+    }
+    let mut ancestor = path.parent();
+    while let Some(dir) = ancestor {
+        if dir.as_os_str().is_empty() {
+            break;
+        }
+        if dir.exists() {
+            if !dir.is_dir() {
+                return Err(format!("{} is not a directory", dir.display()));
+            }
+            let readonly = fs::metadata(dir).is_ok_and(|meta| meta.permissions().readonly());
+            return if readonly {
+                Err(format!("does not exist and {} is read-only", dir.display()))
+            } else {
+                Ok(())
+            };
+        }
+        ancestor = dir.parent();
+    }
+    Ok(())
+}
+
+/// Levenshtein distance, used to suggest the key a typo meant.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut curr_row = vec![i + 1; b_chars.len() + 1];
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            curr_row[j + 1] = (prev_row[j + 1] + 1)
+                .min(curr_row[j] + 1)
+                .min(prev_row[j] + cost);
+        }
+        prev_row = curr_row;
+    }
+    prev_row[b_chars.len()]
+}
+
+// === CLI Editing ===
+
+/// A config key addressed by `axiom config get/set/unset`: a top-level key
+/// such as `max_subagents`, or a dotted `table.key` such as `features.rlm`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigKey {
+    pub table: Option<String>,
+    pub name: String,
+}
+
+impl std::fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.table {
+            Some(table) => write!(f, "{table}.{}", self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// Tables whose keys can be addressed with a dotted key.
+const EDITABLE_TABLES: &[&str] = &["retry", "rlm", "duo", "features", "model_context_windows"];
+
+impl ConfigKey {
+    /// Parse and validate a key against the known config fields, suggesting
+    /// the closest match for typos.
+    pub fn parse(key: &str) -> Result<Self> {
+        let key = key.trim();
+        if let Some((_, new)) = RENAMED_KEYS.iter().find(|(old, _)| *old == key) {
+            anyhow::bail!("`{key}` has been renamed to `{new}`");
+        }
+        let top_level: Vec<&str> = struct_fields::<Config>().to_vec();
+        let Some((table, name)) = key.split_once('.') else {
+            if EDITABLE_TABLES.contains(&key) || key == "hooks" {
+                anyhow::bail!("`{key}` is a table; set one of its keys, e.g. `{key}.<name>`");
+            }
+            if !top_level.contains(&key) {
+                anyhow::bail!(unknown_config_key(key, &top_level));
+            }
+            return Ok(Self {
+                table: None,
+                name: key.to_string(),
+            });
+        };
+
+        let known: Vec<&str> = match table {
+            "retry" => struct_fields::<RetryConfig>().to_vec(),
+            "rlm" => struct_fields::<RlmConfig>().to_vec(),
+            "duo" => struct_fields::<DuoConfig>().to_vec(),
+            "features" => FEATURES.iter().map(|spec| spec.key).collect(),
+            "model_context_windows" if !name.is_empty() && !name.contains('.') => {
+                return Ok(Self {
+                    table: Some(table.to_string()),
+                    name: name.to_string(),
+                });
+            }
+            "hooks" | "profiles" => {
+                anyhow::bail!("`{table}` cannot be edited with `config set`; edit the file instead")
+            }
+            _ => {
+                let tables: Vec<String> = EDITABLE_TABLES
+                    .iter()
+                    .map(|t| format!("{t}.{name}"))
+                    .collect();
+                let candidates: Vec<&str> = tables.iter().map(String::as_str).collect();
+                anyhow::bail!(unknown_config_key(key, &candidates));
+            }
+        };
+        if !known.contains(&name) {
+            let candidates: Vec<String> = known.iter().map(|k| format!("{table}.{k}")).collect();
+            let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+            anyhow::bail!(unknown_config_key(key, &candidates));
+        }
+        Ok(Self {
+            table: Some(table.to_string()),
+            name: name.to_string(),
+        })
+    }
+}
+
+fn unknown_config_key(key: &str, known: &[&str]) -> String {
+    match closest_key(key, known) {
+        Some(candidate) => format!("Unknown config key `{key}` (did you mean `{candidate}`?)"),
+        None => format!("Unknown config key `{key}`; see docs/CONFIGURATION.md"),
+    }
+}
+
+/// Read a key's value from the config file at `path`, if set there.
+pub fn get_config_value(path: &Path, key: &ConfigKey) -> Result<Option<toml::Value>> {
+    let doc = read_config_document(path)?;
+    let table = match &key.table {
+        Some(name) => doc.get(name).and_then(toml_edit::Item::as_table_like),
+        None => Some(doc.as_table() as &dyn toml_edit::TableLike),
+    };
+    table
+        .and_then(|table| table.get(&key.name))
+        .and_then(toml_edit::Item::as_value)
+        .map(plain_value)
+        .transpose()
+}
+
+/// Set a key in the config file, creating the file if needed and keeping the
+/// rest of it (comments included) as is. `raw` is read as a TOML value
+/// (`true`, `3`, `0.5`, `"text"`) and falls back to a plain string.
+/// Returns the value written.
+pub fn set_config_value(path: &Path, key: &ConfigKey, raw: &str) -> Result<toml::Value> {
+    let string = toml_edit::Value::from(raw);
+    let typed = raw.parse::<toml_edit::Value>().ok();
+    let mut last_error = None;
+    for value in typed.into_iter().chain(std::iter::once(string)) {
+        let mut doc = read_config_document(path)?;
+        set_document_value(&mut doc, key, value.clone())?;
+        let updated = doc.to_string();
+        match check_edited_config(&updated) {
+            Ok(()) => {
+                ensure_parent_dir(path)?;
+                fs::write(path, updated)
+                    .with_context(|| format!("Failed to write config to {}", path.display()))?;
+                return plain_value(&value);
+            }
+            Err(err) => last_error = Some(err),
+        }
+    }
+    let err = last_error.expect("at least one candidate value");
+    Err(err.context(format!("Failed to set {key} to {raw}")))
+}
+
+/// Remove a key from the config file. Returns false when it was not set.
+pub fn unset_config_value(path: &Path, key: &ConfigKey) -> Result<bool> {
+    if get_config_value(path, key)?.is_none() {
+        return Ok(false);
+    }
+    let mut doc = read_config_document(path)?;
+    let table = match &key.table {
+        Some(name) => doc
+            .get_mut(name)
+            .and_then(toml_edit::Item::as_table_like_mut),
+        None => Some(doc.as_table_mut() as &mut dyn toml_edit::TableLike),
+    };
+    if table.and_then(|table| table.remove(&key.name)).is_none() {
+        return Ok(false);
+    }
+    fs::write(path, doc.to_string())
+        .with_context(|| format!("Failed to write config to {}", path.display()))?;
+    Ok(true)
+}
+
+/// Read the config file as an editable document, so edits keep the user's
+/// comments and layout. A missing file reads as an empty document.
+fn read_config_document(path: &Path) -> Result<toml_edit::DocumentMut> {
+    if !path.exists() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    contents
+        .parse()
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))
+}
+
+/// Set one key in `doc`. A replaced value keeps its inline comment; a missing
+/// table is appended at the end.
+fn set_document_value(
+    doc: &mut toml_edit::DocumentMut,
+    key: &ConfigKey,
+    mut value: toml_edit::Value,
+) -> Result<()> {
+    let has_content = !doc.as_table().is_empty();
+    let table = match &key.table {
+        Some(name) => doc
+            .entry(name)
+            .or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                if has_content {
+                    table.decor_mut().set_prefix("\n");
+                }
+                toml_edit::Item::Table(table)
+            })
+            .as_table_like_mut()
+            .with_context(|| format!("Failed to set {key}: `{name}` is not a table"))?,
+        None => doc.as_table_mut() as &mut dyn toml_edit::TableLike,
+    };
+    match table.get_mut(&key.name) {
+        Some(toml_edit::Item::Value(existing)) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        Some(_) => anyhow::bail!("Failed to set {key}: it is a table in the config"),
+        None => {
+            table.insert(&key.name, toml_edit::Item::Value(value));
+        }
+    }
+    Ok(())
+}
+
+/// Convert an editable value into a plain `toml::Value`.
+fn plain_value(value: &toml_edit::Value) -> Result<toml::Value> {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    let mut table: toml::Table =
+        toml::from_str(&format!("value = {value}")).context("Failed to read config value")?;
+    table.remove("value").context("Failed to read config value")
+}
+
+/// Check that edited config text still parses and passes validation.
+fn check_edited_config(contents: &str) -> Result<()> {
+    let mut table: toml::Table =
+        toml::from_str(contents).context("Failed to parse edited config")?;
+    migrate_table(&mut table);
+    let parsed: ConfigFile = toml::Value::Table(table)
+        .try_into()
+        .context("Failed to parse edited config")?;
+    parsed.base.validate()
+}
+
+// === Profiles ===
+
+/// A `[profiles.<name>]` section and the keys it overrides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub keys: Vec<String>,
+}
+
+/// Profiles defined in the config file at `path`, sorted by name.
+pub fn list_profiles(path: &Path) -> Result<Vec<ProfileSummary>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let mut table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    migrate_table(&mut table);
+    let Some(toml::Value::Table(profiles)) = table.remove("profiles") else {
+        return Ok(Vec::new());
+    };
+    let mut summaries: Vec<ProfileSummary> = profiles
+        .into_iter()
+        .map(|(name, profile)| ProfileSummary {
+            name,
+            keys: profile
+                .as_table()
+                .map(|t| t.keys().cloned().collect())
+                .unwrap_or_default(),
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(summaries)
+}
+
+/// Append an empty `[profiles.<name>]` section to the config file, creating
+/// the file if needed. The profile inherits every top-level setting until
+/// keys are added to it.
+pub fn create_profile(path: &Path, name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        anyhow::bail!("Invalid profile name '{name}': use letters, digits, `-`, and `_` only");
+    }
+    if list_profiles(path)?
+        .iter()
+        .any(|profile| profile.name == name)
+    {
+        anyhow::bail!("Profile '{name}' already exists in {}", path.display());
+    }
+
+    let mut contents = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?
+    } else {
+        String::new()
+    };
+    if !contents.is_empty() {
+        if !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push('\n');
+    }
+    contents.push_str(&format!(
+        "[profiles.{name}]\n\
+         # Inherits the top-level settings; add keys here to override them, e.g.\n\
+         # default_model = \"model-01\"\n"
+    ));
+    ensure_parent_dir(path)?;
+    fs::write(path, contents)
+        .with_context(|| format!("Failed to write config to {}", path.display()))?;
+    Ok(())
+}
+
 // === Environment Overrides ===
 
 fn apply_env_overrides(config: &mut Config) {
-    if let Ok(value) = std::env::var("AXIOM_API_KEY") {
+    if let Ok(value) = env_var("AXIOM_API_KEY") {
         config.api_key = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_API_KEY_2") {
+    if let Ok(value) = env_var("AXIOM_API_KEY_2") {
         config.api_key_2 = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_BASE_URL") {
+    if let Ok(value) = env_var("AXIOM_BASE_URL") {
         config.base_url = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_BASE_URL_2") {
+    if let Ok(value) = env_var("AXIOM_BASE_URL_2") {
         config.base_url_2 = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_DEFAULT_CODING_MODEL") {
+    if let Ok(value) = env_var("AXIOM_DEFAULT_CODING_MODEL") {
         config.default_coding_model = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_OUTPUT_DIR") {
+    if let Ok(value) = env_var("AXIOM_OUTPUT_DIR") {
         config.output_dir = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_SKILLS_DIR") {
+    if let Ok(value) = env_var("AXIOM_SKILLS_DIR") {
         config.skills_dir = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_MCP_CONFIG") {
+    if let Ok(value) = env_var("AXIOM_MCP_CONFIG") {
         config.mcp_config_path = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_NOTES_PATH") {
+    if let Ok(value) = env_var("AXIOM_NOTES_PATH") {
         config.notes_path = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_MEMORY_PATH") {
+    if let Ok(value) = env_var("AXIOM_MEMORY_PATH") {
         config.memory_path = Some(value);
     }
-    if let Ok(value) = std::env::var("AXIOM_ALLOW_SHELL") {
+    if let Ok(value) = env_var("AXIOM_SESSION_DIR") {
+        config.session_dir = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_ALLOW_SHELL") {
         config.allow_shell = Some(value == "1" || value.eq_ignore_ascii_case("true"));
     }
-    if let Ok(value) = std::env::var("AXIOM_MAX_SUBAGENTS")
+    if let Ok(value) = env_var("AXIOM_MAX_SUBAGENTS")
         && let Ok(parsed) = value.parse::<usize>()
     {
-        config.max_subagents = Some(parsed.clamp(1, 5));
+        config.max_subagents = Some(parsed.min(5));
+    }
+    if let Ok(value) = env_var("AXIOM_MAX_SESSION_COST_USD")
+        && let Ok(parsed) = value.parse::<f64>()
+    {
+        config.max_session_cost_usd = Some(parsed);
     }
-    if let Ok(value) = std::env::var("AXIOM_MODEL_CONTEXT_WINDOWS") {
+    if let Ok(value) = env_var("AXIOM_MODEL_CONTEXT_WINDOWS") {
         config.model_context_windows_raw = Some(parse_model_context_windows(&value));
     }
+    if let Ok(value) = env_var("AXIOM_SEARCH_BACKEND") {
+        config.search_backend = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_BACKEND") {
+        config.backend = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_API_STYLE") {
+        config.api_style = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_MOCK_SCRIPT") {
+        config.mock_script = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_SEARCH_API_KEY") {
+        config.search_api_key = Some(value);
+    }
+    if let Ok(value) = env_var("AXIOM_SEARCH_BASE_URL") {
+        config.search_base_url = Some(value);
+    }
+    for spec in FEATURES {
+        if let Ok(value) = env_var(&feature_env_var(spec.key))
+            && let Some(enabled) = parse_env_flag(&value)
+        {
+            config
+                .features
+                .get_or_insert_with(FeaturesToml::default)
+                .entries
+                .insert(spec.key.to_string(), enabled);
+        }
+    }
+}
+
+/// `AXIOM_FEATURE_<KEY>` variable that sets a feature flag, e.g. `AXIOM_FEATURE_WEB_SEARCH`.
+fn feature_env_var(key: &str) -> String {
+    format!("AXIOM_FEATURE_{}", key.to_ascii_uppercase())
+}
+
+fn parse_env_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
 }
 
 fn normalize_base_url(base: &str) -> String {
@@ -626,22 +1801,10 @@ fn apply_profile(config: ConfigFile, profile: Option<&str>) -> Result<Config> {
         match profiles.and_then(|profiles| profiles.get(profile_name)) {
             Some(override_cfg) => Ok(merge_config(config.base, override_cfg.clone())),
             None => {
-                let available = profiles
-                    .map(|profiles| {
-                        let mut keys = profiles.keys().cloned().collect::<Vec<_>>();
-                        keys.sort();
-                        if keys.is_empty() {
-                            "none".to_string()
-                        } else {
-                            keys.join(", ")
-                        }
-                    })
-                    .unwrap_or_else(|| "none".to_string());
-                anyhow::bail!(
-                    "Profile '{}' not found. Available profiles: {}",
-                    profile_name,
-                    available
-                )
+                let names = profiles
+                    .map(|profiles| profiles.keys().cloned().collect())
+                    .unwrap_or_default();
+                Err(profile_not_found(profile_name, names))
             }
         }
     } else {
@@ -649,6 +1812,36 @@ fn apply_profile(config: ConfigFile, profile: Option<&str>) -> Result<Config> {
     }
 }
 
+/// Without a config file no profile exists, so selecting one is an error.
+fn require_no_profile(profile: Option<&str>) -> Result<()> {
+    match profile {
+        Some(name) => Err(profile_not_found(name, Vec::new())),
+        None => Ok(()),
+    }
+}
+
+fn profile_not_found(name: &str, mut names: Vec<String>) -> anyhow::Error {
+    names.sort();
+    let available = if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    };
+    anyhow::anyhow!(
+        "Profile '{name}' not found. Available profiles: {available} \
+         (create one with `axiom config create-profile {name}`)"
+    )
+}
+
+fn media_model(configured: &Option<String>, default: &str) -> String {
+    configured
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .unwrap_or(default)
+        .to_string()
+}
+
 fn merge_config(base: Config, override_cfg: Config) -> Config {
     Config {
         api_key: override_cfg.api_key.or(base.api_key),
@@ -666,6 +1859,9 @@ fn merge_config(base: Config, override_cfg: Config) -> Config {
         default_music_model: override_cfg
             .default_music_model
             .or(base.default_music_model),
+        backend: override_cfg.backend.or(base.backend),
+        mock_script: override_cfg.mock_script.or(base.mock_script),
+        api_style: override_cfg.api_style.or(base.api_style),
 
         // Coding API configuration
         api_key_2: override_cfg.api_key_2.or(base.api_key_2),
@@ -687,10 +1883,23 @@ fn merge_config(base: Config, override_cfg: Config) -> Config {
         mcp_config_path: override_cfg.mcp_config_path.or(base.mcp_config_path),
         notes_path: override_cfg.notes_path.or(base.notes_path),
         memory_path: override_cfg.memory_path.or(base.memory_path),
+        session_dir: override_cfg.session_dir.or(base.session_dir),
         allow_shell: override_cfg.allow_shell.or(base.allow_shell),
         max_subagents: override_cfg.max_subagents.or(base.max_subagents),
+        shell_max_output_bytes: override_cfg
+            .shell_max_output_bytes
+            .or(base.shell_max_output_bytes),
+        max_session_cost_usd: override_cfg
+            .max_session_cost_usd
+            .or(base.max_session_cost_usd),
         retry: override_cfg.retry.or(base.retry),
         features: override_cfg.features.or(base.features),
+        search_backend: override_cfg.search_backend.or(base.search_backend),
+        search_api_key: override_cfg.search_api_key.or(base.search_api_key),
+        search_base_url: override_cfg.search_base_url.or(base.search_base_url),
+        web_fetch_cache_ttl_secs: override_cfg
+            .web_fetch_cache_ttl_secs
+            .or(base.web_fetch_cache_ttl_secs),
         hooks: override_cfg.hooks.or(base.hooks),
         model_context_windows_raw: override_cfg
             .model_context_windows_raw
@@ -778,6 +1987,38 @@ pub fn save_config_with_base_url(api_key: &str, base_url: &str) -> Result<PathBu
     Ok(config_path)
 }
 
+/// Starting `config.toml` written by `axiom doctor --fix`.
+const DEFAULT_CONFIG_TEMPLATE: &str = r#"# Axiom CLI Configuration
+# See docs/CONFIGURATION.md for every key.
+
+# api_key = "..."            # or set AXIOM_API_KEY
+# base_url = "https://api.axiom.io"
+
+default_model = "model-01"
+allow_shell = false
+max_subagents = 5
+"#;
+
+/// Write the default config template to `path`.
+///
+/// Leaves an existing file alone unless `force` is set, in which case the
+/// previous version is kept next to it with a `.bak` suffix.
+/// Returns whether the file was written.
+pub fn write_default_config(path: &Path, force: bool) -> Result<bool> {
+    if path.exists() {
+        if !force {
+            return Ok(false);
+        }
+        let backup = path.with_extension("toml.bak");
+        fs::copy(path, &backup)
+            .with_context(|| format!("Failed to back up config to {}", backup.display()))?;
+    }
+    ensure_parent_dir(path)?;
+    fs::write(path, DEFAULT_CONFIG_TEMPLATE)
+        .with_context(|| format!("Failed to write config to {}", path.display()))?;
+    Ok(true)
+}
+
 /// Check if an API key is configured (either in config or environment)
 pub fn has_api_key(config: &Config) -> bool {
     config.api_key.is_some()
@@ -911,6 +2152,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn feature_env_vars_override_config_and_yield_to_cli_toggles() -> Result<()> {
+        use crate::features::Feature;
+
+        let _lock = env_lock().lock().unwrap();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let temp_root = env::temp_dir().join(format!(
+            "axiom-cli-feature-env-test-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(temp_root.join(".axiom"))?;
+        let _guard = EnvGuard::new(&temp_root);
+        fs::write(
+            temp_root.join(".axiom").join("config.toml"),
+            "[features]\nmultimedia = false\n",
+        )?;
+
+        // Safety: test-only environment mutation guarded by a global mutex.
+        unsafe {
+            env::set_var("AXIOM_FEATURE_MULTIMEDIA", "1");
+            env::set_var("MINIMAX_FEATURE_WEB_SEARCH", "off");
+            env::set_var("AXIOM_FEATURE_DUO", "maybe");
+        }
+        let loaded = Config::load_for_workspace(None, None, None);
+        // Safety: test-only environment mutation guarded by a global mutex.
+        unsafe {
+            env::remove_var("AXIOM_FEATURE_MULTIMEDIA");
+            env::remove_var("MINIMAX_FEATURE_WEB_SEARCH");
+            env::remove_var("AXIOM_FEATURE_DUO");
+        }
+
+        let mut config = loaded?;
+        let features = config.features();
+        assert!(features.enabled(Feature::Multimedia));
+        assert!(!features.enabled(Feature::WebSearch));
+        assert!(features.enabled(Feature::Duo));
+
+        config.set_feature("web_search", true)?;
+        assert!(config.features().enabled(Feature::WebSearch));
+        Ok(())
+    }
+
     #[test]
     fn test_tilde_expansion_in_paths() -> Result<()> {
         let _lock = env_lock().lock().unwrap();
@@ -938,6 +2225,19 @@ mod tests {
             expected_skills.components().collect::<Vec<_>>()
         );
 
+        let config = Config {
+            session_dir: Some("~/private/sessions".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.session_dir().components().collect::<Vec<_>>(),
+            expected_home
+                .join("private")
+                .join("sessions")
+                .components()
+                .collect::<Vec<_>>()
+        );
+
         let absolute_path = temp_root.join("absolute-path");
         let absolute_str = absolute_path.to_string_lossy().to_string();
         let config = Config {
@@ -1022,10 +2322,381 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn max_session_cost_must_be_positive() {
+        let config = Config {
+            max_session_cost_usd: Some(2.5),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.max_session_cost_usd(), Some(2.5));
+
+        for bad in [0.0, -1.0, f64::NAN] {
+            let config = Config {
+                max_session_cost_usd: Some(bad),
+                ..Default::default()
+            };
+            assert!(config.validate().is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn search_backend_is_validated_and_resolved() {
+        let config = Config {
+            search_backend: Some("Brave".to_string()),
+            search_api_key: Some("brave-key".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let search = config.web_search_config();
+        assert_eq!(search.backend, SearchBackend::Brave);
+        assert_eq!(search.api_key.as_deref(), Some("brave-key"));
+        assert_eq!(
+            display_value("search_api_key", &toml::Value::String("brave-key".into())),
+            "********"
+        );
+
+        let config = Config {
+            search_backend: Some("bing".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert_eq!(
+            Config::default().web_search_config().backend,
+            SearchBackend::DuckDuckGo
+        );
+    }
+
     #[test]
     fn test_missing_api_key_allowed() -> Result<()> {
         let config = Config::default();
         config.validate()?;
         Ok(())
     }
+
+    #[test]
+    fn set_feature_enables_prerequisites_and_reports_dependents() -> Result<()> {
+        use crate::features::Feature;
+
+        let mut config = Config::default();
+        config.set_feature("shell_tool", false)?;
+        config.set_feature("exec_policy", false)?;
+
+        let toggle = config.set_feature("exec_policy", true)?;
+        assert_eq!(toggle.enabled_prerequisites, vec!["shell_tool"]);
+        assert!(config.features().enabled(Feature::ShellTool));
+
+        let toggle = config.set_feature("shell_tool", false)?;
+        assert_eq!(toggle.broken_dependents, vec!["exec_policy"]);
+        assert!(!config.features().enabled(Feature::ShellTool));
+        assert!(config.features().enabled(Feature::ExecPolicy));
+
+        assert!(config.set_feature("warp_drive", true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn project_config_overlays_global_but_not_credentials() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let global = tmp.path().join("global.toml");
+        fs::write(
+            &global,
+            "api_key = \"global-key\"\ndefault_model = \"global-model\"\nmax_subagents = 2\n",
+        )?;
+        let workspace = tmp.path().join("repo");
+        let project = project_config_path(&workspace);
+        ensure_parent_dir(&project)?;
+        fs::write(
+            &project,
+            "default_model = \"project-model\"\nbase_url = \"https://evil.example\"\n\
+             allow_shell = true\n[hooks]\nenabled = true\n",
+        )?;
+
+        let table = load_project_table(Some(&workspace), Some(&global))?
+            .expect("project table")
+            .1;
+        assert_eq!(
+            table.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["default_model"]
+        );
+
+        let entries = Config::effective_entries(Some(global.clone()), None, Some(&workspace))?;
+        let find = |key: &str| entries.iter().find(|e| e.key == key).cloned();
+        let model = find("default_model").expect("default_model");
+        assert_eq!(model.value, "project-model");
+        assert_eq!(model.source, ConfigSource::Project(project.clone()));
+        assert_eq!(
+            find("max_subagents").expect("max_subagents").source,
+            ConfigSource::Global(global.clone())
+        );
+        assert!(find("base_url").is_none_or(|e| !matches!(e.source, ConfigSource::Project(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn project_config_is_skipped_when_it_is_the_global_file() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let global = project_config_path(tmp.path());
+        ensure_parent_dir(&global)?;
+        fs::write(&global, "default_model = \"m\"\n")?;
+        assert!(load_project_table(Some(tmp.path()), Some(&global))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn deprecated_keys_are_mapped_on_load() {
+        let mut table: toml::Table = toml::from_str(
+            "default_text_model = \"old\"\n[model_context_windows_raw]\n\"m\" = 1000\n\
+             [profiles.work]\ndefault_text_model = \"work\"\n",
+        )
+        .unwrap();
+        let renamed = migrate_table(&mut table);
+        assert_eq!(renamed, vec!["default_text_model"]);
+
+        let parsed: ConfigFile = toml::Value::Table(table).try_into().unwrap();
+        assert_eq!(parsed.base.default_model.as_deref(), Some("old"));
+        assert_eq!(
+            parsed.base.model_context_windows_raw.unwrap().get("m"),
+            Some(&1000)
+        );
+        let work = apply_profile(
+            ConfigFile {
+                base: Config::default(),
+                profiles: parsed.profiles,
+            },
+            Some("work"),
+        )
+        .unwrap();
+        assert_eq!(work.default_model.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn check_config_file_reports_syntax_errors_with_position() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("config.toml");
+        fs::write(&path, "default_model = \"m\"\nmax_subagents = = 3\n")?;
+        let issues = check_config_file(&path);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].level, CheckLevel::Error);
+        assert!(
+            issues[0].message.starts_with("line 2, column 17: "),
+            "{}",
+            issues[0].message
+        );
+
+        assert!(check_config_file(&tmp.path().join("missing.toml")).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn check_config_file_flags_typos_models_and_paths() -> Result<()> {
+        let _lock = env_lock().lock().unwrap();
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("config.toml");
+        let blocker = tmp.path().join("file");
+        fs::write(&blocker, "")?;
+        fs::write(
+            &path,
+            format!(
+                "default_text_model = \"mystery-model\"\nallow_shel = true\n\
+                 skills_dir = \"{}\"\nnotes_path = \"{}\"\n\
+                 [retry]\nmax_retrys = 2\n[profiles.work]\nbase_ulr = \"x\"\n",
+                blocker.display(),
+                tmp.path().join("new/notes.txt").display()
+            ),
+        )?;
+
+        let messages: Vec<String> = check_config_file(&path)
+            .into_iter()
+            .inspect(|issue| assert_eq!(issue.level, CheckLevel::Warning))
+            .map(|issue| issue.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "`default_text_model` is deprecated, use `default_model` (run `axiom config migrate`)"
+                    .to_string(),
+                "Unknown key `allow_shel` (did you mean `allow_shell`?)".to_string(),
+                "Unknown key `retry.max_retrys` (did you mean `max_retries`?)".to_string(),
+                "Unknown key `profiles.work.base_ulr` (did you mean `base_url`?)".to_string(),
+                "default_model `mystery-model` is not a known model; add its context window under [model_context_windows] if it is valid"
+                    .to_string(),
+                format!("skills_dir {}: exists but is not a directory", blocker.display()),
+            ]
+        );
+
+        fs::write(&path, "default_model = \"model-01\"\n")?;
+        assert!(check_config_file(&path).is_empty());
+        fs::write(&path, "[model_context_windows_raw]\n\"m\" = 1000\n")?;
+        assert!(check_config_file(&path).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn write_default_config_never_overwrites_without_force() -> Result<()> {
+        let _lock = env_lock().lock().unwrap();
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("nested/config.toml");
+        assert!(write_default_config(&path, false)?);
+        let template = fs::read_to_string(&path)?;
+        assert!(toml::from_str::<Config>(&template).is_ok());
+        assert!(check_config_file(&path).is_empty());
+
+        fs::write(&path, "api_key = \"mine\"\n")?;
+        assert!(!write_default_config(&path, false)?);
+        assert_eq!(fs::read_to_string(&path)?, "api_key = \"mine\"\n");
+
+        assert!(write_default_config(&path, true)?);
+        assert_eq!(fs::read_to_string(&path)?, template);
+        assert_eq!(
+            fs::read_to_string(path.with_extension("toml.bak"))?,
+            "api_key = \"mine\"\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_document_keeps_comments_and_layout() {
+        let input = "# models\ndefault_text_model = \"a\" # inline\nmax_subagents = 2\n\n\
+                     [model_context_windows]\n\"m\" = 1\n\n\
+                     [profiles.work]\ndefault_text_model = \"b\"\ndefault_model = \"c\"\n";
+        let mut doc: toml_edit::DocumentMut = input.parse().unwrap();
+        let changes = migrate_document(&mut doc);
+        assert_eq!(
+            doc.to_string(),
+            "# models\ndefault_model = \"a\" # inline\nmax_subagents = 2\n\n\
+             [model_context_windows]\n\"m\" = 1\n\n\
+             [profiles.work]\ndefault_model = \"c\"\n"
+        );
+        assert_eq!(
+            changes,
+            [
+                "default_text_model -> default_model",
+                "profiles.work.default_text_model: removed, profiles.work.default_model is already set",
+            ]
+        );
+
+        let mut current: toml_edit::DocumentMut = "default_model = \"a\"\n".parse().unwrap();
+        assert!(migrate_document(&mut current).is_empty());
+        assert_eq!(current.to_string(), "default_model = \"a\"\n");
+    }
+
+    #[test]
+    fn config_key_parse_validates_and_suggests() {
+        let key = ConfigKey::parse("features.rlm").unwrap();
+        assert_eq!(key.table.as_deref(), Some("features"));
+        assert_eq!(key.to_string(), "features.rlm");
+        assert!(ConfigKey::parse("max_subagents").unwrap().table.is_none());
+
+        let err = ConfigKey::parse("max_subagent").unwrap_err().to_string();
+        assert!(err.contains("did you mean `max_subagents`?"), "{err}");
+        let err = ConfigKey::parse("retry.max_retrys")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("did you mean `retry.max_retries`?"), "{err}");
+        assert!(ConfigKey::parse("features").is_err());
+        assert!(ConfigKey::parse("hooks.on_start").is_err());
+    }
+
+    #[test]
+    fn set_and_unset_config_value_keep_comments() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("config.toml");
+        fs::write(
+            &path,
+            "# my config\ndefault_model = \"a\"\n\n[retry]\n# retries\nenabled = true\n",
+        )?;
+
+        let written = set_config_value(&path, &ConfigKey::parse("max_subagents")?, "3")?;
+        assert_eq!(written, toml::Value::Integer(3));
+        set_config_value(&path, &ConfigKey::parse("retry.enabled")?, "false")?;
+        set_config_value(&path, &ConfigKey::parse("features.rlm")?, "true")?;
+        set_config_value(&path, &ConfigKey::parse("default_model")?, "model-01")?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "# my config\ndefault_model = \"model-01\"\nmax_subagents = 3\n\n[retry]\n# retries\nenabled = false\n\n[features]\nrlm = true\n"
+        );
+        assert_eq!(
+            get_config_value(&path, &ConfigKey::parse("features.rlm")?)?,
+            Some(toml::Value::Boolean(true))
+        );
+
+        assert!(set_config_value(&path, &ConfigKey::parse("retry.max_retries")?, "many").is_err());
+        assert!(unset_config_value(
+            &path,
+            &ConfigKey::parse("max_subagents")?
+        )?);
+        assert!(!unset_config_value(
+            &path,
+            &ConfigKey::parse("max_subagents")?
+        )?);
+        assert_eq!(
+            get_config_value(&path, &ConfigKey::parse("max_subagents")?)?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_config_value_edits_inline_tables_and_quoted_keys() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("config.toml");
+        fs::write(
+            &path,
+            "max_subagents = 2 # keep small\nfeatures = { rlm = false }\n",
+        )?;
+
+        set_config_value(&path, &ConfigKey::parse("max_subagents")?, "4")?;
+        set_config_value(&path, &ConfigKey::parse("features.rlm")?, "true")?;
+        set_config_value(
+            &path,
+            &ConfigKey::parse("model_context_windows.org/model-1")?,
+            "128000",
+        )?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "max_subagents = 4 # keep small\nfeatures = { rlm = true }\n\n[model_context_windows]\n\"org/model-1\" = 128000\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn create_and_list_profiles() -> Result<()> {
+        let _lock = env_lock().lock().unwrap();
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("config.toml");
+        assert!(list_profiles(&path)?.is_empty());
+
+        fs::write(&path, "# global\ndefault_model = \"a\"\n")?;
+        create_profile(&path, "work")?;
+        fs::write(
+            &path,
+            fs::read_to_string(&path)? + "\n[profiles.home]\nmax_subagents = 2\n",
+        )?;
+        let profiles = list_profiles(&path)?;
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["home", "work"]);
+        assert_eq!(profiles[0].keys, ["max_subagents"]);
+        assert!(profiles[1].keys.is_empty());
+        assert!(fs::read_to_string(&path)?.starts_with("# global\n"));
+
+        let work = Config::load_for_workspace(Some(path.clone()), Some("work"), None)?;
+        assert_eq!(work.default_model.as_deref(), Some("a"));
+        assert!(create_profile(&path, "work").is_err());
+        assert!(create_profile(&path, "bad name").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_profile_errors_even_without_config_file() {
+        let _lock = env_lock().lock().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("missing.toml");
+        let err = Config::load_for_workspace(Some(path.clone()), Some("work"), None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Profile 'work' not found"), "{err}");
+        assert!(err.contains("Available profiles: none"), "{err}");
+        assert!(Config::effective_entries(Some(path), Some("work"), None).is_err());
+    }
 }
@@ -7,6 +7,7 @@
 //! - Proper cancellation support
 //! - Tool execution orchestration
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::pin::pin;
 use std::sync::{Arc, Mutex};
@@ -20,7 +21,11 @@ use tokio::sync::{Mutex as AsyncMutex, RwLock, mpsc};
 use tokio_util::sync::CancellationToken;
 
 use crate::client::AnthropicClient;
-use crate::compaction::{CompactionConfig, compact_messages, maybe_compact, merge_system_prompts};
+use crate::compaction::{
+    CompactionConfig, CompactionPreview, KeepPolicy, apply_compaction, estimate_request_tokens,
+    estimate_system_tokens, maybe_compact, merge_system_prompts, plan_compaction,
+    preview_compaction,
+};
 use crate::config::Config;
 use crate::duo::{DuoSession, SharedDuoSession, session_summary as duo_session_summary};
 use crate::features::{Feature, Features};
@@ -30,14 +35,20 @@ use crate::models::{
     CacheControl, ContentBlock, ContentBlockStart, Delta, Message, MessageRequest, StreamEvent,
     SystemBlock, SystemPrompt, Tool, Usage,
 };
+use crate::pricing::{self, SharedCostBudget, new_shared_cost_budget};
 use crate::prompts;
 use crate::rlm::{RlmSession, SharedRlmSession, session_summary as rlm_session_summary};
+use crate::tools::index::FileIndex;
+use crate::tools::investigator::survey_and_index;
+use crate::tools::media::MediaConfig;
 use crate::tools::plan::{SharedPlanState, new_shared_plan_state};
+use crate::tools::shell::DEFAULT_MAX_OUTPUT_BYTES;
 use crate::tools::spec::{ApprovalRequirement, ToolError, ToolResult};
 use crate::tools::subagent::{
     SharedSubAgentManager, SubAgentRuntime, SubAgentType, new_shared_subagent_manager,
 };
 use crate::tools::todo::{SharedTodoList, new_shared_todo_list};
+use crate::tools::web_search::WebSearchConfig;
 use crate::tools::{ToolContext, ToolRegistryBuilder};
 use crate::tui::app::AppMode;
 
@@ -84,8 +95,14 @@ pub struct EngineConfig {
     pub cache_system: bool,
     /// Enable prompt caching for tools
     pub cache_tools: bool,
-    /// Enable automatic context compaction when thresholds are exceeded.
-    pub auto_compact: bool,
+    /// Backend and credentials for the `web_search` tool.
+    pub web_search: WebSearchConfig,
+    /// Per-stream byte cap for `exec_shell` output.
+    pub shell_max_output_bytes: usize,
+    /// Session spend and cap, checked before each API call.
+    pub cost_budget: SharedCostBudget,
+    /// Endpoint and output directory for multimedia tools.
+    pub media: MediaConfig,
 }
 
 impl Default for EngineConfig {
@@ -105,9 +122,12 @@ impl Default for EngineConfig {
             rlm_session: Arc::new(Mutex::new(RlmSession::default())),
             duo_session: Arc::new(Mutex::new(DuoSession::new())),
             memory_path: PathBuf::from("memory.json"),
-            cache_system: true,  // Enable by default
-            cache_tools: true,   // Enable by default
-            auto_compact: false, // Disabled by default
+            cache_system: true, // Enable by default
+            cache_tools: true,  // Enable by default
+            web_search: WebSearchConfig::default(),
+            shell_max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            cost_budget: new_shared_cost_budget(None),
+            media: MediaConfig::default(),
         }
     }
 }
@@ -175,6 +195,11 @@ pub struct Engine {
     tx_event: mpsc::Sender<Event>,
     cancel_token: CancellationToken,
     tool_exec_lock: Arc<RwLock<()>>,
+    /// What compaction must keep, as of the latest op from the UI
+    keep: KeepPolicy,
+    /// Summary from the last `/compact --preview`, applied by the next manual
+    /// compaction if the conversation has not changed
+    compaction_preview: Option<CompactionPreview>,
 }
 
 #[derive(Debug, Clone)]
@@ -197,7 +222,37 @@ struct ToolUseState {
     id: String,
     name: String,
     input: serde_json::Value,
+    /// Argument JSON accumulated from `input_json_delta` events
     input_buffer: String,
+    /// Whether `ToolCallStarted` has been emitted for this call
+    announced: bool,
+}
+
+impl ToolUseState {
+    /// Parse the accumulated argument JSON, keeping the block-start input when
+    /// nothing (or nothing parseable) was streamed.
+    fn finish_input(&mut self) {
+        if self.input_buffer.trim().is_empty() {
+            return;
+        }
+        if let Some(value) = parse_tool_input(&self.input_buffer) {
+            self.input = value;
+        } else {
+            crate::logging::warn(format!(
+                "Tool '{}' failed to parse input buffer: '{}'",
+                self.name, self.input_buffer
+            ));
+        }
+    }
+
+    fn started_event(&mut self) -> Event {
+        self.announced = true;
+        Event::ToolCallStarted {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            input: self.input.clone(),
+        }
+    }
 }
 
 struct ToolExecOutcome {
@@ -378,6 +433,8 @@ impl Engine {
             tx_event,
             cancel_token: cancel_token.clone(),
             tool_exec_lock,
+            keep: KeepPolicy::default(),
+            compaction_preview: None,
         };
 
         let handle = EngineHandle {
@@ -401,9 +458,20 @@ impl Engine {
                     model,
                     allow_shell,
                     trust_mode,
+                    allowed_tools,
+                    keep,
                 } => {
-                    self.handle_send_message(content, mode, model, allow_shell, trust_mode)
-                        .await;
+                    self.keep = keep;
+                    self.compaction_preview = None;
+                    self.handle_send_message(
+                        content,
+                        mode,
+                        model,
+                        allow_shell,
+                        trust_mode,
+                        allowed_tools,
+                    )
+                    .await;
                 }
                 Op::CancelRequest => {
                     self.cancel_token.cancel();
@@ -442,7 +510,8 @@ impl Engine {
                         self.build_tool_context(),
                         self.session.allow_shell,
                         Some(self.tx_event.clone()),
-                    );
+                    )
+                    .with_cost_budget(self.config.cost_budget.clone());
 
                     let result = self
                         .subagent_manager
@@ -524,6 +593,7 @@ impl Engine {
                     model,
                     workspace,
                 } => {
+                    self.compaction_preview = None;
                     self.session.messages = messages;
                     self.session.system_prompt = system_prompt;
                     self.session.model = model;
@@ -544,69 +614,133 @@ impl Engine {
                 Op::Shutdown => {
                     break;
                 }
-                Op::CompactContext => {
-                    let Some(client) = self.anthropic_client.clone() else {
-                        let message = self.anthropic_client_error.as_deref().map_or_else(
-                            || "Cannot compact context: API client not configured".to_string(),
-                            |err| format!("Cannot compact context: {err}"),
-                        );
-                        let _ = self.tx_event.send(Event::error(message, false)).await;
-                        continue;
-                    };
-
-                    // Manual compaction should force a summary when possible.
-                    let config = CompactionConfig {
-                        model: self.session.model.clone(),
-                        keep_recent: 4,
-                        ..CompactionConfig::default()
-                    };
-
-                    if self.session.messages.len() <= config.keep_recent {
-                        let _ = self
-                            .tx_event
-                            .send(Event::status(
-                                "Not enough messages to compact yet".to_string(),
-                            ))
-                            .await;
-                        continue;
-                    }
+                Op::CompactContext {
+                    target_tokens,
+                    keep,
+                } => {
+                    self.keep = keep;
+                    self.handle_compact_context(target_tokens, false).await;
+                }
+                Op::PreviewCompaction { keep } => {
+                    self.keep = keep;
+                    self.handle_compact_context(None, true).await;
+                }
+                Op::RebuildIndex { workspace } => {
+                    // A full survey reads hundreds of files; keep it off the
+                    // engine loop and report back when done
+                    let tx_event = self.tx_event.clone();
+                    tokio::spawn(async move {
+                        let path = FileIndex::path(&workspace);
+                        let rebuilt =
+                            tokio::task::spawn_blocking(move || survey_and_index(&workspace, true))
+                                .await;
+                        let event = match rebuilt {
+                            Ok((survey, index, _)) => Event::IndexRebuilt {
+                                indexed: index.len(),
+                                total: survey.total,
+                                path,
+                            },
+                            Err(err) => {
+                                Event::error(format!("Failed to rebuild file index: {err}"), true)
+                            }
+                        };
+                        let _ = tx_event.send(event).await;
+                    });
+                }
+            }
+        }
+    }
 
-                    match compact_messages(&client, &self.session.messages, &config).await {
-                        Ok((messages, summary_prompt)) => {
-                            let merged_system = merge_system_prompts(
-                                self.session.system_prompt.as_ref(),
-                                summary_prompt,
-                            );
+    /// Summarize older messages now, or with `preview` only report what would
+    /// be summarized. Applying reuses the previewed summary while the
+    /// conversation is unchanged, so the user gets the summary they saw.
+    async fn handle_compact_context(&mut self, target_tokens: Option<usize>, preview: bool) {
+        let Some(client) = self.anthropic_client.clone() else {
+            let message = self.anthropic_client_error.as_deref().map_or_else(
+                || "Cannot compact context: API client not configured".to_string(),
+                |err| format!("Cannot compact context: {err}"),
+            );
+            let _ = self.tx_event.send(Event::error(message, false)).await;
+            return;
+        };
 
-                            self.session.messages = messages;
-                            self.session.system_prompt = merged_system;
+        // Manual compaction should force a summary when possible.
+        let keep_recent = target_tokens.map_or(self.keep.recent_pairs * 2, |budget| {
+            crate::compaction::keep_recent_within(&self.session.messages, budget)
+        });
+        let config = CompactionConfig {
+            model: self.session.model.clone(),
+            keep_recent,
+            pinned: self.keep.pinned.clone(),
+            ..CompactionConfig::default()
+        };
 
-                            let _ = self
-                                .tx_event
-                                .send(Event::SessionUpdated {
-                                    messages: self.session.messages.clone(),
-                                    system_prompt: self.session.system_prompt.clone(),
-                                })
-                                .await;
+        let plan = plan_compaction(&self.session.messages, &config);
+        if plan.summarized.is_empty() {
+            let _ = self
+                .tx_event
+                .send(Event::status(
+                    "Not enough messages to compact yet".to_string(),
+                ))
+                .await;
+            return;
+        }
 
-                            let _ = self
-                                .tx_event
-                                .send(Event::status("Context compacted successfully".to_string()))
-                                .await;
-                        }
-                        Err(err) => {
-                            let _ = self
-                                .tx_event
-                                .send(Event::error(
-                                    format!("Failed to compact context: {err}"),
-                                    false,
-                                ))
-                                .await;
-                        }
-                    }
+        let previewed = self
+            .compaction_preview
+            .take()
+            .filter(|previewed| previewed.plan == plan);
+        let compaction = match previewed {
+            Some(previewed) => previewed,
+            None => match preview_compaction(&client, &self.session.messages, &config).await {
+                Ok(Some(compaction)) => compaction,
+                Ok(None) => return,
+                Err(err) => {
+                    let _ = self
+                        .tx_event
+                        .send(Event::error(
+                            format!("Failed to compact context: {err}"),
+                            false,
+                        ))
+                        .await;
+                    return;
                 }
-            }
+            },
+        };
+
+        let (messages, summary_prompt) =
+            apply_compaction(&self.session.messages, &compaction, &config);
+        if preview {
+            let _ = self
+                .tx_event
+                .send(Event::CompactionPreview {
+                    summarized_messages: compaction.plan.summarized.len(),
+                    kept_messages: messages.len(),
+                    summarized_tokens: compaction.summarized_tokens(&self.session.messages),
+                    summary_tokens: estimate_system_tokens(&Some(summary_prompt)),
+                    summary: compaction.summary.clone(),
+                })
+                .await;
+            self.compaction_preview = Some(compaction);
+            return;
         }
+
+        self.session.system_prompt =
+            merge_system_prompts(self.session.system_prompt.as_ref(), Some(summary_prompt));
+        self.session.messages = messages;
+
+        let _ = self
+            .tx_event
+            .send(Event::SessionUpdated {
+                messages: self.session.messages.clone(),
+                system_prompt: self.session.system_prompt.clone(),
+            })
+            .await;
+
+        let _ = self
+            .tx_event
+            .send(Event::status("Context compacted successfully".to_string()))
+            .await;
     }
 
     /// Handle a send message operation
@@ -617,6 +751,7 @@ impl Engine {
         model: String,
         allow_shell: bool,
         trust_mode: bool,
+        allowed_tools: Option<Vec<String>>,
     ) {
         // Reset cancel token for the new request (in case previous was cancelled)
         if self.cancel_token.is_cancelled() {
@@ -649,6 +784,7 @@ impl Engine {
 
         // Create turn context
         let mut turn = TurnContext::new(self.config.max_steps);
+        turn.allowed_tools = allowed_tools;
 
         self.session.model = model;
         self.config.model.clone_from(&self.session.model);
@@ -705,18 +841,24 @@ impl Engine {
         if self.config.features.enabled(Feature::WebSearch) {
             builder = builder.with_web_tools();
         }
+        if self.config.features.enabled(Feature::Multimedia) {
+            builder = builder.with_media_tools();
+        }
         if self.config.features.enabled(Feature::ShellTool) && self.session.allow_shell {
             builder = builder.with_shell_tools();
         }
 
         let runtime = if let Some(client) = self.anthropic_client.clone() {
-            Some(SubAgentRuntime::new(
-                client,
-                self.session.model.clone(),
-                tool_context.clone(),
-                self.session.allow_shell,
-                Some(self.tx_event.clone()),
-            ))
+            Some(
+                SubAgentRuntime::new(
+                    client,
+                    self.session.model.clone(),
+                    tool_context.clone(),
+                    self.session.allow_shell,
+                    Some(self.tx_event.clone()),
+                )
+                .with_cost_budget(self.config.cost_budget.clone()),
+            )
         } else {
             None
         };
@@ -754,17 +896,20 @@ impl Engine {
             }
         }
 
-        let tool_registry = match mode {
+        let mut tool_registry = match mode {
             AppMode::Agent | AppMode::Yolo | AppMode::Rlm | AppMode::Duo => {
                 if self.config.features.enabled(Feature::Subagents) {
                     let runtime = if let Some(client) = self.anthropic_client.clone() {
-                        Some(SubAgentRuntime::new(
-                            client,
-                            self.session.model.clone(),
-                            tool_context.clone(),
-                            self.session.allow_shell,
-                            Some(self.tx_event.clone()),
-                        ))
+                        Some(
+                            SubAgentRuntime::new(
+                                client,
+                                self.session.model.clone(),
+                                tool_context.clone(),
+                                self.session.allow_shell,
+                                Some(self.tx_event.clone()),
+                            )
+                            .with_cost_budget(self.config.cost_budget.clone()),
+                        )
                     } else {
                         None
                     };
@@ -783,11 +928,17 @@ impl Engine {
             _ => Some(builder.build(tool_context)),
         };
 
-        let mcp_tools = if self.config.features.enabled(Feature::Mcp) {
+        let mut mcp_tools = if self.config.features.enabled(Feature::Mcp) {
             self.mcp_tools().await
         } else {
             Vec::new()
         };
+        if let Some(allowed) = turn.allowed_tools.as_ref() {
+            if let Some(registry) = tool_registry.as_mut() {
+                registry.retain(|name| allowed.iter().any(|tool| tool == name));
+            }
+            mcp_tools.retain(|tool| allowed.contains(&tool.name));
+        }
         let tools = tool_registry.as_ref().map(|registry| {
             let mut tools = registry.to_api_tools();
             tools.extend(mcp_tools);
@@ -808,6 +959,25 @@ impl Engine {
             .await;
     }
 
+    /// Budget event to emit if a call costing `estimate_usd` would exceed the cap.
+    fn check_budget(&self, estimate_usd: f64) -> Option<Event> {
+        let budget = self.config.cost_budget.lock().ok()?;
+        let limit_usd = budget.limit_usd?;
+        budget
+            .would_exceed(estimate_usd)
+            .then_some(Event::BudgetExceeded {
+                spent_usd: budget.spent_usd,
+                limit_usd,
+                estimate_usd,
+            })
+    }
+
+    fn record_spend(&self, usd: f64) {
+        if let Ok(mut budget) = self.config.cost_budget.lock() {
+            budget.record(usd);
+        }
+    }
+
     fn build_tool_context(&self) -> ToolContext {
         ToolContext::with_options(
             self.session.workspace.clone(),
@@ -815,6 +985,9 @@ impl Engine {
             self.session.notes_path.clone(),
             self.session.mcp_config_path.clone(),
         )
+        .with_web_search(self.config.web_search.clone())
+        .with_shell_max_output_bytes(self.config.shell_max_output_bytes)
+        .with_media(self.config.media.clone())
     }
 
     async fn ensure_mcp_pool(&mut self) -> Result<Arc<AsyncMutex<McpPool>>, ToolError> {
@@ -1014,9 +1187,17 @@ impl Engine {
             }
 
             // Check for context compaction (if conversation is getting long)
-            // Only compact if auto_compact is enabled in config
-            let (messages_for_request, system_for_request) = if self.config.auto_compact {
-                let compaction_config = CompactionConfig::default();
+            // Only compact if the UI turned auto-compaction on for this turn
+            let (messages_for_request, system_for_request) = if let Some(token_threshold) =
+                self.keep.auto_compact_tokens
+            {
+                let compaction_config = CompactionConfig {
+                    token_threshold,
+                    message_threshold: usize::MAX,
+                    keep_recent: self.keep.recent_pairs * 2,
+                    pinned: self.keep.pinned.clone(),
+                    ..CompactionConfig::default()
+                };
                 match maybe_compact(
                     &client,
                     &self.session.messages,
@@ -1086,6 +1267,18 @@ impl Engine {
                 top_p: None,
             };
 
+            // Stop before a call that could push spend past the budget
+            let input_tokens =
+                estimate_request_tokens(&request.messages, &request.system, &request.tools);
+            let estimate_usd = pricing::token_cost(
+                u32::try_from(input_tokens).unwrap_or(u32::MAX),
+                request.max_tokens,
+            );
+            if let Some(event) = self.check_budget(estimate_usd) {
+                let _ = self.tx_event.send(event).await;
+                break;
+            }
+
             // Stream the response
             let stream_result = client.create_message_stream(request).await;
             let stream = match stream_result {
@@ -1108,7 +1301,8 @@ impl Engine {
                 output_tokens: 0,
             };
             let mut current_block_kind: Option<ContentBlockKind> = None;
-            let mut current_tool_index: Option<usize> = None;
+            // Stream block index -> position in `tool_uses`
+            let mut tool_blocks: HashMap<usize, usize> = HashMap::new();
             let mut in_tool_call_block = false;
             let mut pending_message_complete = false;
             let mut last_text_index: Option<usize> = None;
@@ -1172,13 +1366,14 @@ impl Engine {
                                 name, input
                             ));
                             current_block_kind = Some(ContentBlockKind::ToolUse);
-                            current_tool_index = Some(tool_uses.len());
+                            tool_blocks.insert(index as usize, tool_uses.len());
+                            // Arguments are still streaming; surface the name now and
+                            // announce the call once its input is complete.
                             let _ = self
                                 .tx_event
-                                .send(Event::ToolCallStarted {
+                                .send(Event::ToolCallPreparing {
                                     id: id.clone(),
                                     name: name.clone(),
-                                    input: json!({}),
                                 })
                                 .await;
                             tool_uses.push(ToolUseState {
@@ -1186,6 +1381,7 @@ impl Engine {
                                 name,
                                 input,
                                 input_buffer: String::new(),
+                                announced: false,
                             });
                         }
                     },
@@ -1217,21 +1413,10 @@ impl Engine {
                             }
                         }
                         Delta::InputJsonDelta { partial_json } => {
-                            if let Some(index) = current_tool_index
-                                && let Some(tool_state) = tool_uses.get_mut(index)
+                            if let Some(&position) = tool_blocks.get(&(index as usize))
+                                && let Some(tool_state) = tool_uses.get_mut(position)
                             {
                                 tool_state.input_buffer.push_str(&partial_json);
-                                crate::logging::info(format!(
-                                    "Tool '{}' input delta: {} (buffer now: {})",
-                                    tool_state.name, partial_json, tool_state.input_buffer
-                                ));
-                                if let Some(value) = parse_tool_input(&tool_state.input_buffer) {
-                                    tool_state.input = value.clone();
-                                    crate::logging::info(format!(
-                                        "Tool '{}' input parsed: {:?}",
-                                        tool_state.name, value
-                                    ));
-                                }
                             }
                         }
                     },
@@ -1252,33 +1437,11 @@ impl Engine {
                             }
                             Some(ContentBlockKind::ToolUse) | None => {}
                         }
-                        if matches!(stopped_kind, Some(ContentBlockKind::ToolUse))
-                            && let Some(index) = current_tool_index.take()
-                            && let Some(tool_state) = tool_uses.get_mut(index)
+                        if let Some(position) = tool_blocks.remove(&(index as usize))
+                            && let Some(tool_state) = tool_uses.get_mut(position)
                         {
-                            crate::logging::info(format!(
-                                "Tool '{}' block stop. Buffer: '{}', Current input: {:?}",
-                                tool_state.name, tool_state.input_buffer, tool_state.input
-                            ));
-                            if !tool_state.input_buffer.trim().is_empty() {
-                                if let Some(value) = parse_tool_input(&tool_state.input_buffer) {
-                                    tool_state.input = value;
-                                    crate::logging::info(format!(
-                                        "Tool '{}' final input: {:?}",
-                                        tool_state.name, tool_state.input
-                                    ));
-                                } else {
-                                    crate::logging::warn(format!(
-                                        "Tool '{}' failed to parse final input buffer: '{}'",
-                                        tool_state.name, tool_state.input_buffer
-                                    ));
-                                }
-                            } else {
-                                crate::logging::warn(format!(
-                                    "Tool '{}' input buffer is empty, using initial input: {:?}",
-                                    tool_state.name, tool_state.input
-                                ));
-                            }
+                            tool_state.finish_input();
+                            let _ = self.tx_event.send(tool_state.started_event()).await;
                         }
                     }
                     StreamEvent::MessageDelta {
@@ -1292,8 +1455,15 @@ impl Engine {
                 }
             }
 
+            // Tool blocks cut off before their stop event still need announcing
+            for tool_state in tool_uses.iter_mut().filter(|tool| !tool.announced) {
+                tool_state.finish_input();
+                let _ = self.tx_event.send(tool_state.started_event()).await;
+            }
+
             // Update turn usage
             turn.add_usage(&usage);
+            self.record_spend(pricing::usage_cost(&usage));
 
             // Build content blocks
             if !current_thinking.is_empty() {
@@ -1319,6 +1489,7 @@ impl Engine {
                         name: call.name,
                         input: call.args,
                         input_buffer: String::new(),
+                        announced: true,
                     });
                 }
             }
@@ -1392,6 +1563,7 @@ impl Engine {
 
                 let mut approval_required = false;
                 let mut approval_description = "Tool execution requires approval".to_string();
+                let mut approval_preview = None;
                 let mut supports_parallel = McpPool::is_mcp_tool(&tool_name);
                 if let Some(registry) = tool_registry
                     && let Some(spec) = registry.get(&tool_name)
@@ -1399,15 +1571,28 @@ impl Engine {
                     approval_required = spec.approval_requirement() != ApprovalRequirement::Auto;
                     approval_description = spec.description().to_string();
                     supports_parallel = spec.supports_parallel();
+                    if approval_required {
+                        approval_preview = spec.approval_preview(&tool_input, registry.context());
+                    }
                 }
 
-                let result_override = if approval_required {
+                let result_override = if turn
+                    .allowed_tools
+                    .as_ref()
+                    .is_some_and(|allowed| !allowed.contains(&tool_name))
+                {
+                    Some(Err(ToolError::permission_denied(format!(
+                        "Tool '{tool_name}' is not allowed by the active skill"
+                    ))))
+                } else if approval_required {
                     let _ = self
                         .tx_event
                         .send(Event::ApprovalRequired {
                             id: tool_id.clone(),
                             tool_name: tool_name.clone(),
                             description: approval_description,
+                            params: tool_input.clone(),
+                            preview: approval_preview,
                         })
                         .await;
 
@@ -1435,6 +1620,7 @@ impl Engine {
                             id: tool_id.clone(),
                             name: tool_name.clone(),
                             result: result_override.clone(),
+                            duration: started_at.elapsed(),
                         })
                         .await;
                     outcomes[index] = Some(ToolExecOutcome {
@@ -1467,6 +1653,7 @@ impl Engine {
                             id: tool_id.clone(),
                             name: tool_name.clone(),
                             result: result.clone(),
+                            duration: started_at.elapsed(),
                         })
                         .await;
                     outcomes[index] = Some(ToolExecOutcome {
@@ -1499,6 +1686,7 @@ impl Engine {
                             id: tool_id.clone(),
                             name: tool_name.clone(),
                             result: result.clone(),
+                            duration: started_at.elapsed(),
                         })
                         .await;
 
@@ -1520,6 +1708,12 @@ impl Engine {
 
             for outcome in outcomes.into_iter().flatten() {
                 let duration = outcome.started_at.elapsed();
+                if outcome.result.is_ok()
+                    && let Some(estimate) =
+                        pricing::estimate_tool_cost(&outcome.name, &outcome.input)
+                {
+                    self.record_spend(estimate.max_usd);
+                }
                 let mut tool_call =
                     TurnToolCall::new(outcome.id.clone(), outcome.name.clone(), outcome.input);
 
@@ -1575,3 +1769,233 @@ pub fn spawn_engine(config: EngineConfig, api_config: &Config) -> EngineHandle {
 
     handle
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn tool_call_is_announced_once_its_input_is_complete() {
+        let tmp = tempdir().expect("tempdir");
+        let script = tmp.path().join("mock.toml");
+        std::fs::write(
+            &script,
+            "[[responses]]\ntool_calls = [{ name = \"list_dir\", input = { path = \".\" } }]\n\n[[responses]]\ntext = \"Done.\"\n",
+        )
+        .expect("write script");
+        let api_config = Config {
+            backend: Some("mock".to_string()),
+            mock_script: Some(script.display().to_string()),
+            ..Config::default()
+        };
+        let config = EngineConfig {
+            model: "mock-model".to_string(),
+            workspace: tmp.path().to_path_buf(),
+            ..EngineConfig::default()
+        };
+        let handle = spawn_engine(config, &api_config);
+        handle
+            .send(Op::send("look", AppMode::Agent, "mock-model", false, true))
+            .await
+            .expect("send");
+
+        let mut tool_events = Vec::new();
+        let mut rx = handle.rx_event.write().await;
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .expect("engine event")
+                .expect("event channel open");
+            match event {
+                Event::ToolCallPreparing { name, .. } => {
+                    tool_events.push(format!("preparing {name}"));
+                }
+                Event::ToolCallStarted { name, input, .. } => {
+                    tool_events.push(format!("started {name} {input}"));
+                }
+                Event::ToolCallComplete { name, result, .. } => {
+                    assert!(result.is_ok(), "{name} failed: {result:?}");
+                    tool_events.push(format!("complete {name}"));
+                }
+                Event::TurnComplete { .. } => break,
+                _ => {}
+            }
+        }
+        assert_eq!(
+            tool_events,
+            [
+                "preparing list_dir",
+                r#"started list_dir {"path":"."}"#,
+                "complete list_dir",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn compaction_preview_leaves_session_and_applies_same_summary() {
+        let tmp = tempdir().expect("tempdir");
+        let script = tmp.path().join("mock.toml");
+        std::fs::write(
+            &script,
+            "[[responses]]\ntext = \"The user asked about parsers.\"\n",
+        )
+        .expect("write script");
+        let api_config = Config {
+            backend: Some("mock".to_string()),
+            mock_script: Some(script.display().to_string()),
+            ..Config::default()
+        };
+        let config = EngineConfig {
+            model: "mock-model".to_string(),
+            workspace: tmp.path().to_path_buf(),
+            ..EngineConfig::default()
+        };
+        let handle = spawn_engine(config, &api_config);
+        let messages = (0..8)
+            .map(|i| Message {
+                role: if i % 2 == 0 { "user" } else { "assistant" }.to_string(),
+                content: vec![ContentBlock::Text {
+                    text: format!("message {i}"),
+                    cache_control: None,
+                }],
+            })
+            .collect();
+        handle
+            .send(Op::SyncSession {
+                messages,
+                system_prompt: None,
+                model: "mock-model".to_string(),
+                workspace: tmp.path().to_path_buf(),
+            })
+            .await
+            .expect("send");
+        handle
+            .send(Op::PreviewCompaction {
+                keep: KeepPolicy::default(),
+            })
+            .await
+            .expect("send");
+
+        let mut rx = handle.rx_event.write().await;
+        let next = async |rx: &mut mpsc::Receiver<Event>| {
+            tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .expect("engine answered in time")
+                .expect("event channel open")
+        };
+        loop {
+            match next(&mut rx).await {
+                Event::CompactionPreview {
+                    summarized_messages,
+                    kept_messages,
+                    summary,
+                    ..
+                } => {
+                    assert_eq!((summarized_messages, kept_messages), (2, 6));
+                    assert_eq!(summary, "The user asked about parsers.");
+                    break;
+                }
+                Event::SessionUpdated { .. } => panic!("a preview must not compact"),
+                _ => {}
+            }
+        }
+
+        // The script is used up, so a second summary would echo the request
+        handle
+            .send(Op::CompactContext {
+                target_tokens: None,
+                keep: KeepPolicy::default(),
+            })
+            .await
+            .expect("send");
+        loop {
+            if let Event::SessionUpdated {
+                messages,
+                system_prompt,
+            } = next(&mut rx).await
+            {
+                assert_eq!(messages.len(), 6);
+                let Some(SystemPrompt::Blocks(blocks)) = system_prompt else {
+                    panic!("expected a summary block");
+                };
+                assert!(blocks[0].text.contains("The user asked about parsers."));
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rebuild_index_reports_back_through_an_event() {
+        let tmp = tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("lib.rs"), "pub fn parse() {}\n").expect("write");
+        let handle = spawn_engine(EngineConfig::default(), &Config::default());
+        handle
+            .send(Op::RebuildIndex {
+                workspace: tmp.path().to_path_buf(),
+            })
+            .await
+            .expect("send");
+
+        let mut rx = handle.rx_event.write().await;
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .expect("engine event")
+                .expect("event channel open");
+            if let Event::IndexRebuilt { indexed, path, .. } = event {
+                assert_eq!(indexed, 1);
+                assert!(path.exists());
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn skill_tool_restriction_rejects_other_tools() {
+        let tmp = tempdir().expect("tempdir");
+        let script = tmp.path().join("mock.toml");
+        std::fs::write(
+            &script,
+            "[[responses]]\ntool_calls = [{ name = \"list_dir\", input = { path = \".\" } }]\n\n[[responses]]\ntext = \"Done.\"\n",
+        )
+        .expect("write script");
+        let api_config = Config {
+            backend: Some("mock".to_string()),
+            mock_script: Some(script.display().to_string()),
+            ..Config::default()
+        };
+        let config = EngineConfig {
+            model: "mock-model".to_string(),
+            workspace: tmp.path().to_path_buf(),
+            ..EngineConfig::default()
+        };
+        let handle = spawn_engine(config, &api_config);
+        handle
+            .send(Op::SendMessage {
+                content: "look".to_string(),
+                mode: AppMode::Agent,
+                model: "mock-model".to_string(),
+                allow_shell: false,
+                trust_mode: true,
+                allowed_tools: Some(vec!["read_file".to_string()]),
+                keep: KeepPolicy::default(),
+            })
+            .await
+            .expect("send");
+
+        let mut rx = handle.rx_event.write().await;
+        let result = loop {
+            let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .expect("engine event")
+                .expect("event channel open");
+            if let Event::ToolCallComplete { result, .. } = event {
+                break result;
+            }
+        };
+        let err = result.expect_err("list_dir should be rejected");
+        assert!(err.to_string().contains("not allowed by the active skill"));
+    }
+}
@@ -3,12 +3,15 @@
 //! These events flow from the engine to the TUI via a channel,
 //! enabling non-blocking, real-time updates.
 
+use std::path::PathBuf;
+use std::time::Duration;
+
 use serde_json::Value;
 
 use crate::error_hints::{ErrorHint, is_recoverable};
 use crate::models::{Message, SystemPrompt, Usage};
 use crate::tools::spec::{ToolError, ToolResult};
-use crate::tools::subagent::SubAgentResult;
+use crate::tools::subagent::{SubAgentResult, SubAgentStatus};
 
 /// Events emitted by the engine to update the UI.
 #[derive(Debug, Clone)]
@@ -33,7 +36,10 @@ pub enum Event {
     ThinkingComplete { index: usize },
 
     // === Tool Events ===
-    /// Tool call initiated
+    /// Tool call named by the model; its arguments are still streaming
+    ToolCallPreparing { id: String, name: String },
+
+    /// Tool call initiated with its complete input
     ToolCallStarted {
         id: String,
         name: String,
@@ -48,6 +54,8 @@ pub enum Event {
         id: String,
         name: String,
         result: Result<ToolResult, ToolError>,
+        /// Wall-clock time spent executing the tool (excludes approval wait)
+        duration: Duration,
     },
 
     // === Turn Lifecycle ===
@@ -57,6 +65,13 @@ pub enum Event {
     /// The turn is complete (no more tool calls)
     TurnComplete { usage: Usage },
 
+    /// The next API call was blocked because it could exceed the session budget
+    BudgetExceeded {
+        spent_usd: f64,
+        limit_usd: f64,
+        estimate_usd: f64,
+    },
+
     // === Sub-Agent Events (for RLM mode) ===
     /// A sub-agent has been spawned
     AgentSpawned { id: String, prompt: String },
@@ -65,7 +80,11 @@ pub enum Event {
     AgentProgress { id: String, status: String },
 
     /// Sub-agent completed
-    AgentComplete { id: String, result: String },
+    AgentComplete {
+        id: String,
+        status: SubAgentStatus,
+        result: String,
+    },
 
     /// Sub-agent listing
     AgentList { agents: Vec<SubAgentResult> },
@@ -81,6 +100,24 @@ pub enum Event {
     /// Status message for UI display
     Status { message: String },
 
+    /// What a manual compaction would do, from `Op::PreviewCompaction`
+    CompactionPreview {
+        summarized_messages: usize,
+        kept_messages: usize,
+        /// Estimated tokens in the messages the summary would replace
+        summarized_tokens: usize,
+        summary_tokens: usize,
+        summary: String,
+    },
+
+    /// A file index rebuild from `Op::RebuildIndex` finished
+    IndexRebuilt {
+        indexed: usize,
+        /// Files found by the survey
+        total: usize,
+        path: PathBuf,
+    },
+
     /// Engine session messages/system prompt were updated (e.g., compaction)
     SessionUpdated {
         messages: Vec<Message>,
@@ -98,6 +135,10 @@ pub enum Event {
         id: String,
         tool_name: String,
         description: String,
+        /// Tool input, shown in the approval prompt
+        params: serde_json::Value,
+        /// Preview of the tool's effect (e.g. a diff), when the tool provides one
+        preview: Option<String>,
     },
 }
 
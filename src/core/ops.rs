@@ -3,6 +3,7 @@
 //! These operations flow from the TUI to the engine via a channel,
 //! allowing the UI to remain responsive while the engine processes requests.
 
+use crate::compaction::KeepPolicy;
 use crate::models::{Message, SystemPrompt};
 use crate::tui::app::AppMode;
 use std::path::PathBuf;
@@ -17,6 +18,10 @@ pub enum Op {
         model: String,
         allow_shell: bool,
         trust_mode: bool,
+        /// Only offer these tools for this turn (set by an active skill)
+        allowed_tools: Option<Vec<String>>,
+        /// Pins and recent exchanges any compaction this turn must keep
+        keep: KeepPolicy,
     },
 
     /// Cancel the current request
@@ -51,8 +56,18 @@ pub enum Op {
     /// Shutdown the engine
     Shutdown,
 
-    /// Trigger manual context compaction
-    CompactContext,
+    /// Trigger manual context compaction. With a target, keep as many recent
+    /// messages as fit in that many tokens alongside the summary.
+    CompactContext {
+        target_tokens: Option<usize>,
+        keep: KeepPolicy,
+    },
+
+    /// Plan a manual compaction and summarize it without changing the session
+    PreviewCompaction { keep: KeepPolicy },
+
+    /// Rebuild the investigator's file index for `workspace` in the background
+    RebuildIndex { workspace: PathBuf },
 }
 
 impl Op {
@@ -70,6 +85,8 @@ impl Op {
             model: model.into(),
             allow_shell,
             trust_mode,
+            allowed_tools: None,
+            keep: KeepPolicy::default(),
         }
     }
 
@@ -29,6 +29,9 @@ pub struct TurnContext {
 
     /// Usage for this turn
     pub usage: Usage,
+
+    /// Tools the turn may call; `None` allows everything registered
+    pub allowed_tools: Option<Vec<String>>,
 }
 
 /// Record of a tool call within a turn.
@@ -56,6 +59,7 @@ impl TurnContext {
                 input_tokens: 0,
                 output_tokens: 0,
             },
+            allowed_tools: None,
         }
     }
 
@@ -34,6 +34,8 @@ pub enum Feature {
     Duo,
     /// Enable execpolicy integration/tooling.
     ExecPolicy,
+    /// Enable paid multimedia generation tools.
+    Multimedia,
 }
 
 impl Feature {
@@ -124,6 +126,43 @@ pub fn feature_spec_by_key(key: &str) -> Option<&'static FeatureSpec> {
     FEATURES.iter().find(|spec| spec.key == key)
 }
 
+/// Every feature `key` needs, directly or transitively, prerequisites first.
+pub fn prerequisites(key: &str) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    collect_prerequisites(key, &mut out);
+    out
+}
+
+fn collect_prerequisites(key: &str, out: &mut Vec<&'static str>) {
+    let Some(spec) = feature_spec_by_key(key) else {
+        return;
+    };
+    for required in spec.requires {
+        if !out.contains(required) {
+            collect_prerequisites(required, out);
+            out.push(required);
+        }
+    }
+}
+
+/// Every feature that needs `key`, directly or transitively.
+pub fn dependents(key: &str) -> Vec<&'static str> {
+    FEATURES
+        .iter()
+        .filter(|spec| spec.key != key && prerequisites(spec.key).contains(&key))
+        .map(|spec| spec.key)
+        .collect()
+}
+
+/// What `Config::set_feature` changed beyond the flag it was asked to set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureToggle {
+    /// Prerequisites that were off and got enabled along with the feature.
+    pub enabled_prerequisites: Vec<&'static str>,
+    /// Enabled features that need the feature that was just disabled.
+    pub broken_dependents: Vec<&'static str>,
+}
+
 /// Deserializable features table for TOML.
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct FeaturesToml {
@@ -136,57 +175,123 @@ pub struct FeaturesToml {
 pub struct FeatureSpec {
     pub id: Feature,
     pub key: &'static str,
+    /// One-line summary shown by `axiom features describe`.
+    pub description: &'static str,
     pub stage: Stage,
     pub default_enabled: bool,
+    /// Keys of features this one needs; enabling it enables them too.
+    pub requires: &'static [&'static str],
 }
 
 pub const FEATURES: &[FeatureSpec] = &[
     FeatureSpec {
         id: Feature::ShellTool,
         key: "shell_tool",
+        description: "Offer the exec_shell tool; shell access still needs allow_shell or YOLO mode.",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::Subagents,
         key: "subagents",
+        description: "Let the agent spawn background sub-agents in Agent, YOLO, RLM, and Duo modes.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::WebSearch,
         key: "web_search",
+        description: "Offer the web_search and web_fetch tools.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::ApplyPatch,
         key: "apply_patch",
+        description: "Offer the apply_patch tool for applying unified diffs.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::Mcp,
         key: "mcp",
+        description: "Load tools from the MCP servers listed in mcp_config_path.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::Rlm,
         key: "rlm",
+        description: "Offer the RLM context tools while in RLM mode.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::Duo,
         key: "duo",
+        description: "Offer the Duo player/coach file tools while in Duo mode.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
     FeatureSpec {
         id: Feature::ExecPolicy,
         key: "exec_policy",
+        description: "Execpolicy integration for shell commands; reserved, `axiom execpolicy check` works either way.",
+        stage: Stage::Experimental,
+        default_enabled: true,
+        requires: &["shell_tool"],
+    },
+    FeatureSpec {
+        id: Feature::Multimedia,
+        key: "multimedia",
+        description: "Offer the paid generate_image, tts, generate_music, and generate_video tools.",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
     },
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_entries_name_known_features() {
+        for spec in FEATURES {
+            for required in spec.requires {
+                assert!(is_known_feature_key(required), "{} -> {required}", spec.key);
+            }
+            assert!(
+                !prerequisites(spec.key).contains(&spec.key),
+                "{} depends on itself",
+                spec.key
+            );
+        }
+    }
+
+    #[test]
+    fn every_feature_has_a_description() {
+        for spec in FEATURES {
+            assert!(
+                !spec.description.is_empty(),
+                "{} has no description",
+                spec.key
+            );
+        }
+    }
+
+    #[test]
+    fn dependency_edges_resolve_both_ways() {
+        assert_eq!(prerequisites("exec_policy"), vec!["shell_tool"]);
+        assert!(prerequisites("shell_tool").is_empty());
+        assert_eq!(dependents("shell_tool"), vec!["exec_policy"]);
+        assert!(dependents("exec_policy").is_empty());
+    }
+}
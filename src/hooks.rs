@@ -491,7 +491,7 @@ impl HookExecutor {
                 let tool_category = context.tool_name.as_ref().map(|name| match name.as_str() {
                     "exec_shell" => "shell",
                     "write_file" | "edit_file" | "apply_patch" => "file_write",
-                    "read_file" | "list_dir" | "grep_files" => "safe",
+                    "read_file" | "read_many_files" | "list_dir" | "grep_files" => "safe",
                     "analyze_image"
                     | "generate_image"
                     | "generate_video"
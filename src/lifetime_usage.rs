@@ -0,0 +1,158 @@
+//! Running token and cost totals across sessions.
+//!
+//! Totals live in `~/.axiom/usage.json`, keyed by model, and grow after every
+//! completed turn. Costs are estimates from `crate::pricing` text-token rates,
+//! so they are a rough tally rather than a bill.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Usage;
+use crate::pricing;
+use crate::utils::write_bytes;
+
+/// Totals for one model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelTotals {
+    pub turns: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Everything recorded since `since` (the first turn after the last reset).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LifetimeUsage {
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelTotals>,
+}
+
+impl LifetimeUsage {
+    /// Sum across all models.
+    #[must_use]
+    pub fn total(&self) -> ModelTotals {
+        self.models
+            .values()
+            .fold(ModelTotals::default(), |mut sum, model| {
+                sum.turns += model.turns;
+                sum.input_tokens += model.input_tokens;
+                sum.output_tokens += model.output_tokens;
+                sum.cost_usd += model.cost_usd;
+                sum
+            })
+    }
+}
+
+pub struct UsageLedger {
+    path: PathBuf,
+}
+
+impl UsageLedger {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Ledger in the default location (~/.axiom/usage.json)
+    pub fn default_location() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to resolve home directory")?;
+        Ok(Self::new(home.join(".axiom").join("usage.json")))
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Current totals; a missing file means nothing has been recorded yet.
+    pub fn load(&self) -> Result<LifetimeUsage> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", self.path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(LifetimeUsage::default()),
+            Err(err) => Err(err).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    /// Add one completed turn to the totals for `model`.
+    pub fn record(&self, model: &str, usage: &Usage) -> Result<()> {
+        let mut lifetime = self.load()?;
+        lifetime.since.get_or_insert_with(Utc::now);
+        let totals = lifetime.models.entry(model.to_string()).or_default();
+        totals.turns += 1;
+        totals.input_tokens += u64::from(usage.input_tokens);
+        totals.output_tokens += u64::from(usage.output_tokens);
+        totals.cost_usd += pricing::usage_cost(usage);
+        self.save(&lifetime)
+    }
+
+    /// Zero all totals.
+    pub fn reset(&self) -> Result<()> {
+        self.save(&LifetimeUsage::default())
+    }
+
+    fn save(&self, lifetime: &LifetimeUsage) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(lifetime).context("Failed to serialize lifetime usage")?;
+        write_bytes(&self.path, json.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn usage(input_tokens: u32, output_tokens: u32) -> Usage {
+        Usage {
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    #[test]
+    fn record_accumulates_per_model_and_reset_zeroes() {
+        let tmp = tempdir().expect("tempdir");
+        let ledger = UsageLedger::new(tmp.path().join("nested").join("usage.json"));
+        assert!(ledger.load().expect("load").models.is_empty());
+
+        ledger
+            .record("model-a", &usage(1_000_000, 0))
+            .expect("record");
+        ledger
+            .record("model-a", &usage(0, 1_000_000))
+            .expect("record");
+        ledger.record("model-b", &usage(10, 5)).expect("record");
+
+        let lifetime = ledger.load().expect("load");
+        assert!(lifetime.since.is_some());
+        let a = &lifetime.models["model-a"];
+        assert_eq!(
+            (a.turns, a.input_tokens, a.output_tokens),
+            (2, 1_000_000, 1_000_000)
+        );
+        assert!((a.cost_usd - 1.30).abs() < 1e-9);
+        let total = lifetime.total();
+        assert_eq!(total.turns, 3);
+        assert_eq!(total.input_tokens, 1_000_010);
+
+        ledger.reset().expect("reset");
+        let lifetime = ledger.load().expect("load");
+        assert!(lifetime.models.is_empty());
+        assert!(lifetime.since.is_none());
+    }
+
+    #[test]
+    fn corrupt_ledger_reports_path() {
+        let tmp = tempdir().expect("tempdir");
+        let path = tmp.path().join("usage.json");
+        fs::write(&path, "not json").expect("write");
+        let err = UsageLedger::new(path).load().unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to parse"));
+    }
+}
@@ -6,6 +6,7 @@
 //! # Architecture
 //!
 //! - `LlmClient` trait: Async interface for LLM providers (Anthropic, `OpenAI`, etc.)
+//! - `ApiStyle`: Wire format a client speaks, selected by the `api_style` config key
 //! - `RetryConfig`: Configurable retry behavior with exponential backoff and jitter
 //! - `LlmError`: Classified errors with retryability information
 
@@ -26,8 +27,10 @@
 use crate::config::RetryPolicy;
 use crate::models::{MessageRequest, MessageResponse, StreamEvent};
 use anyhow::Result;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 // === LlmClient Trait ===
@@ -68,6 +71,47 @@ pub trait LlmClient: Send + Sync {
     }
 }
 
+/// Wire format spoken by the configured endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApiStyle {
+    /// Anthropic Messages API (`/v1/messages`).
+    #[default]
+    Anthropic,
+    /// `OpenAI` Chat Completions API (`/v1/chat/completions`).
+    OpenAi,
+}
+
+impl ApiStyle {
+    /// Config value for this style.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Anthropic => "anthropic",
+            Self::OpenAi => "openai",
+        }
+    }
+}
+
+impl fmt::Display for ApiStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "anthropic" => Ok(Self::Anthropic),
+            "openai" => Ok(Self::OpenAi),
+            other => Err(format!(
+                "Unknown API style '{other}' (expected anthropic or openai)"
+            )),
+        }
+    }
+}
+
 /// Trait for clients that support configurable retry behavior
 pub trait RetryConfigurable {
     fn retry_config(&self) -> &RetryConfig;
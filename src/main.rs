@@ -1,17 +1,18 @@
 //! CLI entry point for the `Axiom` client.
 
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
-use clap::{Args, CommandFactory, Parser, Subcommand};
-use clap_complete::{Shell, generate};
+use anyhow::{Context, Result};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use dotenvy::dotenv;
 
+mod cassette;
 mod client;
 mod command_safety;
 mod commands;
 mod compaction;
+mod completions;
 mod config;
 mod core;
 mod duo;
@@ -19,16 +20,22 @@ mod error_hints;
 mod execpolicy;
 mod features;
 mod hooks;
+mod lifetime_usage;
 mod llm_client;
 mod logging;
 mod mcp;
+mod mock_backend;
 mod models;
+mod openai_compat;
 mod palette;
 mod pricing;
 mod project_context;
 mod project_doc;
+mod prompt_template;
 mod prompts;
+mod repl;
 mod responses_api_proxy;
+mod review_cache;
 mod rlm;
 mod sandbox;
 mod session_manager;
@@ -39,8 +46,10 @@ mod tools;
 mod tui;
 mod utils;
 
-use crate::config::Config;
+use crate::completions::{CompletionShell, DynamicCompletion};
+use crate::config::{CheckLevel, Config};
 use crate::llm_client::LlmClient;
+use crate::utils::truncate_diff;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -58,6 +67,7 @@ use crate::llm_client::LlmClient;
     Not affiliated with Axiom Inc.",
     after_help = "Examples:\
     \\n   axiom                    # Start interactive chat\
+    \\n   axiom --no-tui           # Chat with a plain prompt (dumb terminals, CI)\
     \\n   axiom modes              # Show all available modes\
     \\n   axiom rlm                # Enter RLM mode\
     \\n   axiom duo                # Enter Duo autocoding mode\
@@ -72,17 +82,29 @@ struct Cli {
     feature_toggles: FeatureToggles,
 
     /// Send a one-shot prompt (non-interactive)
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "prompt_file")]
     prompt: Option<String>,
 
+    /// Send a file's contents as a one-shot prompt (`-` reads stdin)
+    #[arg(long, value_name = "PATH")]
+    prompt_file: Option<PathBuf>,
+
+    /// Substitute `{{NAME}}` in the one-shot/exec prompt (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE", action = clap::ArgAction::Append, global = true)]
+    vars: Vec<String>,
+
     /// YOLO mode: enable agent tools + shell execution
     #[arg(long)]
     yolo: bool,
 
-    /// Maximum number of concurrent sub-agents (1-5)
+    /// Maximum number of concurrent sub-agents (0-5; 0 disables sub-agents)
     #[arg(long)]
     max_subagents: Option<usize>,
 
+    /// Stop sending API requests once estimated session spend would pass this many USD
+    #[arg(long, value_name = "USD")]
+    budget: Option<f64>,
+
     /// Path to config file
     #[arg(long)]
     config: Option<PathBuf>,
@@ -106,6 +128,14 @@ struct Cli {
     /// Continue the most recent session
     #[arg(short = 'c', long = "continue")]
     continue_session: bool,
+
+    /// Use a plain line-mode prompt instead of the full-screen TUI
+    #[arg(long)]
+    no_tui: bool,
+
+    /// Don't write this session to disk (no auto-save or crash recovery)
+    #[arg(long)]
+    no_save: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -114,26 +144,55 @@ enum Commands {
     /// Show all available modes and their descriptions
     Modes,
     /// Run system diagnostics and check configuration
-    Doctor,
+    Doctor {
+        /// Print the report as JSON and exit non-zero if the API is unreachable
+        #[arg(long, conflicts_with = "fix")]
+        json: bool,
+        /// Create a missing config, API key, MCP config, and skills directory, then re-run the checks
+        #[arg(long)]
+        fix: bool,
+        /// With --fix, apply every fix without prompting
+        #[arg(long, requires = "fix")]
+        yes: bool,
+        /// With --fix, overwrite existing config and template files
+        #[arg(long, requires = "fix")]
+        force: bool,
+    },
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for
         #[arg(value_enum)]
-        shell: Shell,
+        shell: CompletionShell,
+        /// Write the completion script to the shell's conventional location
+        #[arg(long)]
+        install: bool,
+        /// Override the install location (implies --install)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
     },
     /// List saved sessions
     Sessions {
+        #[command(subcommand)]
+        action: Option<SessionsAction>,
         /// Maximum number of sessions to display
         #[arg(short, long, default_value = "20")]
         limit: usize,
         /// Search sessions by title
         #[arg(short, long)]
         search: Option<String>,
+        /// Search the messages inside each session and show the matching line
+        #[arg(long, value_name = "TEXT", conflicts_with = "search")]
+        search_content: Option<String>,
+        /// Only list sessions carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
     },
     /// Create default AGENTS.md in current directory
     Init,
     /// Execpolicy tooling
     Execpolicy(ExecpolicyCommand),
+    /// Inspect persistent settings
+    Config(ConfigCli),
     /// Inspect feature flags
     Features(FeaturesCli),
     /// Run a command inside the sandbox
@@ -152,6 +211,14 @@ enum Commands {
     Setup(SetupCliArgs),
     /// Manage MCP servers
     Mcp(McpCliCommand),
+    /// List or validate skills without starting the TUI
+    Skills(SkillsCli),
+    /// Internal: print dynamic completion candidates for shell scripts.
+    #[command(name = "complete-values", hide = true)]
+    Complete {
+        #[arg(value_enum)]
+        kind: DynamicCompletion,
+    },
     /// Internal: run the responses API proxy.
     #[command(hide = true)]
     ResponsesApiProxy(responses_api_proxy::Args),
@@ -171,10 +238,22 @@ struct FeatureToggles {
 impl FeatureToggles {
     fn apply(&self, config: &mut Config) -> Result<()> {
         for feature in &self.enable {
-            config.set_feature(feature, true)?;
+            let toggle = config.set_feature(feature, true)?;
+            if !toggle.enabled_prerequisites.is_empty() {
+                eprintln!(
+                    "Enabling {feature} also enabled: {}",
+                    toggle.enabled_prerequisites.join(", ")
+                );
+            }
         }
         for feature in &self.disable {
-            config.set_feature(feature, false)?;
+            let toggle = config.set_feature(feature, false)?;
+            if !toggle.broken_dependents.is_empty() {
+                eprintln!(
+                    "Warning: disabling {feature} leaves enabled features that need it: {}",
+                    toggle.broken_dependents.join(", ")
+                );
+            }
         }
         Ok(())
     }
@@ -192,6 +271,63 @@ enum ExecpolicySubcommand {
     Check(execpolicy::ExecPolicyCheckCommand),
 }
 
+#[derive(Args, Debug, Clone)]
+struct ConfigCli {
+    #[command(subcommand)]
+    command: ConfigSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ConfigSubcommand {
+    /// Show effective config values and which file, profile, or env var set each
+    Show,
+    /// List every setting with its type, default, current value, and description
+    Keys,
+    /// Rewrite renamed config keys in place (a .bak copy is kept)
+    Migrate,
+    /// Print a key's value from the config file
+    Get {
+        /// Config key, e.g. `max_subagents` or `features.rlm`
+        key: String,
+    },
+    /// Set a key in the config file, keeping comments and layout
+    Set {
+        /// Config key, e.g. `max_subagents` or `features.rlm`
+        key: String,
+        /// Value, read as TOML (`true`, `3`, `"text"`) or else a plain string
+        value: String,
+    },
+    /// Remove a key from the config file
+    Unset {
+        /// Config key, e.g. `max_subagents` or `features.rlm`
+        key: String,
+    },
+    /// List profiles defined in the config file
+    Profiles,
+    /// Add an empty `[profiles.<name>]` section that inherits the defaults
+    CreateProfile {
+        /// Profile name, used with `--profile <name>`
+        name: String,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+struct SkillsCli {
+    /// Only use this skills directory (default: the configured skills_dir plus ./skills)
+    #[arg(long, value_name = "DIR", global = true)]
+    dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: SkillsSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SkillsSubcommand {
+    /// List skills with their descriptions and directories
+    List,
+    /// Check every SKILL.md for front-matter and required fields
+    Validate,
+}
+
 #[derive(Args, Debug, Clone)]
 struct FeaturesCli {
     #[command(subcommand)]
@@ -202,6 +338,11 @@ struct FeaturesCli {
 enum FeaturesSubcommand {
     /// List known feature flags and their state
     List,
+    /// Explain what a feature flag does, its stage, default, and dependencies
+    Describe {
+        /// Feature key, as shown by `features list`
+        name: String,
+    },
 }
 
 #[derive(Args, Debug, Clone)]
@@ -220,6 +361,10 @@ enum SandboxCommand {
         /// Allow outbound network access
         #[arg(long)]
         network: bool,
+        /// Limit outbound network access to localhost, localhost:PORT, or *:PORT (repeatable,
+        /// workspace-write only; hostnames cannot be filtered)
+        #[arg(long, value_name = "HOST")]
+        allow_host: Vec<String>,
         /// Additional writable roots (repeatable)
         #[arg(long, value_name = "PATH")]
         writable_root: Vec<PathBuf>,
@@ -235,6 +380,9 @@ enum SandboxCommand {
         /// Timeout in milliseconds
         #[arg(long, default_value_t = 60_000)]
         timeout_ms: u64,
+        /// Print the prepared sandbox command without executing it
+        #[arg(long)]
+        dry_run: bool,
         /// Command and arguments to run
         #[arg(required = true, trailing_var_arg = true)]
         command: Vec<String>,
@@ -363,7 +511,7 @@ enum CodingSubcommand {
         #[arg(short, long)]
         model: Option<String>,
         /// Max tokens (default: 4096)
-        #[arg(short, long)]
+        #[arg(long)]
         max_tokens: Option<u32>,
         /// Temperature (default: 0.7)
         #[arg(short, long)]
@@ -387,6 +535,9 @@ struct ReviewArgs {
     /// Review staged changes instead of the working tree
     #[arg(long, conflicts_with = "base")]
     staged: bool,
+    /// Read a unified diff from stdin instead of running git diff
+    #[arg(long, conflicts_with_all = ["staged", "base", "path"])]
+    stdin: bool,
     /// Base ref to diff against (e.g. origin/main)
     #[arg(long)]
     base: Option<String>,
@@ -399,6 +550,20 @@ struct ReviewArgs {
     /// Maximum diff characters to include
     #[arg(long, default_value_t = 200_000)]
     max_chars: usize,
+    /// Only print findings at or above this severity (default: all)
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    min_severity: Option<ReviewSeverity>,
+    /// Skip the review cache: ask the model again and don't store the answer
+    #[arg(long)]
+    no_cache: bool,
+}
+
+/// Severity tag the reviewer puts on each finding, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+enum ReviewSeverity {
+    Info,
+    Warning,
+    Error,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -411,6 +576,9 @@ struct ExecArgs {
     /// Enable agentic mode with tool access and auto-approvals
     #[arg(long, default_value_t = false)]
     auto: bool,
+    /// Print one JSON object per event (text, tool_use, tool_result, final) to stdout
+    #[arg(long, default_value_t = false)]
+    json: bool,
 }
 
 #[derive(Args, Debug, Clone, Default)]
@@ -432,6 +600,28 @@ struct SetupCliArgs {
     force: bool,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum SessionsAction {
+    /// Rename a saved session
+    Rename {
+        /// Session ID or unique prefix
+        #[arg(value_name = "ID")]
+        session: String,
+        /// New title
+        #[arg(value_name = "TITLE")]
+        title: String,
+    },
+    /// Show full metadata for one saved session
+    Info {
+        /// Session ID or unique prefix
+        #[arg(value_name = "ID")]
+        session: String,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Args, Debug, Clone)]
 struct McpCliCommand {
     #[command(subcommand)]
@@ -460,6 +650,30 @@ enum McpSubcommand {
         #[arg(value_name = "SERVER")]
         server: Option<String>,
     },
+    /// Enable a server in the MCP config
+    Enable {
+        /// Server name from the MCP config
+        #[arg(value_name = "SERVER")]
+        server: String,
+    },
+    /// Disable a server in the MCP config without removing it
+    Disable {
+        /// Server name from the MCP config
+        #[arg(value_name = "SERVER")]
+        server: String,
+    },
+    /// Call a tool on an MCP server and print the result
+    Call {
+        /// Server name from the MCP config
+        #[arg(value_name = "SERVER")]
+        server: String,
+        /// Tool name as reported by `mcp tools`
+        #[arg(value_name = "TOOL")]
+        tool: String,
+        /// Tool arguments as a JSON object (defaults to `{}`)
+        #[arg(value_name = "JSON")]
+        args: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -471,17 +685,49 @@ async fn main() -> Result<()> {
     // Handle subcommands first
     if let Some(command) = cli.command.clone() {
         return match command {
-            Commands::Doctor => {
+            Commands::Doctor { json: true, .. } => run_doctor_json().await,
+            Commands::Doctor {
+                fix: true,
+                yes,
+                force,
+                ..
+            } => run_doctor_fix(yes, force).await,
+            Commands::Doctor { .. } => {
                 run_doctor().await;
                 Ok(())
             }
-            Commands::Completions { shell } => {
-                generate_completions(shell);
-                Ok(())
+            Commands::Completions {
+                shell,
+                install,
+                path,
+            } => {
+                if install || path.is_some() {
+                    install_completions(shell, path)
+                } else {
+                    generate_completions(shell);
+                    Ok(())
+                }
             }
-            Commands::Sessions { limit, search } => list_sessions(limit, search),
+            Commands::Sessions {
+                action: Some(SessionsAction::Rename { session, title }),
+                ..
+            } => rename_session(&session_dir_from_cli(&cli), &session, &title),
+            Commands::Sessions {
+                action: Some(SessionsAction::Info { session, json }),
+                ..
+            } => session_info(&session_dir_from_cli(&cli), &session, json),
+            Commands::Sessions {
+                search_content: Some(query),
+                limit,
+                tag,
+                ..
+            } => search_session_content(&session_dir_from_cli(&cli), &query, limit, tag),
+            Commands::Sessions {
+                limit, search, tag, ..
+            } => list_sessions(&session_dir_from_cli(&cli), limit, search, tag),
             Commands::Init => init_project(),
             Commands::Execpolicy(command) => run_execpolicy_command(command),
+            Commands::Config(command) => run_config_command(&cli, command),
             Commands::Features(command) => {
                 let config = load_config_from_cli(&cli)?;
                 run_features_command(&config, command)
@@ -505,10 +751,11 @@ async fn main() -> Result<()> {
                     .clone()
                     .or_else(|| config.default_model.clone())
                     .unwrap_or_else(|| "model-01".to_string());
+                let prompt = render_prompt_template(&args.prompt, &cli.vars)?;
                 if args.auto || cli.yolo {
-                    run_exec_agent(&config, &model, &args.prompt).await
+                    run_exec_agent(&config, &model, &prompt, args.json).await
                 } else {
-                    run_one_shot(&config, &model, &args.prompt).await
+                    run_one_shot(&config, &model, &prompt, args.json).await
                 }
             }
             Commands::Setup(args) => {
@@ -520,16 +767,34 @@ async fn main() -> Result<()> {
             }
             Commands::Mcp(args) => {
                 let config = load_config_from_cli(&cli)?;
-                run_mcp_command(&config, args)
+                run_mcp_command(&config, args).await
+            }
+            Commands::Skills(command) => {
+                let config = load_config_from_cli(&cli)?;
+                run_skills_command(&cli, &config, command)
+            }
+            Commands::Complete { kind } => {
+                // Completion output goes to the shell, so a broken config
+                // falls back to the defaults quietly
+                let config = load_config_from_cli(&cli).unwrap_or_default();
+                for candidate in completions::dynamic_candidates(kind, &config) {
+                    println!("{candidate}");
+                }
+                Ok(())
             }
             Commands::ResponsesApiProxy(args) => responses_api_proxy::run_main(args),
         };
     }
 
-    let config = load_config_from_cli(&cli)?;
+    let mut config = load_config_from_cli(&cli)?;
+    if let Some(budget) = cli.budget {
+        config.max_session_cost_usd = Some(budget);
+        config.validate()?;
+    }
 
     let workspace = cli
         .workspace
+        .clone()
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
     let model = config
@@ -538,17 +803,22 @@ async fn main() -> Result<()> {
         .unwrap_or_else(|| "model-01".to_string());
     let max_subagents = cli
         .max_subagents
-        .map_or_else(|| config.max_subagents(), |value| value.clamp(1, 5));
+        .map_or_else(|| config.max_subagents(), |value| value.min(5));
 
     // One-shot prompt mode
+    if let Some(path) = cli.prompt_file.as_deref() {
+        let prompt = render_prompt_template(&read_prompt_file(path)?, &cli.vars)?;
+        return run_one_shot(&config, &model, &prompt, false).await;
+    }
     if let Some(prompt) = cli.prompt {
-        return run_one_shot(&config, &model, &prompt).await;
+        let prompt = render_prompt_template(&prompt, &cli.vars)?;
+        return run_one_shot(&config, &model, &prompt, false).await;
     }
 
     // Handle session resume
     let resume_session_id = if cli.continue_session {
         // Get most recent session
-        match session_manager::SessionManager::default_location() {
+        match session_manager::SessionManager::new(config.session_dir()) {
             Ok(manager) => manager.get_latest_session().ok().flatten().map(|m| m.id),
             Err(_) => None,
         }
@@ -556,6 +826,21 @@ async fn main() -> Result<()> {
         cli.resume.clone()
     };
 
+    if cli.no_tui {
+        return repl::run_repl(
+            &config,
+            repl::ReplOptions {
+                model,
+                workspace,
+                allow_shell: cli.yolo || config.allow_shell(),
+                yolo: cli.yolo,
+                max_subagents,
+                resume_session_id,
+            },
+        )
+        .await;
+    }
+
     // Default: Interactive TUI
     // --yolo starts in YOLO mode (shell + trust + auto-approve)
     tui::run_tui(
@@ -572,27 +857,112 @@ async fn main() -> Result<()> {
             start_in_agent_mode: cli.yolo,
             yolo: cli.yolo, // YOLO mode auto-approves all tool executions
             resume_session_id,
+            no_save: cli.no_save,
             max_subagents,
+            config_path: cli.config.clone(),
+            profile: cli_profile(&cli),
         },
     )
     .await
 }
 
+/// Profile selected by `--profile`, falling back to `AXIOM_PROFILE`
+fn cli_profile(cli: &Cli) -> Option<String> {
+    cli.profile
+        .clone()
+        .or_else(|| crate::config::env_var("AXIOM_PROFILE").ok())
+}
+
+/// Config file selected by `--config`, falling back to the default location
+fn cli_config_path(cli: &Cli) -> Result<PathBuf> {
+    cli.config
+        .clone()
+        .or_else(config::default_config_path)
+        .context("Failed to resolve config path: home directory not found.")
+}
+
 fn load_config_from_cli(cli: &Cli) -> Result<Config> {
-    let profile = cli
-        .profile
+    let profile = cli_profile(cli);
+    let workspace = cli
+        .workspace
         .clone()
-        .or_else(|| std::env::var("AXIOM_PROFILE").ok());
-    let mut config = Config::load(cli.config.clone(), profile.as_deref())?;
+        .or_else(|| std::env::current_dir().ok());
+    let mut config =
+        Config::load_for_workspace(cli.config.clone(), profile.as_deref(), workspace.as_deref())?;
     cli.feature_toggles.apply(&mut config)?;
     Ok(config)
 }
 
+/// Session directory for the `sessions` subcommand. Listing sessions works
+/// without a usable config, so a load error falls back to the default
+/// directory with a warning.
+fn session_dir_from_cli(cli: &Cli) -> PathBuf {
+    match load_config_from_cli(cli) {
+        Ok(config) => config.session_dir(),
+        Err(err) => {
+            eprintln!("Warning: {err:#}; using the default session directory");
+            Config::default().session_dir()
+        }
+    }
+}
+
 /// Generate shell completions for the given shell
-fn generate_completions(shell: Shell) {
+fn generate_completions(shell: CompletionShell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    completions::generate(shell, &mut cmd, &name, &mut io::stdout());
+}
+
+/// Write shell completions to the conventional location for the shell (or `path`)
+fn install_completions(shell: CompletionShell, path: Option<PathBuf>) -> Result<()> {
+    use colored::Colorize;
+
+    let (green_r, green_g, green_b) = palette::GREEN_RGB;
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+
     let mut cmd = Cli::command();
     let name = cmd.get_name().to_string();
-    generate(shell, &mut cmd, name, &mut io::stdout());
+    let target = match path {
+        Some(path) => path,
+        None => completions::default_install_path(shell, &name).ok_or_else(|| {
+            anyhow::anyhow!("No default completion location for {shell}. Use --path to choose one.")
+        })?,
+    };
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let mut buf = Vec::new();
+    completions::generate(shell, &mut cmd, &name, &mut buf);
+    std::fs::write(&target, buf)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", target.display()))?;
+
+    println!(
+        "{} Installed {shell} completions to {}",
+        "✓".truecolor(green_r, green_g, green_b),
+        target.display()
+    );
+    let next_step = match shell {
+        CompletionShell::Bash => format!(
+            "Open a new shell (requires bash-completion) or run: source {}",
+            target.display()
+        ),
+        CompletionShell::Zsh => {
+            let dir = target
+                .parent()
+                .map_or_else(String::new, |p| p.display().to_string());
+            format!("Add `fpath=({dir} $fpath)` before `compinit` in ~/.zshrc, then run: exec zsh")
+        }
+        CompletionShell::Fish => "Open a new fish shell to load the completions".to_string(),
+        CompletionShell::Nushell => format!(
+            "Open a new nu shell (Nushell 0.101+ autoloads this directory), or add `source {}` to config.nu",
+            target.display()
+        ),
+        _ => "Load the file from your shell profile, then restart your shell".to_string(),
+    };
+    println!("  Next: {}", next_step.truecolor(blue_r, blue_g, blue_b));
+    Ok(())
 }
 
 fn run_execpolicy_command(command: ExecpolicyCommand) -> Result<()> {
@@ -601,9 +971,174 @@ fn run_execpolicy_command(command: ExecpolicyCommand) -> Result<()> {
     }
 }
 
+fn run_config_command(cli: &Cli, command: ConfigCli) -> Result<()> {
+    match command.command {
+        ConfigSubcommand::Show => {
+            let profile = cli_profile(cli);
+            let workspace = cli
+                .workspace
+                .clone()
+                .or_else(|| std::env::current_dir().ok());
+            let entries = Config::effective_entries(
+                cli.config.clone(),
+                profile.as_deref(),
+                workspace.as_deref(),
+            )?;
+            if entries.is_empty() {
+                println!("No config values set; using defaults.");
+                return Ok(());
+            }
+            let key_width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+            let value_width = entries
+                .iter()
+                .map(|e| e.value.chars().count().min(48))
+                .max()
+                .unwrap_or(0);
+            for entry in entries {
+                let value = utils::truncate_with_ellipsis(&entry.value, 48, "...");
+                println!(
+                    "{:<key_width$}  {value:<value_width$}  {}",
+                    entry.key, entry.source
+                );
+            }
+            Ok(())
+        }
+        ConfigSubcommand::Migrate => {
+            let path = cli_config_path(cli)?;
+            if path.exists() {
+                let changes = config::migrate_config_file(&path)?;
+                if changes.is_empty() {
+                    println!("{} is up to date.", path.display());
+                } else {
+                    println!("Updated {}:", path.display());
+                    for change in &changes {
+                        println!("  {change}");
+                    }
+                    println!(
+                        "Previous version saved to {}",
+                        path.with_extension("toml.bak").display()
+                    );
+                }
+            } else {
+                println!("No config file at {}.", path.display());
+            }
+            for (legacy, current) in config::legacy_env_vars_in_use() {
+                println!("Environment: rename {legacy} to {current} in your shell profile.");
+            }
+            Ok(())
+        }
+        ConfigSubcommand::Keys => {
+            println!("{}", settings::Settings::load()?.schema_table());
+            Ok(())
+        }
+        ConfigSubcommand::Get { key } => {
+            let key = config::ConfigKey::parse(&key)?;
+            let path = cli_config_path(cli)?;
+            match config::get_config_value(&path, &key)? {
+                Some(toml::Value::String(value)) => println!("{value}"),
+                Some(value) => println!("{value}"),
+                None => println!("{key} is not set in {}", path.display()),
+            }
+            Ok(())
+        }
+        ConfigSubcommand::Set { key, value } => {
+            let key = config::ConfigKey::parse(&key)?;
+            let path = cli_config_path(cli)?;
+            let written = config::set_config_value(&path, &key, &value)?;
+            println!("Set {key} = {written} in {}", path.display());
+            Ok(())
+        }
+        ConfigSubcommand::Profiles => {
+            let path = cli_config_path(cli)?;
+            let profiles = config::list_profiles(&path)?;
+            if profiles.is_empty() {
+                println!("No profiles defined in {}.", path.display());
+                println!("Create one with `axiom config create-profile <name>`.");
+                return Ok(());
+            }
+            let active = cli_profile(cli);
+            let name_width = profiles.iter().map(|p| p.name.len()).max().unwrap_or(0);
+            for profile in profiles {
+                let marker = if active.as_deref() == Some(profile.name.as_str()) {
+                    "*"
+                } else {
+                    " "
+                };
+                let keys = if profile.keys.is_empty() {
+                    "(inherits defaults)".to_string()
+                } else {
+                    profile.keys.join(", ")
+                };
+                println!("{marker} {:<name_width$}  {keys}", profile.name);
+            }
+            Ok(())
+        }
+        ConfigSubcommand::CreateProfile { name } => {
+            let path = cli_config_path(cli)?;
+            config::create_profile(&path, &name)?;
+            println!("Added [profiles.{}] to {}", name.trim(), path.display());
+            println!("Use it with `axiom --profile {}`.", name.trim());
+            Ok(())
+        }
+        ConfigSubcommand::Unset { key } => {
+            let key = config::ConfigKey::parse(&key)?;
+            let path = cli_config_path(cli)?;
+            if config::unset_config_value(&path, &key)? {
+                println!("Removed {key} from {}", path.display());
+            } else {
+                println!("{key} is not set in {}", path.display());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn run_skills_command(cli: &Cli, config: &Config, command: SkillsCli) -> Result<()> {
+    let search_path = match command.dir {
+        Some(dir) => vec![(skills::SkillSource::Workspace, dir)],
+        None => {
+            let workspace = cli
+                .workspace
+                .clone()
+                .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+            skills::skill_search_path(&workspace, config.skills_dir())
+        }
+    };
+
+    match command.command {
+        SkillsSubcommand::List => skills::list(&search_path),
+        SkillsSubcommand::Validate => {
+            let mut report = skills::ValidationReport::default();
+            for (_, dir) in &search_path {
+                // Only an explicit --dir has to exist
+                if search_path.len() > 1 && !dir.exists() {
+                    continue;
+                }
+                let dir_report = skills::validate_dir(dir)?;
+                report.checked += dir_report.checked;
+                report.issues.extend(dir_report.issues);
+            }
+            for issue in &report.issues {
+                println!("{}: {}", issue.path.display(), issue.message);
+            }
+            if report.issues.is_empty() {
+                println!("All {} skill(s) are valid.", report.checked);
+                Ok(())
+            } else {
+                anyhow::bail!(
+                    "Failed to validate skills: {} problem(s) in {} skill(s) checked",
+                    report.issues.len(),
+                    report.checked
+                )
+            }
+        }
+    }
+}
+
 fn run_features_command(config: &Config, command: FeaturesCli) -> Result<()> {
     match command.command {
         FeaturesSubcommand::List => run_features_list(config),
+        FeaturesSubcommand::Describe { name } => run_features_describe(config, &name),
     }
 }
 
@@ -619,14 +1154,52 @@ fn stage_str(stage: features::Stage) -> &'static str {
 
 fn run_features_list(config: &Config) -> Result<()> {
     let features = config.features();
-    println!("feature\tstage\tenabled");
+    println!("feature\tstage\tenabled\trequires");
     for spec in features::FEATURES {
         let enabled = features.enabled(spec.id);
-        println!("{}\t{}\t{enabled}", spec.key, stage_str(spec.stage));
+        let requires = if spec.requires.is_empty() {
+            "-".to_string()
+        } else {
+            spec.requires.join(",")
+        };
+        println!(
+            "{}\t{}\t{enabled}\t{requires}",
+            spec.key,
+            stage_str(spec.stage)
+        );
     }
     Ok(())
 }
 
+fn run_features_describe(config: &Config, name: &str) -> Result<()> {
+    let Some(spec) = features::feature_spec_by_key(name) else {
+        let known: Vec<&str> = features::FEATURES.iter().map(|spec| spec.key).collect();
+        anyhow::bail!("Unknown feature flag: {name} (known: {})", known.join(", "));
+    };
+    let on_off = |enabled: bool| if enabled { "on" } else { "off" };
+    let list_or_none = |keys: &[&str]| {
+        if keys.is_empty() {
+            "none".to_string()
+        } else {
+            keys.join(", ")
+        }
+    };
+    println!("{}", spec.key);
+    println!("  {}", spec.description);
+    println!("  stage:       {}", stage_str(spec.stage));
+    println!("  default:     {}", on_off(spec.default_enabled));
+    println!(
+        "  enabled:     {}",
+        on_off(config.features().enabled(spec.id))
+    );
+    println!("  requires:    {}", list_or_none(spec.requires));
+    println!(
+        "  required by: {}",
+        list_or_none(&features::dependents(spec.key))
+    );
+    Ok(())
+}
+
 fn run_sandbox_command(args: SandboxArgs) -> Result<()> {
     use crate::sandbox::{CommandSpec, SandboxManager};
     use std::io::Read;
@@ -637,17 +1210,20 @@ fn run_sandbox_command(args: SandboxArgs) -> Result<()> {
     let SandboxCommand::Run {
         policy,
         network,
+        allow_host,
         writable_root,
         exclude_tmpdir,
         exclude_slash_tmp,
         cwd,
         timeout_ms,
+        dry_run,
         command,
     } = args.command;
 
     let policy = parse_sandbox_policy(
         &policy,
         network,
+        allow_host,
         writable_root,
         exclude_tmpdir,
         exclude_slash_tmp,
@@ -663,6 +1239,20 @@ fn run_sandbox_command(args: SandboxArgs) -> Result<()> {
     let manager = SandboxManager::new();
     let exec_env = manager.prepare(&spec);
 
+    if !exec_env.policy.network_allowlist().is_empty()
+        && !SandboxManager::supports_host_allowlist(exec_env.sandbox_type)
+    {
+        eprintln!(
+            "warning: --allow-host is not supported by the {} sandbox; allowing all network access",
+            exec_env.sandbox_type
+        );
+    }
+
+    if dry_run {
+        print_sandbox_dry_run(&exec_env)?;
+        return Ok(());
+    }
+
     let mut cmd = Command::new(exec_env.program());
     cmd.args(exec_env.args())
         .current_dir(&exec_env.cwd)
@@ -730,15 +1320,71 @@ fn run_sandbox_command(args: SandboxArgs) -> Result<()> {
     Ok(())
 }
 
+/// Print what `SandboxManager::prepare` produced, without running anything
+fn print_sandbox_dry_run(exec_env: &crate::sandbox::ExecEnv) -> Result<()> {
+    println!("Sandbox type: {}", exec_env.sandbox_type);
+    println!("Policy: {}", serde_json::to_string(&exec_env.policy)?);
+    println!("Working directory: {}", exec_env.cwd.display());
+    println!("Timeout: {}ms", exec_env.timeout.as_millis());
+
+    // The Seatbelt profile is passed inline as `sandbox-exec -p <profile>`; show it separately.
+    #[cfg(target_os = "macos")]
+    let profile_index = (exec_env.sandbox_type == crate::sandbox::SandboxType::MacosSeatbelt
+        && exec_env.command.get(1).is_some_and(|arg| arg == "-p"))
+    .then_some(2);
+    #[cfg(not(target_os = "macos"))]
+    let profile_index: Option<usize> = None;
+
+    println!("Command:");
+    for (idx, arg) in exec_env.command.iter().enumerate() {
+        if Some(idx) == profile_index {
+            println!("  <seatbelt profile>");
+        } else {
+            println!("  {arg}");
+        }
+    }
+
+    println!("Environment overrides:");
+    if exec_env.env.is_empty() {
+        println!("  (none)");
+    } else {
+        let mut env: Vec<_> = exec_env.env.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            println!("  {key}={value}");
+        }
+    }
+
+    if let Some(profile) = profile_index.and_then(|idx| exec_env.command.get(idx)) {
+        println!("Seatbelt profile:");
+        println!("{}", profile.trim());
+    }
+    Ok(())
+}
+
 fn parse_sandbox_policy(
     policy: &str,
     network: bool,
+    allow_host: Vec<String>,
     writable_root: Vec<PathBuf>,
     exclude_tmpdir: bool,
     exclude_slash_tmp: bool,
 ) -> Result<crate::sandbox::SandboxPolicy> {
     use crate::sandbox::SandboxPolicy;
 
+    if !allow_host.is_empty() && policy != "workspace-write" {
+        anyhow::bail!("--allow-host requires --policy workspace-write");
+    }
+    if let Some(host) = allow_host
+        .iter()
+        .find(|host| !SandboxPolicy::is_valid_allowed_host(host))
+    {
+        anyhow::bail!(
+            "Invalid --allow-host value: {host} (the sandbox can only filter localhost, \
+             localhost:PORT, or *:PORT; use --network to allow all hosts)"
+        );
+    }
+
     match policy {
         "danger-full-access" => Ok(SandboxPolicy::DangerFullAccess),
         "read-only" => Ok(SandboxPolicy::ReadOnly),
@@ -748,6 +1394,7 @@ fn parse_sandbox_policy(
         "workspace-write" => Ok(SandboxPolicy::WorkspaceWrite {
             writable_roots: writable_root,
             network_access: network,
+            allowed_hosts: allow_host,
             exclude_tmpdir,
             exclude_slash_tmp,
         }),
@@ -1299,6 +1946,20 @@ async fn run_doctor() {
             "✓".truecolor(green_r, green_g, green_b),
             config_file.display()
         );
+        let issues = crate::config::check_config_file(&config_file);
+        if issues.is_empty() {
+            println!(
+                "  {} config.toml is valid",
+                "✓".truecolor(green_r, green_g, green_b)
+            );
+        }
+        for issue in issues {
+            let marker = match issue.level {
+                CheckLevel::Warning => "!".truecolor(orange_r, orange_g, orange_b),
+                CheckLevel::Error => "✗".truecolor(red_r, red_g, red_b),
+            };
+            println!("  {marker} {}", issue.message);
+        }
     } else {
         println!(
             "  {} config.toml not found (will use defaults)",
@@ -1309,7 +1970,7 @@ async fn run_doctor() {
     // Check API keys
     println!();
     println!("{}", "API Keys:".bold());
-    let has_api_key = if std::env::var("AXIOM_API_KEY").is_ok() {
+    let has_api_key = if crate::config::env_var("AXIOM_API_KEY").is_ok() {
         println!(
             "  {} AXIOM_API_KEY is set",
             "✓".truecolor(green_r, green_g, green_b)
@@ -1543,57 +2204,433 @@ async fn run_doctor() {
     );
 }
 
-/// Test API connectivity by making a minimal request
-async fn test_api_connectivity() -> Result<String> {
-    use crate::client::AnthropicClient;
-    use crate::models::{ContentBlock, Message, MessageRequest};
+/// Run the doctor checks and print them as one JSON object, for monitoring.
+///
+/// Fails (exit code 1) unless the API connectivity check succeeded, so the
+/// command doubles as a health probe; a missing API key counts as a failure.
+async fn run_doctor_json() -> Result<()> {
+    let config_dir = dirs::home_dir().map_or_else(|| PathBuf::from(".axiom"), |h| h.join(".axiom"));
+    let config_file = config_dir.join("config.toml");
 
-    let config = Config::load(None, None)?;
-    let client = AnthropicClient::new(&config)?;
-    let model = client.model().to_string();
+    let api_key_source = if crate::config::env_var("AXIOM_API_KEY").is_ok() {
+        Some("env")
+    } else if Config::load(None, None)
+        .ok()
+        .and_then(|c| c.axiom_api_key().ok())
+        .is_some()
+    {
+        Some("config")
+    } else {
+        None
+    };
 
-    // Minimal request: single word prompt, 1 max token
-    let request = MessageRequest {
-        model: model.clone(),
-        messages: vec![Message {
-            role: "user".to_string(),
-            content: vec![ContentBlock::Text {
-                text: "hi".to_string(),
-                cache_control: None,
-            }],
-        }],
-        max_tokens: 1,
-        system: None,
-        tools: None,
-        tool_choice: None,
-        metadata: None,
-        thinking: None,
-        stream: Some(false),
-        temperature: None,
-        top_p: None,
+    let connectivity = if api_key_source.is_some() {
+        let started = std::time::Instant::now();
+        let result = test_api_connectivity().await;
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        match result {
+            Ok(model) => serde_json::json!({
+                "status": "ok",
+                "model": model,
+                "latency_ms": latency_ms,
+            }),
+            Err(e) => serde_json::json!({
+                "status": "failed",
+                "latency_ms": latency_ms,
+                "error": e.to_string(),
+            }),
+        }
+    } else {
+        serde_json::json!({ "status": "skipped", "error": "no API key configured" })
     };
+    let connected = connectivity["status"] == "ok";
+
+    let config_issues: Vec<serde_json::Value> = crate::config::check_config_file(&config_file)
+        .into_iter()
+        .map(|issue| {
+            let level = match issue.level {
+                CheckLevel::Warning => "warning",
+                CheckLevel::Error => "error",
+            };
+            serde_json::json!({ "level": level, "message": issue.message })
+        })
+        .collect();
+    let config_valid = config_issues.iter().all(|issue| issue["level"] != "error");
 
-    // Use tokio timeout to catch hanging requests
-    let timeout_duration = std::time::Duration::from_secs(15);
-    match tokio::time::timeout(timeout_duration, client.create_message(request)).await {
-        Ok(Ok(_response)) => Ok(model),
-        Ok(Err(e)) => Err(e),
-        Err(_) => anyhow::bail!("Request timeout after 15 seconds"),
-    }
-}
+    let mcp_config = config_dir.join("mcp.json");
+    let mut mcp_servers: Vec<String> = std::fs::read_to_string(&mcp_config)
+        .ok()
+        .and_then(|content| serde_json::from_str::<crate::mcp::McpConfig>(&content).ok())
+        .map(|config| config.servers.into_keys().collect())
+        .unwrap_or_default();
+    mcp_servers.sort();
 
-fn rustc_version() -> String {
-    // Try to get rustc version, fall back to "unknown"
-    std::process::Command::new("rustc")
+    let skills_dir = config_dir.join("skills");
+    let skills_count = std::fs::read_dir(&skills_dir)
+        .map(|entries| entries.filter_map(std::result::Result::ok).count())
+        .unwrap_or(0);
+
+    let sandbox = crate::sandbox::get_platform_sandbox();
+    let report = serde_json::json!({
+        "version": {
+            "axiom": env!("CARGO_PKG_VERSION"),
+            "rust": rustc_version(),
+        },
+        "config": {
+            "path": config_file.display().to_string(),
+            "exists": config_file.exists(),
+            "valid": config_valid,
+            "issues": config_issues,
+        },
+        "api_key": {
+            "configured": api_key_source.is_some(),
+            "source": api_key_source,
+        },
+        "connectivity": connectivity,
+        "mcp": {
+            "config_path": mcp_config.display().to_string(),
+            "exists": mcp_config.exists(),
+            "servers": mcp_servers,
+        },
+        "skills": {
+            "dir": skills_dir.display().to_string(),
+            "exists": skills_dir.exists(),
+            "count": skills_count,
+        },
+        "platform": {
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "sandbox_available": sandbox.is_some(),
+            "sandbox": sandbox.map(|kind| kind.to_string()),
+        },
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    if !connected {
+        anyhow::bail!("API connectivity check failed");
+    }
+    Ok(())
+}
+
+/// Create whatever `doctor` reports missing, asking before each change
+/// unless `yes`, then run the checks again. Existing files are only
+/// replaced with `force`.
+async fn run_doctor_fix(yes: bool, force: bool) -> Result<()> {
+    use colored::Colorize;
+    use std::io::IsTerminal;
+
+    if !yes && !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "Failed to prompt for fixes: stdin is not a terminal (pass --yes to apply them without asking)"
+        );
+    }
+
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+    let (green_r, green_g, green_b) = palette::GREEN_RGB;
+    let (orange_r, orange_g, orange_b) = palette::ORANGE_RGB;
+    let (muted_r, muted_g, muted_b) = palette::SILVER_RGB;
+    let done = "✓".truecolor(green_r, green_g, green_b);
+    let present = "·".truecolor(muted_r, muted_g, muted_b);
+    let skipped = "!".truecolor(orange_r, orange_g, orange_b);
+
+    println!(
+        "{}",
+        "Axiom CLI Doctor --fix"
+            .truecolor(blue_r, blue_g, blue_b)
+            .bold()
+    );
+    println!(
+        "{}",
+        "========================".truecolor(blue_r, blue_g, blue_b)
+    );
+    println!();
+
+    let config_path = crate::config::default_config_path()
+        .context("Failed to resolve config path: home directory not found.")?;
+    if config_path.exists() && !force {
+        println!(
+            "  {present} config.toml already exists at {}",
+            config_path.display()
+        );
+    } else if confirm(
+        &format!("Write a default config.toml to {}?", config_path.display()),
+        yes,
+    )? {
+        crate::config::write_default_config(&config_path, force)?;
+        println!("  {done} Wrote default config to {}", config_path.display());
+    } else {
+        println!("  {skipped} Skipped config.toml");
+    }
+
+    let config = Config::load(Some(config_path.clone()), None).unwrap_or_default();
+    if config.axiom_api_key().is_ok() {
+        println!("  {present} API key already configured");
+    } else if yes {
+        println!(
+            "  {skipped} No API key configured; set AXIOM_API_KEY or run `axiom doctor --fix` without --yes to enter one"
+        );
+    } else {
+        let key = prompt_line("  Enter your API key (leave empty to skip): ")?;
+        if key.is_empty() {
+            println!("  {skipped} Skipped API key");
+        } else {
+            let path = crate::config::save_config_with_base_url(&key, &config.axiom_base_url())?;
+            println!("  {done} Saved API key to {}", path.display());
+        }
+    }
+
+    let config = Config::load(Some(config_path), None).unwrap_or(config);
+    let mcp_path = config.mcp_config_path();
+    if mcp_path.exists() && !force {
+        println!(
+            "  {present} MCP config already exists at {}",
+            mcp_path.display()
+        );
+    } else if confirm(
+        &format!("Create an MCP config template at {}?", mcp_path.display()),
+        yes,
+    )? {
+        write_mcp_config_template(&mcp_path, force)?;
+        println!("  {done} Created MCP config at {}", mcp_path.display());
+    } else {
+        println!("  {skipped} Skipped MCP config");
+    }
+
+    let skills_dir = config.skills_dir();
+    let skill_file = example_skill_path(&skills_dir);
+    if skill_file.exists() && !force {
+        println!(
+            "  {present} Skills directory already set up at {}",
+            skills_dir.display()
+        );
+    } else if confirm(
+        &format!(
+            "Create the skills directory with an example skill at {}?",
+            skills_dir.display()
+        ),
+        yes,
+    )? {
+        write_example_skill(&skills_dir, force)?;
+        println!("  {done} Created example skill at {}", skill_file.display());
+    } else {
+        println!("  {skipped} Skipped skills directory");
+    }
+
+    println!();
+    run_doctor().await;
+    Ok(())
+}
+
+/// Ask a yes/no question, defaulting to yes; `assume_yes` skips the prompt.
+fn confirm(question: &str, assume_yes: bool) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+    let answer = prompt_line(&format!("  {question} [Y/n] "))?;
+    Ok(matches!(
+        answer.to_ascii_lowercase().as_str(),
+        "" | "y" | "yes"
+    ))
+}
+
+fn prompt_line(prompt: &str) -> Result<String> {
+    use std::io::Write;
+
+    print!("{prompt}");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .context("Failed to read answer from stdin")?;
+    Ok(line.trim().to_string())
+}
+
+/// Test API connectivity by making a minimal request
+async fn test_api_connectivity() -> Result<String> {
+    use crate::client::AnthropicClient;
+    use crate::models::{ContentBlock, Message, MessageRequest};
+
+    let config = Config::load(None, None)?;
+    let client = AnthropicClient::new(&config)?;
+    let model = client.model().to_string();
+
+    // Minimal request: single word prompt, 1 max token
+    let request = MessageRequest {
+        model: model.clone(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "hi".to_string(),
+                cache_control: None,
+            }],
+        }],
+        max_tokens: 1,
+        system: None,
+        tools: None,
+        tool_choice: None,
+        metadata: None,
+        thinking: None,
+        stream: Some(false),
+        temperature: None,
+        top_p: None,
+    };
+
+    // Use tokio timeout to catch hanging requests
+    let timeout_duration = std::time::Duration::from_secs(15);
+    match tokio::time::timeout(timeout_duration, client.create_message(request)).await {
+        Ok(Ok(_response)) => Ok(model),
+        Ok(Err(e)) => Err(e),
+        Err(_) => anyhow::bail!("Request timeout after 15 seconds"),
+    }
+}
+
+fn rustc_version() -> String {
+    // Try to get rustc version, fall back to "unknown"
+    std::process::Command::new("rustc")
         .arg("--version")
         .output()
         .ok()
         .and_then(|o| String::from_utf8(o.stdout).ok())
-        .map_or_else(|| "unknown".to_string(), |s| s.trim().to_string())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Show one saved session's details and usage
+fn session_info(session_dir: &Path, id: &str, json: bool) -> Result<()> {
+    use colored::Colorize;
+
+    let manager = session_manager::SessionManager::new(session_dir.to_path_buf())?;
+    let info = manager.session_info(id)?;
+    let meta = &info.metadata;
+    if json {
+        let value = serde_json::json!({
+            "id": meta.id,
+            "title": meta.title,
+            "model": meta.model,
+            "workspace": meta.workspace,
+            "message_count": meta.message_count,
+            "total_tokens": meta.total_tokens,
+            "created_at": meta.created_at,
+            "updated_at": meta.updated_at,
+            "tags": meta.tags,
+            "pin_count": info.pin_count,
+            "file_size": info.file_size,
+            "path": info.path,
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+    let local = |t: &chrono::DateTime<chrono::Utc>| {
+        t.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+    let rows = [
+        ("ID", meta.id.clone()),
+        ("Title", meta.title.clone()),
+        ("Model", meta.model.clone()),
+        ("Workspace", meta.workspace.display().to_string()),
+        ("Messages", meta.message_count.to_string()),
+        ("Tokens", meta.total_tokens.to_string()),
+        ("Created", local(&meta.created_at)),
+        ("Updated", local(&meta.updated_at)),
+        ("Tags", session_manager::format_tags(&meta.tags)),
+        ("Pins", info.pin_count.to_string()),
+        ("File size", format!("{} bytes", info.file_size)),
+        ("Path", info.path.display().to_string()),
+    ];
+    for (label, value) in rows {
+        if value.is_empty() {
+            continue;
+        }
+        println!(
+            "{} {}",
+            format!("{label:>10}:").truecolor(blue_r, blue_g, blue_b),
+            value
+        );
+    }
+    Ok(())
+}
+
+/// Set a saved session's title
+fn rename_session(session_dir: &Path, id: &str, title: &str) -> Result<()> {
+    use colored::Colorize;
+
+    let (green_r, green_g, green_b) = palette::GREEN_RGB;
+    let manager = session_manager::SessionManager::new(session_dir.to_path_buf())?;
+    let metadata = manager.rename_session(id, title)?;
+    println!(
+        "{} Renamed session {} to \"{}\"",
+        "✓".truecolor(green_r, green_g, green_b),
+        &metadata.id[..8],
+        metadata.title
+    );
+    Ok(())
+}
+
+fn search_session_content(
+    session_dir: &Path,
+    query: &str,
+    limit: usize,
+    tag: Option<String>,
+) -> Result<()> {
+    use colored::Colorize;
+    use session_manager::{SessionManager, format_session_line};
+
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+    let (orange_r, orange_g, orange_b) = palette::ORANGE_RGB;
+    let (muted_r, muted_g, muted_b) = palette::SILVER_RGB;
+
+    let manager = SessionManager::new(session_dir.to_path_buf())?;
+    let matches: Vec<_> = manager
+        .search_session_content(query)?
+        .filter(|m| tag.as_deref().is_none_or(|tag| m.metadata.has_tag(tag)))
+        .take(limit.saturating_add(1))
+        .collect();
+
+    if matches.is_empty() {
+        println!(
+            "{}",
+            format!("No sessions mention \"{query}\".").truecolor(orange_r, orange_g, orange_b)
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Sessions mentioning \"{query}\"")
+            .truecolor(blue_r, blue_g, blue_b)
+            .bold()
+    );
+    println!();
+    for found in matches.iter().take(limit) {
+        println!("  {}", format_session_line(&found.metadata));
+        println!("    {}", found.snippet.truecolor(muted_r, muted_g, muted_b));
+    }
+    if matches.len() > limit {
+        println!();
+        println!("  More matches not shown. Use --limit to show more.");
+    }
+
+    println!();
+    println!(
+        "Resume with: {} {}",
+        "axiom --resume".truecolor(blue_r, blue_g, blue_b),
+        "<session-id>".truecolor(muted_r, muted_g, muted_b)
+    );
+    Ok(())
 }
 
 /// List saved sessions
-fn list_sessions(limit: usize, search: Option<String>) -> Result<()> {
+fn list_sessions(
+    session_dir: &Path,
+    limit: usize,
+    search: Option<String>,
+    tag: Option<String>,
+) -> Result<()> {
     use colored::Colorize;
     use session_manager::{SessionManager, format_session_line};
 
@@ -1602,13 +2639,16 @@ fn list_sessions(limit: usize, search: Option<String>) -> Result<()> {
     let (orange_r, orange_g, orange_b) = palette::ORANGE_RGB;
     let (muted_r, muted_g, muted_b) = palette::SILVER_RGB;
 
-    let manager = SessionManager::default_location()?;
+    let manager = SessionManager::new(session_dir.to_path_buf())?;
 
-    let sessions = if let Some(query) = search {
+    let mut sessions = if let Some(query) = search {
         manager.search_sessions(&query)?
     } else {
         manager.list_sessions()?
     };
+    if let Some(tag) = &tag {
+        sessions.retain(|s| s.has_tag(tag));
+    }
 
     if sessions.is_empty() {
         println!(
@@ -1705,7 +2745,77 @@ fn init_project() -> Result<()> {
     Ok(())
 }
 
-async fn run_one_shot(config: &Config, model: &str, prompt: &str) -> Result<()> {
+/// Read a prompt from a file, or from stdin when `path` is `-`
+fn read_prompt_file(path: &std::path::Path) -> Result<String> {
+    use std::io::Read;
+
+    let prompt = if path.as_os_str() == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|e| anyhow::anyhow!("Failed to read prompt from stdin: {e}"))?;
+        buf
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read prompt file {}: {e}", path.display()))?
+    };
+    if prompt.trim().is_empty() {
+        anyhow::bail!("Prompt file {} is empty", path.display());
+    }
+    Ok(prompt)
+}
+
+/// Apply `--var` substitutions to a prompt, warning about placeholders left unfilled
+fn render_prompt_template(prompt: &str, vars: &[String]) -> Result<String> {
+    let vars = prompt_template::parse_vars(vars)?;
+    let rendered = prompt_template::render(prompt, &vars);
+    if !rendered.unresolved.is_empty() {
+        eprintln!(
+            "warning: no --var given for {}; left as-is in the prompt",
+            rendered
+                .unresolved
+                .iter()
+                .map(|name| format!("{{{{{name}}}}}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(rendered.text)
+}
+
+/// One line of `axiom exec --json` output
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExecEvent<'a> {
+    Text {
+        text: &'a str,
+    },
+    ToolUse {
+        id: &'a str,
+        name: &'a str,
+        input: &'a serde_json::Value,
+    },
+    ToolResult {
+        tool_use_id: &'a str,
+        output: &'a str,
+        is_error: bool,
+    },
+    Final {
+        steps: usize,
+        stop_reason: Option<&'a str>,
+        input_tokens: u64,
+        output_tokens: u64,
+    },
+}
+
+impl ExecEvent<'_> {
+    fn emit(&self) -> Result<()> {
+        println!("{}", serde_json::to_string(self)?);
+        Ok(())
+    }
+}
+
+async fn run_one_shot(config: &Config, model: &str, prompt: &str, json: bool) -> Result<()> {
     use crate::client::AnthropicClient;
     use crate::models::{ContentBlock, Message, MessageRequest};
 
@@ -1733,10 +2843,23 @@ async fn run_one_shot(config: &Config, model: &str, prompt: &str) -> Result<()>
 
     let response = client.create_message(request).await?;
 
-    for block in response.content {
+    for block in &response.content {
         if let ContentBlock::Text { text, .. } = block {
-            println!("{text}");
+            if json {
+                ExecEvent::Text { text }.emit()?;
+            } else {
+                println!("{text}");
+            }
+        }
+    }
+    if json {
+        ExecEvent::Final {
+            steps: 1,
+            stop_reason: response.stop_reason.as_deref(),
+            input_tokens: u64::from(response.usage.input_tokens),
+            output_tokens: u64::from(response.usage.output_tokens),
         }
+        .emit()?;
     }
 
     Ok(())
@@ -1750,6 +2873,9 @@ async fn run_review(config: &Config, args: ReviewArgs) -> Result<()> {
 
     let diff = collect_diff(&args)?;
     if diff.trim().is_empty() {
+        if args.stdin {
+            anyhow::bail!("No diff to review. Pipe a unified diff into --stdin.");
+        }
         anyhow::bail!("No diff to review. Stage some changes or specify --base.");
     }
 
@@ -1758,15 +2884,22 @@ async fn run_review(config: &Config, args: ReviewArgs) -> Result<()> {
         .or_else(|| config.default_model.clone())
         .unwrap_or_else(|| "Axiom-model-01".to_string());
 
-    let system = SystemPrompt::Text(
-        "You are a senior code reviewer. Focus on bugs, risks, behavioral regressions, \
-         and missing tests. Provide findings ordered by severity with file references, \
-         then open questions, then a brief summary."
-            .to_string(),
-    );
+    let system_prompt = review_system_prompt(&diff);
     let user_prompt =
         format!("Review the following diff and provide feedback:\n\n{diff}\n\nEnd of diff.");
 
+    let cache = if args.no_cache {
+        None
+    } else {
+        Some(review_cache::ReviewCache::default_location()?)
+    };
+    let cache_key = review_cache::ReviewCache::key(&model, &system_prompt, &user_prompt);
+    if let Some(review) = cache.as_ref().and_then(|cache| cache.get(&cache_key)) {
+        print_review(&review, args.min_severity);
+        eprintln!("(cached; rerun with --no-cache to review again)");
+        return Ok(());
+    }
+
     let client = AnthropicClient::new(config)?;
     let request = MessageRequest {
         model,
@@ -1778,7 +2911,7 @@ async fn run_review(config: &Config, args: ReviewArgs) -> Result<()> {
             }],
         }],
         max_tokens: 4096,
-        system: Some(system),
+        system: Some(SystemPrompt::Text(system_prompt)),
         tools: None,
         tool_choice: None,
         metadata: None,
@@ -1789,54 +2922,260 @@ async fn run_review(config: &Config, args: ReviewArgs) -> Result<()> {
     };
 
     let response = client.create_message(request).await?;
-    for block in response.content {
-        if let ContentBlock::Text { text, .. } = block {
-            println!("{text}");
-        }
+    let review = response
+        .content
+        .into_iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    print_review(&review, args.min_severity);
+    if let Some(cache) = cache.filter(|_| !review.trim().is_empty())
+        && let Err(e) = cache.put(&cache_key, &review)
+    {
+        logging::warn(format!("{e:#}"));
     }
     Ok(())
 }
 
+fn print_review(review: &str, min_severity: Option<ReviewSeverity>) {
+    match min_severity {
+        Some(min) => {
+            let (filtered, hidden) = filter_review_by_severity(review, min);
+            println!("{filtered}");
+            if hidden > 0 {
+                eprintln!(
+                    "({hidden} finding(s) below {} hidden)",
+                    format!("{min:?}").to_lowercase()
+                );
+            }
+        }
+        None => println!("{review}"),
+    }
+}
+
 fn collect_diff(args: &ReviewArgs) -> Result<String> {
-    use std::process::Command;
+    if args.stdin {
+        use std::io::Read;
+
+        let mut diff = String::new();
+        io::stdin()
+            .read_to_string(&mut diff)
+            .map_err(|e| anyhow::anyhow!("Failed to read diff from stdin: {e}"))?;
+        if !diff.trim().is_empty() && !looks_like_unified_diff(&diff) {
+            anyhow::bail!(
+                "Input on stdin does not look like a unified diff (expected `diff --git` or `---`/`+++`/`@@` lines)"
+            );
+        }
+        return Ok(truncate_diff(diff, args.max_chars));
+    }
+
+    let diff = crate::utils::git_diff(
+        std::path::Path::new("."),
+        args.staged,
+        args.base.as_deref(),
+        args.path.as_deref(),
+    )?;
+    Ok(truncate_diff(diff, args.max_chars))
+}
+
+const REVIEW_BASE_PROMPT: &str = "You are a senior code reviewer. Focus on bugs, risks, behavioral regressions, \
+     and missing tests. Provide findings ordered by severity with file references, \
+     then open questions, then a brief summary. Start each finding with a severity tag: \
+     [error], [warning], or [info].";
+
+/// Severity tag at the start of a finding line, ignoring list markers and emphasis
+fn finding_severity(line: &str) -> Option<ReviewSeverity> {
+    let trimmed = line
+        .trim_start()
+        .trim_start_matches(|c: char| c == '-' || c == '*' || c == '_' || c.is_ascii_digit())
+        .trim_start_matches(['.', ')'])
+        .trim_start()
+        .trim_start_matches(['*', '_', '`']);
+    let tag = trimmed.strip_prefix('[')?.split_once(']')?.0;
+    ReviewSeverity::from_str(tag.trim(), true).ok()
+}
 
-    let mut cmd = Command::new("git");
-    cmd.arg("diff");
-    if args.staged {
-        cmd.arg("--cached");
+/// Drop findings below `min`, keeping untagged sections (open questions, summary).
+/// Returns the filtered text and how many findings were hidden.
+fn filter_review_by_severity(text: &str, min: ReviewSeverity) -> (String, usize) {
+    let mut kept = Vec::new();
+    let mut hidden = 0;
+    let mut skipping = false;
+    let mut after_blank = false;
+
+    for line in text.lines() {
+        if let Some(severity) = finding_severity(line) {
+            skipping = severity < min;
+            if skipping {
+                hidden += 1;
+            }
+        } else if skipping {
+            let starts_section = line.starts_with('#')
+                || (after_blank && !line.is_empty() && !line.starts_with(char::is_whitespace));
+            if starts_section {
+                skipping = false;
+            }
+        }
+        after_blank = line.trim().is_empty();
+        if !skipping {
+            kept.push(line);
+        }
     }
-    if let Some(base) = &args.base {
-        cmd.arg(format!("{base}...HEAD"));
+    (kept.join("\n"), hidden)
+}
+
+/// Reviewer prompt plus checklists for the languages that dominate the diff
+fn review_system_prompt(diff: &str) -> String {
+    let mut prompt = REVIEW_BASE_PROMPT.to_string();
+    for language in diff_languages(diff) {
+        if let Some(notes) = review_language_notes(language) {
+            prompt.push_str(&format!("\n\n{language}-specific concerns: {notes}"));
+        }
     }
-    if let Some(path) = &args.path {
-        cmd.arg("--").arg(path);
+    prompt
+}
+
+/// Languages touched by a diff, by changed-line share (at least 20%, top three)
+fn diff_languages(diff: &str) -> Vec<&'static str> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    let mut current = None;
+    let mut total = 0;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path.trim().trim_start_matches("b/");
+            current = (path != "/dev/null")
+                .then(|| language_for_path(path))
+                .flatten();
+            continue;
+        }
+        if line.starts_with("--- ") || line.starts_with("diff --git ") {
+            continue;
+        }
+        if !(line.starts_with('+') || line.starts_with('-')) {
+            continue;
+        }
+        total += 1;
+        if let Some(language) = current {
+            match counts.iter_mut().find(|(name, _)| *name == language) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((language, 1)),
+            }
+        }
     }
 
-    let output = cmd
-        .output()
-        .map_err(|e| anyhow::anyhow!("Failed to run git diff. Is git installed? ({e})"))?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git diff failed: {}", stderr.trim());
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count * 5 >= total)
+        .take(3)
+        .map(|(language, _)| language)
+        .collect()
+}
+
+fn language_for_path(path: &str) -> Option<&'static str> {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    if file == "Dockerfile" {
+        return Some("Dockerfile");
     }
-    let mut diff = String::from_utf8_lossy(&output.stdout).to_string();
-    if diff.len() > args.max_chars {
-        diff.truncate(args.max_chars);
-        diff.push_str("\n...[truncated]\n");
+    let ext = file.rsplit_once('.')?.1.to_ascii_lowercase();
+    let language = match ext.as_str() {
+        "rs" => "Rust",
+        "py" | "pyi" => "Python",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "go" => "Go",
+        "java" | "kt" | "kts" => "JVM",
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C/C++",
+        "sh" | "bash" | "zsh" => "Shell",
+        "sql" => "SQL",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn review_language_notes(language: &str) -> Option<&'static str> {
+    let notes = match language {
+        "Rust" => {
+            "unwrap/expect/indexing that can panic on untrusted input, unsafe blocks and their \
+             invariants, lifetime or borrow workarounds that clone excessively, blocking calls in \
+             async code, integer overflow and lossy `as` casts, errors swallowed with `let _ =` or `.ok()`."
+        }
+        "Python" => {
+            "mutable default arguments, bare or overly broad `except`, late-binding closures in \
+             loops, missing context managers for files/locks, `is` vs `==` comparisons, \
+             shadowed builtins, and unchecked `None` returns."
+        }
+        "JavaScript" | "TypeScript" => {
+            "unhandled promise rejections and missing `await`, `==` coercion, `this` binding in \
+             callbacks, mutation of shared state or props, `any`/non-null assertions hiding type \
+             errors, and unsanitized HTML or URLs (XSS)."
+        }
+        "Go" => {
+            "ignored `err` returns, goroutine leaks and missing context cancellation, data races \
+             on shared maps/slices, loop variable capture, `defer` inside loops, and nil map writes."
+        }
+        "JVM" => {
+            "null handling, resources not closed (use try-with-resources), equals/hashCode \
+             consistency, thread safety of shared fields, and swallowed exceptions."
+        }
+        "C/C++" => {
+            "buffer overflows and off-by-one indexing, use-after-free and double free, \
+             uninitialized memory, integer overflow in size calculations, missing bounds or \
+             null checks, and ownership of raw pointers."
+        }
+        "Shell" => {
+            "unquoted variable expansions, missing `set -euo pipefail`, unsafe `rm`/glob usage, \
+             word splitting on filenames, and command injection through interpolated input."
+        }
+        "SQL" => {
+            "injection via string concatenation, missing indexes for new predicates, \
+             non-transactional multi-step migrations, and destructive changes without backfill."
+        }
+        "Dockerfile" => {
+            "running as root, unpinned base images, secrets baked into layers, and cache-busting \
+             layer order."
+        }
+        _ => return None,
+    };
+    Some(notes)
+}
+
+/// Whether text has the file headers or hunk markers of a unified diff
+fn looks_like_unified_diff(text: &str) -> bool {
+    let mut old_header = false;
+    let mut new_header = false;
+    for line in text.lines() {
+        if line.starts_with("diff --git ") {
+            return true;
+        }
+        if line.starts_with("--- ") {
+            old_header = true;
+        } else if line.starts_with("+++ ") && old_header {
+            new_header = true;
+        } else if line.starts_with("@@ ") && new_header {
+            return true;
+        }
     }
-    Ok(diff)
+    false
 }
 
 // ─── Exec subcommand (agentic headless) ──────────────────────────────────
 
-async fn run_exec_agent(config: &Config, model: &str, prompt: &str) -> Result<()> {
+async fn run_exec_agent(config: &Config, model: &str, prompt: &str, json: bool) -> Result<()> {
     use crate::client::AnthropicClient;
     use crate::models::{ContentBlock, Message, MessageRequest};
     use crate::tools::ToolRegistryBuilder;
     use crate::tools::spec::ToolContext;
 
     let workspace = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    let context = ToolContext::new(&workspace).with_trust_mode(false);
+    let context = ToolContext::new(&workspace)
+        .with_trust_mode(false)
+        .with_web_search(config.web_search_config())
+        .with_shell_max_output_bytes(config.shell_max_output_bytes());
 
     let todo_list = crate::tools::todo::new_shared_todo_list();
     let plan_state = crate::tools::plan::new_shared_plan_state();
@@ -1856,6 +3195,14 @@ async fn run_exec_agent(config: &Config, model: &str, prompt: &str) -> Result<()
         }],
     }];
 
+    let mut steps = 0;
+    let mut stop_reason = None;
+    let (mut input_tokens, mut output_tokens) = (0u64, 0u64);
+    let mut budget = crate::pricing::CostBudget {
+        spent_usd: 0.0,
+        limit_usd: config.max_session_cost_usd(),
+    };
+
     // Agent loop: send → execute tools → send results → repeat
     for _step in 0..25 {
         let request = MessageRequest {
@@ -1872,7 +3219,29 @@ async fn run_exec_agent(config: &Config, model: &str, prompt: &str) -> Result<()
             top_p: None,
         };
 
+        // Stop before a call that could push spend past the budget
+        let request_tokens = crate::compaction::estimate_request_tokens(
+            &request.messages,
+            &request.system,
+            &request.tools,
+        );
+        let estimate_usd = crate::pricing::token_cost(
+            u32::try_from(request_tokens).unwrap_or(u32::MAX),
+            request.max_tokens,
+        );
+        if budget.would_exceed(estimate_usd) {
+            anyhow::bail!(
+                "Session budget reached ({}); raise it with --budget",
+                budget.summary()
+            );
+        }
+
         let response = client.create_message(request).await?;
+        budget.record(crate::pricing::usage_cost(&response.usage));
+        steps += 1;
+        stop_reason.clone_from(&response.stop_reason);
+        input_tokens += u64::from(response.usage.input_tokens);
+        output_tokens += u64::from(response.usage.output_tokens);
 
         let mut has_tool_use = false;
         let mut tool_results: Vec<ContentBlock> = Vec::new();
@@ -1880,16 +3249,33 @@ async fn run_exec_agent(config: &Config, model: &str, prompt: &str) -> Result<()
         for block in &response.content {
             match block {
                 ContentBlock::Text { text, .. } => {
-                    println!("{text}");
+                    if json {
+                        ExecEvent::Text { text }.emit()?;
+                    } else {
+                        println!("{text}");
+                    }
                 }
                 ContentBlock::ToolUse { id, name, input } => {
                     has_tool_use = true;
-                    eprintln!("⚙ {name}");
+                    if json {
+                        ExecEvent::ToolUse { id, name, input }.emit()?;
+                    } else {
+                        eprintln!("⚙ {name}");
+                    }
                     let result = registry.execute(name, input.clone()).await;
+                    let is_error = result.is_err();
                     let output = match result {
                         Ok(text) => text,
                         Err(e) => format!("Error: {e}"),
                     };
+                    if json {
+                        ExecEvent::ToolResult {
+                            tool_use_id: id,
+                            output: &output,
+                            is_error,
+                        }
+                        .emit()?;
+                    }
                     tool_results.push(ContentBlock::ToolResult {
                         tool_use_id: id.clone(),
                         content: output,
@@ -1916,6 +3302,16 @@ async fn run_exec_agent(config: &Config, model: &str, prompt: &str) -> Result<()
         });
     }
 
+    if json {
+        ExecEvent::Final {
+            steps,
+            stop_reason: stop_reason.as_deref(),
+            input_tokens,
+            output_tokens,
+        }
+        .emit()?;
+    }
+
     Ok(())
 }
 
@@ -1941,27 +3337,13 @@ fn run_setup(config: &Config, workspace: &std::path::Path, args: SetupCliArgs) -
 
     if run_mcp {
         let mcp_path = config.mcp_config_path();
-        if mcp_path.exists() && !args.force {
+        if !write_mcp_config_template(&mcp_path, args.force)? {
             println!(
                 "  {} MCP config already exists at {}",
                 "·".truecolor(blue_r, blue_g, blue_b),
                 mcp_path.display()
             );
         } else {
-            if let Some(parent) = mcp_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            let template = serde_json::json!({
-                "mcpServers": {
-                    "example": {
-                        "command": "npx",
-                        "args": ["-y", "@example/mcp-server"],
-                        "env": {},
-                        "disabled": true
-                    }
-                }
-            });
-            std::fs::write(&mcp_path, serde_json::to_string_pretty(&template)?)?;
             println!(
                 "  {} Created MCP config at {}",
                 "✓".truecolor(green_r, green_g, green_b),
@@ -1981,24 +3363,14 @@ fn run_setup(config: &Config, workspace: &std::path::Path, args: SetupCliArgs) -
             config.skills_dir()
         };
 
-        let example_dir = skills_dir.join("example");
-        let skill_file = example_dir.join("SKILL.md");
-
-        if skill_file.exists() && !args.force {
+        let skill_file = example_skill_path(&skills_dir);
+        if !write_example_skill(&skills_dir, args.force)? {
             println!(
                 "  {} Example skill already exists at {}",
                 "·".truecolor(blue_r, blue_g, blue_b),
                 skill_file.display()
             );
         } else {
-            std::fs::create_dir_all(&example_dir)?;
-            std::fs::write(
-                &skill_file,
-                "# Example Skill\n\n\
-                 Description: An example skill template.\n\n\
-                 ## Instructions\n\n\
-                 Replace this with your custom skill instructions.\n",
-            )?;
             println!(
                 "  {} Created example skill at {}",
                 "✓".truecolor(green_r, green_g, green_b),
@@ -2015,9 +3387,46 @@ fn run_setup(config: &Config, workspace: &std::path::Path, args: SetupCliArgs) -
     Ok(())
 }
 
+/// Write the MCP config template unless a config exists (or `force`).
+/// Returns whether the file was written.
+fn write_mcp_config_template(path: &std::path::Path, force: bool) -> Result<bool> {
+    if path.exists() && !force {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&mcp_config_template())?)?;
+    Ok(true)
+}
+
+fn example_skill_path(skills_dir: &std::path::Path) -> PathBuf {
+    skills_dir.join("example").join("SKILL.md")
+}
+
+/// Create the skills directory with an example skill unless one exists (or
+/// `force`). Returns whether the skill was written.
+fn write_example_skill(skills_dir: &std::path::Path, force: bool) -> Result<bool> {
+    let skill_file = example_skill_path(skills_dir);
+    if skill_file.exists() && !force {
+        return Ok(false);
+    }
+    if let Some(parent) = skill_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(
+        &skill_file,
+        "# Example Skill\n\n\
+         Description: An example skill template.\n\n\
+         ## Instructions\n\n\
+         Replace this with your custom skill instructions.\n",
+    )?;
+    Ok(true)
+}
+
 // ─── MCP CLI subcommands ─────────────────────────────────────────────────
 
-fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
+async fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
     use colored::Colorize;
 
     let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
@@ -2029,7 +3438,7 @@ fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
 
     match cmd.command {
         McpSubcommand::Init { force } => {
-            if mcp_path.exists() && !force {
+            if !write_mcp_config_template(&mcp_path, force)? {
                 println!(
                     "{} MCP config already exists at {}",
                     "·".truecolor(muted_r, muted_g, muted_b),
@@ -2037,20 +3446,6 @@ fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
                 );
                 println!("Use --force to overwrite.");
             } else {
-                if let Some(parent) = mcp_path.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-                let template = serde_json::json!({
-                    "mcpServers": {
-                        "example": {
-                            "command": "npx",
-                            "args": ["-y", "@example/mcp-server"],
-                            "env": {},
-                            "disabled": true
-                        }
-                    }
-                });
-                std::fs::write(&mcp_path, serde_json::to_string_pretty(&template)?)?;
                 println!(
                     "{} Created MCP config at {}",
                     "✓".truecolor(green_r, green_g, green_b),
@@ -2075,7 +3470,11 @@ fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
                                 "enabled".truecolor(green_r, green_g, green_b)
                             };
                             println!("  • {} ({})", name, status);
-                            println!("    Command: {} {}", server.command, server.args.join(" "));
+                            println!(
+                                "    {}: {}",
+                                server.effective_transport(),
+                                server.describe()
+                            );
                         }
                     }
                 }
@@ -2096,47 +3495,58 @@ fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
         }
         McpSubcommand::Connect { server } => {
             println!("Connecting to MCP servers...");
-            match crate::mcp::McpPool::from_config_path(&mcp_path) {
-                Ok(pool) => {
-                    let connected = pool.connected_servers();
-                    if connected.is_empty() {
-                        println!(
-                            "  {} No servers connected",
-                            "✗".truecolor(red_r, red_g, red_b)
-                        );
-                    } else {
-                        for name in connected {
-                            if server.as_deref().is_some_and(|s| s != name) {
-                                continue;
-                            }
-                            println!(
-                                "  {} {} connected",
+            match connect_mcp_pool(&mcp_path, server.as_deref()).await {
+                Ok((_, health)) if health.is_empty() => {
+                    println!(
+                        "  {} No servers configured",
+                        "✗".truecolor(red_r, red_g, red_b)
+                    );
+                }
+                Ok((_, health)) => {
+                    let width = health.iter().map(|h| h.name.len()).max().unwrap_or(0);
+                    for entry in &health {
+                        let elapsed = format!("{:>6.2}s", entry.elapsed.as_secs_f64());
+                        let (mark, status, detail) = match &entry.result {
+                            Ok(tools) => (
                                 "✓".truecolor(green_r, green_g, green_b),
-                                name
-                            );
-                        }
+                                "connected".truecolor(green_r, green_g, green_b),
+                                format!("{tools} tools"),
+                            ),
+                            Err(_) if entry.timed_out() => (
+                                "✗".truecolor(red_r, red_g, red_b),
+                                "timed out".truecolor(red_r, red_g, red_b),
+                                String::new(),
+                            ),
+                            Err(e) => (
+                                "✗".truecolor(red_r, red_g, red_b),
+                                "failed   ".truecolor(red_r, red_g, red_b),
+                                format!("{e:#}"),
+                            ),
+                        };
+                        let line = format!(
+                            "  {mark} {:<width$}  {status}  {}  {detail}",
+                            entry.name,
+                            elapsed.truecolor(muted_r, muted_g, muted_b)
+                        );
+                        println!("{}", line.trim_end());
                     }
                 }
                 Err(e) => {
-                    println!("  {} Failed: {}", "✗".truecolor(red_r, red_g, red_b), e);
+                    println!("  {} Failed: {:#}", "✗".truecolor(red_r, red_g, red_b), e);
                 }
             }
         }
         McpSubcommand::Tools { server } => {
             println!("{}", "MCP Tools".truecolor(blue_r, blue_g, blue_b).bold());
 
-            match crate::mcp::McpPool::from_config_path(&mcp_path) {
-                Ok(pool) => {
-                    let tools = pool.all_tools();
+            match connect_mcp_pool(&mcp_path, server.as_deref()).await {
+                Ok((pool, health)) => {
+                    let mut tools = pool.all_tools();
+                    tools.sort_by(|a, b| a.0.cmp(&b.0));
                     if tools.is_empty() {
                         println!("  (no tools discovered)");
                     } else {
-                        for (full_name, tool) in &tools {
-                            if let Some(ref s) = server
-                                && !full_name.starts_with(&format!("mcp_{s}_"))
-                            {
-                                continue;
-                            }
+                        for (_, tool) in &tools {
                             println!(
                                 "  {} — {}",
                                 tool.name,
@@ -2144,12 +3554,264 @@ fn run_mcp_command(config: &Config, cmd: McpCliCommand) -> Result<()> {
                             );
                         }
                     }
+                    for entry in &health {
+                        if let Err(e) = &entry.result {
+                            println!(
+                                "  {} {}: {:#}",
+                                "✗".truecolor(red_r, red_g, red_b),
+                                entry.name,
+                                e
+                            );
+                        }
+                    }
                 }
                 Err(e) => {
                     println!("  {} Failed: {}", "✗".truecolor(red_r, red_g, red_b), e);
                 }
             }
         }
+        McpSubcommand::Enable { server } => {
+            toggle_mcp_server(&mcp_path, &server, false)?;
+        }
+        McpSubcommand::Disable { server } => {
+            toggle_mcp_server(&mcp_path, &server, true)?;
+        }
+        McpSubcommand::Call { server, tool, args } => {
+            let arguments = parse_mcp_call_args(args.as_deref())?;
+            let mut pool = crate::mcp::McpPool::from_config_path(&mcp_path)?;
+            let timeouts = pool.config().timeouts;
+            let conn = pool.get_or_connect(&server).await?;
+
+            if !conn.tools().iter().any(|t| t.name == tool) {
+                let mut names: Vec<&str> = conn.tools().iter().map(|t| t.name.as_str()).collect();
+                names.sort_unstable();
+                let available = if names.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    names.join(", ")
+                };
+                anyhow::bail!(
+                    "Failed to find tool '{tool}' on MCP server '{server}'. Available tools: {available}"
+                );
+            }
+
+            let timeout = conn.config().effective_execute_timeout(&timeouts);
+            let result = conn.call_tool(&tool, arguments, timeout).await?;
+            let output = crate::mcp::format_tool_result(&result);
+            let is_error = result
+                .get("isError")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            if is_error {
+                anyhow::bail!("MCP tool '{tool}' returned an error: {output}");
+            }
+            println!("{output}");
+        }
+    }
+    Ok(())
+}
+
+/// Flip a server's `disabled` flag for `mcp enable` / `mcp disable`
+fn toggle_mcp_server(mcp_path: &std::path::Path, server: &str, disabled: bool) -> Result<()> {
+    use colored::Colorize;
+
+    let (green_r, green_g, green_b) = palette::GREEN_RGB;
+    let (muted_r, muted_g, muted_b) = palette::SILVER_RGB;
+    let state = if disabled { "disabled" } else { "enabled" };
+
+    if crate::mcp::set_server_disabled(mcp_path, server, disabled)? {
+        println!(
+            "{} MCP server {server} {state}",
+            "✓".truecolor(green_r, green_g, green_b)
+        );
+    } else {
+        println!(
+            "{} MCP server {server} is already {state}",
+            "·".truecolor(muted_r, muted_g, muted_b)
+        );
     }
     Ok(())
 }
+
+/// Load the MCP config and connect to one server, or to every enabled server
+async fn connect_mcp_pool(
+    mcp_path: &std::path::Path,
+    server: Option<&str>,
+) -> Result<(crate::mcp::McpPool, Vec<crate::mcp::ServerHealth>)> {
+    let mut pool = crate::mcp::McpPool::from_config_path(mcp_path)?;
+    let health = pool.connect_with_health(server).await;
+    Ok((pool, health))
+}
+
+/// Starter `mcp.json` written by `setup --mcp` and `mcp init`
+fn mcp_config_template() -> serde_json::Value {
+    serde_json::json!({
+        "mcpServers": {
+            "example": {
+                "command": "npx",
+                "args": ["-y", "@example/mcp-server"],
+                "env": {},
+                "disabled": true
+            },
+            "example-remote": {
+                "url": "https://mcp.example.com/mcp",
+                "transport": "http",
+                "headers": { "Authorization": "Bearer YOUR_TOKEN" },
+                "disabled": true
+            }
+        }
+    })
+}
+
+/// Parse the JSON arguments for `mcp call`, which must be an object
+fn parse_mcp_call_args(raw: Option<&str>) -> Result<serde_json::Value> {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(serde_json::json!({}));
+    };
+    let value: serde_json::Value = serde_json::from_str(raw)
+        .with_context(|| format!("Failed to parse tool arguments as JSON: {raw}"))?;
+    if !value.is_object() {
+        anyhow::bail!("Failed to parse tool arguments: expected a JSON object, got {raw}");
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn exec_events_serialize_with_a_type_tag() {
+        let input = serde_json::json!({"path": "a.txt"});
+        let event = ExecEvent::ToolUse {
+            id: "t1",
+            name: "read_file",
+            input: &input,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({"type": "tool_use", "id": "t1", "name": "read_file", "input": {"path": "a.txt"}})
+        );
+        let event = ExecEvent::Final {
+            steps: 2,
+            stop_reason: Some("end_turn"),
+            input_tokens: 10,
+            output_tokens: 5,
+        };
+        assert_eq!(serde_json::to_value(&event).unwrap()["type"], "final");
+    }
+
+    #[test]
+    fn mcp_call_args_must_be_an_object() {
+        assert_eq!(parse_mcp_call_args(None).unwrap(), serde_json::json!({}));
+        assert_eq!(
+            parse_mcp_call_args(Some(r#"{"path": "a.txt"}"#)).unwrap(),
+            serde_json::json!({"path": "a.txt"})
+        );
+        assert!(parse_mcp_call_args(Some("[1, 2]")).is_err());
+        assert!(parse_mcp_call_args(Some("{not json")).is_err());
+    }
+
+    #[test]
+    fn unified_diff_detection() {
+        assert!(looks_like_unified_diff(
+            "diff --git a/x b/x\nindex 1..2 100644\n"
+        ));
+        assert!(looks_like_unified_diff(
+            "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n"
+        ));
+        assert!(!looks_like_unified_diff("just some text\n+++ not a diff\n"));
+        assert!(!looks_like_unified_diff("--- a/x\n@@ -1 +1 @@\n"));
+    }
+
+    #[test]
+    fn diff_languages_ranks_by_changed_lines() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+                    @@ -1,2 +1,3 @@\n-a\n+b\n+c\n\
+                    --- a/tool.py\n+++ b/tool.py\n@@ -1 +1 @@\n-x\n+y\n\
+                    --- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-m\n+n\n\
+                    --- a/old.go\n+++ /dev/null\n@@ -1 +0,0 @@\n-gone\n";
+        assert_eq!(diff_languages(diff), vec!["Rust", "Python"]);
+
+        let prompt = review_system_prompt(diff);
+        assert!(prompt.starts_with(REVIEW_BASE_PROMPT));
+        assert!(prompt.contains("Rust-specific concerns: unwrap"));
+        assert!(prompt.contains("Python-specific concerns: mutable default"));
+        assert!(!prompt.contains("Go-specific"));
+    }
+
+    #[test]
+    fn review_severity_filter_keeps_higher_findings_and_sections() {
+        let review = "## Findings\n\
+                      1. **[error]** `src/a.rs:10` panics on empty input\n   \
+                      details for the error\n\
+                      2. [warning] `src/b.rs:3` missing test\n   \
+                      more warning detail\n\
+                      - [Info] naming nit\n\n\
+                      Open questions:\n\
+                      - Is this intended?";
+        let (filtered, hidden) = filter_review_by_severity(review, ReviewSeverity::Warning);
+        assert_eq!(hidden, 1);
+        assert!(filtered.contains("panics on empty input"));
+        assert!(filtered.contains("more warning detail"));
+        assert!(!filtered.contains("naming nit"));
+        assert!(filtered.contains("Open questions:\n- Is this intended?"));
+
+        let (filtered, hidden) = filter_review_by_severity(review, ReviewSeverity::Error);
+        assert_eq!(hidden, 2);
+        assert!(!filtered.contains("missing test"));
+        assert!(!filtered.contains("more warning detail"));
+        assert!(filtered.contains("details for the error"));
+    }
+
+    #[test]
+    fn truncate_diff_respects_char_boundaries() {
+        let diff = truncate_diff("ééé".to_string(), 3);
+        assert!(diff.starts_with('é'));
+        assert!(diff.ends_with("[truncated]\n"));
+        assert_eq!(truncate_diff("short".to_string(), 10), "short");
+    }
+
+    #[test]
+    fn doctor_fix_templates_are_idempotent() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mcp = tmp.path().join("mcp.json");
+        assert!(write_mcp_config_template(&mcp, false).expect("mcp"));
+        std::fs::write(&mcp, "{}").expect("edit");
+        assert!(!write_mcp_config_template(&mcp, false).expect("mcp"));
+        assert_eq!(std::fs::read_to_string(&mcp).expect("read"), "{}");
+        assert!(write_mcp_config_template(&mcp, true).expect("mcp"));
+
+        let skills = tmp.path().join("skills");
+        assert!(write_example_skill(&skills, false).expect("skill"));
+        assert!(!write_example_skill(&skills, false).expect("skill"));
+        assert!(example_skill_path(&skills).exists());
+
+        assert!(Cli::try_parse_from(["axiom", "doctor", "--fix", "--yes", "--force"]).is_ok());
+        assert!(Cli::try_parse_from(["axiom", "doctor", "--yes"]).is_err());
+        assert!(Cli::try_parse_from(["axiom", "doctor", "--fix", "--json"]).is_err());
+    }
+
+    #[test]
+    fn completions_cover_subcommand_flags() {
+        for shell in CompletionShell::value_variants() {
+            let mut cmd = Cli::command();
+            let mut buf = Vec::new();
+            completions::generate(*shell, &mut cmd, "axiom", &mut buf);
+            let script = String::from_utf8(buf).expect("utf-8 completions");
+            assert!(
+                script.contains("dry-run"),
+                "{shell} is missing sandbox flags"
+            );
+            assert!(
+                script.contains("max-tokens"),
+                "{shell} is missing coding flags"
+            );
+        }
+    }
+}
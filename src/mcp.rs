@@ -4,20 +4,23 @@
 //! - Connection pooling for server reuse
 //! - Automatic tool discovery via `tools/list`
 //! - Configurable timeouts per-server and globally
+//! - stdio, Streamable HTTP, and legacy HTTP+SSE transports
 //! - Backward compatibility with existing sync API
 
 #![allow(dead_code)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::process::{Child, ChildStdin, ChildStdout};
@@ -65,16 +68,52 @@ impl Default for McpTimeouts {
     }
 }
 
+/// How the CLI talks to an MCP server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    /// Local child process speaking JSON-RPC over stdin/stdout
+    Stdio,
+    /// Legacy HTTP+SSE: a GET event stream plus POSTs to the endpoint it announces
+    Sse,
+    /// Streamable HTTP: each message is POSTed and answered as JSON or an event stream
+    Http,
+}
+
+impl std::fmt::Display for McpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Stdio => "stdio",
+            Self::Sse => "sse",
+            Self::Http => "http",
+        })
+    }
+}
+
 /// Configuration for a single MCP server
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct McpServerConfig {
+    /// Executable for stdio servers
+    #[serde(default)]
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Endpoint for `http`/`sse` servers
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Defaults to `http` when `url` is set, otherwise `stdio`
+    #[serde(default)]
+    pub transport: Option<McpTransport>,
+    /// Extra request headers (e.g. `Authorization`) for `http`/`sse` servers
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
     #[serde(default)]
     pub connect_timeout: Option<u64>,
+    /// Startup deadline in milliseconds; takes precedence over `connect_timeout`
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
     #[serde(default)]
     pub execute_timeout: Option<u64>,
     #[serde(default)]
@@ -84,10 +123,62 @@ pub struct McpServerConfig {
 }
 
 impl McpServerConfig {
+    pub fn effective_transport(&self) -> McpTransport {
+        self.transport.unwrap_or(if self.url.is_some() {
+            McpTransport::Http
+        } else {
+            McpTransport::Stdio
+        })
+    }
+
+    /// Expand `${VAR}` / `$VAR` in `env` values, `args`, `url`, and `headers`.
+    /// Unset variables become empty strings and are reported as verbose warnings.
+    pub fn expand_env_refs(&mut self, server: &str, lookup: impl Fn(&str) -> Option<String>) {
+        let mut missing = Vec::new();
+        let mut expand = |value: &mut String| {
+            *value = expand_env_vars(value, &lookup, &mut missing);
+        };
+        self.env.values_mut().for_each(&mut expand);
+        self.args.iter_mut().for_each(&mut expand);
+        self.headers.values_mut().for_each(&mut expand);
+        if let Some(url) = self.url.as_mut() {
+            expand(url);
+        }
+
+        missing.sort();
+        missing.dedup();
+        for var in missing {
+            crate::logging::warn(format!(
+                "MCP server '{server}' references ${var}, which is not set; using an empty value"
+            ));
+        }
+    }
+
+    /// Short description of where the server lives, for listings
+    pub fn describe(&self) -> String {
+        match self.effective_transport() {
+            McpTransport::Stdio => format!("{} {}", self.command, self.args.join(" "))
+                .trim_end()
+                .to_string(),
+            transport => format!(
+                "{} ({transport})",
+                self.url.as_deref().unwrap_or("<missing url>")
+            ),
+        }
+    }
+
     pub fn effective_connect_timeout(&self, global: &McpTimeouts) -> u64 {
         self.connect_timeout.unwrap_or(global.connect_timeout)
     }
 
+    /// Deadline for spawning/opening, initializing, and listing tools
+    pub fn connect_deadline(&self, global: &McpTimeouts) -> Duration {
+        self.timeout_ms.map_or_else(
+            || Duration::from_secs(self.effective_connect_timeout(global)),
+            Duration::from_millis,
+        )
+    }
+
     pub fn effective_execute_timeout(&self, global: &McpTimeouts) -> u64 {
         self.execute_timeout.unwrap_or(global.execute_timeout)
     }
@@ -97,6 +188,51 @@ impl McpServerConfig {
     }
 }
 
+/// Replace `${VAR}` and `$VAR` references, collecting the names of unset variables.
+/// A `$` not followed by a variable name is kept as-is.
+fn expand_env_vars(
+    input: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+    missing: &mut Vec<String>,
+) -> String {
+    let context = |name: &str| -> Result<Option<String>, std::convert::Infallible> {
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Ok(None);
+        }
+        Ok(Some(lookup(name).unwrap_or_else(|| {
+            missing.push(name.to_string());
+            String::new()
+        })))
+    };
+    match shellexpand::env_with_context(input, context) {
+        Ok(expanded) => expanded.into_owned(),
+        Err(err) => match err.cause {},
+    }
+}
+
+/// Startup of an MCP server exceeded its connect deadline
+#[derive(Debug, thiserror::Error)]
+#[error("MCP server '{server}' did not finish starting within {:.1}s", timeout.as_secs_f64())]
+pub struct McpConnectTimeout {
+    pub server: String,
+    pub timeout: Duration,
+}
+
+/// Result of trying to connect to one server
+#[derive(Debug)]
+pub struct ServerHealth {
+    pub name: String,
+    pub elapsed: Duration,
+    /// Number of discovered tools, or why the connection failed
+    pub result: Result<usize>,
+}
+
+impl ServerHealth {
+    pub fn timed_out(&self) -> bool {
+        matches!(&self.result, Err(e) if e.is::<McpConnectTimeout>())
+    }
+}
+
 // === MCP Tool Definition ===
 
 /// Tool discovered from an MCP server
@@ -124,15 +260,49 @@ pub enum ConnectionState {
 /// Manages a single async connection to an MCP server
 pub struct McpConnection {
     name: String,
-    _child: Child,
-    stdin: ChildStdin,
-    reader: tokio::io::BufReader<ChildStdout>,
+    transport: Transport,
     tools: Vec<McpTool>,
     request_id: AtomicU64,
     state: ConnectionState,
     config: McpServerConfig,
 }
 
+/// Wire-level channel to a server
+enum Transport {
+    Stdio {
+        _child: Child,
+        stdin: ChildStdin,
+        reader: tokio::io::BufReader<ChildStdout>,
+    },
+    Http(HttpTransport),
+    Sse(SseTransport),
+}
+
+/// Streamable HTTP: every message is a POST; responses come back in the POST body
+struct HttpTransport {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    headers: HeaderMap,
+    session_id: Option<String>,
+    pending: VecDeque<serde_json::Value>,
+}
+
+/// Legacy HTTP+SSE: responses arrive on a long-lived GET stream, requests are POSTed
+/// to the endpoint the server announces on that stream
+struct SseTransport {
+    client: reqwest::Client,
+    endpoint: reqwest::Url,
+    headers: HeaderMap,
+    events: tokio::sync::mpsc::UnboundedReceiver<serde_json::Value>,
+    stream_task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for SseTransport {
+    fn drop(&mut self) {
+        self.stream_task.abort();
+    }
+}
+
 impl McpConnection {
     /// Connect to an MCP server and initialize it
     pub async fn connect(
@@ -140,49 +310,34 @@ impl McpConnection {
         config: McpServerConfig,
         global_timeouts: &McpTimeouts,
     ) -> Result<Self> {
-        let connect_timeout_secs = config.effective_connect_timeout(global_timeouts);
-
-        let mut cmd = tokio::process::Command::new(&config.command);
-        cmd.args(&config.args)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::null())
-            .kill_on_drop(true);
-
-        for (key, value) in &config.env {
-            cmd.env(key, value);
+        let deadline = config.connect_deadline(global_timeouts);
+
+        // One deadline covers the whole startup so a hung server can't stall callers;
+        // dropping the future kills a spawned child (kill_on_drop)
+        match tokio::time::timeout(deadline, Self::start(name.clone(), config, deadline)).await {
+            Ok(result) => result,
+            Err(_) => Err(McpConnectTimeout {
+                server: name,
+                timeout: deadline,
+            }
+            .into()),
         }
+    }
 
-        let mut child = cmd
-            .spawn()
-            .with_context(|| format!("Failed to spawn MCP server '{name}'"))?;
-
-        let stdin = child.stdin.take().context("Failed to get MCP stdin")?;
-        let stdout = child.stdout.take().context("Failed to get MCP stdout")?;
+    async fn start(name: String, config: McpServerConfig, deadline: Duration) -> Result<Self> {
+        let transport = Transport::open(&name, &config, deadline).await?;
 
         let mut conn = Self {
-            name: name.clone(),
-            _child: child,
-            stdin,
-            reader: tokio::io::BufReader::new(stdout),
+            name,
+            transport,
             tools: Vec::new(),
             request_id: AtomicU64::new(1),
             state: ConnectionState::Connecting,
             config,
         };
 
-        // Initialize with timeout
-        tokio::time::timeout(Duration::from_secs(connect_timeout_secs), conn.initialize())
-            .await
-            .with_context(|| format!("MCP server '{name}' initialization timed out"))??;
-
-        // Discover tools with timeout
-        tokio::time::timeout(
-            Duration::from_secs(connect_timeout_secs),
-            conn.discover_tools(),
-        )
-        .await
-        .with_context(|| format!("MCP server '{name}' tool discovery timed out"))??;
+        conn.initialize().await?;
+        conn.discover_tools().await?;
 
         conn.state = ConnectionState::Ready;
         Ok(conn)
@@ -255,7 +410,7 @@ impl McpConnection {
         }
 
         let call_id = self.next_id();
-        self.send(serde_json::json!({
+        let request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": call_id,
             "method": "tools/call",
@@ -263,17 +418,20 @@ impl McpConnection {
                 "name": tool_name,
                 "arguments": arguments
             }
-        }))
-        .await?;
+        });
 
-        let response = tokio::time::timeout(Duration::from_secs(timeout_secs), self.recv(call_id))
-            .await
-            .with_context(|| {
-                format!(
-                    "MCP tool '{}' on server '{}' timed out after {}s",
-                    tool_name, self.name, timeout_secs
-                )
-            })??;
+        // HTTP servers answer inside the POST, so the send is covered by the timeout too
+        let response = tokio::time::timeout(Duration::from_secs(timeout_secs), async {
+            self.send(request).await?;
+            self.recv(call_id).await
+        })
+        .await
+        .with_context(|| {
+            format!(
+                "MCP tool '{}' on server '{}' timed out after {}s",
+                tool_name, self.name, timeout_secs
+            )
+        })??;
 
         if let Some(error) = response.get("error") {
             return Err(anyhow::anyhow!(
@@ -318,37 +476,83 @@ impl McpConnection {
     }
 
     async fn send(&mut self, msg: serde_json::Value) -> Result<()> {
-        let line = serde_json::to_string(&msg)? + "\n";
-        self.stdin.write_all(line.as_bytes()).await?;
-        self.stdin.flush().await?;
-        Ok(())
+        match &mut self.transport {
+            Transport::Stdio { stdin, .. } => {
+                let line = serde_json::to_string(&msg)? + "\n";
+                stdin.write_all(line.as_bytes()).await?;
+                stdin.flush().await?;
+                Ok(())
+            }
+            Transport::Http(http) => http.post(&self.name, &msg).await,
+            Transport::Sse(sse) => {
+                let response = sse
+                    .client
+                    .post(sse.endpoint.clone())
+                    .headers(sse.headers.clone())
+                    .json(&msg)
+                    .send()
+                    .await
+                    .with_context(|| format!("Failed to send MCP request to '{}'", self.name))?;
+                ensure_http_success(&self.name, response).await?;
+                Ok(())
+            }
+        }
     }
 
     async fn recv(&mut self, expected_id: u64) -> Result<serde_json::Value> {
-        let mut line = String::new();
-        loop {
-            line.clear();
-            let bytes = self.reader.read_line(&mut line).await?;
-            if bytes == 0 {
-                self.state = ConnectionState::Disconnected;
-                anyhow::bail!(
-                    "Failed to read MCP response: server '{}' closed connection",
-                    self.name
-                );
+        match &mut self.transport {
+            Transport::Stdio { reader, .. } => {
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    let bytes = reader.read_line(&mut line).await?;
+                    if bytes == 0 {
+                        self.state = ConnectionState::Disconnected;
+                        anyhow::bail!(
+                            "Failed to read MCP response: server '{}' closed connection",
+                            self.name
+                        );
+                    }
+
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+
+                    if let Ok(value) = serde_json::from_str::<serde_json::Value>(trimmed) {
+                        // Check if this is a response with the expected id
+                        if response_id(&value) == Some(expected_id) {
+                            return Ok(value);
+                        }
+                        // Skip notifications (no id) and responses with different ids
+                    }
+                }
             }
-
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
+            Transport::Http(http) => {
+                let position = http
+                    .pending
+                    .iter()
+                    .position(|value| response_id(value) == Some(expected_id));
+                match position.and_then(|index| http.pending.remove(index)) {
+                    Some(value) => Ok(value),
+                    None => anyhow::bail!(
+                        "Failed to read MCP response: server '{}' did not answer request {expected_id}",
+                        self.name
+                    ),
+                }
             }
-
-            if let Ok(value) = serde_json::from_str::<serde_json::Value>(trimmed) {
-                // Check if this is a response with the expected id
-                if value.get("id").and_then(serde_json::Value::as_u64) == Some(expected_id) {
+            Transport::Sse(sse) => loop {
+                let Some(value) = sse.events.recv().await else {
+                    self.state = ConnectionState::Disconnected;
+                    anyhow::bail!(
+                        "Failed to read MCP response: server '{}' closed its event stream",
+                        self.name
+                    );
+                };
+                if response_id(&value) == Some(expected_id) {
                     return Ok(value);
                 }
-                // Skip notifications (no id) and responses with different ids
-            }
+            },
         }
     }
 
@@ -359,6 +563,284 @@ impl McpConnection {
     }
 }
 
+impl Transport {
+    async fn open(name: &str, config: &McpServerConfig, connect_timeout: Duration) -> Result<Self> {
+        match config.effective_transport() {
+            McpTransport::Stdio => {
+                if config.command.is_empty() {
+                    anyhow::bail!(
+                        "Failed to start MCP server '{name}': stdio transport needs a `command`"
+                    );
+                }
+                let mut cmd = tokio::process::Command::new(&config.command);
+                cmd.args(&config.args)
+                    .stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::null())
+                    .kill_on_drop(true);
+
+                for (key, value) in &config.env {
+                    cmd.env(key, value);
+                }
+
+                let mut child = cmd
+                    .spawn()
+                    .with_context(|| format!("Failed to spawn MCP server '{name}'"))?;
+
+                let stdin = child.stdin.take().context("Failed to get MCP stdin")?;
+                let stdout = child.stdout.take().context("Failed to get MCP stdout")?;
+                Ok(Self::Stdio {
+                    _child: child,
+                    stdin,
+                    reader: tokio::io::BufReader::new(stdout),
+                })
+            }
+            McpTransport::Http => {
+                let (client, url, headers) = http_parts(name, config, connect_timeout)?;
+                Ok(Self::Http(HttpTransport {
+                    client,
+                    url,
+                    headers,
+                    session_id: None,
+                    pending: VecDeque::new(),
+                }))
+            }
+            McpTransport::Sse => {
+                let (client, url, headers) = http_parts(name, config, connect_timeout)?;
+                open_sse(name, client, url, headers).await.map(Self::Sse)
+            }
+        }
+    }
+}
+
+impl HttpTransport {
+    /// POST one message and queue whatever JSON-RPC messages come back
+    async fn post(&mut self, name: &str, msg: &serde_json::Value) -> Result<()> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .headers(self.headers.clone())
+            .header(ACCEPT, "application/json, text/event-stream")
+            .json(msg);
+        if let Some(session_id) = &self.session_id {
+            request = request.header(MCP_SESSION_HEADER, session_id);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send MCP request to '{name}'"))?;
+        let response = ensure_http_success(name, response).await?;
+
+        if let Some(session_id) = response
+            .headers()
+            .get(MCP_SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+
+        if is_event_stream {
+            // Read until the stream carries a response; the server may keep it open afterwards
+            let mut parser = SseParser::default();
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk =
+                    chunk.with_context(|| format!("Failed to read MCP stream from '{name}'"))?;
+                let mut answered = false;
+                for event in parser.push(&chunk) {
+                    for value in json_messages(&event.data) {
+                        answered |= is_response(&value);
+                        self.pending.push_back(value);
+                    }
+                }
+                if answered {
+                    break;
+                }
+            }
+        } else {
+            let body = response
+                .text()
+                .await
+                .with_context(|| format!("Failed to read MCP response from '{name}'"))?;
+            self.pending.extend(json_messages(&body));
+        }
+        Ok(())
+    }
+}
+
+async fn open_sse(
+    name: &str,
+    client: reqwest::Client,
+    url: reqwest::Url,
+    headers: HeaderMap,
+) -> Result<SseTransport> {
+    let response = client
+        .get(url.clone())
+        .headers(headers.clone())
+        .header(ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .with_context(|| format!("Failed to open MCP event stream for '{name}'"))?;
+    let response = ensure_http_success(name, response).await?;
+
+    let (endpoint_tx, endpoint_rx) = tokio::sync::oneshot::channel::<String>();
+    let (events_tx, events) = tokio::sync::mpsc::unbounded_channel();
+    let stream_task = tokio::spawn(async move {
+        let mut endpoint_tx = Some(endpoint_tx);
+        let mut parser = SseParser::default();
+        let mut stream = response.bytes_stream();
+        while let Some(Ok(chunk)) = stream.next().await {
+            for event in parser.push(&chunk) {
+                if event.event == "endpoint" {
+                    if let Some(tx) = endpoint_tx.take() {
+                        let _ = tx.send(event.data.trim().to_string());
+                    }
+                    continue;
+                }
+                for value in json_messages(&event.data) {
+                    if events_tx.send(value).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    let endpoint = endpoint_rx.await.map_err(|_| {
+        anyhow::anyhow!("Failed to connect MCP server '{name}': event stream closed before announcing an endpoint")
+    })?;
+    let endpoint = url
+        .join(&endpoint)
+        .with_context(|| format!("Failed to parse MCP endpoint '{endpoint}' from '{name}'"))?;
+
+    Ok(SseTransport {
+        client,
+        endpoint,
+        headers,
+        events,
+        stream_task,
+    })
+}
+
+const MCP_SESSION_HEADER: &str = "mcp-session-id";
+
+fn http_parts(
+    name: &str,
+    config: &McpServerConfig,
+    connect_timeout: Duration,
+) -> Result<(reqwest::Client, reqwest::Url, HeaderMap)> {
+    let raw_url = config.url.as_deref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Failed to connect MCP server '{name}': {} transport needs a `url`",
+            config.effective_transport()
+        )
+    })?;
+    let url = reqwest::Url::parse(raw_url)
+        .with_context(|| format!("Failed to parse URL for MCP server '{name}': {raw_url}"))?;
+
+    let mut headers = HeaderMap::new();
+    for (key, value) in &config.headers {
+        let key = HeaderName::from_bytes(key.as_bytes())
+            .with_context(|| format!("Invalid header name '{key}' for MCP server '{name}'"))?;
+        let value = HeaderValue::from_str(value)
+            .with_context(|| format!("Invalid value for header '{key}' on MCP server '{name}'"))?;
+        headers.insert(key, value);
+    }
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .build()
+        .context("Failed to build HTTP client for MCP")?;
+    Ok((client, url, headers))
+}
+
+async fn ensure_http_success(name: &str, response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    anyhow::bail!(
+        "MCP server '{name}' returned HTTP {status}: {}",
+        body.trim()
+    )
+}
+
+fn response_id(value: &serde_json::Value) -> Option<u64> {
+    value.get("id").and_then(serde_json::Value::as_u64)
+}
+
+fn is_response(value: &serde_json::Value) -> bool {
+    value.get("id").is_some() && (value.get("result").is_some() || value.get("error").is_some())
+}
+
+/// JSON-RPC messages in a payload, flattening batches and skipping anything unparsable
+fn json_messages(data: &str) -> Vec<serde_json::Value> {
+    match serde_json::from_str::<serde_json::Value>(data.trim()) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(value) => vec![value],
+        Err(_) => Vec::new(),
+    }
+}
+
+/// One dispatched server-sent event
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental `text/event-stream` parser
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed raw bytes, returning any events completed by them
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: self.data.join("\n"),
+                    });
+                    self.data.clear();
+                }
+                self.event = None;
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = line.split_once(':').map_or((line, ""), |(field, value)| {
+                (field, value.strip_prefix(' ').unwrap_or(value))
+            });
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
 impl Drop for McpConnection {
     fn drop(&mut self) {
         // Child is automatically killed due to kill_on_drop(true)
@@ -387,8 +869,12 @@ impl McpPool {
         let config = if path.exists() {
             let contents = fs::read_to_string(path)
                 .with_context(|| format!("Failed to read MCP config: {}", path.display()))?;
-            serde_json::from_str(&contents)
-                .with_context(|| format!("Failed to parse MCP config: {}", path.display()))?
+            let mut config: McpConfig = serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse MCP config: {}", path.display()))?;
+            for (name, server) in &mut config.servers {
+                server.expand_env_refs(name, |var| std::env::var(var).ok());
+            }
+            config
         } else {
             McpConfig::default()
         };
@@ -437,22 +923,79 @@ impl McpPool {
 
     /// Connect to all enabled servers, returning errors for failed connections
     pub async fn connect_all(&mut self) -> Vec<(String, anyhow::Error)> {
-        let mut errors = Vec::new();
-        let names: Vec<String> = self
-            .config
-            .servers
-            .keys()
-            .filter(|n| !self.config.servers[*n].disabled)
-            .cloned()
-            .collect();
+        self.connect_with_health(None)
+            .await
+            .into_iter()
+            .filter_map(|health| health.result.err().map(|e| (health.name, e)))
+            .collect()
+    }
+
+    /// Connect to one server (or every enabled server) concurrently, reporting
+    /// per-server status and startup time. A slow server only costs its own deadline.
+    pub async fn connect_with_health(&mut self, only: Option<&str>) -> Vec<ServerHealth> {
+        let names: Vec<String> = match only {
+            Some(name) => vec![name.to_string()],
+            None => self
+                .config
+                .servers
+                .iter()
+                .filter(|(_, server)| !server.disabled)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        };
 
+        let mut report = Vec::new();
+        let mut pending = Vec::new();
         for name in names {
-            if let Err(e) = self.get_or_connect(&name).await {
-                errors.push((name, e));
+            if let Some(conn) = self.connections.get(&name).filter(|c| c.is_ready()) {
+                report.push(ServerHealth {
+                    name,
+                    elapsed: Duration::ZERO,
+                    result: Ok(conn.tools().len()),
+                });
+                continue;
             }
+            let result = match self.config.servers.get(&name) {
+                None => Err(anyhow::anyhow!("Failed to find MCP server: {name}")),
+                Some(server) if server.disabled => Err(anyhow::anyhow!(
+                    "Failed to connect MCP server '{name}': server is disabled"
+                )),
+                Some(server) => Ok(server.clone()),
+            };
+            match result {
+                Ok(server) => pending.push((name, server)),
+                Err(e) => report.push(ServerHealth {
+                    name,
+                    elapsed: Duration::ZERO,
+                    result: Err(e),
+                }),
+            }
+        }
+
+        let timeouts = self.config.timeouts;
+        let attempts =
+            futures_util::future::join_all(pending.into_iter().map(|(name, server)| async move {
+                let started = Instant::now();
+                let result = McpConnection::connect(name.clone(), server, &timeouts).await;
+                (name, started.elapsed(), result)
+            }))
+            .await;
+
+        for (name, elapsed, result) in attempts {
+            let result = result.map(|conn| {
+                let tool_count = conn.tools().len();
+                self.connections.insert(name.clone(), conn);
+                tool_count
+            });
+            report.push(ServerHealth {
+                name,
+                elapsed,
+                result,
+            });
         }
 
-        errors
+        report.sort_by(|a, b| a.name.cmp(&b.name));
+        report
     }
 
     /// Get all discovered tools with server-prefixed names
@@ -567,6 +1110,197 @@ pub fn format_tool_result(result: &serde_json::Value) -> String {
     }
 }
 
+// === Config Editing ===
+
+/// Set a server's `disabled` flag in the config file, editing the text in place so
+/// key order, indentation, and other servers are left untouched.
+/// Returns `false` when the flag already had the requested value.
+pub fn set_server_disabled(path: &Path, server: &str, disabled: bool) -> Result<bool> {
+    if !path.exists() {
+        anyhow::bail!("No MCP config at {}", path.display());
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read MCP config: {}", path.display()))?;
+    let config: McpConfig = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse MCP config: {}", path.display()))?;
+    if !config.servers.contains_key(server) {
+        let mut names: Vec<&str> = config.servers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let available = if names.is_empty() {
+            "(none)".to_string()
+        } else {
+            names.join(", ")
+        };
+        anyhow::bail!("No MCP server named '{server}'. Configured servers: {available}");
+    }
+    let Some(updated) = set_server_disabled_in(&contents, server, disabled)
+        .with_context(|| format!("Failed to update MCP config: {}", path.display()))?
+    else {
+        return Ok(false);
+    };
+    fs::write(path, updated)
+        .with_context(|| format!("Failed to write MCP config: {}", path.display()))?;
+    Ok(true)
+}
+
+/// Text-level edit behind [`set_server_disabled`]; `None` means nothing to change.
+fn set_server_disabled_in(text: &str, server: &str, disabled: bool) -> Result<Option<String>> {
+    let bytes = text.as_bytes();
+    let root = json_members(bytes, skip_ws(bytes, 0))?;
+
+    let mut servers = None;
+    for key in ["servers", "mcpServers"] {
+        if let Some(member) = root.iter().find(|m| m.key == key) {
+            let members = json_members(bytes, member.value.0)?;
+            if let Some(entry) = members.into_iter().find(|m| m.key == server) {
+                servers = Some(entry);
+                break;
+            }
+        }
+    }
+    let Some(entry) = servers else {
+        anyhow::bail!("No MCP server named '{server}'");
+    };
+
+    let (obj_start, obj_end) = entry.value;
+    let fields = json_members(bytes, obj_start)?;
+    let value = if disabled { "true" } else { "false" };
+    let mut out = String::with_capacity(text.len() + 24);
+
+    if let Some(field) = fields.iter().find(|m| m.key == "disabled") {
+        let (start, end) = field.value;
+        if text[start..end] == *value {
+            return Ok(None);
+        }
+        out.push_str(&text[..start]);
+        out.push_str(value);
+        out.push_str(&text[end..]);
+    } else if let Some(last) = fields.last() {
+        if !disabled {
+            return Ok(None);
+        }
+        // Reuse the whitespace that precedes the last key so indentation matches
+        let indent = &text[last.leading_ws..last.key_start];
+        out.push_str(&text[..last.value.1]);
+        out.push_str(&format!(",{indent}\"disabled\": {value}"));
+        out.push_str(&text[last.value.1..]);
+    } else {
+        if !disabled {
+            return Ok(None);
+        }
+        out.push_str(&text[..obj_start]);
+        out.push_str(&format!("{{ \"disabled\": {value} }}"));
+        out.push_str(&text[obj_end..]);
+    }
+    Ok(Some(out))
+}
+
+/// One `"key": value` pair located by byte offsets
+struct JsonMember {
+    key: String,
+    /// Where the whitespace before the key starts (just after `{` or `,`)
+    leading_ws: usize,
+    key_start: usize,
+    /// `[start, end)` of the value
+    value: (usize, usize),
+}
+
+/// Members of the object starting at `start` (which must be `{`)
+fn json_members(bytes: &[u8], start: usize) -> Result<Vec<JsonMember>> {
+    if bytes.get(start) != Some(&b'{') {
+        anyhow::bail!("expected a JSON object at byte {start}");
+    }
+    let mut members = Vec::new();
+    let mut i = start + 1;
+    loop {
+        let leading_ws = i;
+        i = skip_ws(bytes, i);
+        match bytes.get(i) {
+            Some(b'}') => return Ok(members),
+            Some(b'"') => {}
+            _ => anyhow::bail!("expected a key or `}}` at byte {i}"),
+        }
+        let key_start = i;
+        let key_end = skip_json_value(bytes, i)?;
+        let key: String = serde_json::from_slice(&bytes[key_start..key_end])?;
+        i = skip_ws(bytes, key_end);
+        if bytes.get(i) != Some(&b':') {
+            anyhow::bail!("expected `:` at byte {i}");
+        }
+        let value_start = skip_ws(bytes, i + 1);
+        let value_end = skip_json_value(bytes, value_start)?;
+        members.push(JsonMember {
+            key,
+            leading_ws,
+            key_start,
+            value: (value_start, value_end),
+        });
+        i = skip_ws(bytes, value_end);
+        match bytes.get(i) {
+            Some(b',') => i += 1,
+            Some(b'}') => return Ok(members),
+            _ => anyhow::bail!("expected `,` or `}}` at byte {i}"),
+        }
+    }
+}
+
+fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// End offset of the JSON value starting at `start`
+fn skip_json_value(bytes: &[u8], start: usize) -> Result<usize> {
+    match bytes.get(start) {
+        Some(b'"') => {
+            let mut i = start + 1;
+            while let Some(&b) = bytes.get(i) {
+                match b {
+                    b'\\' => i += 2,
+                    b'"' => return Ok(i + 1),
+                    _ => i += 1,
+                }
+            }
+            anyhow::bail!("unterminated string at byte {start}")
+        }
+        Some(b'{' | b'[') => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while let Some(&b) = bytes.get(i) {
+                match b {
+                    b'"' => {
+                        i = skip_json_value(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            anyhow::bail!("unterminated object or array at byte {start}")
+        }
+        Some(_) => {
+            let mut i = start;
+            while bytes
+                .get(i)
+                .is_some_and(|b| !b.is_ascii_whitespace() && !matches!(b, b',' | b'}' | b']'))
+            {
+                i += 1;
+            }
+            Ok(i)
+        }
+        None => anyhow::bail!("unexpected end of input"),
+    }
+}
+
 // === Backward Compatibility - Sync API (Legacy) ===
 
 /// Legacy input struct for adding MCP servers
@@ -912,7 +1646,11 @@ mod tests {
             command: "test".to_string(),
             args: vec![],
             env: HashMap::new(),
+            url: None,
+            transport: None,
+            headers: HashMap::new(),
             connect_timeout: Some(20),
+            timeout_ms: None,
             execute_timeout: None,
             read_timeout: Some(180),
             disabled: false,
@@ -994,6 +1732,278 @@ mod tests {
         assert!(!status.success());
     }
 
+    #[test]
+    fn test_mcp_config_parse_remote_servers() {
+        let json = r#"{
+            "servers": {
+                "hosted": { "url": "https://mcp.example.com/mcp" },
+                "legacy": {
+                    "url": "https://mcp.example.com/sse",
+                    "transport": "sse",
+                    "headers": {"Authorization": "Bearer t"}
+                },
+                "local": { "command": "node", "args": ["server.js"] }
+            }
+        }"#;
+
+        let config: McpConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.servers["hosted"].effective_transport(),
+            McpTransport::Http
+        );
+        assert_eq!(
+            config.servers["legacy"].effective_transport(),
+            McpTransport::Sse
+        );
+        assert_eq!(
+            config.servers["legacy"].headers.get("Authorization"),
+            Some(&"Bearer t".to_string())
+        );
+        assert_eq!(
+            config.servers["local"].effective_transport(),
+            McpTransport::Stdio
+        );
+        assert_eq!(config.servers["local"].describe(), "node server.js");
+        assert_eq!(
+            config.servers["legacy"].describe(),
+            "https://mcp.example.com/sse (sse)"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |var: &str| match var {
+            "TOKEN" => Some("secret".to_string()),
+            "HOME" => Some("/home/me".to_string()),
+            _ => None,
+        };
+        let mut missing = Vec::new();
+        assert_eq!(
+            expand_env_vars("Bearer ${TOKEN}", &lookup, &mut missing),
+            "Bearer secret"
+        );
+        assert_eq!(
+            expand_env_vars("$HOME/data:$MISSING!", &lookup, &mut missing),
+            "/home/me/data:!"
+        );
+        assert_eq!(
+            expand_env_vars("cost $5 and ${} and $", &lookup, &mut missing),
+            "cost $5 and ${} and $"
+        );
+        assert_eq!(missing, vec!["MISSING".to_string()]);
+    }
+
+    #[test]
+    fn test_server_expand_env_refs() {
+        let mut server: McpServerConfig = serde_json::from_value(serde_json::json!({
+            "command": "server",
+            "args": ["--token", "${GITHUB_TOKEN}"],
+            "env": {"TOKEN": "$GITHUB_TOKEN", "OTHER": "${NOPE}"}
+        }))
+        .unwrap();
+        server.expand_env_refs("gh", |var| {
+            (var == "GITHUB_TOKEN").then(|| "ghp_123".to_string())
+        });
+        assert_eq!(server.args, vec!["--token", "ghp_123"]);
+        assert_eq!(server.env["TOKEN"], "ghp_123");
+        assert_eq!(server.env["OTHER"], "");
+    }
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: endpoint\r\ndata: /mess").is_empty());
+        let events = parser.push(b"ages?id=1\r\n\r\n: keepalive\n\ndata: {\"a\":\ndata: 1}\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: "endpoint".to_string(),
+                    data: "/messages?id=1".to_string(),
+                },
+                SseEvent {
+                    event: "message".to_string(),
+                    data: "{\"a\":\n1}".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_messages_flattens_batches() {
+        assert_eq!(json_messages(r#"[{"id":1},{"id":2}]"#).len(), 2);
+        assert_eq!(json_messages(r#"{"id":1}"#).len(), 1);
+        assert!(json_messages("not json").is_empty());
+    }
+
+    /// Minimal Streamable HTTP server: JSON for initialize, SSE for everything else
+    fn spawn_http_mcp_server() -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").expect("bind test server");
+        let url = format!("http://{}/mcp", server.server_addr());
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                let _ = std::io::Read::read_to_string(request.as_reader(), &mut body);
+                let msg: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+                let has_session = request
+                    .headers()
+                    .iter()
+                    .any(|h| h.field.equiv("Mcp-Session-Id") && h.value.as_str() == "s-1");
+                let id = msg.get("id").cloned().unwrap_or_default();
+                let method = msg
+                    .get("method")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                let result = match method {
+                    "initialize" => serde_json::json!({"protocolVersion": "2024-11-05"}),
+                    "tools/list" => serde_json::json!({"tools": [{"name": "echo"}]}),
+                    "tools/call" if has_session => serde_json::json!({
+                        "content": [{"type": "text", "text": msg["params"]["arguments"]["text"]}]
+                    }),
+                    "tools/call" => serde_json::json!({"isError": true, "content": []}),
+                    _ => {
+                        let _ = request.respond(tiny_http::Response::empty(202));
+                        continue;
+                    }
+                };
+                let reply = serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result});
+                let response = if method == "initialize" {
+                    tiny_http::Response::from_string(reply.to_string())
+                        .with_header(
+                            tiny_http::Header::from_bytes("Content-Type", "application/json")
+                                .unwrap(),
+                        )
+                        .with_header(
+                            tiny_http::Header::from_bytes("Mcp-Session-Id", "s-1").unwrap(),
+                        )
+                } else {
+                    tiny_http::Response::from_string(format!(
+                        ": ping\n\nevent: message\ndata: {reply}\n\n"
+                    ))
+                    .with_header(
+                        tiny_http::Header::from_bytes("Content-Type", "text/event-stream").unwrap(),
+                    )
+                };
+                let _ = request.respond(response);
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_round_trip() {
+        let url = spawn_http_mcp_server();
+        let config: McpConfig = serde_json::from_value(serde_json::json!({
+            "servers": { "remote": { "url": url } }
+        }))
+        .unwrap();
+        let mut pool = McpPool::new(config);
+
+        assert!(pool.connect_all().await.is_empty());
+        assert_eq!(pool.connected_servers(), vec!["remote"]);
+        assert_eq!(pool.all_tools()[0].0, "mcp_remote_echo");
+
+        let result = pool
+            .call_tool("mcp_remote_echo", serde_json::json!({"text": "hi"}))
+            .await
+            .unwrap();
+        assert_eq!(format_tool_result(&result), "hi");
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_connect_with_health_reports_timeouts_concurrently() {
+        let config: McpConfig = serde_json::from_value(serde_json::json!({
+            "servers": {
+                "hung-a": { "command": "sleep", "args": ["5"], "timeout_ms": 300 },
+                "hung-b": { "command": "sleep", "args": ["5"], "timeout_ms": 300 },
+                "missing": { "command": "/nonexistent/mcp-server" },
+                "off": { "command": "sleep", "disabled": true }
+            }
+        }))
+        .unwrap();
+        let mut pool = McpPool::new(config);
+
+        let started = Instant::now();
+        let health = pool.connect_with_health(None).await;
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let names: Vec<&str> = health.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["hung-a", "hung-b", "missing"]);
+        assert!(health[0].timed_out() && health[1].timed_out());
+        assert!(health[2].result.is_err() && !health[2].timed_out());
+        assert!(pool.connected_servers().is_empty());
+    }
+
+    const EDIT_SAMPLE: &str = r#"{
+  "timeouts": { "connect_timeout": 5 },
+  "mcpServers": {
+    "github": {
+      "command": "gh-mcp",
+      "args": ["--x", "}{\""]
+    },
+    "fs": { "command": "fs-mcp", "disabled": true },
+    "empty": {}
+  }
+}
+"#;
+
+    #[test]
+    fn test_set_server_disabled_inserts_with_matching_indent() {
+        let updated = set_server_disabled_in(EDIT_SAMPLE, "github", true)
+            .unwrap()
+            .unwrap();
+        assert!(
+            updated.contains("\"args\": [\"--x\", \"}{\\\"\"],\n      \"disabled\": true\n    },")
+        );
+        let config: McpConfig = serde_json::from_str(&updated).unwrap();
+        assert!(config.servers["github"].disabled);
+        assert!(config.servers["fs"].disabled);
+        assert_eq!(config.timeouts.connect_timeout, 5);
+    }
+
+    #[test]
+    fn test_set_server_disabled_flips_existing_flag_only() {
+        let updated = set_server_disabled_in(EDIT_SAMPLE, "fs", false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            EDIT_SAMPLE.replace("\"disabled\": true", "\"disabled\": false")
+        );
+        assert!(
+            set_server_disabled_in(EDIT_SAMPLE, "fs", true)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            set_server_disabled_in(EDIT_SAMPLE, "github", false)
+                .unwrap()
+                .is_none()
+        );
+
+        let updated = set_server_disabled_in(EDIT_SAMPLE, "empty", true)
+            .unwrap()
+            .unwrap();
+        assert!(updated.contains("\"empty\": { \"disabled\": true }"));
+    }
+
+    #[test]
+    fn test_set_server_disabled_unknown_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mcp.json");
+        fs::write(&path, EDIT_SAMPLE).unwrap();
+        let err = set_server_disabled(&path, "nope", true)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No MCP server named 'nope'"));
+        assert!(err.contains("empty, fs, github"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), EDIT_SAMPLE);
+
+        assert!(set_server_disabled(&path, "github", true).unwrap());
+        assert!(McpPool::from_config_path(&path).unwrap().config().servers["github"].disabled);
+    }
+
     #[tokio::test]
     async fn test_mcp_pool_empty_config() {
         let pool = McpPool::new(McpConfig::default());
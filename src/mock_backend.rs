@@ -0,0 +1,346 @@
+//! Scripted LLM backend for offline UI and engine development.
+//!
+//! Selected with `backend = "mock"`. Replies come from the TOML script named
+//! by `mock_script`, served in order; once the script runs out (or when none
+//! is configured) the backend echoes the latest user message. Streamed
+//! replies are rendered as SSE so they go through the same parser as live
+//! traffic.
+//!
+//! ```toml
+//! [[responses]]
+//! text = "Let me look around."
+//! tool_calls = [{ name = "list_dir", input = { path = "." } }]
+//!
+//! [[responses]]
+//! text = "Done."
+//! ```
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::models::{ContentBlock, MessageRequest, MessageResponse, Usage};
+use crate::utils::estimate_message_chars;
+
+#[derive(Debug, Default, Deserialize)]
+struct MockScript {
+    #[serde(default)]
+    responses: Vec<MockResponse>,
+}
+
+/// One scripted assistant turn.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct MockResponse {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<MockToolCall>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MockToolCall {
+    name: String,
+    #[serde(default = "empty_input")]
+    input: Value,
+}
+
+fn empty_input() -> Value {
+    json!({})
+}
+
+/// Serves scripted responses in place of the API.
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    responses: Vec<MockResponse>,
+    next: AtomicUsize,
+    tool_ids: AtomicUsize,
+}
+
+impl MockBackend {
+    /// Load the script at `path`, or echo every message when there is none.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mock script {}", path.display()))?;
+        let script: MockScript = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse mock script {}", path.display()))?;
+        Ok(Self {
+            responses: script.responses,
+            ..Self::default()
+        })
+    }
+
+    /// Next scripted reply, or an echo of the request once the script is used up.
+    pub fn respond(&self, request: &MessageRequest) -> MessageResponse {
+        let index = self.next.fetch_add(1, Ordering::SeqCst);
+        let scripted = self
+            .responses
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| echo(request));
+
+        let mut content = Vec::new();
+        if let Some(text) = scripted.text.filter(|text| !text.is_empty()) {
+            content.push(ContentBlock::Text {
+                text,
+                cache_control: None,
+            });
+        }
+        for call in &scripted.tool_calls {
+            let id = self.tool_ids.fetch_add(1, Ordering::SeqCst) + 1;
+            content.push(ContentBlock::ToolUse {
+                id: format!("toolu_mock_{id}"),
+                name: call.name.clone(),
+                input: call.input.clone(),
+            });
+        }
+        let stop_reason = if scripted.tool_calls.is_empty() {
+            "end_turn"
+        } else {
+            "tool_use"
+        };
+        let output_tokens = content
+            .iter()
+            .map(|block| match block {
+                ContentBlock::Text { text, .. } => text.len(),
+                ContentBlock::ToolUse { input, .. } => input.to_string().len(),
+                _ => 0,
+            })
+            .sum::<usize>()
+            / 4;
+
+        MessageResponse {
+            id: format!("msg_mock_{}", index + 1),
+            r#type: "message".to_string(),
+            role: "assistant".to_string(),
+            content,
+            model: request.model.clone(),
+            stop_reason: Some(stop_reason.to_string()),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: token_count(estimate_message_chars(&request.messages) / 4),
+                output_tokens: token_count(output_tokens),
+            },
+        }
+    }
+}
+
+fn token_count(tokens: usize) -> u32 {
+    u32::try_from(tokens).unwrap_or(u32::MAX)
+}
+
+/// Echo the latest user text, or acknowledge tool results.
+fn echo(request: &MessageRequest) -> MockResponse {
+    let Some(message) = request.messages.iter().rev().find(|m| m.role == "user") else {
+        return MockResponse {
+            text: Some("(mock) No user message.".to_string()),
+            ..MockResponse::default()
+        };
+    };
+    let text: Vec<&str> = message
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    let reply = if text.is_empty() {
+        let results = message
+            .content
+            .iter()
+            .filter(|block| matches!(block, ContentBlock::ToolResult { .. }))
+            .count();
+        let noun = if results == 1 { "result" } else { "results" };
+        format!("(mock) Received {results} tool {noun}.")
+    } else {
+        format!("(mock) {}", text.join("\n"))
+    };
+    MockResponse {
+        text: Some(reply),
+        ..MockResponse::default()
+    }
+}
+
+/// Render a response as the SSE body the streaming API would send.
+pub fn sse_body(response: &MessageResponse) -> String {
+    let mut events = vec![json!({
+        "type": "message_start",
+        "message": {
+            "id": response.id,
+            "type": "message",
+            "role": "assistant",
+            "content": [],
+            "model": response.model,
+            "stop_reason": null,
+            "stop_sequence": null,
+            "usage": {"input_tokens": response.usage.input_tokens, "output_tokens": 0},
+        },
+    })];
+    for (index, block) in response.content.iter().enumerate() {
+        match block {
+            ContentBlock::Text { text, .. } => {
+                events.push(json!({
+                    "type": "content_block_start",
+                    "index": index,
+                    "content_block": {"type": "text", "text": ""},
+                }));
+                for chunk in text.split_inclusive(' ') {
+                    events.push(json!({
+                        "type": "content_block_delta",
+                        "index": index,
+                        "delta": {"type": "text_delta", "text": chunk},
+                    }));
+                }
+            }
+            ContentBlock::ToolUse { id, name, input } => {
+                events.push(json!({
+                    "type": "content_block_start",
+                    "index": index,
+                    "content_block": {"type": "tool_use", "id": id, "name": name, "input": {}},
+                }));
+                // Split the arguments like a provider streaming them piecemeal
+                let json = input.to_string();
+                let chars: Vec<char> = json.chars().collect();
+                for chunk in chars.chunks(8) {
+                    let partial: String = chunk.iter().collect();
+                    events.push(json!({
+                        "type": "content_block_delta",
+                        "index": index,
+                        "delta": {"type": "input_json_delta", "partial_json": partial},
+                    }));
+                }
+            }
+            _ => continue,
+        }
+        events.push(json!({"type": "content_block_stop", "index": index}));
+    }
+    events.push(json!({
+        "type": "message_delta",
+        "delta": {"stop_reason": response.stop_reason, "stop_sequence": null},
+        "usage": {
+            "input_tokens": response.usage.input_tokens,
+            "output_tokens": response.usage.output_tokens,
+        },
+    }));
+    events.push(json!({"type": "message_stop"}));
+
+    events
+        .iter()
+        .map(|event| format!("data: {event}\n\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Message;
+    use tempfile::tempdir;
+
+    fn request(content: Vec<ContentBlock>) -> MessageRequest {
+        MessageRequest {
+            model: "mock-model".to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content,
+            }],
+            max_tokens: 16,
+            ..MessageRequest::default()
+        }
+    }
+
+    fn text(text: &str) -> ContentBlock {
+        ContentBlock::Text {
+            text: text.to_string(),
+            cache_control: None,
+        }
+    }
+
+    #[test]
+    fn script_is_served_in_order_then_echoes() {
+        let tmp = tempdir().expect("tempdir");
+        let path = tmp.path().join("mock.toml");
+        std::fs::write(
+            &path,
+            "[[responses]]\ntext = \"Looking.\"\ntool_calls = [{ name = \"list_dir\", input = { path = \".\" } }]\n\n[[responses]]\ntext = \"Done.\"\n",
+        )
+        .expect("write script");
+        let backend = MockBackend::load(Some(&path)).expect("load");
+
+        let first = backend.respond(&request(vec![text("hi")]));
+        assert_eq!(first.stop_reason.as_deref(), Some("tool_use"));
+        assert!(matches!(
+            &first.content[1],
+            ContentBlock::ToolUse { id, name, input }
+                if id == "toolu_mock_1" && name == "list_dir" && input["path"] == "."
+        ));
+
+        let tool_result = ContentBlock::ToolResult {
+            tool_use_id: "toolu_mock_1".to_string(),
+            content: "src/".to_string(),
+        };
+        let second = backend.respond(&request(vec![tool_result.clone()]));
+        assert!(matches!(&second.content[0], ContentBlock::Text { text, .. } if text == "Done."));
+
+        let echoed = backend.respond(&request(vec![text("again")]));
+        assert!(
+            matches!(&echoed.content[0], ContentBlock::Text { text, .. } if text == "(mock) again")
+        );
+        assert_eq!(echoed.stop_reason.as_deref(), Some("end_turn"));
+        let ack = backend.respond(&request(vec![tool_result]));
+        assert!(
+            matches!(&ack.content[0], ContentBlock::Text { text, .. } if text == "(mock) Received 1 tool result.")
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_client_streams_without_api_key() {
+        use crate::client::AnthropicClient;
+        use crate::config::Config;
+        use crate::models::{ContentBlockStart, Delta, StreamEvent};
+        use futures_util::StreamExt;
+
+        let config = Config {
+            backend: Some("mock".to_string()),
+            ..Config::default()
+        };
+        let client = AnthropicClient::with_cassette(&config, "mock-model".to_string(), None)
+            .expect("client without api key");
+        let mut stream = client
+            .create_message_stream(request(vec![text("hello there")]))
+            .await
+            .expect("stream");
+        let mut reply = String::new();
+        let mut stop_reason = None;
+        while let Some(event) = stream.next().await {
+            match event.expect("event") {
+                StreamEvent::ContentBlockStart {
+                    content_block: ContentBlockStart::Text { .. },
+                    ..
+                } => {}
+                StreamEvent::ContentBlockDelta {
+                    delta: Delta::TextDelta { text },
+                    ..
+                } => reply.push_str(&text),
+                StreamEvent::MessageDelta { delta, .. } => stop_reason = delta.stop_reason,
+                _ => {}
+            }
+        }
+        assert_eq!(reply, "(mock) hello there");
+        assert_eq!(stop_reason.as_deref(), Some("end_turn"));
+    }
+
+    #[test]
+    fn invalid_script_reports_path() {
+        let tmp = tempdir().expect("tempdir");
+        let path = tmp.path().join("mock.toml");
+        std::fs::write(&path, "[[responses]]\ntool_calls = [{ input = 1 }]\n").expect("write");
+        let err = MockBackend::load(Some(&path)).unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to parse mock script"));
+    }
+}
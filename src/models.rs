@@ -109,7 +109,7 @@ pub struct MessageResponse {
 }
 
 /// Token usage metadata for a response.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
@@ -165,6 +165,73 @@ pub fn context_window_for_model(
     None
 }
 
+// === OpenAI-Compatible Wire Types ===
+
+/// Response (or streamed chunk) from an OpenAI-compatible `/chat/completions` endpoint.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChatCompletionResponse {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub choices: Vec<ChatChoice>,
+    #[serde(default)]
+    pub usage: Option<ChatUsage>,
+}
+
+/// A completion choice; `message` is set on full responses, `delta` on chunks.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChatChoice {
+    #[serde(default)]
+    pub message: Option<ChatMessage>,
+    #[serde(default)]
+    pub delta: Option<ChatMessage>,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Assistant message (or partial message) in a chat completion.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChatMessage {
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Reasoning text from providers that expose it (e.g. vLLM, DeepSeek)
+    #[serde(default)]
+    pub reasoning_content: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<ChatToolCall>,
+}
+
+/// A function tool call; streamed calls arrive as fragments keyed by `index`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChatToolCall {
+    #[serde(default)]
+    pub index: Option<u32>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub function: Option<ChatFunctionCall>,
+}
+
+/// Function name and JSON-encoded arguments of a tool call.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChatFunctionCall {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub arguments: Option<String>,
+}
+
+/// Token usage reported by a chat completion.
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+pub struct ChatUsage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+}
+
 // === Streaming Structures ===
 
 #[allow(dead_code)]
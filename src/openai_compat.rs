@@ -0,0 +1,625 @@
+//! Translation between the Anthropic-style message model and the `OpenAI`
+//! Chat Completions wire format, used when `api_style = "openai"`.
+//!
+//! Requests are converted from `MessageRequest`; full responses and streamed
+//! chunks are converted back into `MessageResponse` and `StreamEvent`s so the
+//! engine never sees the difference. Tool results become `tool` role messages
+//! and tool calls carry JSON-encoded `arguments`.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+
+use crate::logging;
+use crate::models::{
+    ChatCompletionResponse, ChatMessage, ContentBlock, ContentBlockStart, Delta, MessageDelta,
+    MessageRequest, MessageResponse, StreamEvent, SystemPrompt, Usage,
+};
+
+// === Requests ===
+
+/// Build a `/chat/completions` body from an Anthropic-style request.
+pub fn chat_request(request: &MessageRequest) -> Value {
+    let mut messages = Vec::new();
+    if let Some(system) = &request.system {
+        let text = match system {
+            SystemPrompt::Text(text) => text.clone(),
+            SystemPrompt::Blocks(blocks) => blocks
+                .iter()
+                .map(|block| block.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        };
+        if !text.is_empty() {
+            messages.push(json!({"role": "system", "content": text}));
+        }
+    }
+    for message in &request.messages {
+        if message.role == "assistant" {
+            messages.push(assistant_message(&message.content));
+        } else {
+            messages.extend(user_messages(&message.content));
+        }
+    }
+
+    let mut body = json!({
+        "model": request.model,
+        "messages": messages,
+        "max_tokens": request.max_tokens,
+    });
+    if let Some(tools) = request.tools.as_ref().filter(|tools| !tools.is_empty()) {
+        body["tools"] = tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.input_schema,
+                    },
+                })
+            })
+            .collect();
+        if let Some(choice) = request.tool_choice.as_ref().and_then(tool_choice) {
+            body["tool_choice"] = choice;
+        }
+    }
+    if let Some(temperature) = request.temperature {
+        body["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = request.top_p {
+        body["top_p"] = json!(top_p);
+    }
+    if request.stream == Some(true) {
+        body["stream"] = json!(true);
+        body["stream_options"] = json!({"include_usage": true});
+    }
+    body
+}
+
+/// Assistant turn: text joined into `content`, tool uses as `tool_calls`.
+/// Thinking blocks have no Chat Completions equivalent and are dropped.
+fn assistant_message(content: &[ContentBlock]) -> Value {
+    let mut text = Vec::new();
+    let mut tool_calls = Vec::new();
+    for block in content {
+        match block {
+            ContentBlock::Text { text: chunk, .. } => text.push(chunk.as_str()),
+            ContentBlock::ToolUse { id, name, input } => tool_calls.push(json!({
+                "id": id,
+                "type": "function",
+                "function": {"name": name, "arguments": input.to_string()},
+            })),
+            ContentBlock::Thinking { .. } | ContentBlock::ToolResult { .. } => {}
+        }
+    }
+    let mut message = json!({
+        "role": "assistant",
+        "content": if text.is_empty() { Value::Null } else { json!(text.join("\n")) },
+    });
+    if !tool_calls.is_empty() {
+        message["tool_calls"] = Value::Array(tool_calls);
+    }
+    message
+}
+
+/// User turn: each tool result becomes a `tool` message (which must directly
+/// follow the assistant's calls), then any text as a `user` message.
+fn user_messages(content: &[ContentBlock]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    let mut text = Vec::new();
+    for block in content {
+        match block {
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+            } => messages.push(json!({
+                "role": "tool",
+                "tool_call_id": tool_use_id,
+                "content": content,
+            })),
+            ContentBlock::Text { text: chunk, .. } => text.push(chunk.as_str()),
+            ContentBlock::Thinking { .. } | ContentBlock::ToolUse { .. } => {}
+        }
+    }
+    if !text.is_empty() {
+        messages.push(json!({"role": "user", "content": text.join("\n")}));
+    }
+    messages
+}
+
+/// Map an Anthropic `tool_choice` to its Chat Completions form.
+fn tool_choice(choice: &Value) -> Option<Value> {
+    match choice.get("type").and_then(Value::as_str)? {
+        "auto" => Some(json!("auto")),
+        "any" => Some(json!("required")),
+        "none" => Some(json!("none")),
+        "tool" => {
+            let name = choice.get("name").and_then(Value::as_str)?;
+            Some(json!({"type": "function", "function": {"name": name}}))
+        }
+        _ => None,
+    }
+}
+
+// === Responses ===
+
+/// Convert a full chat completion into a `MessageResponse`.
+pub fn message_response(response: ChatCompletionResponse) -> MessageResponse {
+    let choice = response.choices.into_iter().next().unwrap_or_default();
+    let message = choice.message.unwrap_or_default();
+    let mut content = Vec::new();
+    if let Some(thinking) = message.reasoning_content.filter(|t| !t.is_empty()) {
+        content.push(ContentBlock::Thinking { thinking });
+    }
+    if let Some(text) = message.content.filter(|t| !t.is_empty()) {
+        content.push(ContentBlock::Text {
+            text,
+            cache_control: None,
+        });
+    }
+    for call in message.tool_calls {
+        let function = call.function.unwrap_or_default();
+        let name = function.name.unwrap_or_default();
+        content.push(ContentBlock::ToolUse {
+            input: tool_input(&name, function.arguments.as_deref().unwrap_or("")),
+            id: call.id.unwrap_or_default(),
+            name,
+        });
+    }
+    let usage = response.usage.unwrap_or_default();
+
+    MessageResponse {
+        id: response.id,
+        r#type: "message".to_string(),
+        role: "assistant".to_string(),
+        content,
+        model: response.model,
+        stop_reason: choice.finish_reason.map(|reason| stop_reason(&reason)),
+        stop_sequence: None,
+        usage: Usage {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        },
+    }
+}
+
+/// Map a `finish_reason` to the Anthropic `stop_reason` vocabulary.
+fn stop_reason(finish_reason: &str) -> String {
+    match finish_reason {
+        "stop" => "end_turn",
+        "length" => "max_tokens",
+        "tool_calls" | "function_call" => "tool_use",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Parse JSON-encoded tool arguments, falling back to an empty object.
+fn tool_input(name: &str, arguments: &str) -> Value {
+    if arguments.trim().is_empty() {
+        return json!({});
+    }
+    serde_json::from_str(arguments).unwrap_or_else(|err| {
+        logging::warn(format!(
+            "Failed to parse arguments for tool '{name}': {err}"
+        ));
+        json!({})
+    })
+}
+
+// === Streaming ===
+
+/// Kind of content block currently open in the translated stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OpenBlock {
+    Text,
+    Thinking,
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Turns streamed chat completion chunks into Anthropic-style stream events.
+///
+/// Text and reasoning stream through as they arrive. Tool call fragments are
+/// buffered and emitted as complete blocks when the stream finishes, since
+/// providers may interleave fragments of parallel calls.
+#[derive(Debug, Default)]
+pub struct ChatStreamTranslator {
+    started: bool,
+    next_index: u32,
+    open: Option<(OpenBlock, u32)>,
+    tool_calls: BTreeMap<u32, PendingToolCall>,
+    stop_reason: Option<String>,
+    usage: Usage,
+}
+
+impl ChatStreamTranslator {
+    /// Translate one chunk into zero or more stream events.
+    pub fn push(&mut self, chunk: ChatCompletionResponse) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        if !self.started {
+            self.started = true;
+            events.push(StreamEvent::MessageStart {
+                message: MessageResponse {
+                    id: chunk.id.clone(),
+                    r#type: "message".to_string(),
+                    role: "assistant".to_string(),
+                    content: Vec::new(),
+                    model: chunk.model.clone(),
+                    stop_reason: None,
+                    stop_sequence: None,
+                    usage: Usage::default(),
+                },
+            });
+        }
+        if let Some(usage) = chunk.usage {
+            self.usage = Usage {
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+            };
+        }
+        for choice in chunk.choices {
+            if let Some(delta) = choice.delta {
+                self.push_delta(delta, &mut events);
+            }
+            if let Some(reason) = choice.finish_reason {
+                self.stop_reason = Some(stop_reason(&reason));
+            }
+        }
+        events
+    }
+
+    fn push_delta(&mut self, delta: ChatMessage, events: &mut Vec<StreamEvent>) {
+        if let Some(thinking) = delta.reasoning_content.filter(|t| !t.is_empty()) {
+            let index = self.open_block(OpenBlock::Thinking, events);
+            events.push(StreamEvent::ContentBlockDelta {
+                index,
+                delta: Delta::ThinkingDelta { thinking },
+            });
+        }
+        if let Some(text) = delta.content.filter(|t| !t.is_empty()) {
+            let index = self.open_block(OpenBlock::Text, events);
+            events.push(StreamEvent::ContentBlockDelta {
+                index,
+                delta: Delta::TextDelta { text },
+            });
+        }
+        for call in delta.tool_calls {
+            let key = call.index.unwrap_or(0);
+            let pending = self.tool_calls.entry(key).or_default();
+            if let Some(id) = call.id {
+                pending.id = id;
+            }
+            if let Some(function) = call.function {
+                if let Some(name) = function.name {
+                    pending.name.push_str(&name);
+                }
+                if let Some(arguments) = function.arguments {
+                    pending.arguments.push_str(&arguments);
+                }
+            }
+        }
+    }
+
+    /// Index of an open block of `kind`, closing any other block first.
+    fn open_block(&mut self, kind: OpenBlock, events: &mut Vec<StreamEvent>) -> u32 {
+        if let Some((open, index)) = self.open
+            && open == kind
+        {
+            return index;
+        }
+        self.close_block(events);
+        let index = self.next_index;
+        self.next_index += 1;
+        let content_block = match kind {
+            OpenBlock::Text => ContentBlockStart::Text {
+                text: String::new(),
+            },
+            OpenBlock::Thinking => ContentBlockStart::Thinking {
+                thinking: String::new(),
+            },
+        };
+        events.push(StreamEvent::ContentBlockStart {
+            index,
+            content_block,
+        });
+        self.open = Some((kind, index));
+        index
+    }
+
+    fn close_block(&mut self, events: &mut Vec<StreamEvent>) {
+        if let Some((_, index)) = self.open.take() {
+            events.push(StreamEvent::ContentBlockStop { index });
+        }
+    }
+
+    /// Flush buffered tool calls and close the message.
+    pub fn finish(&mut self) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        self.close_block(&mut events);
+        for call in std::mem::take(&mut self.tool_calls).into_values() {
+            let index = self.next_index;
+            self.next_index += 1;
+            events.push(StreamEvent::ContentBlockStart {
+                index,
+                content_block: ContentBlockStart::ToolUse {
+                    id: call.id,
+                    name: call.name,
+                    input: json!({}),
+                },
+            });
+            if !call.arguments.trim().is_empty() {
+                events.push(StreamEvent::ContentBlockDelta {
+                    index,
+                    delta: Delta::InputJsonDelta {
+                        partial_json: call.arguments,
+                    },
+                });
+            }
+            events.push(StreamEvent::ContentBlockStop { index });
+        }
+        events.push(StreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: self.stop_reason.take(),
+                stop_sequence: None,
+            },
+            usage: Some(self.usage.clone()),
+        });
+        events.push(StreamEvent::MessageStop);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::AnthropicClient;
+    use crate::config::Config;
+    use crate::models::{Message, Tool};
+    use futures_util::StreamExt;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn text(text: &str) -> ContentBlock {
+        ContentBlock::Text {
+            text: text.to_string(),
+            cache_control: None,
+        }
+    }
+
+    fn request(messages: Vec<Message>) -> MessageRequest {
+        MessageRequest {
+            model: "gpt-test".to_string(),
+            messages,
+            max_tokens: 64,
+            system: Some(SystemPrompt::Text("Be brief.".to_string())),
+            tools: Some(vec![Tool {
+                name: "read_file".to_string(),
+                description: "Read a file".to_string(),
+                input_schema: json!({"type": "object"}),
+                cache_control: None,
+            }]),
+            tool_choice: Some(json!({"type": "auto"})),
+            ..MessageRequest::default()
+        }
+    }
+
+    #[test]
+    fn request_maps_tools_and_tool_results() {
+        let body = chat_request(&request(vec![
+            Message {
+                role: "user".to_string(),
+                content: vec![text("show main")],
+            },
+            Message {
+                role: "assistant".to_string(),
+                content: vec![
+                    ContentBlock::Thinking {
+                        thinking: "hmm".to_string(),
+                    },
+                    ContentBlock::ToolUse {
+                        id: "call_1".to_string(),
+                        name: "read_file".to_string(),
+                        input: json!({"path": "main.rs"}),
+                    },
+                ],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "call_1".to_string(),
+                    content: "fn main() {}".to_string(),
+                }],
+            },
+        ]));
+
+        assert_eq!(
+            body["messages"],
+            json!([
+                {"role": "system", "content": "Be brief."},
+                {"role": "user", "content": "show main"},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "read_file", "arguments": "{\"path\":\"main.rs\"}"},
+                }]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "fn main() {}"},
+            ])
+        );
+        assert_eq!(body["tools"][0]["function"]["name"], "read_file");
+        assert_eq!(body["tool_choice"], "auto");
+        assert!(body.get("stream").is_none());
+    }
+
+    #[test]
+    fn response_maps_tool_calls_and_finish_reason() {
+        let response: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "model": "gpt-test",
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": "Reading it.",
+                    "tool_calls": [{
+                        "id": "call_9",
+                        "type": "function",
+                        "function": {"name": "read_file", "arguments": "{\"path\":\"a.rs\"}"},
+                    }],
+                },
+                "finish_reason": "tool_calls",
+            }],
+            "usage": {"prompt_tokens": 12, "completion_tokens": 5},
+        }))
+        .expect("response");
+        let message = message_response(response);
+
+        assert_eq!(message.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(message.usage.input_tokens, 12);
+        assert!(
+            matches!(&message.content[0], ContentBlock::Text { text, .. } if text == "Reading it.")
+        );
+        assert!(matches!(
+            &message.content[1],
+            ContentBlock::ToolUse { id, name, input }
+                if id == "call_9" && name == "read_file" && input["path"] == "a.rs"
+        ));
+    }
+
+    #[test]
+    fn stream_translator_emits_blocks_in_order() {
+        let chunk = |value: Value| -> ChatCompletionResponse {
+            serde_json::from_value(value).expect("chunk")
+        };
+        let mut translator = ChatStreamTranslator::default();
+        let mut events = Vec::new();
+        for value in [
+            json!({"choices": [{"delta": {"reasoning_content": "plan"}}]}),
+            json!({"choices": [{"delta": {"content": "Hi "}}]}),
+            json!({"choices": [{"delta": {"content": "there"}}]}),
+            json!({"choices": [{"delta": {"tool_calls": [{"index": 0, "id": "call_1", "function": {"name": "read_file", "arguments": "{\"pa"}}]}}]}),
+            json!({"choices": [{"delta": {"tool_calls": [{"index": 0, "function": {"arguments": "th\":\"x\"}"}}]}}]}),
+            json!({"choices": [{"delta": {}, "finish_reason": "tool_calls"}]}),
+            json!({"choices": [], "usage": {"prompt_tokens": 7, "completion_tokens": 3}}),
+        ] {
+            events.extend(translator.push(chunk(value)));
+        }
+        events.extend(translator.finish());
+
+        let summary: Vec<String> = events
+            .iter()
+            .map(|event| match event {
+                StreamEvent::MessageStart { .. } => "start".to_string(),
+                StreamEvent::ContentBlockStart {
+                    index,
+                    content_block,
+                } => match content_block {
+                    ContentBlockStart::Text { .. } => format!("{index}:text"),
+                    ContentBlockStart::Thinking { .. } => format!("{index}:thinking"),
+                    ContentBlockStart::ToolUse { name, .. } => format!("{index}:tool {name}"),
+                },
+                StreamEvent::ContentBlockDelta { delta, .. } => match delta {
+                    Delta::TextDelta { text } => format!("text {text}"),
+                    Delta::ThinkingDelta { thinking } => format!("thinking {thinking}"),
+                    Delta::InputJsonDelta { partial_json } => format!("json {partial_json}"),
+                },
+                StreamEvent::ContentBlockStop { index } => format!("{index}:stop"),
+                StreamEvent::MessageDelta { delta, usage } => format!(
+                    "delta {:?} {}",
+                    delta.stop_reason,
+                    usage.as_ref().map_or(0, |u| u.output_tokens)
+                ),
+                StreamEvent::MessageStop => "end".to_string(),
+                StreamEvent::Ping => "ping".to_string(),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                "start",
+                "0:thinking",
+                "thinking plan",
+                "0:stop",
+                "1:text",
+                "text Hi ",
+                "text there",
+                "1:stop",
+                "2:tool read_file",
+                "json {\"path\":\"x\"}",
+                "2:stop",
+                "delta Some(\"tool_use\") 3",
+                "end",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn client_speaks_chat_completions() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(body_partial_json(json!({"stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                concat!(
+                    "data: {\"choices\":[{\"delta\":{\"content\":\"streamed\"}}]}\n\n",
+                    "data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+                    "data: [DONE]\n\n",
+                ),
+                "text/event-stream",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(json!({"model": "gpt-test"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "chatcmpl-2",
+                "model": "gpt-test",
+                "choices": [{"message": {"content": "plain"}, "finish_reason": "stop"}],
+            })))
+            .mount(&server)
+            .await;
+
+        let config = Config {
+            api_key: Some("test-key".to_string()),
+            base_url: Some(server.uri()),
+            api_style: Some("openai".to_string()),
+            ..Config::default()
+        };
+        let client =
+            AnthropicClient::with_cassette(&config, "gpt-test".to_string(), None).expect("client");
+        let prompt = || {
+            request(vec![Message {
+                role: "user".to_string(),
+                content: vec![text("hi")],
+            }])
+        };
+
+        let message = client.create_message(prompt()).await.expect("message");
+        assert!(matches!(&message.content[0], ContentBlock::Text { text, .. } if text == "plain"));
+        assert_eq!(message.stop_reason.as_deref(), Some("end_turn"));
+
+        let mut stream = client
+            .create_message_stream(prompt())
+            .await
+            .expect("stream");
+        let mut streamed = String::new();
+        while let Some(event) = stream.next().await {
+            if let StreamEvent::ContentBlockDelta {
+                delta: Delta::TextDelta { text },
+                ..
+            } = event.expect("event")
+            {
+                streamed.push_str(&text);
+            }
+        }
+        assert_eq!(streamed, "streamed");
+    }
+}
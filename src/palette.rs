@@ -94,6 +94,9 @@ pub struct UiTheme {
     pub header_bg: Color,
 }
 
+/// Theme names accepted by `ui_theme`
+pub const THEME_NAMES: &[&str] = &["default", "dark", "light"];
+
 pub fn ui_theme(name: &str) -> UiTheme {
     match name.to_ascii_lowercase().as_str() {
         "dark" => UiTheme {
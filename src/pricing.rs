@@ -1,7 +1,11 @@
 //! Cost estimation for LLM API tools
 
+use std::sync::{Arc, Mutex};
+
 use serde_json::Value;
 
+use crate::models::Usage;
+
 /// API Pricing constants (as of 2024)
 pub mod prices {
     /// Image generation: $0.0035 per image
@@ -23,6 +27,11 @@ pub mod prices {
 
     /// Voice cloning: $3.00 per voice
     pub const VOICE_CLONE: f64 = 3.00;
+
+    /// Text input: $0.20 per million tokens
+    pub const TEXT_INPUT_PER_MTOK: f64 = 0.20;
+    /// Text output: $1.10 per million tokens
+    pub const TEXT_OUTPUT_PER_MTOK: f64 = 1.10;
 }
 
 /// Estimated cost for a tool execution
@@ -64,10 +73,17 @@ impl CostEstimate {
 
 /// Estimate cost for image generation
 #[must_use]
-pub fn estimate_image_cost(_params: &Value) -> CostEstimate {
+pub fn estimate_image_cost(params: &Value) -> CostEstimate {
+    let count = params
+        .get("n")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+        .clamp(1, 9);
+    let count_f64 = f64::from(u32::try_from(count).unwrap_or(1));
+
     CostEstimate::fixed(
-        prices::IMAGE_PER_UNIT,
-        "Image generation: $0.0035 per image",
+        count_f64 * prices::IMAGE_PER_UNIT,
+        format!("Image generation: {count} × $0.0035 per image"),
     )
 }
 
@@ -80,14 +96,23 @@ pub fn estimate_audio_cost(params: &Value) -> CostEstimate {
         .map_or(100usize, str::len);
     let text_len_f64 = f64::from(u32::try_from(text_len).unwrap_or(u32::MAX));
 
-    let min = text_len_f64 * prices::AUDIO_TURBO_PER_CHAR;
-    let max = text_len_f64 * prices::AUDIO_HD_PER_CHAR;
+    let turbo = text_len_f64 * prices::AUDIO_TURBO_PER_CHAR;
+    let hd = text_len_f64 * prices::AUDIO_HD_PER_CHAR;
 
-    CostEstimate::new(
-        min,
-        max,
-        format!("TTS: ~{text_len} chars @ $0.00006-0.0001/char"),
-    )
+    match params.get("model").and_then(|m| m.as_str()) {
+        Some(model) if model.contains("turbo") => CostEstimate::fixed(
+            turbo,
+            format!("TTS (turbo): {text_len} chars @ $0.00006/char"),
+        ),
+        Some(model) if model.contains("hd") => {
+            CostEstimate::fixed(hd, format!("TTS (HD): {text_len} chars @ $0.0001/char"))
+        }
+        _ => CostEstimate::new(
+            turbo,
+            hd,
+            format!("TTS: ~{text_len} chars @ $0.00006-0.0001/char"),
+        ),
+    }
 }
 
 /// Estimate cost for video generation
@@ -152,3 +177,127 @@ pub fn estimate_tool_cost(tool_name: &str, params: &Value) -> Option<CostEstimat
         _ => None,
     }
 }
+
+/// Cost of a text request given its token counts
+#[must_use]
+pub fn token_cost(input_tokens: u32, output_tokens: u32) -> f64 {
+    (f64::from(input_tokens) * prices::TEXT_INPUT_PER_MTOK
+        + f64::from(output_tokens) * prices::TEXT_OUTPUT_PER_MTOK)
+        / 1_000_000.0
+}
+
+/// Cost of a completed API call
+#[must_use]
+pub fn usage_cost(usage: &Usage) -> f64 {
+    token_cost(usage.input_tokens, usage.output_tokens)
+}
+
+/// Session spend and the optional cap on it.
+///
+/// Shared between the TUI and the engine: the engine records spend and
+/// checks the cap before each API call, the TUI displays and raises it.
+#[derive(Debug, Clone, Default)]
+pub struct CostBudget {
+    /// Estimated USD spent this session
+    pub spent_usd: f64,
+    /// Hard cap in USD; `None` means unlimited
+    pub limit_usd: Option<f64>,
+}
+
+pub type SharedCostBudget = Arc<Mutex<CostBudget>>;
+
+#[must_use]
+pub fn new_shared_cost_budget(limit_usd: Option<f64>) -> SharedCostBudget {
+    Arc::new(Mutex::new(CostBudget {
+        spent_usd: 0.0,
+        limit_usd,
+    }))
+}
+
+impl CostBudget {
+    /// Add spend to the session total
+    pub fn record(&mut self, usd: f64) {
+        self.spent_usd += usd;
+    }
+
+    /// Whether spending `estimate_usd` more would go over the cap
+    #[must_use]
+    pub fn would_exceed(&self, estimate_usd: f64) -> bool {
+        self.limit_usd
+            .is_some_and(|limit| self.spent_usd + estimate_usd > limit)
+    }
+
+    /// Raise the cap by `amount_usd`, starting from current spend when unset
+    pub fn raise(&mut self, amount_usd: f64) {
+        let base = self.limit_usd.unwrap_or(self.spent_usd);
+        self.limit_usd = Some(base + amount_usd);
+    }
+
+    /// Status line like "$0.0123 of $1.00 spent"
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match self.limit_usd {
+            Some(limit) => format!("${:.4} of ${limit:.2} spent", self.spent_usd),
+            None => format!("${:.4} spent (no budget)", self.spent_usd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn token_cost_uses_per_million_rates() {
+        let cost = token_cost(1_000_000, 1_000_000);
+        assert!((cost - 1.30).abs() < 1e-9);
+        assert!(token_cost(0, 0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn media_estimates_follow_tool_parameters() {
+        let images = estimate_tool_cost("generate_image", &json!({"n": 3})).expect("image");
+        assert!((images.min_usd - 3.0 * prices::IMAGE_PER_UNIT).abs() < 1e-9);
+        assert!(images.breakdown.contains("3 ×"));
+
+        let params = json!({"text": "a".repeat(1000), "model": "speech-02-turbo"});
+        let turbo = estimate_tool_cost("tts", &params).expect("tts");
+        assert!((turbo.max_usd - 0.06).abs() < 1e-9);
+        let either = estimate_tool_cost("tts", &json!({"text": "a".repeat(1000)})).expect("tts");
+        assert_eq!(either.display(), "$0.0600 - $0.1000");
+
+        let video = estimate_tool_cost(
+            "generate_video",
+            &json!({"duration": 10, "resolution": "768P"}),
+        )
+        .expect("video");
+        assert!((video.min_usd - prices::VIDEO_768P_10S).abs() < 1e-9);
+    }
+
+    #[test]
+    fn budget_blocks_only_when_cap_would_be_passed() {
+        let mut budget = CostBudget::default();
+        assert!(!budget.would_exceed(1_000.0));
+
+        budget.limit_usd = Some(1.0);
+        budget.record(0.9);
+        assert!(!budget.would_exceed(0.1));
+        assert!(budget.would_exceed(0.2));
+
+        budget.raise(0.5);
+        assert_eq!(budget.limit_usd, Some(1.5));
+        assert!(!budget.would_exceed(0.2));
+        assert_eq!(budget.summary(), "$0.9000 of $1.50 spent");
+    }
+
+    #[test]
+    fn raise_without_cap_starts_from_spend() {
+        let mut budget = CostBudget {
+            spent_usd: 2.0,
+            limit_usd: None,
+        };
+        budget.raise(1.0);
+        assert_eq!(budget.limit_usd, Some(3.0));
+    }
+}
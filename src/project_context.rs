@@ -137,13 +137,20 @@ pub fn load_project_context(workspace: &Path) -> ProjectContext {
 pub fn load_project_context_with_parents(workspace: &Path) -> ProjectContext {
     let mut ctx = load_project_context(workspace);
 
-    // If no context found in workspace, check parent directories
-    if !ctx.has_instructions() {
+    // If no context found in workspace, check parent directories up to the
+    // repository top-level (which also covers linked worktrees)
+    let repo_root = crate::utils::git_toplevel(workspace).and_then(|root| root.canonicalize().ok());
+    let is_repo_root = |dir: &Path| {
+        repo_root
+            .as_deref()
+            .is_some_and(|root| dir.canonicalize().is_ok_and(|dir| dir == root))
+    };
+    if !ctx.has_instructions() && !is_repo_root(workspace) {
         let mut current = workspace.parent();
 
         while let Some(parent) = current {
             // Stop at git root or filesystem root
-            if parent.join(".git").exists() {
+            if is_repo_root(parent) || parent.join(".git").exists() {
                 let parent_ctx = load_project_context(parent);
                 if parent_ctx.has_instructions() {
                     ctx.instructions = parent_ctx.instructions;
@@ -439,6 +446,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parent_search_stops_at_the_git_top_level() {
+        let tmp = tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "Outside the repo").expect("write");
+        let repo = tmp.path().join("repo");
+        let nested = repo.join("crates").join("core");
+        fs::create_dir_all(&nested).expect("mkdir");
+        let git_init = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&repo)
+            .status();
+        if !git_init.is_ok_and(|status| status.success()) {
+            return;
+        }
+
+        assert!(!load_project_context_with_parents(&repo).has_instructions());
+        assert!(!load_project_context_with_parents(&nested).has_instructions());
+
+        fs::write(repo.join("AGENTS.md"), "Repo instructions").expect("write");
+        let ctx = load_project_context_with_parents(&nested);
+        assert!(ctx.instructions.unwrap().contains("Repo instructions"));
+    }
+
     #[test]
     fn test_merge_contexts() {
         let mut ctx1 = ProjectContext::empty(PathBuf::from("/a"));
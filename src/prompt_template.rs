@@ -0,0 +1,115 @@
+//! `{{name}}` substitution for one-shot and exec prompts.
+//!
+//! Values come from repeated `--var name=value` flags. Placeholders without a
+//! value are left in place and reported so the caller can warn about them.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+/// Prompt text after substitution, plus any placeholders left unresolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedPrompt {
+    pub text: String,
+    pub unresolved: Vec<String>,
+}
+
+/// Parse `name=value` pairs from `--var` flags. Later pairs override earlier ones.
+pub fn parse_vars(items: &[String]) -> Result<HashMap<String, String>> {
+    let mut vars = HashMap::new();
+    for item in items {
+        let Some((name, value)) = item.split_once('=') else {
+            anyhow::bail!("Failed to parse --var '{item}': expected NAME=VALUE");
+        };
+        let name = name.trim();
+        if !is_valid_name(name) {
+            anyhow::bail!(
+                "Failed to parse --var '{item}': names may only contain letters, digits, '_', '-' and '.'"
+            );
+        }
+        vars.insert(name.to_string(), value.to_string());
+    }
+    Ok(vars)
+}
+
+/// Replace `{{name}}` (whitespace inside the braces is ignored) with values from `vars`.
+pub fn render(template: &str, vars: &HashMap<String, String>) -> RenderedPrompt {
+    let mut text = String::with_capacity(template.len());
+    let mut unresolved = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        text.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let raw = &after_open[..end];
+        let name = raw.trim();
+        match vars.get(name) {
+            Some(value) if is_valid_name(name) => text.push_str(value),
+            _ => {
+                text.push_str(&rest[start..start + 2 + end + 2]);
+                if is_valid_name(name) && !unresolved.iter().any(|n| n == name) {
+                    unresolved.push(name.to_string());
+                }
+            }
+        }
+        rest = &after_open[end + 2..];
+    }
+    text.push_str(rest);
+
+    RenderedPrompt { text, unresolved }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[&str]) -> HashMap<String, String> {
+        let items: Vec<String> = pairs.iter().map(|s| (*s).to_string()).collect();
+        parse_vars(&items).unwrap()
+    }
+
+    #[test]
+    fn substitutes_known_variables() {
+        let rendered = render(
+            "Review {{file}} for {{ focus }}; again {{file}}.",
+            &vars(&["file=src/main.rs", "focus=bugs"]),
+        );
+        assert_eq!(
+            rendered.text,
+            "Review src/main.rs for bugs; again src/main.rs."
+        );
+        assert!(rendered.unresolved.is_empty());
+    }
+
+    #[test]
+    fn reports_unresolved_placeholders_once() {
+        let rendered = render("{{a}} {{b}} {{b}}", &vars(&["a=1"]));
+        assert_eq!(rendered.text, "1 {{b}} {{b}}");
+        assert_eq!(rendered.unresolved, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn leaves_non_placeholder_braces_alone() {
+        let rendered = render("fn f() {{ }} and {{unclosed", &HashMap::new());
+        assert_eq!(rendered.text, "fn f() {{ }} and {{unclosed");
+        assert!(rendered.unresolved.is_empty());
+    }
+
+    #[test]
+    fn parse_vars_rejects_malformed_pairs() {
+        assert!(parse_vars(&["novalue".to_string()]).is_err());
+        assert!(parse_vars(&["bad name=x".to_string()]).is_err());
+        assert_eq!(vars(&["k=a=b"])["k"], "a=b");
+    }
+}
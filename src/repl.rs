@@ -0,0 +1,465 @@
+//! Line-mode chat REPL for terminals where the TUI cannot run (`--no-tui`).
+//!
+//! Drives the same engine as the TUI but reads prompts with rustyline and
+//! prints streamed output straight to stdout. Only a subset of slash commands
+//! is available.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rustyline::Editor;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+
+use crate::compaction::{DEFAULT_AUTO_COMPACT_TOKENS, KeepPolicy};
+use crate::config::Config;
+use crate::core::engine::{EngineConfig, EngineHandle, spawn_engine};
+use crate::core::events::Event;
+use crate::core::ops::Op;
+use crate::core::session::SessionUsage;
+use crate::models::Message;
+use crate::palette;
+use crate::pricing::new_shared_cost_budget;
+use crate::session_manager::SessionManager;
+use crate::settings::Settings;
+use crate::tui::app::AppMode;
+use crate::utils::truncate_with_ellipsis;
+
+/// Options for the line-mode REPL, mirroring the TUI's startup options.
+pub struct ReplOptions {
+    pub model: String,
+    pub workspace: PathBuf,
+    pub allow_shell: bool,
+    pub yolo: bool,
+    pub max_subagents: usize,
+    pub resume_session_id: Option<String>,
+}
+
+/// A line typed at the REPL prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ReplInput {
+    Prompt(String),
+    Help,
+    Exit,
+    Clear,
+    Compact,
+    Usage,
+    Model(Option<String>),
+    Mode(Option<String>),
+    Unsupported(String),
+}
+
+fn parse_input(line: &str) -> ReplInput {
+    let line = line.trim();
+    let Some(command) = line.strip_prefix('/') else {
+        return ReplInput::Prompt(line.to_string());
+    };
+    let (name, arg) = command
+        .split_once(char::is_whitespace)
+        .map_or((command, ""), |(name, arg)| (name, arg.trim()));
+    let arg = (!arg.is_empty()).then(|| arg.to_string());
+    match name {
+        "help" | "?" => ReplInput::Help,
+        "exit" | "quit" | "q" => ReplInput::Exit,
+        "clear" => ReplInput::Clear,
+        "compact" => ReplInput::Compact,
+        "usage" | "tokens" => ReplInput::Usage,
+        "model" => ReplInput::Model(arg),
+        "mode" => ReplInput::Mode(arg),
+        _ => ReplInput::Unsupported(name.to_string()),
+    }
+}
+
+fn parse_mode(name: &str) -> Option<AppMode> {
+    match name.to_ascii_lowercase().as_str() {
+        "normal" => Some(AppMode::Normal),
+        "agent" => Some(AppMode::Agent),
+        "plan" => Some(AppMode::Plan),
+        "yolo" => Some(AppMode::Yolo),
+        _ => None,
+    }
+}
+
+struct ReplState {
+    model: String,
+    workspace: PathBuf,
+    mode: AppMode,
+    allow_shell: bool,
+    usage: SessionUsage,
+    keep: KeepPolicy,
+}
+
+/// Run the line-mode REPL until `/exit` or end of input.
+pub async fn run_repl(config: &Config, options: ReplOptions) -> Result<()> {
+    // Fail before the first prompt rather than on every message
+    let _ = crate::client::AnthropicClient::new(config)?;
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        colored::control::set_override(false);
+    }
+
+    let mut state = ReplState {
+        model: options.model,
+        workspace: options.workspace,
+        mode: if options.yolo {
+            AppMode::Yolo
+        } else {
+            AppMode::Normal
+        },
+        allow_shell: options.allow_shell,
+        usage: SessionUsage::default(),
+        keep: KeepPolicy {
+            auto_compact_tokens: Settings::load()
+                .is_ok_and(|settings| settings.auto_compact)
+                .then_some(DEFAULT_AUTO_COMPACT_TOKENS),
+            ..KeepPolicy::default()
+        },
+    };
+
+    let engine_config = EngineConfig {
+        model: state.model.clone(),
+        workspace: state.workspace.clone(),
+        allow_shell: state.allow_shell,
+        trust_mode: options.yolo,
+        notes_path: config.notes_path(),
+        mcp_config_path: config.mcp_config_path(),
+        max_subagents: options.max_subagents,
+        features: config.features(),
+        memory_path: config.memory_path(),
+        web_search: config.web_search_config(),
+        shell_max_output_bytes: config.shell_max_output_bytes(),
+        cost_budget: new_shared_cost_budget(config.max_session_cost_usd()),
+        media: config.media_config(),
+        ..EngineConfig::default()
+    };
+    let handle = spawn_engine(engine_config, config);
+
+    if let Some(id) = options.resume_session_id {
+        let manager = SessionManager::new(config.session_dir())?;
+        let saved = if id == "latest" {
+            let latest = manager
+                .get_latest_session()?
+                .context("No saved sessions to resume")?;
+            manager.load_session(&latest.id)
+        } else {
+            manager.load_session_by_prefix(&id)
+        }
+        .with_context(|| format!("Failed to resume session {id}"))?;
+        state.model.clone_from(&saved.metadata.model);
+        state.workspace.clone_from(&saved.metadata.workspace);
+        println!(
+            "Resumed session {} ({} messages)",
+            truncate_with_ellipsis(&saved.metadata.id, 8, ""),
+            saved.messages.len()
+        );
+        sync_session(&handle, &state, saved.messages).await?;
+    }
+
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+    println!(
+        "{} {} | {} mode | /help for commands, /exit to quit",
+        "Axiom".truecolor(blue_r, blue_g, blue_b).bold(),
+        state.model,
+        state.mode.label()
+    );
+
+    let mut editor = Editor::<(), DefaultHistory>::new()?;
+    let history_path = dirs::home_dir()
+        .map(|h| h.join(".axiom").join("repl_history"))
+        .unwrap_or_default();
+    let _ = editor.load_history(&history_path);
+
+    loop {
+        let prompt = format!("{}> ", state.mode.label().to_ascii_lowercase());
+        let line = match tokio::task::block_in_place(|| editor.readline(&prompt)) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.trim());
+
+        match parse_input(&line) {
+            ReplInput::Prompt(content) => {
+                handle
+                    .send(Op::SendMessage {
+                        content,
+                        mode: state.mode,
+                        model: state.model.clone(),
+                        allow_shell: state.allow_shell,
+                        trust_mode: state.mode == AppMode::Yolo,
+                        allowed_tools: None,
+                        keep: state.keep.clone(),
+                    })
+                    .await?;
+                run_turn(&handle, &mut editor, &mut state).await?;
+            }
+            ReplInput::Help => print_help(),
+            ReplInput::Exit => break,
+            ReplInput::Clear => {
+                state.usage = SessionUsage::default();
+                sync_session(&handle, &state, Vec::new()).await?;
+                println!("Conversation cleared.");
+            }
+            ReplInput::Compact => {
+                handle
+                    .send(Op::CompactContext {
+                        target_tokens: None,
+                        keep: state.keep.clone(),
+                    })
+                    .await?;
+                wait_for_status(&handle).await;
+            }
+            ReplInput::Usage => println!(
+                "Tokens this session: {} in, {} out ({} total)",
+                state.usage.input_tokens,
+                state.usage.output_tokens,
+                state.usage.total()
+            ),
+            ReplInput::Model(None) => println!("Model: {}", state.model),
+            ReplInput::Model(Some(model)) => {
+                state.model.clone_from(&model);
+                handle.send(Op::SetModel { model }).await?;
+                wait_for_status(&handle).await;
+            }
+            ReplInput::Mode(None) => println!("Mode: {}", state.mode.label()),
+            ReplInput::Mode(Some(name)) => match parse_mode(&name) {
+                Some(mode) => {
+                    state.mode = mode;
+                    state.allow_shell = true;
+                    println!("Switched to {} mode", mode.label());
+                }
+                None => println!("Unknown mode `{name}`; use normal, agent, plan, or yolo"),
+            },
+            ReplInput::Unsupported(name) => {
+                println!("/{name} is not available with --no-tui; /help lists what is");
+            }
+        }
+    }
+
+    let _ = editor.save_history(&history_path);
+    let _ = handle.send(Op::Shutdown).await;
+    Ok(())
+}
+
+async fn sync_session(
+    handle: &EngineHandle,
+    state: &ReplState,
+    messages: Vec<Message>,
+) -> Result<()> {
+    handle
+        .send(Op::SyncSession {
+            messages,
+            system_prompt: None,
+            model: state.model.clone(),
+            workspace: state.workspace.clone(),
+        })
+        .await?;
+    wait_for_status(handle).await;
+    Ok(())
+}
+
+/// Wait for the status or error that ends a non-turn op and print errors.
+async fn wait_for_status(handle: &EngineHandle) {
+    let mut rx = handle.rx_event.write().await;
+    while let Some(event) = rx.recv().await {
+        match event {
+            Event::Status { message } => {
+                if !message.starts_with("Session context synced") {
+                    println!("{message}");
+                }
+                break;
+            }
+            Event::Error { .. } => {
+                print_error(&event);
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Stream one turn's events to the terminal until the engine finishes it.
+/// Ctrl+C cancels the turn instead of exiting.
+async fn run_turn(
+    handle: &EngineHandle,
+    editor: &mut Editor<(), DefaultHistory>,
+    state: &mut ReplState,
+) -> Result<()> {
+    let (red_r, red_g, red_b) = palette::RED_RGB;
+    let (orange_r, orange_g, orange_b) = palette::ORANGE_RGB;
+    let (silver_r, silver_g, silver_b) = palette::SILVER_RGB;
+    let mut rx = handle.rx_event.write().await;
+    let mut mid_line = false;
+
+    loop {
+        let event = tokio::select! {
+            event = rx.recv() => event,
+            _ = tokio::signal::ctrl_c() => {
+                handle.cancel();
+                continue;
+            }
+        };
+        let Some(event) = event else {
+            anyhow::bail!("Engine stopped unexpectedly");
+        };
+        if mid_line && !matches!(event, Event::MessageDelta { .. }) {
+            println!();
+            mid_line = false;
+        }
+        match event {
+            Event::MessageDelta { content, .. } => {
+                print!("{content}");
+                io::stdout().flush()?;
+                mid_line = !content.ends_with('\n');
+            }
+            Event::ToolCallStarted { name, input, .. } => {
+                let summary = truncate_with_ellipsis(&input.to_string(), 80, "...");
+                eprintln!(
+                    "{}",
+                    format!("⚙ {name} {summary}").truecolor(silver_r, silver_g, silver_b)
+                );
+            }
+            Event::ToolCallComplete {
+                name,
+                result: Err(err),
+                ..
+            } => {
+                eprintln!(
+                    "{}",
+                    format!("✗ {name}: {err}").truecolor(red_r, red_g, red_b)
+                );
+            }
+            Event::ApprovalRequired {
+                id,
+                description,
+                preview,
+                ..
+            } => {
+                eprintln!(
+                    "{}",
+                    format!("Approval required: {description}")
+                        .truecolor(orange_r, orange_g, orange_b)
+                );
+                if let Some(preview) = preview {
+                    eprintln!("{preview}");
+                }
+                let answer = tokio::task::block_in_place(|| editor.readline("Allow? [y/N] "))
+                    .unwrap_or_default();
+                if matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+                    handle.approve_tool_call(id).await?;
+                } else {
+                    handle.deny_tool_call(id).await?;
+                }
+            }
+            Event::AgentSpawned { id, .. } => {
+                eprintln!(
+                    "{}",
+                    format!("Sub-agent {id} started").truecolor(silver_r, silver_g, silver_b)
+                );
+            }
+            Event::AgentComplete { id, status, .. } => {
+                eprintln!(
+                    "{}",
+                    format!("Sub-agent {id} finished: {status:?}")
+                        .truecolor(silver_r, silver_g, silver_b)
+                );
+            }
+            Event::Status { message } => {
+                eprintln!("{}", message.truecolor(silver_r, silver_g, silver_b));
+            }
+            Event::BudgetExceeded {
+                spent_usd,
+                limit_usd,
+                ..
+            } => {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Session budget reached: ${spent_usd:.4} of ${limit_usd:.2} spent. Turn stopped."
+                    )
+                    .truecolor(red_r, red_g, red_b)
+                );
+            }
+            Event::Error { .. } => print_error(&event),
+            Event::TurnComplete { usage } => {
+                state.usage.add(&usage);
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+}
+
+fn print_error(event: &Event) {
+    let Event::Error { message, hint, .. } = event else {
+        return;
+    };
+    let (red_r, red_g, red_b) = palette::RED_RGB;
+    match hint {
+        Some(hint) => {
+            eprintln!(
+                "{}: {} ({})",
+                "Error".truecolor(red_r, red_g, red_b),
+                hint.message,
+                hint.error_type.label()
+            );
+            eprintln!("  {}", hint.suggestion);
+        }
+        None => eprintln!("{}: {message}", "Error".truecolor(red_r, red_g, red_b)),
+    }
+}
+
+fn print_help() {
+    let (blue_r, blue_g, blue_b) = palette::BLUE_RGB;
+    println!(
+        "{}",
+        "Commands (--no-tui)"
+            .truecolor(blue_r, blue_g, blue_b)
+            .bold()
+    );
+    println!();
+    println!("  /model [name]   Show or switch the model");
+    println!("  /mode [name]    Show or switch mode: normal, agent, plan, yolo");
+    println!("  /compact        Summarize older messages to free context");
+    println!("  /clear          Start a fresh conversation");
+    println!("  /usage          Show tokens used this session");
+    println!("  /help           Show this help");
+    println!("  /exit           Exit");
+    println!();
+    println!("Ctrl+C cancels a running response; Ctrl+D exits.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_splits_commands_from_prompts() {
+        assert_eq!(
+            parse_input("  explain this  "),
+            ReplInput::Prompt("explain this".to_string())
+        );
+        assert_eq!(parse_input("/quit"), ReplInput::Exit);
+        assert_eq!(parse_input("/model"), ReplInput::Model(None));
+        assert_eq!(
+            parse_input("/model  model-02 "),
+            ReplInput::Model(Some("model-02".to_string()))
+        );
+        assert_eq!(
+            parse_input("/theme dark"),
+            ReplInput::Unsupported("theme".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_mode_accepts_line_mode_subset() {
+        assert_eq!(parse_mode("Agent"), Some(AppMode::Agent));
+        assert_eq!(parse_mode("yolo"), Some(AppMode::Yolo));
+        assert_eq!(parse_mode("duo"), None);
+    }
+}
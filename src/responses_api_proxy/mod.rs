@@ -32,7 +32,8 @@ pub struct Args {
     #[arg(long)]
     pub http_shutdown: bool,
 
-    /// Absolute URL the proxy should forward requests to.
+    /// Absolute URL the proxy should forward `/v1/responses` requests to.
+    /// `/v1/chat/completions` requests go to the sibling `chat/completions` path.
     #[arg(long, default_value = "https://api.axiom.io/v1/responses")]
     pub upstream_url: String,
 }
@@ -45,6 +46,8 @@ struct ServerInfo {
 
 struct ForwardConfig {
     upstream_url: Url,
+    /// Sibling `/chat/completions` endpoint, when the upstream ends in `/responses`
+    chat_completions_url: Option<Url>,
     host_header: HeaderValue,
 }
 
@@ -62,6 +65,7 @@ pub fn run_main(args: Args) -> Result<()> {
         HeaderValue::from_str(&host).context("constructing Host header from upstream URL")?;
 
     let forward_config = Arc::new(ForwardConfig {
+        chat_completions_url: chat_completions_url(&upstream_url),
         upstream_url,
         host_header,
     });
@@ -126,22 +130,33 @@ fn write_server_info(path: &Path, port: u16) -> Result<()> {
     Ok(())
 }
 
+/// The `/chat/completions` endpoint next to a `/responses` upstream URL.
+fn chat_completions_url(upstream_url: &Url) -> Option<Url> {
+    let base = upstream_url.path().strip_suffix("/responses")?;
+    let mut url = upstream_url.clone();
+    url.set_path(&format!("{base}/chat/completions"));
+    Some(url)
+}
+
 fn forward_request(
     client: &Client,
     auth_header: &'static str,
     config: &ForwardConfig,
     mut req: Request,
 ) -> Result<()> {
-    // Only allow POST /v1/responses exactly, no query string.
+    // Only allow POST /v1/responses or /v1/chat/completions exactly, no query string.
     let method = req.method().clone();
     let url_path = req.url().to_string();
-    let allow = method == Method::Post && url_path == "/v1/responses";
-
-    if !allow {
+    let upstream_url = match url_path.as_str() {
+        "/v1/responses" => Some(&config.upstream_url),
+        "/v1/chat/completions" => config.chat_completions_url.as_ref(),
+        _ => None,
+    };
+    let Some(upstream_url) = upstream_url.filter(|_| method == Method::Post) else {
         let resp = Response::new_empty(StatusCode(403));
         let _ = req.respond(resp);
         return Ok(());
-    }
+    };
 
     // Read request body
     let mut body = Vec::new();
@@ -176,7 +191,7 @@ fn forward_request(
     headers.insert(HOST, config.host_header.clone());
 
     let upstream_resp = client
-        .post(config.upstream_url.clone())
+        .post(upstream_url.clone())
         .headers(headers)
         .body(body)
         .send()
@@ -221,3 +236,19 @@ fn forward_request(
     let _ = req.respond(response);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_completions_url_is_sibling_of_responses() {
+        let upstream = Url::parse("https://api.axiom.io/v1/responses").unwrap();
+        assert_eq!(
+            chat_completions_url(&upstream).unwrap().as_str(),
+            "https://api.axiom.io/v1/chat/completions"
+        );
+        let other = Url::parse("http://localhost:8080/generate").unwrap();
+        assert!(chat_completions_url(&other).is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//! On-disk cache of `axiom review` results.
+//!
+//! Entries live in `~/.axiom/review-cache/<key>.md`, where the key hashes the
+//! model, the prompts, and the diff. Re-running a review on an unchanged diff
+//! returns the stored text instead of calling the API again.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::utils::write_bytes;
+
+pub struct ReviewCache {
+    dir: PathBuf,
+}
+
+impl ReviewCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Cache in the default location (~/.axiom/review-cache)
+    pub fn default_location() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to resolve home directory")?;
+        Ok(Self::new(home.join(".axiom").join("review-cache")))
+    }
+
+    /// Stable key for a review request. Every input that changes the answer goes in.
+    pub fn key(model: &str, system_prompt: &str, user_prompt: &str) -> String {
+        let hasher =
+            [model, system_prompt, user_prompt]
+                .iter()
+                .fold(Fnv128::new(), |mut h, part| {
+                    h.write(part.as_bytes());
+                    // Separator so ("ab", "c") and ("a", "bc") hash differently.
+                    h.write(&[0xff]);
+                    h
+                });
+        format!("{:032x}", hasher.finish())
+    }
+
+    /// Cached review text, if a previous run stored one under `key`
+    pub fn get(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.path(key)).ok()
+    }
+
+    pub fn put(&self, key: &str, review: &str) -> Result<()> {
+        write_bytes(&self.path(key), review.as_bytes())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.md"))
+    }
+}
+
+/// 128-bit FNV-1a. `DefaultHasher` output isn't guaranteed stable across Rust
+/// releases, which would silently invalidate the cache on upgrade.
+struct Fnv128(u128);
+
+impl Fnv128 {
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u128::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u128 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn key_depends_on_every_input() {
+        let base = ReviewCache::key("m", "sys", "diff");
+        assert_eq!(base, ReviewCache::key("m", "sys", "diff"));
+        assert_eq!(base.len(), 32);
+        assert_ne!(base, ReviewCache::key("m2", "sys", "diff"));
+        assert_ne!(base, ReviewCache::key("m", "sys2", "diff"));
+        assert_ne!(base, ReviewCache::key("m", "sys", "diff2"));
+        assert_ne!(
+            ReviewCache::key("ab", "c", ""),
+            ReviewCache::key("a", "bc", "")
+        );
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let tmp = tempdir().expect("tempdir");
+        let cache = ReviewCache::new(tmp.path().join("review-cache"));
+        let key = ReviewCache::key("m", "sys", "diff");
+        assert!(cache.get(&key).is_none());
+        cache.put(&key, "[info] looks fine").expect("put");
+        assert_eq!(cache.get(&key).as_deref(), Some("[info] looks fine"));
+    }
+}
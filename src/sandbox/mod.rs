@@ -1,8 +1,4 @@
 //! Sandbox module for secure command execution.
-//! NOTE: Not yet integrated into shell tool - planned security feature.
-
-#![allow(dead_code)]
-
 //!
 //! This module provides sandboxing capabilities for shell commands executed by
 //! Axiom CLI. Sandboxing restricts what system resources a command can access,
@@ -14,6 +10,9 @@
 //! - **Linux**: Uses Landlock (kernel 5.13+) for filesystem access control
 //! - **Windows**: Falls back to no sandboxing
 //!
+//! The shell tool (`tools::shell::ShellManager`) routes every command through
+//! `SandboxManager::prepare`; trust (YOLO) mode uses `DangerFullAccess`.
+//!
 //! # Usage
 //!
 //! ```rust,ignore
@@ -27,6 +26,8 @@
 //! // exec_env.command now contains the sandboxed command
 //! ```
 
+#![allow(dead_code)]
+
 pub mod policy;
 
 #[cfg(target_os = "macos")]
@@ -252,6 +253,14 @@ pub fn is_sandbox_available() -> bool {
     get_platform_sandbox().is_some()
 }
 
+/// Mark a sandboxed command's environment with the sandbox kind. The
+/// `MINIMAX_SANDBOX` alias keeps scripts written before the rename working.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn insert_sandbox_marker(env: &mut HashMap<String, String>, kind: &str) {
+    env.insert("AXIOM_SANDBOX".to_string(), kind.to_string());
+    env.insert("MINIMAX_SANDBOX".to_string(), kind.to_string());
+}
+
 /// Manager for sandbox operations.
 ///
 /// The `SandboxManager` is responsible for:
@@ -355,7 +364,7 @@ impl SandboxManager {
 
         // Add sandbox indicator to environment
         let mut env = spec.env.clone();
-        env.insert("AXIOM_SANDBOX".to_string(), "seatbelt".to_string());
+        insert_sandbox_marker(&mut env, "seatbelt");
 
         ExecEnv {
             command,
@@ -380,7 +389,7 @@ impl SandboxManager {
 
         // Add sandbox indicator to environment
         let mut env = spec.env.clone();
-        env.insert("AXIOM_SANDBOX".to_string(), "landlock".to_string());
+        insert_sandbox_marker(&mut env, "landlock");
 
         // Note: Full Landlock implementation would use a helper binary that:
         // 1. Sets up the Landlock ruleset based on policy
@@ -399,6 +408,17 @@ impl SandboxManager {
         }
     }
 
+    /// Whether a sandbox type can restrict network access to specific hosts.
+    ///
+    /// Other sandboxes treat a host allowlist as plain network access.
+    pub fn supports_host_allowlist(sandbox_type: SandboxType) -> bool {
+        match sandbox_type {
+            #[cfg(target_os = "macos")]
+            SandboxType::MacosSeatbelt => true,
+            _ => false,
+        }
+    }
+
     /// Check if a command failure was due to sandbox denial.
     ///
     /// This helps distinguish between legitimate command failures and
@@ -547,6 +567,23 @@ mod tests {
         assert!(!env.is_sandboxed());
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_landlock_sets_current_and_legacy_markers() {
+        let spec = CommandSpec::shell("echo test", PathBuf::from("/tmp"), Duration::from_secs(30));
+
+        let env = SandboxManager::prepare_landlock(&spec);
+
+        assert_eq!(
+            env.env.get("AXIOM_SANDBOX").map(String::as_str),
+            Some("landlock")
+        );
+        assert_eq!(
+            env.env.get("MINIMAX_SANDBOX").map(String::as_str),
+            Some("landlock")
+        );
+    }
+
     #[test]
     fn test_exec_env_helpers() {
         let env = ExecEnv {
@@ -57,6 +57,13 @@ pub enum SandboxPolicy {
         #[serde(default)]
         network_access: bool,
 
+        /// Destinations outbound connections are limited to: `localhost`,
+        /// `localhost:port`, or `*:port`. Seatbelt cannot filter by hostname or
+        /// IP address, so those are rejected. Non-empty implies network access;
+        /// empty leaves `network_access` in charge.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allowed_hosts: Vec<String>,
+
         /// Exclude TMPDIR from writable paths.
         #[serde(default)]
         exclude_tmpdir: bool,
@@ -73,6 +80,7 @@ impl Default for SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: false,
+            allowed_hosts: vec![],
             exclude_tmpdir: false,
             exclude_slash_tmp: false,
         }
@@ -85,6 +93,7 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![],
             network_access: true,
+            allowed_hosts: vec![],
             exclude_tmpdir: false,
             exclude_slash_tmp: false,
         }
@@ -95,6 +104,7 @@ impl SandboxPolicy {
         SandboxPolicy::WorkspaceWrite {
             writable_roots: roots,
             network_access: network,
+            allowed_hosts: vec![],
             exclude_tmpdir: false,
             exclude_slash_tmp: false,
         }
@@ -119,8 +129,39 @@ impl SandboxPolicy {
         match self {
             SandboxPolicy::DangerFullAccess => true,
             SandboxPolicy::ReadOnly => false,
-            SandboxPolicy::ExternalSandbox { network_access }
-            | SandboxPolicy::WorkspaceWrite { network_access, .. } => *network_access,
+            SandboxPolicy::ExternalSandbox { network_access } => *network_access,
+            SandboxPolicy::WorkspaceWrite {
+                network_access,
+                allowed_hosts,
+                ..
+            } => *network_access || !allowed_hosts.is_empty(),
+        }
+    }
+
+    /// Hosts that outbound connections are limited to, if any.
+    ///
+    /// An empty slice means network access is all-or-nothing.
+    pub fn network_allowlist(&self) -> &[String] {
+        match self {
+            SandboxPolicy::WorkspaceWrite { allowed_hosts, .. } => allowed_hosts,
+            _ => &[],
+        }
+    }
+
+    /// Returns true if `host` is a destination the sandbox can enforce.
+    ///
+    /// Seatbelt's `remote ip` filter only understands `localhost` or `*` as the
+    /// host, so this accepts `localhost` with an optional `:port` (or `:*`) and
+    /// `*:port`. Hostnames and IP addresses would make the profile fail to
+    /// compile and are rejected.
+    pub fn is_valid_allowed_host(host: &str) -> bool {
+        let (name, port) = host.split_once(':').unwrap_or((host, "*"));
+        let port_ok = port == "*" || (!port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+        match name {
+            "localhost" => port_ok,
+            // `*:*` is plain network access; say `--network` instead
+            "*" => port_ok && port != "*",
+            _ => false,
         }
     }
 
@@ -291,6 +332,36 @@ mod tests {
         assert!(policy.should_sandbox());
     }
 
+    #[test]
+    fn test_allowed_hosts_imply_network() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allowed_hosts: vec!["*:443".to_string()],
+            exclude_tmpdir: false,
+            exclude_slash_tmp: false,
+        };
+        assert!(policy.has_network_access());
+        assert_eq!(policy.network_allowlist(), ["*:443".to_string()]);
+        assert!(SandboxPolicy::default().network_allowlist().is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_allowed_host() {
+        assert!(SandboxPolicy::is_valid_allowed_host("localhost"));
+        assert!(SandboxPolicy::is_valid_allowed_host("localhost:8080"));
+        assert!(SandboxPolicy::is_valid_allowed_host("*:443"));
+        // Seatbelt cannot filter by hostname or address
+        assert!(!SandboxPolicy::is_valid_allowed_host("github.com"));
+        assert!(!SandboxPolicy::is_valid_allowed_host("10.0.0.1:*"));
+        assert!(!SandboxPolicy::is_valid_allowed_host("*"));
+        assert!(!SandboxPolicy::is_valid_allowed_host(""));
+        assert!(!SandboxPolicy::is_valid_allowed_host(
+            "evil\")(allow default"
+        ));
+        assert!(!SandboxPolicy::is_valid_allowed_host("host:abc"));
+    }
+
     #[test]
     fn test_writable_root_basic() {
         let root = WritableRoot::new(PathBuf::from("/project"));
@@ -313,6 +384,7 @@ mod tests {
         let policy = SandboxPolicy::WorkspaceWrite {
             writable_roots: vec![PathBuf::from("/extra")],
             network_access: true,
+            allowed_hosts: vec![],
             exclude_tmpdir: false,
             exclude_slash_tmp: false,
         };
@@ -154,7 +154,12 @@ fn generate_policy(policy: &SandboxPolicy, cwd: &Path) -> String {
     // Add network policy if enabled
     if policy.has_network_access() {
         full_policy.push('\n');
-        full_policy.push_str(SEATBELT_NETWORK_POLICY);
+        let allowlist = policy.network_allowlist();
+        if allowlist.is_empty() {
+            full_policy.push_str(SEATBELT_NETWORK_POLICY);
+        } else {
+            full_policy.push_str(&generate_network_allowlist_policy(allowlist));
+        }
     }
 
     // Add Darwin user cache directory access (needed by many macOS tools)
@@ -177,6 +182,29 @@ fn generate_policy(policy: &SandboxPolicy, cwd: &Path) -> String {
     full_policy
 }
 
+/// Generate network rules that only allow outbound connections to `hosts`.
+///
+/// `remote ip` only accepts `localhost` or `*` as the host, which
+/// `SandboxPolicy::is_valid_allowed_host` enforces before we get here.
+fn generate_network_allowlist_policy(hosts: &[String]) -> String {
+    let mut rules = String::from(
+        "\n; Network access limited to allowlisted hosts\n\
+         (allow system-socket)\n\
+         (allow network-outbound (literal \"/private/var/run/mDNSResponder\"))\n",
+    );
+    for host in hosts {
+        let target = if host.contains(':') {
+            host.clone()
+        } else {
+            format!("{host}:*")
+        };
+        rules.push_str(&format!(
+            "(allow network-outbound (remote ip \"{target}\"))\n"
+        ));
+    }
+    rules
+}
+
 /// Generate the write access portion of the Seatbelt policy.
 fn generate_write_policy(policy: &SandboxPolicy, cwd: &Path) -> String {
     // Full disk write access
@@ -347,6 +375,49 @@ mod tests {
         assert!(result.contains("network-inbound"));
     }
 
+    #[test]
+    fn test_generate_policy_with_allowed_hosts() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allowed_hosts: vec!["*:443".to_string(), "localhost:8080".to_string()],
+            exclude_tmpdir: false,
+            exclude_slash_tmp: false,
+        };
+        let cwd = Path::new("/tmp/test");
+        let result = generate_policy(&policy, cwd);
+
+        assert!(result.contains(r#"(remote ip "*:443")"#));
+        assert!(result.contains(r#"(remote ip "localhost:8080")"#));
+        assert!(!result.contains("network-inbound"));
+    }
+
+    /// The profile must compile under the real `sandbox-exec`, not just
+    /// contain the expected text.
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_allowed_hosts_profile_runs_under_sandbox_exec() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            allowed_hosts: vec!["*:443".to_string(), "localhost:8080".to_string()],
+            exclude_tmpdir: false,
+            exclude_slash_tmp: false,
+        };
+        let cwd = std::env::temp_dir();
+        let args = create_seatbelt_args(vec!["/usr/bin/true".to_string()], &policy, &cwd);
+        let output = Command::new(SANDBOX_EXEC_PATH)
+            .args(&args)
+            .current_dir(&cwd)
+            .output()
+            .expect("run sandbox-exec");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
     #[test]
     fn test_generate_policy_read_only() {
         let policy = SandboxPolicy::ReadOnly;
@@ -9,17 +9,37 @@
 #![allow(dead_code)] // Public API - session persistence functions for future TUI integration
 
 use crate::models::{ContentBlock, Message, SystemPrompt};
+use crate::tools::security::{redact_json, redact_secrets};
 use crate::tui::app::PinnedMessage;
+use crate::tui::scrolling::TranscriptScroll;
 use crate::utils::truncate_to_boundary;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use uuid::Uuid;
 
 /// Maximum number of sessions to retain
 const MAX_SESSIONS: usize = 50;
 
+/// Maximum characters shown around a content search hit
+const SNIPPET_CHARS: usize = 80;
+
+/// Snapshots of an in-progress turn are named `recovery-<pid>.snapshot`, one
+/// per process. One found at startup whose owner is gone means that run died
+/// before the turn completed. Not `.json`, so listings skip them.
+const RECOVERY_PREFIX: &str = "recovery-";
+const RECOVERY_SUFFIX: &str = ".snapshot";
+
+/// Snapshot name used before snapshots were per process; it has no owner.
+const LEGACY_RECOVERY_FILE: &str = "recovery.snapshot";
+
+/// `recovery-<pid>.lock` files this process holds locked for its lifetime,
+/// one per sessions directory, so other instances leave its snapshot alone.
+static RECOVERY_LOCKS: Mutex<Vec<(PathBuf, fs::File)>> = Mutex::new(Vec::new());
+
 /// Session metadata stored with each saved session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMetadata {
@@ -39,6 +59,26 @@ pub struct SessionMetadata {
     pub model: String,
     /// Workspace directory
     pub workspace: PathBuf,
+    /// User-assigned tags for grouping sessions
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Transcript scroll anchor when the session was last saved
+    #[serde(default)]
+    pub transcript_scroll: TranscriptScroll,
+}
+
+impl SessionMetadata {
+    /// Whether the session carries `tag` (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+}
+
+/// A session whose message content matched a search, with the matching line
+#[derive(Debug, Clone)]
+pub struct ContentMatch {
+    pub metadata: SessionMetadata,
+    pub snippet: String,
 }
 
 /// A saved session containing full conversation history
@@ -54,10 +94,24 @@ pub struct SavedSession {
     pub pinned_messages: Vec<PinnedMessage>,
 }
 
+/// Metadata summary of one saved session file, read without its messages
+#[derive(Debug, Clone)]
+pub struct SessionInfo {
+    pub metadata: SessionMetadata,
+    /// Number of pinned messages
+    pub pin_count: usize,
+    /// Size of the session file in bytes
+    pub file_size: u64,
+    /// Path of the session file
+    pub path: PathBuf,
+}
+
 /// Manager for session persistence operations
 pub struct SessionManager {
     /// Directory where sessions are stored
     sessions_dir: PathBuf,
+    /// Redact secrets from sessions before they are written
+    redact: bool,
 }
 
 impl SessionManager {
@@ -65,15 +119,26 @@ impl SessionManager {
     pub fn new(sessions_dir: PathBuf) -> std::io::Result<Self> {
         // Ensure the sessions directory exists
         fs::create_dir_all(&sessions_dir)?;
-        Ok(Self { sessions_dir })
+        Ok(Self {
+            sessions_dir,
+            redact: false,
+        })
+    }
+
+    /// Redact secrets from everything this manager writes (`redact_on_save`)
+    #[must_use]
+    pub fn with_redaction(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
     }
 
-    /// Create a `SessionManager` using the default location (~/.axiom/sessions)
-    pub fn default_location() -> std::io::Result<Self> {
-        let home = dirs::home_dir().ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::NotFound, "Home directory not found")
-        })?;
-        Self::new(home.join(".axiom").join("sessions"))
+    /// The session as it should be written: redacted when enabled
+    fn for_disk<'a>(&self, session: &'a SavedSession) -> Cow<'a, SavedSession> {
+        if self.redact {
+            Cow::Owned(redact_session(session))
+        } else {
+            Cow::Borrowed(session)
+        }
     }
 
     /// Save a session to disk
@@ -81,7 +146,7 @@ impl SessionManager {
         let filename = format!("{}.json", session.metadata.id);
         let path = self.sessions_dir.join(&filename);
 
-        let content = serde_json::to_string_pretty(session)
+        let content = serde_json::to_string_pretty(&self.for_disk(session))
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
         fs::write(&path, content)?;
@@ -106,9 +171,15 @@ impl SessionManager {
 
     /// Load a session by partial ID prefix
     pub fn load_session_by_prefix(&self, prefix: &str) -> std::io::Result<SavedSession> {
+        let id = self.resolve_prefix(prefix)?;
+        self.load_session(&id)
+    }
+
+    /// Resolve a partial ID prefix to the one session ID it matches
+    fn resolve_prefix(&self, prefix: &str) -> std::io::Result<String> {
         let sessions = self.list_sessions()?;
 
-        let matches: Vec<_> = sessions
+        let mut matches: Vec<_> = sessions
             .into_iter()
             .filter(|s| s.id.starts_with(prefix))
             .collect();
@@ -118,7 +189,7 @@ impl SessionManager {
                 std::io::ErrorKind::NotFound,
                 format!("No session found with prefix: {prefix}"),
             )),
-            1 => self.load_session(&matches[0].id),
+            1 => Ok(matches.remove(0).id),
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 format!(
@@ -130,6 +201,33 @@ impl SessionManager {
         }
     }
 
+    /// Read metadata, pin count, and file size for a session given its ID or a
+    /// unique ID prefix. Message bodies are skipped rather than deserialized.
+    pub fn session_info(&self, id_or_prefix: &str) -> std::io::Result<SessionInfo> {
+        #[derive(Deserialize)]
+        struct SavedSessionSummary {
+            metadata: SessionMetadata,
+            #[serde(default)]
+            pinned_messages: Vec<serde::de::IgnoredAny>,
+        }
+
+        let mut path = self.sessions_dir.join(format!("{id_or_prefix}.json"));
+        if !path.is_file() {
+            let id = self.resolve_prefix(id_or_prefix)?;
+            path = self.sessions_dir.join(format!("{id}.json"));
+        }
+        let file_size = fs::metadata(&path)?.len();
+        let file = std::io::BufReader::new(fs::File::open(&path)?);
+        let summary: SavedSessionSummary = serde_json::from_reader(file)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(SessionInfo {
+            metadata: summary.metadata,
+            pin_count: summary.pinned_messages.len(),
+            file_size,
+            path,
+        })
+    }
+
     /// List all saved sessions, sorted by most recently updated
     pub fn list_sessions(&self) -> std::io::Result<Vec<SessionMetadata>> {
         let mut sessions = Vec::new();
@@ -164,6 +262,209 @@ impl SessionManager {
         Ok(session.metadata)
     }
 
+    /// Load a session by exact ID, falling back to a unique ID prefix
+    fn load_session_by_id_or_prefix(&self, id_or_prefix: &str) -> std::io::Result<SavedSession> {
+        match self.load_session(id_or_prefix) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.load_session_by_prefix(id_or_prefix)
+            }
+            result => result,
+        }
+    }
+
+    /// Rename a session given its ID or a unique ID prefix
+    pub fn rename_session(
+        &self,
+        id_or_prefix: &str,
+        title: &str,
+    ) -> std::io::Result<SessionMetadata> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Session title cannot be empty",
+            ));
+        }
+        let mut session = self.load_session_by_id_or_prefix(id_or_prefix)?;
+        session.metadata.title = title.to_string();
+        self.save_session(&session)?;
+        Ok(session.metadata)
+    }
+
+    /// Add `tag` to a session, or remove it if already present.
+    /// Returns the updated metadata and whether the tag was added.
+    pub fn toggle_tag(
+        &self,
+        id_or_prefix: &str,
+        tag: &str,
+    ) -> std::io::Result<(SessionMetadata, bool)> {
+        let tag = tag.trim().trim_start_matches('#');
+        if tag.is_empty() || tag.chars().any(char::is_whitespace) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Tags must be a single non-empty word",
+            ));
+        }
+        let mut session = self.load_session_by_id_or_prefix(id_or_prefix)?;
+        let tags = &mut session.metadata.tags;
+        let added = if let Some(pos) = tags.iter().position(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.remove(pos);
+            false
+        } else {
+            tags.push(tag.to_string());
+            true
+        };
+        self.save_session(&session)?;
+        Ok((session.metadata, added))
+    }
+
+    /// Record where the transcript was scrolled, without touching `updated_at`
+    pub fn set_transcript_scroll(&self, id: &str, scroll: TranscriptScroll) -> std::io::Result<()> {
+        let mut session = self.load_session(id)?;
+        if session.metadata.transcript_scroll == scroll {
+            return Ok(());
+        }
+        session.metadata.transcript_scroll = scroll;
+        self.save_session(&session).map(|_| ())
+    }
+
+    fn recovery_path(&self, pid: u32) -> PathBuf {
+        self.sessions_dir
+            .join(format!("{RECOVERY_PREFIX}{pid}{RECOVERY_SUFFIX}"))
+    }
+
+    fn recovery_lock_path(&self, pid: u32) -> PathBuf {
+        self.sessions_dir
+            .join(format!("{RECOVERY_PREFIX}{pid}.lock"))
+    }
+
+    /// Lock this process's `recovery-<pid>.lock` until it exits.
+    fn hold_recovery_lock(&self) -> std::io::Result<()> {
+        let mut locks = RECOVERY_LOCKS
+            .lock()
+            .map_err(|_| std::io::Error::other("recovery lock poisoned"))?;
+        if locks.iter().any(|(dir, _)| *dir == self.sessions_dir) {
+            return Ok(());
+        }
+        let file = fs::File::create(self.recovery_lock_path(std::process::id()))?;
+        file.try_lock().map_err(std::io::Error::from)?;
+        locks.push((self.sessions_dir.clone(), file));
+        Ok(())
+    }
+
+    /// Whether the process that wrote snapshot `pid` still holds its lock.
+    fn recovery_owner_alive(&self, pid: u32) -> bool {
+        let Ok(file) = fs::File::open(self.recovery_lock_path(pid)) else {
+            return false;
+        };
+        matches!(file.try_lock(), Err(fs::TryLockError::WouldBlock))
+    }
+
+    /// Write this process's in-progress snapshot, replacing its previous one
+    /// atomically
+    pub fn save_recovery_snapshot(&self, session: &SavedSession) -> std::io::Result<()> {
+        self.hold_recovery_lock()?;
+        let path = self.recovery_path(std::process::id());
+        let tmp = path.with_extension("snapshot.tmp");
+        let content = serde_json::to_string(&self.for_disk(session))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Remove this process's snapshot once the turn has been saved normally
+    pub fn clear_recovery_snapshot(&self) -> std::io::Result<()> {
+        remove_if_present(&self.recovery_path(std::process::id()))
+    }
+
+    /// Turn snapshots left by unfinished runs into regular saved sessions.
+    ///
+    /// Snapshots of this process and of other instances that are still
+    /// running are left alone. Returns the metadata of the most recent
+    /// recovered session so the caller can offer to resume it; `None` means
+    /// no run died mid-turn. Unreadable snapshots are discarded.
+    pub fn promote_recovery_snapshot(&self) -> std::io::Result<Option<SessionMetadata>> {
+        let mut recovered: Option<SessionMetadata> = None;
+        for entry in fs::read_dir(&self.sessions_dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            // Locks left by instances that exited cleanly
+            if let Some(pid) = name
+                .strip_prefix(RECOVERY_PREFIX)
+                .and_then(|rest| rest.strip_suffix(".lock"))
+                .and_then(|pid| pid.parse::<u32>().ok())
+            {
+                if pid != std::process::id()
+                    && !self.recovery_owner_alive(pid)
+                    && !self.recovery_path(pid).exists()
+                {
+                    remove_if_present(&path)?;
+                }
+                continue;
+            }
+            let owner = if name == LEGACY_RECOVERY_FILE {
+                None
+            } else if let Some(pid) = name
+                .strip_prefix(RECOVERY_PREFIX)
+                .and_then(|rest| rest.strip_suffix(RECOVERY_SUFFIX))
+                .and_then(|pid| pid.parse::<u32>().ok())
+            {
+                Some(pid)
+            } else {
+                continue;
+            };
+            if let Some(pid) = owner
+                && (pid == std::process::id() || self.recovery_owner_alive(pid))
+            {
+                continue;
+            }
+            if let Some(metadata) = self.promote_snapshot_file(&path)?
+                && recovered
+                    .as_ref()
+                    .is_none_or(|latest| metadata.updated_at > latest.updated_at)
+            {
+                recovered = Some(metadata);
+            }
+            if let Some(pid) = owner {
+                remove_if_present(&self.recovery_lock_path(pid))?;
+            }
+        }
+        Ok(recovered)
+    }
+
+    /// Save one abandoned snapshot as a session and delete the snapshot.
+    fn promote_snapshot_file(&self, path: &Path) -> std::io::Result<Option<SessionMetadata>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let Ok(snapshot) = serde_json::from_str::<SavedSession>(&content) else {
+            remove_if_present(path)?;
+            return Ok(None);
+        };
+        // Keep the title and tags of a session that was already saved
+        let session = match self.load_session(&snapshot.metadata.id) {
+            Ok(existing) => {
+                let mut session = update_session(
+                    existing,
+                    &snapshot.messages,
+                    snapshot.metadata.total_tokens,
+                    None,
+                    snapshot.pinned_messages,
+                );
+                session.system_prompt = snapshot.system_prompt.or(session.system_prompt);
+                session
+            }
+            Err(_) => snapshot,
+        };
+        self.save_session(&session)?;
+        remove_if_present(path)?;
+        Ok(Some(session.metadata))
+    }
+
     /// Delete a session by ID
     pub fn delete_session(&self, id: &str) -> std::io::Result<()> {
         let filename = format!("{id}.json");
@@ -201,6 +502,86 @@ impl SessionManager {
             .filter(|s| s.title.to_lowercase().contains(&query_lower))
             .collect())
     }
+
+    /// Search message text and tool output, most recent session first.
+    ///
+    /// Sessions are loaded one at a time as the iterator advances, so taking
+    /// only the first few matches avoids reading every session file.
+    pub fn search_session_content<'a>(
+        &'a self,
+        query: &str,
+    ) -> std::io::Result<impl Iterator<Item = ContentMatch> + 'a> {
+        let query_lower = query.trim().to_lowercase();
+        let sessions = if query_lower.is_empty() {
+            Vec::new()
+        } else {
+            self.list_sessions()?
+        };
+
+        Ok(sessions.into_iter().filter_map(move |metadata| {
+            let session = self.load_session(&metadata.id).ok()?;
+            let snippet = session
+                .messages
+                .iter()
+                .flat_map(|m| &m.content)
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text, .. } => Some(text.as_str()),
+                    ContentBlock::ToolResult { content, .. } => Some(content.as_str()),
+                    _ => None,
+                })
+                .flat_map(str::lines)
+                .find_map(|line| match_snippet(line, &query_lower))?;
+            Some(ContentMatch { metadata, snippet })
+        }))
+    }
+}
+
+/// Delete `path`, treating an already-missing file as success.
+fn remove_if_present(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// The part of `line` around `query_lower`, capped at `SNIPPET_CHARS`
+fn match_snippet(line: &str, query_lower: &str) -> Option<String> {
+    let line = line.trim();
+    // Lowercase char by char, remembering which original char each lowered
+    // char came from, since lowercasing can change the length (`İ` -> `i̇`)
+    let mut lowered = String::with_capacity(line.len());
+    let mut origin = Vec::with_capacity(line.len());
+    for (index, c) in line.chars().enumerate() {
+        for lower in c.to_lowercase() {
+            lowered.push(lower);
+            origin.push(index);
+        }
+    }
+    let byte_pos = lowered.find(query_lower)?;
+
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= SNIPPET_CHARS {
+        return Some(line.to_string());
+    }
+    let lowered_start = lowered[..byte_pos].chars().count();
+    let lowered_end = lowered_start + query_lower.chars().count();
+    let match_start = origin[lowered_start];
+    let query_len = origin[lowered_end - 1] + 1 - match_start;
+    let lead = SNIPPET_CHARS.saturating_sub(query_len) / 2;
+    let start = match_start
+        .saturating_sub(lead)
+        .min(chars.len() - SNIPPET_CHARS);
+    let end = start + SNIPPET_CHARS;
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
 }
 
 /// Create a new `SavedSession` from conversation state
@@ -237,6 +618,8 @@ pub fn create_saved_session(
             total_tokens,
             model: model.to_string(),
             workspace: workspace.to_path_buf(),
+            tags: Vec::new(),
+            transcript_scroll: TranscriptScroll::ToBottom,
         },
         messages: messages.to_vec(),
         system_prompt: system_prompt_to_string(system_prompt),
@@ -261,6 +644,52 @@ pub fn update_session(
     session
 }
 
+/// Copy of a session with secrets replaced by `[REDACTED]` in its title,
+/// message text, tool inputs and results, system prompt, and pins
+#[must_use]
+pub fn redact_session(session: &SavedSession) -> SavedSession {
+    let mut redacted = session.clone();
+    redacted.metadata.title = redact_secrets(&redacted.metadata.title);
+    for message in &mut redacted.messages {
+        for block in &mut message.content {
+            match block {
+                ContentBlock::Text { text, .. } => *text = redact_secrets(text),
+                ContentBlock::Thinking { thinking } => *thinking = redact_secrets(thinking),
+                ContentBlock::ToolUse { input, .. } => redact_json(input),
+                ContentBlock::ToolResult { content, .. } => *content = redact_secrets(content),
+            }
+        }
+    }
+    if let Some(prompt) = redacted.system_prompt.as_mut() {
+        *prompt = redact_secrets(prompt);
+    }
+    for pin in &mut redacted.pinned_messages {
+        pin.content = redact_secrets(&pin.content);
+    }
+    redacted
+}
+
+/// Drop tool calls that never got a result, e.g. because the process died
+/// mid-tool, so the conversation can be sent to the API again. Assistant
+/// messages left empty are removed.
+pub fn drop_unanswered_tool_uses(messages: &mut Vec<Message>) {
+    let answered: std::collections::HashSet<String> = messages
+        .iter()
+        .flat_map(|m| &m.content)
+        .filter_map(|block| match block {
+            ContentBlock::ToolResult { tool_use_id, .. } => Some(tool_use_id.clone()),
+            _ => None,
+        })
+        .collect();
+    for message in messages.iter_mut() {
+        message.content.retain(|block| match block {
+            ContentBlock::ToolUse { id, .. } => answered.contains(id),
+            _ => true,
+        });
+    }
+    messages.retain(|m| !m.content.is_empty());
+}
+
 fn system_prompt_to_string(system_prompt: Option<&SystemPrompt>) -> Option<String> {
     match system_prompt {
         Some(SystemPrompt::Text(text)) => Some(text.clone()),
@@ -293,13 +722,26 @@ pub fn format_session_line(meta: &SessionMetadata) -> String {
     let age = format_age(&meta.updated_at);
     let truncated_title = truncate_title(&meta.title, 40);
 
-    format!(
+    let mut line = format!(
         "{} | {} | {} msgs | {}",
         &meta.id[..8],
         truncated_title,
         meta.message_count,
         age
-    )
+    );
+    if !meta.tags.is_empty() {
+        line.push_str(" | ");
+        line.push_str(&format_tags(&meta.tags));
+    }
+    line
+}
+
+/// Render tags compactly as `#a #b`
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("#{t}"))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Format a datetime as relative age
@@ -346,6 +788,301 @@ mod tests {
         let _ = manager;
     }
 
+    #[test]
+    fn redaction_applies_to_the_saved_copy_only() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions"))
+            .expect("new")
+            .with_redaction(true);
+        let mut messages = vec![make_test_message(
+            "user",
+            "use key sk-api-abcdefghijklmnop1234",
+        )];
+        messages.push(Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: "t1".to_string(),
+                name: "exec_shell".to_string(),
+                input: serde_json::json!({"command": "export API_KEY=abcdefgh12345678"}),
+            }],
+        });
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 0, None, vec![]);
+
+        let path = manager.save_session(&session).expect("save");
+        let on_disk = fs::read_to_string(path).expect("read");
+        assert!(!on_disk.contains("sk-api-abcdefghijklmnop1234"));
+        assert!(!on_disk.contains("abcdefgh12345678"));
+        assert!(on_disk.contains("[REDACTED]"));
+        let in_memory = serde_json::to_string(&session).expect("json");
+        assert!(in_memory.contains("sk-api-abcdefghijklmnop1234"));
+    }
+
+    #[test]
+    fn recovery_snapshot_is_promoted_to_a_saved_session() {
+        let tmp = tempdir().expect("tempdir");
+        let sessions = tmp.path().join("sessions");
+        let manager = SessionManager::new(sessions.clone()).expect("new");
+        assert!(
+            manager
+                .promote_recovery_snapshot()
+                .expect("promote")
+                .is_none()
+        );
+
+        let messages = vec![make_test_message("user", "Unfinished work")];
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 0, None, vec![]);
+        manager.save_recovery_snapshot(&session).expect("snapshot");
+        assert!(manager.list_sessions().expect("list").is_empty());
+        // Our own snapshot belongs to a live run
+        assert!(
+            manager
+                .promote_recovery_snapshot()
+                .expect("promote")
+                .is_none()
+        );
+
+        // Another instance that is still running holds its lock
+        let own = manager.recovery_path(std::process::id());
+        let live_pid = u32::MAX - 1;
+        fs::copy(&own, manager.recovery_path(live_pid)).expect("copy");
+        let live_lock = fs::File::create(manager.recovery_lock_path(live_pid)).expect("lock");
+        live_lock.try_lock().expect("lock");
+        assert!(
+            manager
+                .promote_recovery_snapshot()
+                .expect("promote")
+                .is_none()
+        );
+        drop(live_lock);
+
+        // Once it is gone, its snapshot is recovered
+        let recovered = manager
+            .promote_recovery_snapshot()
+            .expect("promote")
+            .expect("snapshot present");
+        assert_eq!(recovered.id, session.metadata.id);
+        assert_eq!(recovered.title, "Unfinished work");
+        assert_eq!(manager.list_sessions().expect("list").len(), 1);
+        assert!(!manager.recovery_path(live_pid).exists());
+        assert!(!manager.recovery_lock_path(live_pid).exists());
+
+        // A clean exit only clears this process's snapshot
+        fs::copy(&own, sessions.join(LEGACY_RECOVERY_FILE)).expect("copy");
+        manager.clear_recovery_snapshot().expect("clear");
+        manager.clear_recovery_snapshot().expect("clear twice");
+        assert!(!own.exists());
+        assert!(
+            manager
+                .promote_recovery_snapshot()
+                .expect("promote")
+                .is_some()
+        );
+        assert!(
+            manager
+                .promote_recovery_snapshot()
+                .expect("promote")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn unanswered_tool_uses_are_dropped() {
+        let tool_use = |id: &str| ContentBlock::ToolUse {
+            id: id.to_string(),
+            name: "read_file".to_string(),
+            input: serde_json::json!({}),
+        };
+        let mut messages = vec![
+            make_test_message("user", "look around"),
+            Message {
+                role: "assistant".to_string(),
+                content: vec![tool_use("done")],
+            },
+            Message {
+                role: "user".to_string(),
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "done".to_string(),
+                    content: "ok".to_string(),
+                }],
+            },
+            Message {
+                role: "assistant".to_string(),
+                content: vec![tool_use("pending")],
+            },
+        ];
+        drop_unanswered_tool_uses(&mut messages);
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(
+            messages[1].content[0],
+            ContentBlock::ToolUse { ref id, .. } if id == "done"
+        ));
+    }
+
+    #[test]
+    fn test_rename_session_by_prefix() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let messages = vec![make_test_message("user", "Original title")];
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 0, None, vec![]);
+        let id = session.metadata.id.clone();
+        manager.save_session(&session).expect("save");
+
+        let renamed = manager
+            .rename_session(&id[..8], "  Better title ")
+            .expect("rename");
+        assert_eq!(renamed.id, id);
+        assert_eq!(renamed.title, "Better title");
+        assert_eq!(
+            manager.list_sessions().expect("list")[0].title,
+            "Better title"
+        );
+        assert_eq!(manager.load_session(&id).expect("load").messages.len(), 1);
+
+        assert!(manager.rename_session(&id, "   ").is_err());
+        assert!(manager.rename_session("missing", "x").is_err());
+    }
+
+    #[test]
+    fn session_info_reads_metadata_without_messages() {
+        use crate::tui::app::PinSource;
+
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let messages = vec![
+            make_test_message("user", "Inspect me"),
+            make_test_message("assistant", "Sure"),
+        ];
+        let pins = vec![PinnedMessage::new("Sure".to_string(), PinSource::Assistant)];
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 42, None, pins);
+        let id = session.metadata.id.clone();
+        manager.save_session(&session).expect("save");
+
+        let info = manager.session_info(&id[..8]).expect("info");
+        assert_eq!(info.metadata.id, id);
+        assert_eq!(info.metadata.message_count, 2);
+        assert_eq!(info.metadata.total_tokens, 42);
+        assert_eq!(info.pin_count, 1);
+        assert_eq!(
+            info.file_size,
+            fs::metadata(&info.path).expect("stat").len()
+        );
+        assert!(manager.session_info(&id).is_ok());
+        assert!(manager.session_info("missing").is_err());
+    }
+
+    #[test]
+    fn test_toggle_tag_and_format() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let messages = vec![make_test_message("user", "Tag me")];
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 0, None, vec![]);
+        let id = session.metadata.id.clone();
+        manager.save_session(&session).expect("save");
+
+        let (meta, added) = manager.toggle_tag(&id, "#infra").expect("tag");
+        assert!(added);
+        assert!(meta.has_tag("INFRA"));
+        assert!(format_session_line(&meta).ends_with(" | #infra"));
+
+        let (meta, added) = manager.toggle_tag(&id[..8], "Infra").expect("untag");
+        assert!(!added);
+        assert!(meta.tags.is_empty());
+        assert!(manager.toggle_tag(&id, "two words").is_err());
+    }
+
+    #[test]
+    fn test_metadata_without_tags_deserializes() {
+        let json = r#"{"id":"abc","title":"t","created_at":"2025-01-01T00:00:00Z",
+            "updated_at":"2025-01-01T00:00:00Z","message_count":0,"total_tokens":0,
+            "model":"m","workspace":"."}"#;
+        let meta: SessionMetadata = serde_json::from_str(json).expect("deserialize");
+        assert!(meta.tags.is_empty());
+        assert_eq!(meta.transcript_scroll, TranscriptScroll::ToBottom);
+    }
+
+    #[test]
+    fn test_transcript_scroll_round_trips() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let session = create_saved_session(
+            &[make_test_message("user", "Long investigation")],
+            "test-model",
+            tmp.path(),
+            0,
+            None,
+            vec![],
+        );
+        let id = session.metadata.id.clone();
+        manager.save_session(&session).expect("save");
+
+        let anchor = TranscriptScroll::Scrolled {
+            cell_index: 3,
+            line_in_cell: 7,
+        };
+        manager.set_transcript_scroll(&id, anchor).expect("set");
+        let loaded = manager.load_session(&id).expect("load");
+        assert_eq!(loaded.metadata.transcript_scroll, anchor);
+        assert_eq!(loaded.metadata.updated_at, session.metadata.updated_at);
+    }
+
+    #[test]
+    fn test_search_session_content() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = SessionManager::new(tmp.path().join("sessions")).expect("new");
+        let long_line = format!(
+            "{} thread 'main' PANIC AT SHUTDOWN here {}",
+            "x".repeat(100),
+            "y".repeat(100)
+        );
+        let messages = vec![
+            make_test_message("user", "Why does it crash?"),
+            make_test_message("assistant", &format!("Looking at the log:\n{long_line}")),
+        ];
+        let session = create_saved_session(&messages, "test-model", tmp.path(), 0, None, vec![]);
+        manager.save_session(&session).expect("save");
+        let other = create_saved_session(
+            &[make_test_message("user", "Unrelated")],
+            "test-model",
+            tmp.path(),
+            0,
+            None,
+            vec![],
+        );
+        manager.save_session(&other).expect("save");
+
+        let matches: Vec<_> = manager
+            .search_session_content("panic at shutdown")
+            .expect("search")
+            .collect();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].metadata.id, session.metadata.id);
+        let snippet = &matches[0].snippet;
+        assert!(snippet.contains("PANIC AT SHUTDOWN"));
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert_eq!(snippet.chars().count(), SNIPPET_CHARS + 2);
+
+        assert_eq!(
+            manager
+                .search_session_content("   ")
+                .expect("search")
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_match_snippet_maps_position_back_after_lowercasing() {
+        // `İ` lowercases to two chars, which shifted the window off the match
+        let line = format!("{} needle {}", "İ".repeat(100), "z".repeat(100));
+        let snippet = match_snippet(&line, "needle").expect("match");
+        assert!(snippet.contains(" needle "), "{snippet}");
+        assert_eq!(snippet.chars().count(), SNIPPET_CHARS + 2);
+
+        let line = format!("{} İSTANBUL {}", "x".repeat(100), "y".repeat(100));
+        let snippet = match_snippet(&line, &"İstanbul".to_lowercase()).expect("match");
+        assert!(snippet.contains(" İSTANBUL "), "{snippet}");
+    }
+
     #[test]
     fn test_save_and_load_session() {
         let tmp = tempdir().expect("tempdir");
@@ -7,6 +7,10 @@ use std::path::PathBuf;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::tui::paste_burst::{
+    DEFAULT_PASTE_BURST_IDLE_MS, DEFAULT_PASTE_BURST_INTERVAL_MS, DEFAULT_PASTE_BURST_MIN_CHARS,
+};
+
 /// User settings with defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -15,10 +19,16 @@ pub struct Settings {
     pub theme: String,
     /// Auto-compact conversations when they get long
     pub auto_compact: bool,
+    /// Percent of the context window at which auto-compaction kicks in
+    pub auto_compact_threshold: u8,
+    /// Recent user/assistant exchanges compaction never summarizes
+    pub compact_keep_pairs: usize,
     /// Show thinking blocks from the model
     pub show_thinking: bool,
     /// Show detailed tool output
     pub show_tool_details: bool,
+    /// Truncate long tool output in the transcript until expanded
+    pub collapse_tool_output: bool,
     /// Default mode: "normal", "agent", "plan", "yolo", "rlm", "duo"
     pub default_mode: String,
     /// Sidebar width as percentage of terminal width
@@ -33,6 +43,20 @@ pub struct Settings {
     pub default_model: Option<String>,
     /// Show tutorial on first startup
     pub show_tutorial: bool,
+    /// Rows the composer grows to before it scrolls
+    pub max_composer_lines: usize,
+    /// Vim-style transcript navigation (j/k, g/G, Ctrl-d/Ctrl-u, /) on an empty composer
+    pub vim_mode: bool,
+    /// Collapse a batch of sub-agent completions into one summary cell
+    pub aggregate_subagents: bool,
+    /// Fast keystrokes in a row before typed input is treated as a paste
+    pub paste_burst_min_chars: u16,
+    /// Longest gap in milliseconds between keystrokes of one paste burst
+    pub paste_burst_interval_ms: u64,
+    /// Idle milliseconds after which a detected paste is inserted
+    pub paste_burst_idle_ms: u64,
+    /// Replace secrets with `[REDACTED]` in sessions written to disk
+    pub redact_on_save: bool,
 }
 
 impl Default for Settings {
@@ -40,8 +64,11 @@ impl Default for Settings {
         Self {
             theme: "default".to_string(),
             auto_compact: false,
+            auto_compact_threshold: 80,
+            compact_keep_pairs: crate::compaction::DEFAULT_KEEP_PAIRS,
             show_thinking: true,
             show_tool_details: true,
+            collapse_tool_output: true,
             default_mode: "normal".to_string(),
             sidebar_width_percent: 28,
             max_input_history: 100,
@@ -49,6 +76,13 @@ impl Default for Settings {
             input_history_max: 1000,
             default_model: None,
             show_tutorial: true,
+            max_composer_lines: 8,
+            vim_mode: false,
+            aggregate_subagents: false,
+            paste_burst_min_chars: DEFAULT_PASTE_BURST_MIN_CHARS,
+            paste_burst_interval_ms: DEFAULT_PASTE_BURST_INTERVAL_MS,
+            paste_burst_idle_ms: DEFAULT_PASTE_BURST_IDLE_MS,
+            redact_on_save: false,
         }
     }
 }
@@ -71,11 +105,33 @@ impl Settings {
 
         let content = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read settings from {}", path.display()))?;
-        let settings: Settings = toml::from_str(&content)
+        let mut settings: Settings = toml::from_str(&content)
             .with_context(|| format!("Failed to parse settings from {}", path.display()))?;
+        settings.clamp_to_schema();
         Ok(settings)
     }
 
+    /// Pull numeric settings back into their schema range. A hand-edited file
+    /// skips the checks `set` makes.
+    fn clamp_to_schema(&mut self) {
+        for spec in SETTINGS {
+            let SettingKind::Range { min, max } = spec.kind else {
+                continue;
+            };
+            let Some(current) = self.get(spec.key).and_then(|v| v.parse::<usize>().ok()) else {
+                continue;
+            };
+            let clamped = current.clamp(min, max);
+            if clamped != current {
+                crate::logging::warn(format!(
+                    "Setting {} = {current} is outside {min}-{max}; using {clamped}",
+                    spec.key
+                ));
+                let _ = self.set(spec.key, &clamped.to_string());
+            }
+        }
+    }
+
     /// Save settings to disk
     pub fn save(&self) -> Result<()> {
         let path = Self::path()?;
@@ -93,107 +149,165 @@ impl Settings {
         Ok(())
     }
 
-    /// Set a single setting by key
+    /// Set a single setting by key, validating the value against its schema
     pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
-        match key {
-            "theme" => {
-                if !["default", "dark", "light"].contains(&value) {
-                    anyhow::bail!(
-                        "Failed to update setting: invalid theme '{value}'. Expected: default, dark, light."
-                    );
-                }
-                self.theme = value.to_string();
-            }
-            "auto_compact" | "compact" => {
-                self.auto_compact = parse_bool(value)?;
-            }
-            "show_thinking" | "thinking" => {
-                self.show_thinking = parse_bool(value)?;
-            }
-            "show_tool_details" | "tool_details" => {
-                self.show_tool_details = parse_bool(value)?;
-            }
-            "default_mode" | "mode" => {
-                let normalized = if value == "edit" { "normal" } else { value };
-                if !["normal", "agent", "plan", "yolo", "rlm", "duo"].contains(&normalized) {
-                    anyhow::bail!(
-                        "Failed to update setting: invalid mode '{value}'. Expected: normal, agent, plan, yolo, rlm, duo."
-                    );
-                }
-                self.default_mode = normalized.to_string();
-            }
-            "sidebar_width" | "sidebar" => {
-                let width: u16 = value
-                    .parse()
-                    .map_err(|_| {
-                        anyhow::anyhow!(
-                            "Failed to update setting: invalid width '{value}'. Expected a number between 10-50."
-                        )
-                    })?;
-                if !(10..=50).contains(&width) {
-                    anyhow::bail!(
-                        "Failed to update setting: width must be between 10 and 50 percent."
-                    );
-                }
-                self.sidebar_width_percent = width;
-            }
-            "max_history" | "history" => {
-                let max: usize = value.parse().map_err(|_| {
-                    anyhow::anyhow!(
-                        "Failed to update setting: invalid max history '{value}'. Expected a positive number."
-                    )
-                })?;
-                self.max_input_history = max;
-            }
-            "input_history_max" => {
-                let max: usize = value.parse().map_err(|_| {
-                    anyhow::anyhow!(
-                        "Failed to update setting: invalid input history max '{value}'. Expected a positive number."
-                    )
-                })?;
-                self.input_history_max = max;
-            }
-            "input_history_path" => {
-                self.input_history_path = PathBuf::from(value);
-            }
-            "default_model" | "model" => {
-                self.default_model = Some(value.to_string());
-            }
-            "show_tutorial" | "tutorial" => {
-                self.show_tutorial = parse_bool(value)?;
-            }
-            _ => {
-                anyhow::bail!("Failed to update setting: unknown setting '{key}'.");
-            }
+        let Some(spec) = SettingSpec::find(key) else {
+            anyhow::bail!(
+                "Failed to update setting: unknown setting '{key}'. Available: {}.",
+                SETTINGS
+                    .iter()
+                    .map(|s| s.key)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        };
+        let value = spec.validate(value)?;
+
+        match spec.key {
+            "theme" => self.theme = value,
+            "auto_compact" => self.auto_compact = parse_bool(&value)?,
+            "auto_compact_threshold" => self.auto_compact_threshold = value.parse()?,
+            "compact_keep_pairs" => self.compact_keep_pairs = value.parse()?,
+            "show_thinking" => self.show_thinking = parse_bool(&value)?,
+            "show_tool_details" => self.show_tool_details = parse_bool(&value)?,
+            "collapse_tool_output" => self.collapse_tool_output = parse_bool(&value)?,
+            "default_mode" => self.default_mode = value,
+            "sidebar_width" => self.sidebar_width_percent = value.parse()?,
+            "max_history" => self.max_input_history = value.parse()?,
+            "input_history_max" => self.input_history_max = value.parse()?,
+            "input_history_path" => self.input_history_path = PathBuf::from(value),
+            "default_model" => self.default_model = Some(value),
+            "show_tutorial" => self.show_tutorial = parse_bool(&value)?,
+            "max_composer_lines" => self.max_composer_lines = value.parse()?,
+            "vim_mode" => self.vim_mode = parse_bool(&value)?,
+            "aggregate_subagents" => self.aggregate_subagents = parse_bool(&value)?,
+            "paste_burst_min_chars" => self.paste_burst_min_chars = value.parse()?,
+            "paste_burst_interval_ms" => self.paste_burst_interval_ms = value.parse()?,
+            "paste_burst_idle_ms" => self.paste_burst_idle_ms = value.parse()?,
+            "redact_on_save" => self.redact_on_save = parse_bool(&value)?,
+            other => unreachable!("setting '{other}' has a spec but no field"),
         }
         Ok(())
     }
 
+    /// Current value of a setting by key or alias, formatted as `/set` accepts it
+    pub fn get(&self, key: &str) -> Option<String> {
+        let on_off = |b: bool| if b { "on" } else { "off" }.to_string();
+        let value = match SettingSpec::find(key)?.key {
+            "theme" => self.theme.clone(),
+            "auto_compact" => on_off(self.auto_compact),
+            "auto_compact_threshold" => self.auto_compact_threshold.to_string(),
+            "compact_keep_pairs" => self.compact_keep_pairs.to_string(),
+            "show_thinking" => on_off(self.show_thinking),
+            "show_tool_details" => on_off(self.show_tool_details),
+            "collapse_tool_output" => on_off(self.collapse_tool_output),
+            "default_mode" => self.default_mode.clone(),
+            "sidebar_width" => self.sidebar_width_percent.to_string(),
+            "max_history" => self.max_input_history.to_string(),
+            "input_history_max" => self.input_history_max.to_string(),
+            "input_history_path" => self.input_history_path.display().to_string(),
+            "default_model" => self
+                .default_model
+                .clone()
+                .unwrap_or_else(|| "(default)".to_string()),
+            "show_tutorial" => on_off(self.show_tutorial),
+            "max_composer_lines" => self.max_composer_lines.to_string(),
+            "vim_mode" => on_off(self.vim_mode),
+            "aggregate_subagents" => on_off(self.aggregate_subagents),
+            "paste_burst_min_chars" => self.paste_burst_min_chars.to_string(),
+            "paste_burst_interval_ms" => self.paste_burst_interval_ms.to_string(),
+            "paste_burst_idle_ms" => self.paste_burst_idle_ms.to_string(),
+            "redact_on_save" => on_off(self.redact_on_save),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Every setting with its type, default, current value, and description
+    pub fn schema_table(&self) -> String {
+        let defaults = Self::default();
+        let rows: Vec<[String; 5]> = SETTINGS
+            .iter()
+            .map(|spec| {
+                [
+                    spec.key.to_string(),
+                    spec.kind.type_name(),
+                    defaults.get(spec.key).unwrap_or_default(),
+                    self.get(spec.key).unwrap_or_default(),
+                    spec.description.to_string(),
+                ]
+            })
+            .collect();
+
+        let header = ["KEY", "TYPE", "DEFAULT", "CURRENT", "DESCRIPTION"].map(String::from);
+        let mut widths = [0usize; 4];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        std::iter::once(&header)
+            .chain(&rows)
+            .map(|row| {
+                let mut line = String::new();
+                for (width, cell) in widths.iter().zip(row) {
+                    line.push_str(&format!("{cell:<width$}  "));
+                }
+                line.push_str(&row[4]);
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Get all settings as a displayable string
     pub fn display(&self) -> String {
         let mut lines = Vec::new();
         lines.push("Settings:".to_string());
         lines.push("─────────────────────────────".to_string());
-        lines.push(format!("  theme:              {}", self.theme));
-        lines.push(format!("  auto_compact:       {}", self.auto_compact));
-        lines.push(format!("  show_thinking:      {}", self.show_thinking));
-        lines.push(format!("  show_tool_details:  {}", self.show_tool_details));
-        lines.push(format!("  default_mode:       {}", self.default_mode));
-        lines.push(format!(
-            "  sidebar_width:      {}%",
-            self.sidebar_width_percent
-        ));
-        lines.push(format!("  max_history:        {}", self.max_input_history));
-        lines.push(format!(
-            "  input_history_path: {}",
-            self.input_history_path.display()
-        ));
-        lines.push(format!("  input_history_max:  {}", self.input_history_max));
-        lines.push(format!(
-            "  default_model:      {}",
-            self.default_model.as_deref().unwrap_or("(default)")
-        ));
-        lines.push(format!("  show_tutorial:      {}", self.show_tutorial));
+        // Pad every key to the longest one so the values line up
+        let mut row = |key: &str, value: String| {
+            lines.push(format!("  {:<24}{value}", format!("{key}:")));
+        };
+        row("theme", self.theme.clone());
+        row("auto_compact", self.auto_compact.to_string());
+        row(
+            "auto_compact_threshold",
+            format!("{}%", self.auto_compact_threshold),
+        );
+        row("compact_keep_pairs", self.compact_keep_pairs.to_string());
+        row("show_thinking", self.show_thinking.to_string());
+        row("show_tool_details", self.show_tool_details.to_string());
+        row(
+            "collapse_tool_output",
+            self.collapse_tool_output.to_string(),
+        );
+        row("default_mode", self.default_mode.clone());
+        row("sidebar_width", format!("{}%", self.sidebar_width_percent));
+        row("max_history", self.max_input_history.to_string());
+        row(
+            "input_history_path",
+            self.input_history_path.display().to_string(),
+        );
+        row("input_history_max", self.input_history_max.to_string());
+        row(
+            "default_model",
+            self.default_model
+                .clone()
+                .unwrap_or_else(|| "(default)".to_string()),
+        );
+        row("show_tutorial", self.show_tutorial.to_string());
+        row("max_composer_lines", self.max_composer_lines.to_string());
+        row("vim_mode", self.vim_mode.to_string());
+        row("aggregate_subagents", self.aggregate_subagents.to_string());
+        row(
+            "paste_burst",
+            format!(
+                "{} chars, {}ms apart, {}ms idle",
+                self.paste_burst_min_chars, self.paste_burst_interval_ms, self.paste_burst_idle_ms
+            ),
+        );
+        row("redact_on_save", self.redact_on_save.to_string());
         lines.push(String::new());
         lines.push(format!(
             "Config file: {}",
@@ -203,23 +317,250 @@ impl Settings {
     }
 
     /// Get available setting keys and their descriptions
-    pub fn available_settings() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("theme", "Color theme: default, dark, light"),
-            ("auto_compact", "Auto-compact conversations: on/off"),
-            ("show_thinking", "Show model thinking: on/off"),
-            ("show_tool_details", "Show detailed tool output: on/off"),
-            (
-                "default_mode",
-                "Default mode: normal, agent, plan, yolo, rlm, duo",
-            ),
-            ("sidebar_width", "Sidebar width percentage: 10-50"),
-            ("max_history", "Max input history entries"),
-            ("input_history_path", "Path to input history file"),
-            ("input_history_max", "Max input history entries to persist"),
-            ("default_model", "Default model name"),
-            ("show_tutorial", "Show tutorial on startup: on/off"),
-        ]
+    pub fn available_settings() -> Vec<(&'static str, String)> {
+        SETTINGS
+            .iter()
+            .map(|spec| {
+                (
+                    spec.key,
+                    format!("{}: {}", spec.description, spec.kind.hint()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The type and allowed values of a setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Bool,
+    Choice(&'static [&'static str]),
+    Range { min: usize, max: usize },
+    Text,
+}
+
+impl SettingKind {
+    /// Type with its constraint, e.g. `int 10-50` or `choice default|dark|light`
+    pub fn type_name(self) -> String {
+        match self {
+            Self::Bool => "bool".to_string(),
+            Self::Choice(choices) => format!("choice {}", choices.join("|")),
+            Self::Range { min, max } => format!("int {min}-{max}"),
+            Self::Text => "string".to_string(),
+        }
+    }
+
+    /// Short description of accepted values, e.g. `on/off` or `10-50`
+    pub fn hint(self) -> String {
+        match self {
+            Self::Bool => "on/off".to_string(),
+            Self::Choice(choices) => choices.join(", "),
+            Self::Range { min, max } => format!("{min}-{max}"),
+            Self::Text => "any text".to_string(),
+        }
+    }
+}
+
+/// Schema entry for a settable key
+#[derive(Debug, Clone, Copy)]
+pub struct SettingSpec {
+    pub key: &'static str,
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    pub kind: SettingKind,
+}
+
+/// Every key `/set` can persist, with its accepted values
+pub const SETTINGS: &[SettingSpec] = &[
+    SettingSpec {
+        key: "theme",
+        aliases: &[],
+        description: "Color theme",
+        kind: SettingKind::Choice(crate::palette::THEME_NAMES),
+    },
+    SettingSpec {
+        key: "auto_compact",
+        aliases: &["compact"],
+        description: "Auto-compact conversations",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "auto_compact_threshold",
+        aliases: &["compact_threshold"],
+        description: "Context window percent that triggers auto-compact",
+        kind: SettingKind::Range { min: 10, max: 100 },
+    },
+    SettingSpec {
+        key: "compact_keep_pairs",
+        aliases: &["keep_pairs"],
+        description: "Recent exchanges compaction keeps verbatim",
+        kind: SettingKind::Range { min: 1, max: 20 },
+    },
+    SettingSpec {
+        key: "show_thinking",
+        aliases: &["thinking"],
+        description: "Show model thinking",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "show_tool_details",
+        aliases: &["tool_details"],
+        description: "Show detailed tool output",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "collapse_tool_output",
+        aliases: &["collapse"],
+        description: "Truncate long tool output until expanded",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "default_mode",
+        aliases: &["mode"],
+        description: "Default mode",
+        kind: SettingKind::Choice(&["normal", "agent", "plan", "yolo", "rlm", "duo"]),
+    },
+    SettingSpec {
+        key: "sidebar_width",
+        aliases: &["sidebar"],
+        description: "Sidebar width percentage",
+        kind: SettingKind::Range { min: 10, max: 50 },
+    },
+    SettingSpec {
+        key: "max_history",
+        aliases: &["history"],
+        description: "Max input history entries",
+        kind: SettingKind::Range {
+            min: 1,
+            max: 10_000,
+        },
+    },
+    SettingSpec {
+        key: "input_history_path",
+        aliases: &[],
+        description: "Path to input history file",
+        kind: SettingKind::Text,
+    },
+    SettingSpec {
+        key: "input_history_max",
+        aliases: &[],
+        description: "Max input history entries to persist",
+        kind: SettingKind::Range {
+            min: 1,
+            max: 100_000,
+        },
+    },
+    SettingSpec {
+        key: "default_model",
+        aliases: &["model"],
+        description: "Default model name",
+        kind: SettingKind::Text,
+    },
+    SettingSpec {
+        key: "show_tutorial",
+        aliases: &["tutorial"],
+        description: "Show tutorial on startup",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "max_composer_lines",
+        aliases: &["composer_lines"],
+        description: "Max input box height before it scrolls",
+        kind: SettingKind::Range { min: 1, max: 50 },
+    },
+    SettingSpec {
+        key: "vim_mode",
+        aliases: &["vim"],
+        description: "Vim-style transcript keys on an empty composer",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "aggregate_subagents",
+        aliases: &["aggregate"],
+        description: "Summarize finished sub-agents in one cell",
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "paste_burst_min_chars",
+        aliases: &[],
+        description: "Fast keystrokes before typing is treated as a paste",
+        kind: SettingKind::Range { min: 2, max: 64 },
+    },
+    SettingSpec {
+        key: "paste_burst_interval_ms",
+        aliases: &[],
+        description: "Max ms between keystrokes of a paste burst",
+        kind: SettingKind::Range { min: 1, max: 200 },
+    },
+    SettingSpec {
+        key: "paste_burst_idle_ms",
+        aliases: &[],
+        description: "Idle ms before a detected paste is inserted",
+        kind: SettingKind::Range { min: 1, max: 1000 },
+    },
+    SettingSpec {
+        key: "redact_on_save",
+        aliases: &["redact"],
+        description: "Redact secrets from saved sessions",
+        kind: SettingKind::Bool,
+    },
+];
+
+impl SettingSpec {
+    /// Look up a spec by key or alias
+    pub fn find(key: &str) -> Option<&'static SettingSpec> {
+        SETTINGS
+            .iter()
+            .find(|spec| spec.key == key || spec.aliases.contains(&key))
+    }
+
+    /// Check `value` against this setting's kind, returning the normalized value
+    pub fn validate(&self, value: &str) -> Result<String> {
+        self.kind.validate(self.key, value)
+    }
+}
+
+impl SettingKind {
+    /// Check `value` for the setting `key`, returning the normalized value
+    pub fn validate(self, key: &str, value: &str) -> Result<String> {
+        let value = value.trim();
+        match self {
+            Self::Bool => parse_bool(value)
+                .map(|b| if b { "on" } else { "off" }.to_string())
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "Failed to update setting: invalid {key} '{value}'. Expected on/off, true/false, yes/no."
+                    )
+                }),
+            Self::Choice(choices) => {
+                // `edit` was the old name for normal mode.
+                let value = if key == "default_mode" && value == "edit" {
+                    "normal".to_string()
+                } else {
+                    value.to_lowercase()
+                };
+                if choices.contains(&value.as_str()) {
+                    Ok(value)
+                } else {
+                    anyhow::bail!(
+                        "Failed to update setting: invalid {key} '{value}'. Expected one of: {}.",
+                        choices.join(", ")
+                    )
+                }
+            }
+            Self::Range { min, max } => match value.parse::<usize>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(n.to_string()),
+                _ => anyhow::bail!(
+                    "Failed to update setting: invalid {key} '{value}'. Expected a number between {min} and {max}."
+                ),
+            },
+            Self::Text => {
+                if value.is_empty() {
+                    anyhow::bail!("Failed to update setting: {key} cannot be empty.");
+                }
+                Ok(value.to_string())
+            }
+        }
     }
 }
 
@@ -231,7 +572,7 @@ fn default_input_history_path() -> PathBuf {
 }
 
 /// Parse a boolean value from various formats
-fn parse_bool(value: &str) -> Result<bool> {
+pub(crate) fn parse_bool(value: &str) -> Result<bool> {
     match value.to_lowercase().as_str() {
         "on" | "true" | "yes" | "1" | "enabled" => Ok(true),
         "off" | "false" | "no" | "0" | "disabled" => Ok(false),
@@ -240,3 +581,87 @@ fn parse_bool(value: &str) -> Result<bool> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_aligns_every_value() {
+        let display = Settings::default().display();
+        let columns: Vec<usize> = display
+            .lines()
+            .filter(|line| line.starts_with("  "))
+            .map(|line| line.len() - line[2..].split_once(':').unwrap().1.trim_start().len())
+            .collect();
+        assert!(columns.len() > 10);
+        assert!(
+            columns.iter().all(|&column| column == columns[0]),
+            "{display}"
+        );
+    }
+
+    #[test]
+    fn clamp_to_schema_fixes_hand_edited_values() {
+        let mut settings = Settings {
+            compact_keep_pairs: 0,
+            sidebar_width_percent: 90,
+            ..Settings::default()
+        };
+        settings.clamp_to_schema();
+        assert_eq!(settings.compact_keep_pairs, 1);
+        assert_eq!(settings.sidebar_width_percent, 50);
+    }
+
+    #[test]
+    fn set_rejects_out_of_schema_values() {
+        let mut settings = Settings::default();
+        let err = settings.set("sidebar_width", "80").unwrap_err().to_string();
+        assert!(err.contains("between 10 and 50"), "{err}");
+        let err = settings.set("theme", "neon").unwrap_err().to_string();
+        assert!(err.contains("default, dark, light"), "{err}");
+        assert!(settings.set("compact", "maybe").is_err());
+        assert!(settings.set("max_history", "0").is_err());
+        let err = settings.set("nope", "1").unwrap_err().to_string();
+        assert!(err.contains("Available: theme"), "{err}");
+        assert_eq!(settings.sidebar_width_percent, 28);
+    }
+
+    #[test]
+    fn schema_covers_every_setting() {
+        let settings = Settings::default();
+        for spec in SETTINGS {
+            let current = settings.get(spec.key).expect("every spec has a value");
+            if spec.kind != SettingKind::Text {
+                assert!(spec.validate(&current).is_ok(), "{}", spec.key);
+            }
+        }
+        let table = settings.schema_table();
+        assert!(table.starts_with("KEY"));
+        assert!(table.contains("sidebar_width"));
+        assert!(table.contains("int 10-50"));
+        assert_eq!(table.lines().count(), SETTINGS.len() + 1);
+    }
+
+    #[test]
+    fn set_normalizes_and_applies_aliases() {
+        let mut settings = Settings::default();
+        settings.set("mode", "edit").unwrap();
+        assert_eq!(settings.default_mode, "normal");
+        settings.set("theme", "Dark").unwrap();
+        assert_eq!(settings.theme, "dark");
+        settings.set("compact", "yes").unwrap();
+        assert!(settings.auto_compact);
+        settings.set("sidebar", " 30 ").unwrap();
+        assert_eq!(settings.sidebar_width_percent, 30);
+        settings.set("paste_burst_idle_ms", "40").unwrap();
+        assert_eq!(settings.paste_burst_idle_ms, 40);
+        assert!(settings.set("paste_burst_min_chars", "1").is_err());
+        settings.set("compact_threshold", "65").unwrap();
+        assert_eq!(settings.auto_compact_threshold, 65);
+        assert!(settings.set("auto_compact_threshold", "5").is_err());
+        settings.set("keep_pairs", "5").unwrap();
+        assert_eq!(settings.compact_keep_pairs, 5);
+        assert!(settings.set("compact_keep_pairs", "0").is_err());
+    }
+}
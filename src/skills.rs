@@ -2,6 +2,7 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Result};
 
@@ -16,14 +17,171 @@ pub fn default_skills_dir() -> PathBuf {
     )
 }
 
+/// Skill search path, lowest precedence first: the global skills directory,
+/// then the workspace `skills/` folder. Later entries win on name conflicts.
+#[must_use]
+pub fn skill_search_path(workspace: &Path, global: PathBuf) -> Vec<(SkillSource, PathBuf)> {
+    let local = workspace.join("skills");
+    let same_dir = match (local.canonicalize(), global.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => local == global,
+    };
+    let mut path = vec![(SkillSource::Global, global)];
+    if !same_dir {
+        path.push((SkillSource::Workspace, local));
+    }
+    path
+}
+
 // === Types ===
 
+/// Which directory on the search path a skill came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillSource {
+    Global,
+    Workspace,
+}
+
+impl SkillSource {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Workspace => "workspace",
+        }
+    }
+}
+
 /// Parsed representation of a SKILL.md definition.
 #[derive(Debug, Clone)]
 pub struct Skill {
     pub name: String,
     pub description: String,
     pub body: String,
+    /// Path of the SKILL.md the skill was loaded from
+    pub path: PathBuf,
+    pub source: SkillSource,
+    /// A lower-precedence skill with the same name was replaced by this one
+    pub overrides: bool,
+    /// Parameters declared in front-matter, substituted into `{{name}}` placeholders
+    pub params: Vec<SkillParam>,
+    /// Tools the agent may use while the skill is active (empty: no restriction)
+    pub tools: Vec<String>,
+}
+
+/// A skill parameter; parameters without a default must be passed at activation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillParam {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+impl Skill {
+    /// Usage line for `/skill`, e.g. `/skill write-tests framework=<value> [style=unit]`.
+    #[must_use]
+    pub fn usage(&self) -> String {
+        let mut usage = format!("/skill {}", self.name);
+        for param in &self.params {
+            match param.default.as_deref() {
+                Some(default) => usage.push_str(&format!(" [{}={default}]", param.name)),
+                None => usage.push_str(&format!(" {}=<value>", param.name)),
+            }
+        }
+        usage
+    }
+
+    /// Substitute `key=value` arguments (falling back to defaults) into the
+    /// instructions. Unknown keys and missing required parameters are errors.
+    pub fn render(&self, args: &[(String, String)]) -> Result<String> {
+        if let Some((key, _)) = args
+            .iter()
+            .find(|(key, _)| !self.params.iter().any(|p| &p.name == key))
+        {
+            anyhow::bail!(
+                "Failed to activate skill '{}': unknown parameter '{key}'. Usage: {}",
+                self.name,
+                self.usage()
+            );
+        }
+
+        let mut body = self.body.clone();
+        let mut missing = Vec::new();
+        for param in &self.params {
+            let value = args
+                .iter()
+                .rev()
+                .find(|(key, _)| *key == param.name)
+                .map(|(_, value)| value.as_str())
+                .or(param.default.as_deref());
+            match value {
+                Some(value) => body = body.replace(&format!("{{{{{}}}}}", param.name), value),
+                None => missing.push(param.name.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Failed to activate skill '{}': missing required parameter(s) {}. Usage: {}",
+                self.name,
+                missing.join(", "),
+                self.usage()
+            );
+        }
+        Ok(body)
+    }
+}
+
+/// Split `/skill` arguments into `key=value` pairs. Values may be quoted.
+pub fn parse_skill_args(input: &str) -> Result<Vec<(String, String)>> {
+    let words = shlex::split(input).with_context(|| {
+        format!("Failed to parse skill arguments: unbalanced quotes in '{input}'")
+    })?;
+    words
+        .into_iter()
+        .map(|word| match word.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => anyhow::bail!("Failed to parse skill argument '{word}': expected key=value"),
+        })
+        .collect()
+}
+
+/// Parse a `params:` front-matter value: `framework, style=unit`.
+fn parse_params(value: &str) -> Vec<SkillParam> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.split_once('=') {
+            Some((name, default)) => SkillParam {
+                name: name.trim().to_string(),
+                default: Some(default.trim().to_string()),
+            },
+            None => SkillParam {
+                name: item.to_string(),
+                default: None,
+            },
+        })
+        .collect()
+}
+
+/// Names used as `{{name}}` placeholders in skill instructions.
+fn placeholders(body: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("}}") else { break };
+        let name = rest[..end].trim();
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+        rest = &rest[end + 2..];
+    }
+    names
 }
 
 /// Collection of discovered skills.
@@ -33,6 +191,26 @@ pub struct SkillRegistry {
 }
 
 impl SkillRegistry {
+    /// Discover skills along a search path. A skill in a later directory
+    /// replaces one with the same name from an earlier directory.
+    #[must_use]
+    pub fn discover_path(search_path: &[(SkillSource, PathBuf)]) -> Self {
+        let mut registry = Self::default();
+        for (source, dir) in search_path {
+            for mut skill in Self::discover(dir).skills {
+                skill.source = *source;
+                if let Some(existing) = registry.skills.iter_mut().find(|s| s.name == skill.name) {
+                    skill.overrides = true;
+                    *existing = skill;
+                } else {
+                    registry.skills.push(skill);
+                }
+            }
+        }
+        registry.skills.sort_by(|a, b| a.name.cmp(&b.name));
+        registry
+    }
+
     /// Discover skills from the given directory.
     #[must_use]
     pub fn discover(dir: &Path) -> Self {
@@ -58,37 +236,81 @@ impl SkillRegistry {
         registry
     }
 
-    fn parse_skill(_path: &Path, content: &str) -> Option<Skill> {
-        let trimmed = content.trim_start();
-        let (frontmatter, body) = if trimmed.starts_with("---") {
+    /// Parse a SKILL.md. Without front-matter (or without a `name`), the whole
+    /// file is the instructions, named after its directory.
+    fn parse_skill(path: &Path, content: &str) -> Option<Skill> {
+        let parts = Self::split_loose_frontmatter(content).and_then(|(frontmatter, body)| {
+            frontmatter_field(frontmatter, "name").map(|name| (name, frontmatter, body))
+        });
+        let Some((name, frontmatter, body)) = parts else {
+            return Self::parse_plain_skill(path, content);
+        };
+        let description = frontmatter_field(frontmatter, "description").unwrap_or_default();
+        let body = body.trim().to_string();
+        let mut params = frontmatter_field(frontmatter, "params")
+            .map(|value| parse_params(&value))
+            .unwrap_or_default();
+        // Placeholders missing from `params:` are still required parameters.
+        for name in placeholders(&body) {
+            if !params.iter().any(|p| p.name == name) {
+                params.push(SkillParam {
+                    name: name.to_string(),
+                    default: None,
+                });
+            }
+        }
+        let tools = frontmatter_list(frontmatter, "tools")
+            .or_else(|| frontmatter_list(frontmatter, "allowed-tools"))
+            .unwrap_or_default();
+
+        Some(Skill {
+            name,
+            description,
+            body,
+            path: path.to_path_buf(),
+            source: SkillSource::Global,
+            overrides: false,
+            params,
+            tools,
+        })
+    }
+
+    /// Front-matter fenced by `---` lines, or everything before the first
+    /// `---` for older files that omit the opening fence.
+    fn split_loose_frontmatter(content: &str) -> Option<(&str, &str)> {
+        if content.trim_start().starts_with("---") {
             let start = content.find("---")?;
             let rest = &content[start + 3..];
             let end = rest.find("---")?;
-            (&rest[..end], &rest[end + 3..])
+            Some((&rest[..end], &rest[end + 3..]))
         } else {
             let frontmatter_end = content.find("---")?;
-            (&content[..frontmatter_end], &content[frontmatter_end + 3..])
-        };
-        let name = frontmatter
-            .lines()
-            .find(|l| l.starts_with("name:"))
-            .and_then(|l| l.split(':').nth(1))?
-            .trim()
-            .to_string();
+            Some((&content[..frontmatter_end], &content[frontmatter_end + 3..]))
+        }
+    }
 
-        let description = frontmatter
+    /// A freeform SKILL.md: described by its first line, e.g. a `# Title`.
+    fn parse_plain_skill(path: &Path, content: &str) -> Option<Skill> {
+        let name = path.parent()?.file_name()?.to_str()?.to_string();
+        let body = content.trim().to_string();
+        if body.is_empty() {
+            return None;
+        }
+        let description = body
             .lines()
-            .find(|l| l.starts_with("description:"))
-            .and_then(|l| l.split(':').nth(1))
-            .map(|s| s.trim().to_string())
-            .unwrap_or_default();
-
-        let body = body.trim().to_string();
-
+            .map(|line| line.trim_start_matches('#').trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string();
         Some(Skill {
             name,
             description,
             body,
+            path: path.to_path_buf(),
+            source: SkillSource::Global,
+            overrides: false,
+            params: Vec::new(),
+            tools: Vec::new(),
         })
     }
 
@@ -113,6 +335,304 @@ impl SkillRegistry {
     pub fn len(&self) -> usize {
         self.skills.len()
     }
+
+    /// Compare against a freshly discovered registry, by skill name.
+    #[must_use]
+    pub fn diff(&self, newer: &Self) -> SkillChanges {
+        let mut changes = SkillChanges::default();
+        for skill in &newer.skills {
+            match self.get(&skill.name) {
+                None => changes.added.push(skill.name.clone()),
+                Some(old)
+                    if old.description != skill.description
+                        || old.body != skill.body
+                        || old.params != skill.params
+                        || old.tools != skill.tools
+                        || old.path != skill.path =>
+                {
+                    changes.changed.push(skill.name.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = self
+            .skills
+            .iter()
+            .filter(|skill| newer.get(&skill.name).is_none())
+            .map(|skill| skill.name.clone())
+            .collect();
+        changes
+    }
+}
+
+/// Skills added, removed, or edited between two scans.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl SkillChanges {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One line per non-empty category, e.g. `  added: lint, review`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("changed", &self.changed),
+        ]
+        .iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(label, names)| format!("  {label}: {}", names.join(", ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+// === Watching ===
+
+/// How often the TUI checks skill directories for edits.
+pub const SKILL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Every SKILL.md on a search path with its modification time.
+type SkillFingerprint = Vec<(PathBuf, Option<SystemTime>)>;
+
+fn fingerprint(search_path: &[(SkillSource, PathBuf)]) -> SkillFingerprint {
+    let mut files = Vec::new();
+    for (_, dir) in search_path {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path().join("SKILL.md");
+            if let Ok(meta) = fs::metadata(&path) {
+                files.push((path, meta.modified().ok()));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Polls skill directories and reports when SKILL.md files changed. A change
+/// is only reported once the files look the same on two consecutive polls, so
+/// an editor's save-in-several-writes reloads once.
+#[derive(Debug, Default)]
+pub struct SkillWatcher {
+    loaded: SkillFingerprint,
+    pending: Option<SkillFingerprint>,
+    last_poll: Option<Instant>,
+}
+
+impl SkillWatcher {
+    #[must_use]
+    pub fn new(search_path: &[(SkillSource, PathBuf)]) -> Self {
+        Self {
+            loaded: fingerprint(search_path),
+            ..Self::default()
+        }
+    }
+
+    /// Record the current files as loaded, e.g. after `/skills reload`.
+    pub fn reset(&mut self, search_path: &[(SkillSource, PathBuf)]) {
+        self.loaded = fingerprint(search_path);
+        self.pending = None;
+    }
+
+    /// Returns true when the skills should be reloaded.
+    pub fn poll(&mut self, search_path: &[(SkillSource, PathBuf)], now: Instant) -> bool {
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < SKILL_POLL_INTERVAL)
+        {
+            return false;
+        }
+        self.last_poll = Some(now);
+        let current = fingerprint(search_path);
+        if current == self.loaded {
+            self.pending = None;
+            false
+        } else if self.pending.as_ref() == Some(&current) {
+            self.pending = None;
+            self.loaded = current;
+            true
+        } else {
+            self.pending = Some(current);
+            false
+        }
+    }
+}
+
+/// A list in front-matter: `key: [a, b]`, `key: a, b`, or a `- item` block
+/// on the following lines.
+fn frontmatter_list(frontmatter: &str, key: &str) -> Option<Vec<String>> {
+    let value = frontmatter_field(frontmatter, key)?;
+    let items: Vec<String> = if value.is_empty() {
+        frontmatter
+            .lines()
+            .skip_while(|line| line.split_once(':').is_none_or(|(k, _)| k.trim() != key))
+            .skip(1)
+            .map_while(|line| line.trim().strip_prefix('-').map(str::to_string))
+            .collect()
+    } else {
+        value
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(str::to_string)
+            .collect()
+    };
+    Some(
+        items
+            .iter()
+            .map(|item| {
+                item.trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string()
+            })
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+/// Value of `key: value` in front-matter. Only the first colon separates, so
+/// descriptions may contain colons.
+fn frontmatter_field(frontmatter: &str, key: &str) -> Option<String> {
+    frontmatter.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+// === Validation ===
+
+/// A problem found in a skill directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillIssue {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+/// Result of validating every skill under a skills directory.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    /// Skill directories checked
+    pub checked: usize,
+    pub issues: Vec<SkillIssue>,
+}
+
+/// Check each `<dir>/<skill>/SKILL.md`: front-matter fences, required `name`
+/// and `description`, a non-empty body, and unique names.
+pub fn validate_dir(dir: &Path) -> Result<ValidationReport> {
+    let mut skill_dirs = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Failed to read skills directory {}", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            skill_dirs.push(entry.path());
+        }
+    }
+    skill_dirs.sort();
+
+    let mut report = ValidationReport {
+        checked: skill_dirs.len(),
+        issues: Vec::new(),
+    };
+    let mut seen: Vec<(String, PathBuf)> = Vec::new();
+    for skill_dir in skill_dirs {
+        let path = skill_dir.join("SKILL.md");
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.issues.push(SkillIssue {
+                    path,
+                    message: format!("cannot read SKILL.md: {e}"),
+                });
+                continue;
+            }
+        };
+        let problems = validate_skill(&content);
+        if problems.is_empty()
+            && let Some(name) = split_frontmatter(&content)
+                .ok()
+                .and_then(|(frontmatter, _)| frontmatter_field(frontmatter, "name"))
+        {
+            if let Some((_, first)) = seen.iter().find(|(n, _)| *n == name) {
+                report.issues.push(SkillIssue {
+                    path: path.clone(),
+                    message: format!("duplicate name '{name}' (also in {})", first.display()),
+                });
+            } else {
+                seen.push((name, path.clone()));
+            }
+        }
+        report
+            .issues
+            .extend(problems.into_iter().map(|message| SkillIssue {
+                path: path.clone(),
+                message,
+            }));
+    }
+    Ok(report)
+}
+
+/// Problems with a single SKILL.md's contents (empty when valid).
+pub fn validate_skill(content: &str) -> Vec<String> {
+    let (frontmatter, body) = match split_frontmatter(content) {
+        Ok(parts) => parts,
+        Err(message) => return vec![message.to_string()],
+    };
+
+    let mut problems = Vec::new();
+    match frontmatter_field(frontmatter, "name") {
+        None => problems.push("missing required `name` field".to_string()),
+        Some(name) if name.is_empty() => problems.push("`name` is empty".to_string()),
+        Some(name) if name.contains(char::is_whitespace) => problems.push(format!(
+            "`name` '{name}' contains whitespace; /skill <name> can't select it"
+        )),
+        Some(_) => {}
+    }
+    match frontmatter_field(frontmatter, "description") {
+        None => problems.push("missing required `description` field".to_string()),
+        Some(description) if description.is_empty() => {
+            problems.push("`description` is empty".to_string());
+        }
+        Some(_) => {}
+    }
+    if body.trim().is_empty() {
+        problems.push("no instructions after the front-matter".to_string());
+    }
+    let params = frontmatter_field(frontmatter, "params")
+        .map(|value| parse_params(&value))
+        .unwrap_or_default();
+    for name in placeholders(body) {
+        if !params.iter().any(|p| p.name == name) {
+            problems.push(format!(
+                "`{{{{{name}}}}}` is used in the instructions but not declared in `params`"
+            ));
+        }
+    }
+    problems
+}
+
+/// Split strict `---` fenced front-matter from the body.
+fn split_frontmatter(content: &str) -> std::result::Result<(&str, &str), &'static str> {
+    let rest = content
+        .trim_start()
+        .strip_prefix("---")
+        .filter(|rest| rest.starts_with('\n') || rest.starts_with("\r\n"))
+        .ok_or("front-matter must start with a `---` line")?;
+    let end = rest
+        .find("\n---")
+        .ok_or("front-matter is missing its closing `---` line")?;
+    Ok((&rest[..end], &rest[end + 4..]))
 }
 
 // === Inline Skill Parsing ===
@@ -165,33 +685,51 @@ pub fn is_inline_skill_prefix(input: &str) -> bool {
 
 // === CLI Helpers ===
 
-#[allow(dead_code)] // CLI utility for future use
-pub fn list(skills_dir: &Path) -> Result<()> {
-    if !skills_dir.exists() {
-        println!("No skills directory found at {}", skills_dir.display());
-        return Ok(());
-    }
-
-    let mut entries = Vec::new();
-    for entry in fs::read_dir(skills_dir)? {
-        let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            entries.push(entry.file_name().to_string_lossy().to_string());
+/// Print each skill's name, source, description, and directory.
+pub fn list(search_path: &[(SkillSource, PathBuf)]) -> Result<()> {
+    let registry = SkillRegistry::discover_path(search_path);
+    if registry.is_empty() {
+        println!("No skills found. Searched:");
+        for (source, dir) in search_path {
+            println!("  {} ({})", dir.display(), source.label());
         }
-    }
-
-    if entries.is_empty() {
-        println!("No skills found in {}", skills_dir.display());
         return Ok(());
     }
 
-    entries.sort();
-    for entry in entries {
-        println!("{entry}");
+    let name_width = registry
+        .list()
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0);
+    for skill in registry.list() {
+        let dir = skill.path.parent().unwrap_or(&skill.path);
+        // The source only means something when more than one dir was searched
+        let origin = if search_path.len() > 1 {
+            format!("[{}]  ", skill_origin(skill))
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<name_width$}  {origin}{}  ({})",
+            skill.name,
+            skill.description,
+            dir.display()
+        );
     }
     Ok(())
 }
 
+/// Source label, noting when the skill replaces one from a lower-precedence dir
+#[must_use]
+pub fn skill_origin(skill: &Skill) -> String {
+    if skill.overrides {
+        format!("{}, overrides global", skill.source.label())
+    } else {
+        skill.source.label().to_string()
+    }
+}
+
 #[allow(dead_code)] // CLI utility for future use
 pub fn show(skills_dir: &Path, name: &str) -> Result<()> {
     let path = skills_dir.join(name).join("SKILL.md");
@@ -200,3 +738,260 @@ pub fn show(skills_dir: &Path, name: &str) -> Result<()> {
     println!("{contents}");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const VALID: &str =
+        "---\nname: asset-kit\ndescription: Game assets: icons and music\n---\nDo the thing.\n";
+
+    fn write_skill(root: &Path, dir: &str, content: &str) {
+        let skill_dir = root.join(dir);
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+        fs::write(skill_dir.join("SKILL.md"), content).expect("write");
+    }
+
+    #[test]
+    fn workspace_skills_override_global_ones() {
+        let global = tempdir().expect("tempdir");
+        let workspace = tempdir().expect("tempdir");
+        let local = workspace.path().join("skills");
+        write_skill(global.path(), "shared", VALID);
+        write_skill(
+            global.path(),
+            "only-global",
+            "---\nname: only-global\ndescription: g\n---\nbody\n",
+        );
+        write_skill(
+            &local,
+            "shared",
+            "---\nname: asset-kit\ndescription: local version\n---\nbody\n",
+        );
+
+        let search_path = skill_search_path(workspace.path(), global.path().to_path_buf());
+        assert_eq!(search_path.len(), 2);
+        let registry = SkillRegistry::discover_path(&search_path);
+        assert_eq!(registry.len(), 2);
+
+        let shared = registry.get("asset-kit").expect("shared");
+        assert_eq!(shared.description, "local version");
+        assert_eq!(shared.source, SkillSource::Workspace);
+        assert_eq!(skill_origin(shared), "workspace, overrides global");
+
+        let global_only = registry.get("only-global").expect("global");
+        assert_eq!(global_only.source, SkillSource::Global);
+        assert!(!global_only.overrides);
+    }
+
+    #[test]
+    fn search_path_skips_workspace_when_it_is_the_global_dir() {
+        let workspace = tempdir().expect("tempdir");
+        let local = workspace.path().join("skills");
+        fs::create_dir_all(&local).expect("mkdir");
+        let search_path = skill_search_path(workspace.path(), local);
+        assert_eq!(search_path.len(), 1);
+    }
+
+    #[test]
+    fn description_keeps_text_after_colons() {
+        let skill = SkillRegistry::parse_skill(Path::new("SKILL.md"), VALID).expect("skill");
+        assert_eq!(skill.name, "asset-kit");
+        assert_eq!(skill.description, "Game assets: icons and music");
+    }
+
+    #[test]
+    fn validate_skill_reports_each_problem() {
+        assert!(validate_skill(VALID).is_empty());
+        assert_eq!(
+            validate_skill("name: x\n---\nbody"),
+            vec!["front-matter must start with a `---` line"]
+        );
+        assert_eq!(
+            validate_skill("---\nname: x\n"),
+            vec!["front-matter is missing its closing `---` line"]
+        );
+        let problems = validate_skill("---\nname: my skill\n---\n\n");
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("contains whitespace"));
+        assert!(problems[1].contains("`description`"));
+        assert!(problems[2].contains("no instructions"));
+    }
+
+    #[test]
+    fn validate_dir_flags_missing_files_and_duplicates() {
+        let tmp = tempdir().expect("tempdir");
+        write_skill(tmp.path(), "a", VALID);
+        write_skill(tmp.path(), "b", VALID);
+        fs::create_dir_all(tmp.path().join("empty")).expect("mkdir");
+
+        let report = validate_dir(tmp.path()).expect("validate");
+        assert_eq!(report.checked, 3);
+        let messages: Vec<&str> = report.issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with("duplicate name 'asset-kit'"));
+        assert!(messages[1].starts_with("cannot read SKILL.md"));
+    }
+
+    #[test]
+    fn skill_params_substitute_defaults_and_reject_missing() {
+        let content = "---\nname: write-tests\ndescription: Tests\nparams: framework, style=unit\n---\nUse {{framework}} for {{style}} tests.\n";
+        let skill =
+            SkillRegistry::parse_skill(Path::new("SKILL.md"), content).expect("parse skill");
+        assert_eq!(
+            skill.usage(),
+            "/skill write-tests framework=<value> [style=unit]"
+        );
+
+        let args = parse_skill_args("framework=pytest").expect("args");
+        assert_eq!(
+            skill.render(&args).expect("render"),
+            "Use pytest for unit tests."
+        );
+        let args = parse_skill_args("framework=pytest style=\"property based\"").expect("args");
+        assert_eq!(
+            skill.render(&args).expect("render"),
+            "Use pytest for property based tests."
+        );
+
+        let err = skill.render(&[]).unwrap_err().to_string();
+        assert!(err.contains("missing required parameter(s) framework"));
+        let args = parse_skill_args("runner=jest").expect("args");
+        assert!(
+            skill
+                .render(&args)
+                .unwrap_err()
+                .to_string()
+                .contains("unknown parameter 'runner'")
+        );
+        assert!(parse_skill_args("framework").is_err());
+    }
+
+    #[test]
+    fn validate_flags_undeclared_placeholders() {
+        let problems =
+            validate_skill("---\nname: x\ndescription: y\nparams: a\n---\nUse {{a}} and {{b}}.\n");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("`{{b}}`"));
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_skills() {
+        let dir = tempdir().expect("tempdir");
+        write_skill(
+            dir.path(),
+            "kept",
+            "---\nname: kept\ndescription: k\n---\nbody\n",
+        );
+        write_skill(
+            dir.path(),
+            "edited",
+            "---\nname: edited\ndescription: e\n---\nold\n",
+        );
+        write_skill(
+            dir.path(),
+            "gone",
+            "---\nname: gone\ndescription: g\n---\nbody\n",
+        );
+        let before = SkillRegistry::discover_path(&[(SkillSource::Global, dir.path().into())]);
+
+        write_skill(
+            dir.path(),
+            "edited",
+            "---\nname: edited\ndescription: e\n---\nnew\n",
+        );
+        fs::remove_dir_all(dir.path().join("gone")).expect("rm");
+        write_skill(
+            dir.path(),
+            "fresh",
+            "---\nname: fresh\ndescription: f\n---\nbody\n",
+        );
+        let after = SkillRegistry::discover_path(&[(SkillSource::Global, dir.path().into())]);
+
+        let changes = before.diff(&after);
+        assert_eq!(changes.added, vec!["fresh"]);
+        assert_eq!(changes.removed, vec!["gone"]);
+        assert_eq!(changes.changed, vec!["edited"]);
+        assert_eq!(
+            changes.summary(),
+            "  added: fresh\n  removed: gone\n  changed: edited"
+        );
+        assert!(after.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn watcher_reloads_once_files_settle() {
+        let dir = tempdir().expect("tempdir");
+        let search_path = [(SkillSource::Global, dir.path().to_path_buf())];
+        let mut watcher = SkillWatcher::new(&search_path);
+        let start = Instant::now();
+        assert!(!watcher.poll(&search_path, start));
+
+        write_skill(dir.path(), "fresh", VALID);
+        let later = |polls: u32| start + SKILL_POLL_INTERVAL * polls;
+        assert!(
+            !watcher.poll(&search_path, later(1)),
+            "first sighting is debounced"
+        );
+        assert!(
+            !watcher.poll(&search_path, later(1)),
+            "polls inside the interval are skipped"
+        );
+        assert!(watcher.poll(&search_path, later(2)));
+        assert!(!watcher.poll(&search_path, later(3)));
+    }
+
+    #[test]
+    fn undeclared_placeholders_are_required_params() {
+        let skill = SkillRegistry::parse_skill(
+            Path::new("SKILL.md"),
+            "---\nname: greet\ndescription: d\nparams: tone=warm\n---\nGreet {{who}} in a {{tone}} way.\n",
+        )
+        .expect("skill");
+        assert_eq!(skill.usage(), "/skill greet [tone=warm] who=<value>");
+        let err = skill.render(&[]).unwrap_err().to_string();
+        assert!(err.contains("missing required parameter(s) who"));
+        let args = parse_skill_args("who=Ada").expect("args");
+        assert_eq!(
+            skill.render(&args).expect("render"),
+            "Greet Ada in a warm way."
+        );
+    }
+
+    #[test]
+    fn frontmatter_tools_and_plain_skills() {
+        let dir = tempdir().expect("tempdir");
+        write_skill(
+            dir.path(),
+            "listed",
+            "---\nname: listed\ndescription: d\ntools: [read_file, \"grep_files\"]\n---\nbody\n",
+        );
+        write_skill(
+            dir.path(),
+            "block",
+            "---\nname: block\ndescription: d\ntools:\n  - list_dir\n  - read_file\n---\nbody\n",
+        );
+        write_skill(
+            dir.path(),
+            "legacy",
+            "---\nname: legacy\ndescription: d\nallowed-tools: tts, generate_image\n---\nbody\n",
+        );
+        write_skill(
+            dir.path(),
+            "plain",
+            "# Tidy imports\n\nSort and dedupe imports.\n",
+        );
+
+        let registry = SkillRegistry::discover_path(&[(SkillSource::Global, dir.path().into())]);
+        let tools = |name: &str| registry.get(name).expect(name).tools.clone();
+        assert_eq!(tools("listed"), ["read_file", "grep_files"]);
+        assert_eq!(tools("block"), ["list_dir", "read_file"]);
+        assert_eq!(tools("legacy"), ["tts", "generate_image"]);
+
+        let plain = registry.get("plain").expect("plain skill");
+        assert_eq!(plain.description, "Tidy imports");
+        assert!(plain.body.contains("Sort and dedupe imports."));
+        assert!(plain.tools.is_empty());
+    }
+}
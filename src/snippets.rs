@@ -3,11 +3,12 @@
 //! Snippets are stored at ~/.axiom/snippets.toml and provide quick access
 //! to common prompt templates like code reviews, explanations, etc.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use toml_edit::{DocumentMut, Item, Table, TableLike, value};
 
 /// A single snippet with name, description, and template text
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +19,52 @@ pub struct Snippet {
     pub description: String,
     /// The template text to insert
     pub template: String,
+    /// Shipped with axiom rather than defined in the snippets file
+    #[serde(skip)]
+    pub builtin: bool,
+}
+
+/// Marker in a template where the cursor lands after insertion
+pub const CURSOR_MARKER: &str = "$CURSOR";
+
+impl Snippet {
+    /// Fill `${1}`, `${2}`, ... from `args` (`${1:default}` when the argument
+    /// is missing, empty otherwise) and remove the `$CURSOR` marker. Returns
+    /// the text and the cursor position in chars: the marker, or the end.
+    pub fn expand(&self, args: &[String]) -> (String, usize) {
+        let mut text = String::with_capacity(self.template.len());
+        let mut cursor = None;
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('$') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(after) = rest.strip_prefix(CURSOR_MARKER) {
+                cursor.get_or_insert(text.chars().count());
+                rest = after;
+                continue;
+            }
+            let placeholder = rest.strip_prefix("${").and_then(|inner| {
+                inner
+                    .find('}')
+                    .map(|end| (&inner[..end], &inner[end + 1..]))
+            });
+            if let Some((inner, after)) = placeholder {
+                let (index, default) = inner.split_once(':').unwrap_or((inner, ""));
+                if let Ok(index) = index.parse::<usize>()
+                    && index > 0
+                {
+                    text.push_str(args.get(index - 1).map_or(default, String::as_str));
+                    rest = after;
+                    continue;
+                }
+            }
+            text.push('$');
+            rest = &rest[1..];
+        }
+        text.push_str(rest);
+        let cursor = cursor.unwrap_or_else(|| text.chars().count());
+        (text, cursor)
+    }
 }
 
 /// Collection of snippets loaded from config
@@ -25,7 +72,7 @@ pub struct Snippet {
 pub struct SnippetsConfig {
     /// Map of snippet name to snippet data
     #[serde(default)]
-    pub snippets: HashMap<String, SnippetEntry>,
+    pub snippets: BTreeMap<String, SnippetEntry>,
 }
 
 /// Individual snippet entry in TOML format
@@ -60,7 +107,7 @@ impl SnippetRegistry {
     }
 
     /// Load snippets from a specific path
-    pub fn load_from(path: &PathBuf) -> Self {
+    pub fn load_from(path: &Path) -> Self {
         if !path.exists() {
             return Self::with_defaults();
         }
@@ -77,15 +124,11 @@ impl SnippetRegistry {
         }
     }
 
-    /// Try to load snippets from disk
-    fn try_load_from(path: &PathBuf) -> Result<Self> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read snippets from {}", path.display()))?;
-
-        let config: SnippetsConfig = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse snippets from {}", path.display()))?;
-
-        let mut snippets = HashMap::new();
+    /// Try to load snippets from disk; they are added to the built-ins and
+    /// replace any built-in with the same name
+    fn try_load_from(path: &Path) -> Result<Self> {
+        let config = read_config(path)?;
+        let mut snippets = Self::default_snippets();
 
         // Convert config entries to Snippet structs
         for (name, entry) in config.snippets {
@@ -103,6 +146,7 @@ impl SnippetRegistry {
                     name,
                     description,
                     template: entry.template,
+                    builtin: false,
                 },
             );
         }
@@ -133,6 +177,7 @@ impl SnippetRegistry {
                 template:
                     "Please review this code for bugs, performance issues, and style improvements:"
                         .to_string(),
+                builtin: true,
             },
         );
 
@@ -142,6 +187,7 @@ impl SnippetRegistry {
                 name: "explain".to_string(),
                 description: "Ask for explanation".to_string(),
                 template: "Please explain how this works in detail:".to_string(),
+                builtin: true,
             },
         );
 
@@ -151,6 +197,7 @@ impl SnippetRegistry {
                 name: "test".to_string(),
                 description: "Generate tests".to_string(),
                 template: "Write unit tests for this code:".to_string(),
+                builtin: true,
             },
         );
 
@@ -160,6 +207,7 @@ impl SnippetRegistry {
                 name: "doc".to_string(),
                 description: "Generate docs".to_string(),
                 template: "Add documentation comments to this code:".to_string(),
+                builtin: true,
             },
         );
 
@@ -170,6 +218,7 @@ impl SnippetRegistry {
                 description: "Refactoring request".to_string(),
                 template: "Refactor this code to improve readability and maintainability:"
                     .to_string(),
+                builtin: true,
             },
         );
 
@@ -179,6 +228,7 @@ impl SnippetRegistry {
                 name: "optimize".to_string(),
                 description: "Performance optimization".to_string(),
                 template: "Optimize this code for better performance:".to_string(),
+                builtin: true,
             },
         );
 
@@ -204,6 +254,7 @@ impl SnippetRegistry {
     }
 
     /// Get the number of loaded snippets
+    #[allow(dead_code)]
     pub fn len(&self) -> usize {
         self.snippets.len()
     }
@@ -236,6 +287,96 @@ impl Default for SnippetRegistry {
     }
 }
 
+/// Subcommands of `/snippet` that can't be used as snippet names
+const RESERVED_NAMES: &[&str] = &["save", "delete"];
+
+fn read_config(path: &Path) -> Result<SnippetsConfig> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read snippets from {}", path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse snippets from {}", path.display()))
+}
+
+/// Read `snippets.toml` as an editable document, so saving and deleting keep
+/// the user's comments and formatting
+fn read_document(path: &Path) -> Result<DocumentMut> {
+    if !path.exists() {
+        return Ok(DocumentMut::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read snippets from {}", path.display()))?;
+    content
+        .parse()
+        .with_context(|| format!("Failed to parse snippets from {}", path.display()))
+}
+
+fn write_document(path: &Path, doc: &DocumentMut) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, doc.to_string())
+        .with_context(|| format!("Failed to write snippets to {}", path.display()))
+}
+
+/// The `[snippets]` table of `doc`, added when missing
+fn snippets_table<'a>(doc: &'a mut DocumentMut, path: &Path) -> Result<&'a mut dyn TableLike> {
+    doc.entry("snippets")
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_like_mut()
+        .with_context(|| {
+            format!(
+                "Failed to edit {}: `snippets` is not a table",
+                path.display()
+            )
+        })
+}
+
+/// Store `template` as the user snippet `name`, returning whether one with
+/// that name was replaced.
+pub fn save_user_snippet(path: &Path, name: &str, template: &str) -> Result<bool> {
+    if name.is_empty() || name.contains(char::is_whitespace) || name.starts_with('/') {
+        anyhow::bail!("Failed to save snippet: '{name}' is not a valid name (one word, no spaces)");
+    }
+    if RESERVED_NAMES.contains(&name) {
+        anyhow::bail!("Failed to save snippet: '{name}' is reserved by /snippet {name}");
+    }
+    let replaced = path.exists() && read_config(path)?.snippets.contains_key(name);
+    let mut doc = read_document(path)?;
+    let snippets = snippets_table(&mut doc, path)?;
+    // Replacing keeps the entry's description and comments
+    match snippets.get_mut(name).and_then(Item::as_table_like_mut) {
+        Some(entry) => match entry.get_mut("template") {
+            Some(item) => *item = value(template),
+            None => {
+                entry.insert("template", value(template));
+            }
+        },
+        None => {
+            let mut entry = Table::new();
+            entry.insert("template", value(template));
+            snippets.insert(name, Item::Table(entry));
+        }
+    }
+    write_document(path, &doc)?;
+    Ok(replaced)
+}
+
+/// Remove the user snippet `name`, returning whether it existed.
+pub fn delete_user_snippet(path: &Path, name: &str) -> Result<bool> {
+    if !path.exists() || !read_config(path)?.snippets.contains_key(name) {
+        return Ok(false);
+    }
+    let mut doc = read_document(path)?;
+    snippets_table(&mut doc, path)?.remove(name);
+    write_document(path, &doc)?;
+    Ok(true)
+}
+
 /// Get a snippet by name (convenience function that loads defaults)
 #[allow(dead_code)]
 pub fn get_snippet(name: &str) -> Option<Snippet> {
@@ -312,6 +453,31 @@ mod tests {
         assert!(registry.get("test").is_some());
     }
 
+    #[test]
+    fn expand_fills_placeholders_and_places_cursor() {
+        let snippet = Snippet {
+            name: "fix".to_string(),
+            description: String::new(),
+            template: "Fix ${1} in ${2:src/main.rs}: $CURSOR (cost $5)".to_string(),
+            builtin: false,
+        };
+        let (text, cursor) = snippet.expand(&["the panic".to_string()]);
+        assert_eq!(text, "Fix the panic in src/main.rs:  (cost $5)");
+        assert_eq!(cursor, "Fix the panic in src/main.rs: ".chars().count());
+
+        let (text, cursor) = snippet.expand(&[]);
+        assert_eq!(text, "Fix  in src/main.rs:  (cost $5)");
+        assert_eq!(cursor, 21);
+
+        let plain = Snippet {
+            template: "Explain ${x} déjà".to_string(),
+            ..snippet
+        };
+        let (text, cursor) = plain.expand(&[]);
+        assert_eq!(text, "Explain ${x} déjà");
+        assert_eq!(cursor, text.chars().count());
+    }
+
     #[test]
     fn test_get_snippet() {
         let registry = SnippetRegistry::with_defaults();
@@ -361,6 +527,58 @@ mod tests {
         assert_eq!(edit_distance("help", "h3lp"), 1); // substitution
     }
 
+    #[test]
+    fn user_snippets_are_saved_listed_and_deleted() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("snippets.toml");
+
+        assert!(!save_user_snippet(&path, "bisect", "Bisect ${1} until $CURSOR").expect("save"));
+        assert!(!save_user_snippet(&path, "review", "My review").expect("save"));
+        assert!(save_user_snippet(&path, "review", "My review").expect("save again"));
+        assert!(save_user_snippet(&path, "save", "x").is_err());
+        assert!(save_user_snippet(&path, "two words", "x").is_err());
+
+        let registry = SnippetRegistry::load_from(&path);
+        assert!(!registry.is_using_defaults());
+        let bisect = registry.get("bisect").expect("user snippet");
+        assert!(!bisect.builtin);
+        assert_eq!(bisect.description, "Custom snippet");
+        let review = registry.get("review").expect("overridden built-in");
+        assert_eq!(review.template, "My review");
+        assert!(!review.builtin);
+        assert!(registry.get("explain").expect("built-in kept").builtin);
+
+        assert!(delete_user_snippet(&path, "bisect").expect("delete"));
+        assert!(!delete_user_snippet(&path, "bisect").expect("delete again"));
+        let registry = SnippetRegistry::load_from(&path);
+        assert!(registry.get("bisect").is_none());
+    }
+
+    #[test]
+    fn saving_and_deleting_keep_comments() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("snippets.toml");
+        std::fs::write(
+            &path,
+            "# My snippets\n\n[snippets.bisect]\n# narrows a regression\ntemplate = \"old\"\ndescription = \"Bisect\"\n\n[snippets.gone]\ntemplate = \"x\"\n",
+        )
+        .expect("write");
+
+        assert!(save_user_snippet(&path, "bisect", "new").expect("save"));
+        assert!(!save_user_snippet(&path, "fresh", "hello").expect("save"));
+        assert!(delete_user_snippet(&path, "gone").expect("delete"));
+
+        let content = std::fs::read_to_string(&path).expect("read");
+        assert!(content.starts_with("# My snippets\n"), "{content}");
+        assert!(content.contains("# narrows a regression"), "{content}");
+        assert!(!content.contains("gone"), "{content}");
+        let registry = SnippetRegistry::load_from(&path);
+        let bisect = registry.get("bisect").expect("bisect");
+        assert_eq!(bisect.template, "new");
+        assert_eq!(bisect.description, "Bisect");
+        assert_eq!(registry.get("fresh").expect("fresh").template, "hello");
+    }
+
     #[test]
     fn test_default_snippets_toml() {
         let toml = default_snippets_toml();
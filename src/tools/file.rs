@@ -1,15 +1,16 @@
-//! File system tools: `read_file`, `write_file`, `edit_file`, `list_dir`
+//! File system tools: `read_file`, `read_many_files`, `write_file`, `edit_file`, `list_dir`
 //!
 //! These tools provide safe file system operations within the workspace,
 //! with path validation to prevent escaping the workspace boundary.
 
 use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
-    optional_str, required_str,
+    optional_str, optional_u64, required_str,
 };
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use std::fs;
+use std::path::PathBuf;
 
 // === ReadFileTool ===
 
@@ -23,7 +24,7 @@ impl ToolSpec for ReadFileTool {
     }
 
     fn description(&self) -> &'static str {
-        "Read a UTF-8 file from the workspace."
+        "Read a UTF-8 file from the workspace. Use start_line/end_line to read a slice of a large file."
     }
 
     fn input_schema(&self) -> Value {
@@ -33,6 +34,14 @@ impl ToolSpec for ReadFileTool {
                 "path": {
                     "type": "string",
                     "description": "Path to the file (relative to workspace or absolute)"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line to read (1-based, default: 1)"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line to read (inclusive, default: end of file)"
                 }
             },
             "required": ["path"]
@@ -50,12 +59,198 @@ impl ToolSpec for ReadFileTool {
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
         let path_str = required_str(&input, "path")?;
         let file_path = context.resolve_path(path_str)?;
+        let start = input.get("start_line").and_then(Value::as_u64);
+        let end = input.get("end_line").and_then(Value::as_u64);
+        if let (Some(start), Some(end)) = (start, end)
+            && end < start
+        {
+            return Err(ToolError::invalid_input(format!(
+                "end_line ({end}) is before start_line ({start})"
+            )));
+        }
 
         let contents = fs::read_to_string(&file_path).map_err(|e| {
             ToolError::execution_failed(format!("Failed to read {}: {}", file_path.display(), e))
         })?;
 
-        Ok(ToolResult::success(contents))
+        if start.is_none() && end.is_none() {
+            return Ok(ToolResult::success(contents));
+        }
+        Ok(ToolResult::success(slice_lines(&contents, start, end)))
+    }
+}
+
+/// Return lines `start..=end` (1-based) of `contents` under a header naming the
+/// range. Ranges past the end of the file are clamped and noted.
+fn slice_lines(contents: &str, start: Option<u64>, end: Option<u64>) -> String {
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let total = lines.len();
+    let to_usize = |n: u64| usize::try_from(n).unwrap_or(usize::MAX);
+    let start = start.map_or(1, to_usize).max(1);
+    let requested_end = end.map(to_usize);
+    let end = requested_end.unwrap_or(total).min(total);
+
+    if start > total {
+        return format!("[{total} lines total; start_line {start} is past the end of the file]\n");
+    }
+
+    let mut output = format!("[lines {start}-{end} of {total}");
+    if let Some(requested) = requested_end
+        && requested > total
+    {
+        output.push_str(&format!("; end_line {requested} clamped to end of file"));
+    }
+    output.push_str("]\n");
+    output.push_str(&lines[start - 1..end].concat());
+    output
+}
+
+// === ReadManyFilesTool ===
+
+/// Default total byte budget for `read_many_files`.
+const READ_MANY_DEFAULT_BYTES: u64 = 100_000;
+/// Upper bound on the byte budget a caller may request.
+const READ_MANY_MAX_BYTES: u64 = 1_000_000;
+/// Files matched by a glob beyond this count are listed but not read.
+const READ_MANY_MAX_FILES: usize = 50;
+
+/// Tool for reading several UTF-8 files in one call.
+pub struct ReadManyFilesTool;
+
+#[async_trait]
+impl ToolSpec for ReadManyFilesTool {
+    fn name(&self) -> &'static str {
+        "read_many_files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read several UTF-8 files in one call, given a list of paths and/or a glob (e.g. 'src/**/*.rs'). Returns each file under a '=== path ===' header; output stops at a total byte cap."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Files to read (relative to workspace or absolute)"
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Glob relative to the workspace; common build and dependency directories are skipped"
+                },
+                "max_bytes": {
+                    "type": "integer",
+                    "description": "Total bytes of file content to return (default: 100000, max: 1000000)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly, ToolCapability::Sandboxable]
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let mut requested: Vec<String> = input
+            .get("paths")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(pattern) = optional_str(&input, "glob") {
+            let root = context.resolve_path(".")?;
+            let mut matched: Vec<String> = super::search::collect_files(
+                &root,
+                &[pattern.to_string()],
+                &super::search::default_excludes(),
+            )?
+            .into_iter()
+            .map(|path| {
+                path.strip_prefix(&root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+            matched.sort();
+            requested.extend(matched);
+        }
+        if requested.is_empty() {
+            return Err(ToolError::invalid_input(
+                "Provide `paths`, a `glob`, or both",
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        requested.retain(|path| seen.insert(path.clone()));
+
+        let budget = usize::try_from(
+            optional_u64(&input, "max_bytes", READ_MANY_DEFAULT_BYTES).min(READ_MANY_MAX_BYTES),
+        )
+        .unwrap_or(usize::MAX);
+        let (to_read, over_limit) = requested.split_at(requested.len().min(READ_MANY_MAX_FILES));
+
+        let mut output = String::new();
+        let mut used = 0;
+        let mut skipped: Vec<&str> = over_limit.iter().map(String::as_str).collect();
+        for (idx, path) in to_read.iter().enumerate() {
+            if used >= budget {
+                skipped.splice(0..0, to_read[idx..].iter().map(String::as_str));
+                break;
+            }
+            let contents = context.resolve_path(path).and_then(|resolved| {
+                fs::read_to_string(&resolved).map_err(|e| {
+                    ToolError::execution_failed(format!(
+                        "Failed to read {}: {e}",
+                        resolved.display()
+                    ))
+                })
+            });
+            match contents {
+                Ok(contents) => {
+                    let remaining = budget - used;
+                    if contents.len() <= remaining {
+                        output.push_str(&format!("=== {path} ({} bytes) ===\n", contents.len()));
+                        output.push_str(&contents);
+                        used += contents.len();
+                    } else {
+                        let mut cut = remaining;
+                        while !contents.is_char_boundary(cut) {
+                            cut -= 1;
+                        }
+                        output.push_str(&format!(
+                            "=== {path} (first {cut} of {} bytes) ===\n",
+                            contents.len()
+                        ));
+                        output.push_str(&contents[..cut]);
+                        used = budget;
+                    }
+                }
+                Err(e) => output.push_str(&format!("=== {path} ===\n[error: {e}]")),
+            }
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+
+        if !skipped.is_empty() {
+            output.push_str(&format!(
+                "[truncated: {} file(s) not read (byte cap {budget}, file cap {READ_MANY_MAX_FILES}): {}]\n",
+                skipped.len(),
+                skipped.join(", ")
+            ));
+        }
+        Ok(ToolResult::success(output))
     }
 }
 
@@ -180,10 +375,49 @@ impl ToolSpec for EditFileTool {
         ApprovalRequirement::Suggest
     }
 
+    fn approval_preview(&self, input: &Value, context: &ToolContext) -> Option<String> {
+        let path_str = required_str(input, "path").ok()?;
+        let edit = EditFileTool::prepare(input, context).ok()?;
+        Some(super::patch::unified_diff(
+            path_str,
+            &edit.original,
+            &edit.updated,
+        ))
+    }
+
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
-        let path_str = required_str(&input, "path")?;
-        let search = required_str(&input, "search")?;
-        let replace = required_str(&input, "replace")?;
+        let PreparedEdit {
+            file_path,
+            updated,
+            count,
+            ..
+        } = EditFileTool::prepare(&input, context)?;
+
+        fs::write(&file_path, &updated).map_err(|e| {
+            ToolError::execution_failed(format!("Failed to write {}: {}", file_path.display(), e))
+        })?;
+
+        Ok(ToolResult::success(format!(
+            "Replaced {} occurrence(s) in {}",
+            count,
+            file_path.display()
+        )))
+    }
+}
+
+/// An `edit_file` call resolved against the file on disk, not yet written.
+struct PreparedEdit {
+    file_path: PathBuf,
+    original: String,
+    updated: String,
+    count: usize,
+}
+
+impl EditFileTool {
+    fn prepare(input: &Value, context: &ToolContext) -> Result<PreparedEdit, ToolError> {
+        let path_str = required_str(input, "path")?;
+        let search = required_str(input, "search")?;
+        let replace = required_str(input, "replace")?;
 
         if search.is_empty() {
             return Err(ToolError::invalid_input("Search string cannot be empty."));
@@ -191,11 +425,11 @@ impl ToolSpec for EditFileTool {
 
         let file_path = context.resolve_path(path_str)?;
 
-        let contents = fs::read_to_string(&file_path).map_err(|e| {
+        let original = fs::read_to_string(&file_path).map_err(|e| {
             ToolError::execution_failed(format!("Failed to read {}: {}", file_path.display(), e))
         })?;
 
-        let count = contents.matches(search).count();
+        let count = original.matches(search).count();
         if count == 0 {
             return Err(ToolError::execution_failed(format!(
                 "Search string not found in {}",
@@ -203,17 +437,13 @@ impl ToolSpec for EditFileTool {
             )));
         }
 
-        let updated = contents.replace(search, replace);
-
-        fs::write(&file_path, &updated).map_err(|e| {
-            ToolError::execution_failed(format!("Failed to write {}: {}", file_path.display(), e))
-        })?;
-
-        Ok(ToolResult::success(format!(
-            "Replaced {} occurrence(s) in {}",
+        let updated = original.replace(search, replace);
+        Ok(PreparedEdit {
+            file_path,
+            original,
+            updated,
             count,
-            file_path.display()
-        )))
+        })
     }
 }
 
@@ -486,6 +716,137 @@ mod tests {
         assert!(result.content.contains("nested.txt"));
     }
 
+    #[test]
+    fn test_edit_file_approval_preview_is_a_diff() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        fs::write(tmp.path().join("a.txt"), "one\ntwo\nthree\n").expect("write");
+
+        let tool = EditFileTool;
+        let preview = tool
+            .approval_preview(
+                &json!({"path": "a.txt", "search": "two", "replace": "2"}),
+                &ctx,
+            )
+            .expect("preview");
+        assert_eq!(
+            preview,
+            "--- a/a.txt\n+++ b/a.txt\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n"
+        );
+        assert!(
+            tool.approval_preview(
+                &json!({"path": "a.txt", "search": "missing", "replace": "x"}),
+                &ctx,
+            )
+            .is_none()
+        );
+        // Previewing must not touch the file.
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("a.txt")).expect("read"),
+            "one\ntwo\nthree\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_file_line_range() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        fs::write(tmp.path().join("lines.txt"), "one\ntwo\nthree\nfour\n").expect("write");
+
+        let tool = ReadFileTool;
+        let whole = tool
+            .execute(json!({"path": "lines.txt"}), &ctx)
+            .await
+            .expect("execute");
+        assert_eq!(whole.content, "one\ntwo\nthree\nfour\n");
+
+        let slice = tool
+            .execute(
+                json!({"path": "lines.txt", "start_line": 2, "end_line": 3}),
+                &ctx,
+            )
+            .await
+            .expect("execute");
+        assert_eq!(slice.content, "[lines 2-3 of 4]\ntwo\nthree\n");
+
+        let clamped = tool
+            .execute(
+                json!({"path": "lines.txt", "start_line": 3, "end_line": 10}),
+                &ctx,
+            )
+            .await
+            .expect("execute");
+        assert_eq!(
+            clamped.content,
+            "[lines 3-4 of 4; end_line 10 clamped to end of file]\nthree\nfour\n"
+        );
+
+        let past_end = tool
+            .execute(json!({"path": "lines.txt", "start_line": 9}), &ctx)
+            .await
+            .expect("execute");
+        assert!(past_end.success);
+        assert!(past_end.content.contains("start_line 9 is past the end"));
+
+        let inverted = tool
+            .execute(
+                json!({"path": "lines.txt", "start_line": 3, "end_line": 2}),
+                &ctx,
+            )
+            .await;
+        assert!(inverted.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_many_files_with_glob_and_byte_cap() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        fs::create_dir(tmp.path().join("src")).expect("mkdir");
+        fs::write(tmp.path().join("src/a.rs"), "fn a() {}\n").expect("write");
+        fs::write(tmp.path().join("src/b.rs"), "fn b() {}\n").expect("write");
+        fs::write(tmp.path().join("notes.txt"), "0123456789").expect("write");
+
+        let tool = ReadManyFilesTool;
+        let result = tool
+            .execute(json!({"paths": ["notes.txt"], "glob": "*.rs"}), &ctx)
+            .await
+            .expect("execute");
+        assert!(result.success);
+        assert!(
+            result
+                .content
+                .contains("=== notes.txt (10 bytes) ===\n0123456789\n")
+        );
+        assert!(
+            result
+                .content
+                .contains("=== src/a.rs (10 bytes) ===\nfn a() {}\n")
+        );
+        assert!(result.content.contains("=== src/b.rs"));
+
+        let capped = tool
+            .execute(
+                json!({"paths": ["notes.txt", "src/a.rs", "missing.txt"], "max_bytes": 4}),
+                &ctx,
+            )
+            .await
+            .expect("execute");
+        assert!(
+            capped
+                .content
+                .contains("=== notes.txt (first 4 of 10 bytes) ===\n0123\n")
+        );
+        assert!(capped.content.contains(
+            "[truncated: 2 file(s) not read (byte cap 4, file cap 50): src/a.rs, missing.txt]"
+        ));
+
+        let escaped = tool
+            .execute(json!({"paths": ["../outside.txt"]}), &ctx)
+            .await
+            .expect("execute");
+        assert!(escaped.content.contains("[error:"));
+    }
+
     #[test]
     fn test_read_file_tool_properties() {
         let tool = ReadFileTool;
@@ -23,6 +23,13 @@ fn run_git(args: &[&str], cwd: &std::path::Path) -> Result<String, String> {
     }
 }
 
+/// Repository root for repo-wide commands (status, commit, branch, stash), so
+/// they behave the same from a subdirectory or a linked worktree. Commands that
+/// take workspace-relative paths keep running in the workspace.
+fn repo_root(workspace: &std::path::Path) -> std::path::PathBuf {
+    crate::utils::git_toplevel(workspace).unwrap_or_else(|| workspace.to_path_buf())
+}
+
 /// Check if git is available in the workspace
 fn is_git_repo(cwd: &std::path::Path) -> bool {
     run_git(&["rev-parse", "--git-dir"], cwd).is_ok()
@@ -173,16 +180,20 @@ impl ToolSpec for GitStatusTool {
         if !is_git_repo(&context.workspace) {
             return Ok(ToolResult::error("Not a git repository"));
         }
+        let root = repo_root(&context.workspace);
 
         let mut output = String::new();
+        if root != context.workspace {
+            output.push_str(&format!("Repository: {}\n", root.display()));
+        }
 
         // Current branch
-        match run_git(&["branch", "--show-current"], &context.workspace) {
+        match run_git(&["branch", "--show-current"], &root) {
             Ok(branch) => output.push_str(&format!("Branch: {}\n", branch.trim())),
             Err(e) => return Ok(ToolResult::error(format!("Failed to get branch: {e}"))),
         }
 
-        // Status
+        // Status, with paths relative to the workspace the model works in
         match run_git(&["status", "--short"], &context.workspace) {
             Ok(status) => {
                 if status.trim().is_empty() {
@@ -196,7 +207,7 @@ impl ToolSpec for GitStatusTool {
         }
 
         // Recent commits
-        if let Ok(log) = run_git(&["log", "--oneline", "-5"], &context.workspace) {
+        if let Ok(log) = run_git(&["log", "--oneline", "-5"], &root) {
             output.push_str("\nRecent commits:\n");
             output.push_str(&log);
         }
@@ -249,6 +260,7 @@ impl ToolSpec for GitCommitTool {
         if !is_git_repo(&context.workspace) {
             return Ok(ToolResult::error("Not a git repository"));
         }
+        let root = repo_root(&context.workspace);
 
         let message = required_str(&input, "message")?;
         let stage_all = input
@@ -257,12 +269,12 @@ impl ToolSpec for GitCommitTool {
             .unwrap_or(false);
 
         // Stage all if requested
-        if stage_all && let Err(e) = run_git(&["add", "-A"], &context.workspace) {
+        if stage_all && let Err(e) = run_git(&["add", "-A"], &root) {
             return Ok(ToolResult::error(format!("Failed to stage changes: {e}")));
         }
 
         // Create commit
-        match run_git(&["commit", "-m", message], &context.workspace) {
+        match run_git(&["commit", "-m", message], &root) {
             Ok(output) => Ok(ToolResult::success(format!("Commit created:\n{}", output))),
             Err(e) => Ok(ToolResult::error(format!("Failed to create commit: {e}"))),
         }
@@ -375,10 +387,11 @@ impl ToolSpec for GitBranchTool {
         if !is_git_repo(&context.workspace) {
             return Ok(ToolResult::error("Not a git repository"));
         }
+        let root = repo_root(&context.workspace);
 
         if let Some(new_branch) = input.get("create").and_then(|v| v.as_str()) {
             // Create new branch
-            match run_git(&["checkout", "-b", new_branch], &context.workspace) {
+            match run_git(&["checkout", "-b", new_branch], &root) {
                 Ok(output) => Ok(ToolResult::success(format!(
                     "Created and switched to branch '{}':\n{}",
                     new_branch, output
@@ -387,7 +400,7 @@ impl ToolSpec for GitBranchTool {
             }
         } else {
             // List branches
-            match run_git(&["branch", "-a"], &context.workspace) {
+            match run_git(&["branch", "-a"], &root) {
                 Ok(output) => Ok(ToolResult::success(output)),
                 Err(e) => Ok(ToolResult::error(format!("Failed to list branches: {e}"))),
             }
@@ -395,6 +408,318 @@ impl ToolSpec for GitBranchTool {
     }
 }
 
+// === GitBlameTool ===
+
+/// Blame lines reported before the output is truncated.
+const MAX_BLAME_LINES: usize = 400;
+
+/// Tool for showing who last changed each line of a file
+pub struct GitBlameTool;
+
+/// One blamed line parsed from `git blame --line-porcelain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlameLine {
+    hash: String,
+    author: String,
+    date: String,
+    summary: String,
+    line: usize,
+    content: String,
+}
+
+/// Parse `git blame --line-porcelain` output, which repeats the commit
+/// headers for every line.
+fn parse_line_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+    for raw in output.lines() {
+        if let Some(content) = raw.strip_prefix('\t') {
+            if let Some(mut entry) = current.take() {
+                entry.content = content.to_string();
+                lines.push(entry);
+            }
+            continue;
+        }
+        if let Some(entry) = current.as_mut() {
+            if let Some(author) = raw.strip_prefix("author ") {
+                entry.author = author.to_string();
+            } else if let Some(time) = raw.strip_prefix("author-time ") {
+                entry.date = time
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                    .map_or_else(|| time.to_string(), |dt| dt.format("%Y-%m-%d").to_string());
+            } else if let Some(summary) = raw.strip_prefix("summary ") {
+                entry.summary = summary.to_string();
+            }
+            continue;
+        }
+        let mut parts = raw.split_whitespace();
+        if let (Some(hash), Some(_orig), Some(line)) = (parts.next(), parts.next(), parts.next())
+            && hash.len() >= 40
+            && let Ok(line) = line.parse()
+        {
+            current = Some(BlameLine {
+                hash: hash.chars().take(8).collect(),
+                author: String::new(),
+                date: String::new(),
+                summary: String::new(),
+                line,
+                content: String::new(),
+            });
+        }
+    }
+    lines
+}
+
+/// Render blamed lines, then the summary of each commit seen once.
+fn format_blame(lines: &[BlameLine]) -> String {
+    let author_width = lines
+        .iter()
+        .map(|l| l.author.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(20);
+    let mut out = String::new();
+    let mut commits: Vec<(&str, &str)> = Vec::new();
+    for l in lines {
+        let author: String = l.author.chars().take(author_width).collect();
+        out.push_str(&format!(
+            "{} {author:<author_width$} {} {:>5} | {}\n",
+            l.hash, l.date, l.line, l.content
+        ));
+        if !commits.iter().any(|(hash, _)| *hash == l.hash) {
+            commits.push((&l.hash, &l.summary));
+        }
+    }
+    out.push_str("\nCommits:\n");
+    for (hash, summary) in commits {
+        out.push_str(&format!("{hash} {summary}\n"));
+    }
+    out
+}
+
+#[async_trait]
+impl ToolSpec for GitBlameTool {
+    fn name(&self) -> &'static str {
+        "git_blame"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show who last changed each line of a file: short commit hash, author, date, and the commit summary. Use start_line/end_line to blame a range."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File to blame, relative to the workspace"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line to blame (1-based, default: 1)"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line to blame (inclusive, default: end of file)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::ReadOnly]
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        if !is_git_repo(&context.workspace) {
+            return Ok(ToolResult::error("Not a git repository"));
+        }
+
+        let path = required_str(&input, "path")?;
+        context.resolve_path(path)?;
+        let start = input.get("start_line").and_then(Value::as_u64);
+        let end = input.get("end_line").and_then(Value::as_u64);
+        if let (Some(start), Some(end)) = (start, end)
+            && end < start
+        {
+            return Err(ToolError::invalid_input(format!(
+                "end_line ({end}) is before start_line ({start})"
+            )));
+        }
+
+        let range = match (start, end) {
+            (None, None) => None,
+            (start, Some(end)) => Some(format!("{},{end}", start.unwrap_or(1).max(1))),
+            (Some(start), None) => Some(format!("{},", start.max(1))),
+        };
+        let mut args = vec!["blame", "--line-porcelain"];
+        if let Some(range) = range.as_deref() {
+            args.push("-L");
+            args.push(range);
+        }
+        args.push("--");
+        args.push(path);
+
+        let output = match run_git(&args, &context.workspace) {
+            Ok(output) => output,
+            Err(e) => return Ok(ToolResult::error(format!("Failed to blame {path}: {e}"))),
+        };
+        let lines = parse_line_porcelain(&output);
+        if lines.is_empty() {
+            return Ok(ToolResult::success(format!("No lines to blame in {path}")));
+        }
+
+        let total = lines.len();
+        let shown = &lines[..total.min(MAX_BLAME_LINES)];
+        let mut content = format_blame(shown);
+        if total > shown.len() {
+            let last = shown.last().map_or(0, |l| l.line);
+            content.push_str(&format!(
+                "\n[truncated: showing {} of {total} lines (through line {last}); pass start_line/end_line to see the rest]\n",
+                shown.len()
+            ));
+        }
+        Ok(ToolResult::success(content))
+    }
+}
+
+// === GitStashTool ===
+
+/// Tool for snapshotting and restoring uncommitted work with git stash
+pub struct GitStashTool;
+
+/// Resolve a stash target to a `stash@{n}` ref. Accepts `stash@{n}` as-is or
+/// a commit hash (prefix) reported by an earlier push, which stays valid
+/// after other stashes shift the indices.
+fn resolve_stash_ref(target: &str, cwd: &std::path::Path) -> Result<String, String> {
+    if target.starts_with("stash@{") {
+        return Ok(target.to_string());
+    }
+    let list = run_git(&["stash", "list", "--format=%gd %H"], cwd)?;
+    list.lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, hash)| target.len() >= 7 && hash.starts_with(target))
+        .map(|(stash_ref, _)| stash_ref.to_string())
+        .ok_or_else(|| format!("no stash entry matches '{target}'"))
+}
+
+#[async_trait]
+impl ToolSpec for GitStashTool {
+    fn name(&self) -> &'static str {
+        "git_stash"
+    }
+
+    fn description(&self) -> &'static str {
+        "Snapshot or restore uncommitted work. action='push' stashes changes and returns a stash ref and commit hash; action='pop' restores a stash (default: latest); action='list' shows stashes. Push before risky edits and pop to roll back."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["push", "pop", "list"],
+                    "description": "Stash operation to run"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Description for push (default: 'axiom snapshot')"
+                },
+                "include_untracked": {
+                    "type": "boolean",
+                    "description": "Also stash untracked files on push (default: true)"
+                },
+                "stash": {
+                    "type": "string",
+                    "description": "Stash to pop: a stash@{n} ref or the commit hash returned by push (default: latest)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        vec![
+            ToolCapability::WritesFiles,
+            ToolCapability::RequiresApproval,
+        ]
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Suggest
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        if !is_git_repo(&context.workspace) {
+            return Ok(ToolResult::error("Not a git repository"));
+        }
+        let root = repo_root(&context.workspace);
+
+        match required_str(&input, "action")? {
+            "push" => {
+                let message = optional_str(&input, "message").unwrap_or("axiom snapshot");
+                let include_untracked = input
+                    .get("include_untracked")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(true);
+                let before = run_git(&["rev-parse", "-q", "--verify", "stash@{0}"], &root).ok();
+
+                let mut args = vec!["stash", "push", "-m", message];
+                if include_untracked {
+                    args.push("--include-untracked");
+                }
+                if let Err(e) = run_git(&args, &root) {
+                    return Ok(ToolResult::error(format!("Failed to stash changes: {e}")));
+                }
+
+                let after = run_git(&["rev-parse", "-q", "--verify", "stash@{0}"], &root).ok();
+                match after {
+                    Some(hash) if after != before => {
+                        let hash = hash.trim();
+                        Ok(ToolResult::success(format!(
+                            "Stashed changes as stash@{{0}} ({hash}): {message}\nRestore with action='pop', stash='{hash}'"
+                        ))
+                        .with_metadata(json!({ "stash_ref": "stash@{0}", "stash_commit": hash })))
+                    }
+                    _ => Ok(ToolResult::success("No local changes to stash")),
+                }
+            }
+            "pop" => {
+                let target = match optional_str(&input, "stash") {
+                    Some(target) => match resolve_stash_ref(target, &root) {
+                        Ok(stash_ref) => stash_ref,
+                        Err(e) => {
+                            return Ok(ToolResult::error(format!("Failed to pop stash: {e}")));
+                        }
+                    },
+                    None => "stash@{0}".to_string(),
+                };
+                match run_git(&["stash", "pop", &target], &root) {
+                    Ok(output) => Ok(ToolResult::success(format!("Restored {target}:\n{output}"))),
+                    Err(e) => Ok(ToolResult::error(format!("Failed to pop {target}: {e}"))),
+                }
+            }
+            "list" => match run_git(&["stash", "list", "--format=%gd %h %s"], &root) {
+                Ok(output) if output.trim().is_empty() => Ok(ToolResult::success("No stashes")),
+                Ok(output) => Ok(ToolResult::success(output)),
+                Err(e) => Ok(ToolResult::error(format!("Failed to list stashes: {e}"))),
+            },
+            other => Err(ToolError::invalid_input(format!(
+                "Unknown action '{other}'; expected push, pop, or list"
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,5 +733,110 @@ mod tests {
         let commit_tool = GitCommitTool;
         assert_eq!(commit_tool.name(), "git_commit");
         assert!(!commit_tool.is_read_only());
+
+        let blame_tool = GitBlameTool;
+        assert_eq!(blame_tool.name(), "git_blame");
+        assert!(blame_tool.is_read_only());
+    }
+
+    #[tokio::test]
+    async fn status_from_a_subdirectory_is_relative_to_it() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        run_git(&["init", "-q"], dir).expect("git init");
+        let sub = dir.join("sub");
+        std::fs::create_dir(&sub).expect("mkdir");
+        std::fs::write(dir.join("top.txt"), "x\n").expect("write");
+        std::fs::write(sub.join("inner.txt"), "y\n").expect("write");
+        run_git(&["add", "sub/inner.txt"], dir).expect("git add");
+
+        let result = GitStatusTool
+            .execute(json!({}), &ToolContext::new(sub))
+            .await
+            .expect("status");
+        assert!(result.success, "{}", result.content);
+        assert!(result.content.starts_with("Repository: "));
+        assert!(
+            result.content.contains("A  inner.txt"),
+            "{}",
+            result.content
+        );
+        assert!(
+            result.content.contains("?? ../top.txt"),
+            "{}",
+            result.content
+        );
+    }
+
+    #[tokio::test]
+    async fn stash_push_returns_ref_that_pop_restores() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        let git = |args: &[&str]| run_git(args, dir).expect("git");
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(dir.join("file.txt"), "one\n").expect("write");
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+        std::fs::write(dir.join("file.txt"), "two\n").expect("write");
+
+        let ctx = ToolContext::new(dir.to_path_buf());
+        let tool = GitStashTool;
+        let pushed = tool
+            .execute(
+                json!({"action": "push", "message": "before refactor"}),
+                &ctx,
+            )
+            .await
+            .expect("push");
+        assert!(pushed.success, "{}", pushed.content);
+        let hash = pushed.metadata.as_ref().expect("metadata")["stash_commit"]
+            .as_str()
+            .expect("hash")
+            .to_string();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("file.txt")).unwrap(),
+            "one\n"
+        );
+
+        let empty = tool
+            .execute(json!({"action": "push"}), &ctx)
+            .await
+            .expect("push");
+        assert_eq!(empty.content, "No local changes to stash");
+
+        let popped = tool
+            .execute(json!({"action": "pop", "stash": &hash[..10]}), &ctx)
+            .await
+            .expect("pop");
+        assert!(popped.success, "{}", popped.content);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("file.txt")).unwrap(),
+            "two\n"
+        );
+    }
+
+    #[test]
+    fn blame_porcelain_parses_each_line_and_lists_commits() {
+        let hash_a = "a".repeat(40);
+        let hash_b = "b".repeat(40);
+        let output = format!(
+            "{hash_a} 1 1 2\nauthor Ada Lovelace\nauthor-time 1700000000\nsummary Add parser\nfilename src/lib.rs\n\tfn parse() {{\n\
+             {hash_a} 2 2\nauthor Ada Lovelace\nauthor-time 1700000000\nsummary Add parser\nfilename src/lib.rs\n\t    todo!()\n\
+             {hash_b} 3 3 1\nauthor Bob\nauthor-time 1710000000\nsummary Close brace\nfilename src/lib.rs\n\t}}\n"
+        );
+
+        let lines = parse_line_porcelain(&output);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].hash, "aaaaaaaa");
+        assert_eq!(lines[0].date, "2023-11-14");
+        assert_eq!(lines[1].content, "    todo!()");
+        assert_eq!(lines[2].author, "Bob");
+        assert_eq!(lines[2].line, 3);
+
+        let text = format_blame(&lines);
+        assert!(text.contains("aaaaaaaa Ada Lovelace 2023-11-14     1 | fn parse() {"));
+        assert!(text.ends_with("Commits:\naaaaaaaa Add parser\nbbbbbbbb Close brace\n"));
     }
 }
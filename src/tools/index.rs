@@ -0,0 +1,247 @@
+//! Incremental file index for the codebase investigator.
+//!
+//! Maps each surveyed file to its modification time, a one-line summary, and
+//! the symbols it defines. The index lives at `.axiom/index/files.json` in the
+//! workspace; a refresh only re-reads files whose mtime or size changed, so
+//! repeated investigations skip most of the reading.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Files larger than this are listed without a summary or symbols.
+const MAX_INDEXED_BYTES: u64 = 256 * 1024;
+
+/// Most symbols kept per file.
+const MAX_SYMBOLS: usize = 20;
+
+/// Longest summary kept per file, in characters.
+const MAX_SUMMARY_CHARS: usize = 100;
+
+/// What the index knows about one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Modification time in seconds since the Unix epoch
+    pub mtime: u64,
+    pub size: u64,
+    /// First comment or code line, trimmed
+    pub summary: String,
+    /// Names of functions, types, and classes defined in the file
+    pub symbols: Vec<String>,
+}
+
+/// Counts from one `FileIndex::refresh`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshStats {
+    /// Entries whose mtime and size still matched
+    pub reused: usize,
+    /// Files read because they were new or changed
+    pub updated: usize,
+    /// Entries dropped because the file is gone or no longer surveyed
+    pub removed: usize,
+}
+
+/// Cached file summaries keyed by workspace-relative path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileIndex {
+    files: BTreeMap<PathBuf, IndexEntry>,
+}
+
+impl FileIndex {
+    /// Location of the index file for `workspace`.
+    pub fn path(workspace: &Path) -> PathBuf {
+        workspace.join(".axiom").join("index").join("files.json")
+    }
+
+    /// Load the index for `workspace`; a missing or unreadable index is empty.
+    pub fn load(workspace: &Path) -> Self {
+        fs::read_to_string(Self::path(workspace))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the index under `workspace`, with a `.gitignore` beside it so the
+    /// cache never gets committed.
+    pub fn save(&self, workspace: &Path) -> Result<()> {
+        let path = Self::path(workspace);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+            let gitignore = parent.join(".gitignore");
+            if !gitignore.exists() {
+                fs::write(&gitignore, "# Local cache written by axiom\n*\n")
+                    .with_context(|| format!("Failed to write {}", gitignore.display()))?;
+            }
+        }
+        let content = serde_json::to_string(self).context("Failed to serialize file index")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write file index to {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn get(&self, relative: &Path) -> Option<&IndexEntry> {
+        self.files.get(relative)
+    }
+
+    /// Bring the index in line with `files` (relative to `root`), re-reading
+    /// only files whose mtime or size changed.
+    pub fn refresh(&mut self, root: &Path, files: &[PathBuf]) -> RefreshStats {
+        let mut stats = RefreshStats::default();
+        let mut next = BTreeMap::new();
+        for relative in files {
+            let Ok(metadata) = fs::metadata(root.join(relative)) else {
+                continue;
+            };
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |elapsed| elapsed.as_secs());
+            let size = metadata.len();
+            let entry = match self.files.remove(relative) {
+                Some(entry) if entry.mtime == mtime && entry.size == size => {
+                    stats.reused += 1;
+                    entry
+                }
+                _ => {
+                    stats.updated += 1;
+                    index_file(&root.join(relative), mtime, size)
+                }
+            };
+            next.insert(relative.clone(), entry);
+        }
+        stats.removed = self.files.len();
+        self.files = next;
+        stats
+    }
+}
+
+/// Read one file and extract its summary and symbols.
+fn index_file(path: &Path, mtime: u64, size: u64) -> IndexEntry {
+    let content = if size <= MAX_INDEXED_BYTES {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    } else {
+        None
+    };
+    let content = content.unwrap_or_default();
+    IndexEntry {
+        mtime,
+        size,
+        summary: summarize(&content),
+        symbols: extract_symbols(&content),
+    }
+}
+
+/// First meaningful line, with comment markers stripped.
+fn summarize(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches(['/', '#', '!', '*', '-', '"', '<'])
+                .trim()
+        })
+        .find(|line| line.chars().any(char::is_alphanumeric))
+        .unwrap_or_default();
+    crate::utils::truncate_with_ellipsis(line, MAX_SUMMARY_CHARS, "...")
+}
+
+/// Names defined at the start of a line: Rust, Python, JS/TS, and Go items.
+fn extract_symbols(content: &str) -> Vec<String> {
+    static SYMBOL: OnceLock<Regex> = OnceLock::new();
+    let symbol = SYMBOL.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?(?:fn|struct|enum|trait|def|class|interface|function|func|type)\s+(?:\([^)]*\)\s*)?([A-Za-z_][A-Za-z0-9_]*)",
+        )
+        .expect("valid symbol regex")
+    });
+    let mut symbols: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if let Some(name) = symbol.captures(line).and_then(|caps| caps.get(1))
+            && !symbols.iter().any(|s| s == name.as_str())
+        {
+            symbols.push(name.as_str().to_string());
+            if symbols.len() == MAX_SYMBOLS {
+                break;
+            }
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn refresh_rereads_only_changed_files() {
+        let tmp = tempdir().expect("tempdir");
+        let root = tmp.path();
+        fs::write(
+            root.join("lib.rs"),
+            "//! Parser entry points\npub fn parse() {}\nstruct Token;\n",
+        )
+        .expect("write");
+        fs::write(
+            root.join("tool.py"),
+            "class Runner:\n    def run(self): pass\n",
+        )
+        .expect("write");
+        let files = vec![PathBuf::from("lib.rs"), PathBuf::from("tool.py")];
+
+        let mut index = FileIndex::default();
+        let stats = index.refresh(root, &files);
+        assert_eq!(stats.updated, 2);
+        let entry = index.get(Path::new("lib.rs")).expect("indexed");
+        assert_eq!(entry.summary, "Parser entry points");
+        assert_eq!(entry.symbols, vec!["parse", "Token"]);
+        assert_eq!(
+            index.get(Path::new("tool.py")).expect("indexed").symbols,
+            vec!["Runner", "run"]
+        );
+
+        index.save(root).expect("save");
+        let gitignore = root.join(".axiom").join("index").join(".gitignore");
+        assert!(
+            fs::read_to_string(gitignore)
+                .expect("gitignore")
+                .contains("*\n")
+        );
+        let mut index = FileIndex::load(root);
+        fs::write(root.join("tool.py"), "def main():\n    pass\n\n").expect("write");
+        let stats = index.refresh(root, &files[1..]);
+        assert_eq!(
+            stats,
+            RefreshStats {
+                reused: 0,
+                updated: 1,
+                removed: 1
+            }
+        );
+        assert_eq!(
+            index.get(Path::new("tool.py")).expect("indexed").symbols,
+            vec!["main"]
+        );
+
+        let stats = index.refresh(root, &files[1..]);
+        assert_eq!(stats.reused, 1);
+        assert_eq!(stats.updated, 0);
+    }
+}
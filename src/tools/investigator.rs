@@ -1,16 +1,213 @@
 //! Codebase Investigator tool: autonomous codebase analysis.
 //!
 //! Spawns a specialized sub-agent to explore and report on the codebase structure,
-//! patterns, and architecture.
+//! patterns, and architecture. Before spawning it surveys the workspace, skipping
+//! `.gitignore`/`.axiomignore` matches and vendored or generated directories, and
+//! hands the agent a capped file map so it starts in the relevant code. Summaries
+//! and symbols in the map come from the incremental index in `super::index`.
 
+use super::index::{FileIndex, RefreshStats};
 use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec, required_str,
 };
 use super::subagent::{SharedSubAgentManager, SubAgentRuntime, SubAgentType};
 use async_trait::async_trait;
 use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Directory names never surveyed: VCS data, build output, and dependencies.
+pub const SCAN_EXCLUDED_DIRS: &[&str] = &[
+    ".git",
+    ".axiom",
+    "target",
+    "node_modules",
+    ".codex",
+    ".aleph",
+    "dist",
+    "build",
+];
+
+/// Ignore files read from each surveyed directory.
+const IGNORE_FILES: &[&str] = &[".gitignore", ".axiomignore"];
+
+/// Most files listed in the file map handed to the investigator.
+const MAX_SURVEY_FILES: usize = 300;
+
+/// One pattern from an ignore file, relative to the directory holding it.
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    /// Directory of the ignore file, relative to the survey root
+    base: PathBuf,
+    pattern: String,
+    negated: bool,
+    dir_only: bool,
+    /// Pattern contains a `/`, so it matches the path rather than the name
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    fn parse(base: &Path, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let line = line.strip_prefix("**/").unwrap_or(line);
+        let anchored = line.contains('/');
+        let pattern = line.trim_start_matches('/').to_string();
+        (!pattern.is_empty()).then(|| Self {
+            base: base.to_path_buf(),
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(path) = relative.strip_prefix(&self.base) else {
+            return false;
+        };
+        if self.anchored {
+            glob_match(&path.to_string_lossy().replace('\\', "/"), &self.pattern)
+        } else {
+            path.file_name()
+                .is_some_and(|name| glob_match(&name.to_string_lossy(), &self.pattern))
+        }
+    }
+}
+
+/// Match `text` against a glob with `*` and `?` wildcards.
+fn glob_match(text: &str, pattern: &str) -> bool {
+    let (text, pattern): (Vec<char>, Vec<char>) =
+        (text.chars().collect(), pattern.chars().collect());
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            t += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Files found by a workspace survey.
+#[derive(Debug, Default)]
+pub struct Survey {
+    /// First `MAX_SURVEY_FILES` paths, relative to the root
+    pub files: Vec<PathBuf>,
+    /// Every file that was not ignored
+    pub total: usize,
+}
+
+/// Walk `root` breadth-first, skipping ignored paths and `SCAN_EXCLUDED_DIRS`.
+pub fn survey_workspace(root: &Path, max_files: usize) -> Survey {
+    let mut survey = Survey::default();
+    let mut rules: Vec<IgnoreRule> = Vec::new();
+    let mut queue = std::collections::VecDeque::from([PathBuf::new()]);
+
+    while let Some(relative_dir) = queue.pop_front() {
+        let dir = root.join(&relative_dir);
+        for name in IGNORE_FILES {
+            if let Ok(content) = fs::read_to_string(dir.join(name)) {
+                rules.extend(
+                    content
+                        .lines()
+                        .filter_map(|line| IgnoreRule::parse(&relative_dir, line)),
+                );
+            }
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(fs::DirEntry::file_name);
+        for entry in entries {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let is_dir = file_type.is_dir();
+            let name = entry.file_name();
+            if is_dir && SCAN_EXCLUDED_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                continue;
+            }
+            let relative = relative_dir.join(&name);
+            // Later rules win, so `!keep.rs` can re-include a file
+            let ignored = rules
+                .iter()
+                .rev()
+                .find(|rule| rule.matches(&relative, is_dir))
+                .is_some_and(|rule| !rule.negated);
+            if ignored {
+                continue;
+            }
+            if is_dir {
+                queue.push_back(relative);
+            } else if file_type.is_file() {
+                survey.total += 1;
+                if survey.files.len() < max_files {
+                    survey.files.push(relative);
+                }
+            }
+        }
+    }
+    survey
+}
+
+/// Survey `workspace` and bring its file index up to date, starting from an
+/// empty index when `rebuild` is set.
+pub fn survey_and_index(workspace: &Path, rebuild: bool) -> (Survey, FileIndex, RefreshStats) {
+    let survey = survey_workspace(workspace, MAX_SURVEY_FILES);
+    let mut index = if rebuild {
+        FileIndex::default()
+    } else {
+        FileIndex::load(workspace)
+    };
+    let stats = index.refresh(workspace, &survey.files);
+    if let Err(err) = index.save(workspace) {
+        crate::logging::warn(format!("Failed to save file index: {err:#}"));
+    }
+    (survey, index, stats)
+}
+
+/// One file-map line: path, summary, and defined symbols.
+fn file_map_line(path: &Path, index: &FileIndex) -> String {
+    let mut line = format!("- {}", path.display());
+    if let Some(entry) = index.get(path) {
+        if !entry.summary.is_empty() {
+            line.push_str(" — ");
+            line.push_str(&entry.summary);
+        }
+        if !entry.symbols.is_empty() {
+            line.push_str(&format!(" [{}]", entry.symbols.join(", ")));
+        }
+    }
+    line.push('\n');
+    line
+}
+
 /// Tool for investigating the codebase.
 pub struct CodebaseInvestigatorTool {
     manager: SharedSubAgentManager,
@@ -55,16 +252,35 @@ impl ToolSpec for CodebaseInvestigatorTool {
         ApprovalRequirement::Suggest
     }
 
-    async fn execute(&self, input: Value, _context: &ToolContext) -> Result<ToolResult, ToolError> {
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
         let objective = required_str(&input, "objective")?;
 
+        let workspace = context.workspace.clone();
+        let (survey, index, stats) =
+            tokio::task::spawn_blocking(move || survey_and_index(&workspace, false))
+                .await
+                .map_err(|e| {
+                    ToolError::execution_failed(format!("Failed to survey workspace: {e}"))
+                })?;
+        let file_map: String = survey
+            .files
+            .iter()
+            .map(|path| file_map_line(path, &index))
+            .collect();
+
         let prompt = format!(
             "Your task is to investigate the codebase with the following objective: {}\n\n\
-            1. Start by listing the root directory.\n\
+            Workspace file map (examined {} of {} files; paths matched by .gitignore or \
+            .axiomignore and vendored/build directories are excluded, so do not explore them):\n\
+            {}\n\
+            1. Start from the file map above rather than listing directories.\n\
             2. Explore relevant directories and read key files.\n\
             3. Identify core components, architectural patterns, and dependencies.\n\
             4. Provide a structured report summarizing your findings.",
-            objective
+            objective,
+            survey.files.len(),
+            survey.total,
+            file_map
         );
 
         let mut manager = self
@@ -84,6 +300,75 @@ impl ToolSpec for CodebaseInvestigatorTool {
                 ToolError::execution_failed(format!("Failed to spawn investigator: {e}"))
             })?;
 
-        Ok(ToolResult::json(&result).map_err(|e| ToolError::execution_failed(e.to_string()))?)
+        Ok(ToolResult::json(&result)
+            .map_err(|e| ToolError::execution_failed(e.to_string()))?
+            .with_metadata(json!({
+                "files_examined": survey.files.len(),
+                "files_total": survey.total,
+                "index_reused": stats.reused,
+                "index_updated": stats.updated,
+            })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, relative: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(path, "x").expect("write");
+    }
+
+    #[test]
+    fn survey_skips_ignored_and_excluded_paths() {
+        let tmp = tempdir().expect("tempdir");
+        let root = tmp.path();
+        write(root, "src/main.rs");
+        write(root, "src/gen/schema.rs");
+        write(root, "src/gen/keep.rs");
+        write(root, "node_modules/pkg/index.js");
+        write(root, "vendor/lib.c");
+        write(root, "app.log");
+        fs::write(root.join(".gitignore"), "*.log\n/vendor/\n").expect("write");
+        fs::write(root.join("src/.axiomignore"), "gen/*\n!gen/keep.rs\n").expect("write");
+
+        let survey = survey_workspace(root, MAX_SURVEY_FILES);
+        let files: Vec<String> = survey
+            .files
+            .iter()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ".gitignore",
+                "src/.axiomignore",
+                "src/main.rs",
+                "src/gen/keep.rs"
+            ]
+        );
+        assert_eq!(survey.total, 4);
+    }
+
+    #[test]
+    fn survey_caps_listed_files_but_counts_all() {
+        let tmp = tempdir().expect("tempdir");
+        for i in 0..5 {
+            write(tmp.path(), &format!("f{i}.txt"));
+        }
+        let survey = survey_workspace(tmp.path(), 2);
+        assert_eq!(survey.files.len(), 2);
+        assert_eq!(survey.total, 5);
+    }
+
+    #[test]
+    fn glob_match_handles_wildcards() {
+        assert!(glob_match("schema.rs", "*.rs"));
+        assert!(glob_match("a.b.log", "*.log"));
+        assert!(glob_match("f1.txt", "f?.txt"));
+        assert!(!glob_match("main.rs", "*.log"));
     }
 }
@@ -0,0 +1,742 @@
+//! Paid multimedia generation tools: `generate_image`, `tts`,
+//! `generate_music`, and `generate_video`.
+//!
+//! Every call is billed by the provider, so each tool requires explicit
+//! approval; the approval popup prices the call with `crate::pricing` before
+//! anything is sent. Generated files are written to `output_dir` (relative
+//! paths resolve against the workspace) and the result lists their paths.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::spec::{
+    ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
+    optional_str, optional_u64, required_str,
+};
+use crate::utils::output_path;
+
+const DEFAULT_API_BASE: &str = "https://api.axiom.io/v1";
+pub const DEFAULT_IMAGE_MODEL: &str = "image-01";
+pub const DEFAULT_AUDIO_MODEL: &str = "speech-02-hd";
+pub const DEFAULT_MUSIC_MODEL: &str = "music-1.5";
+pub const DEFAULT_VIDEO_MODEL: &str = "MiniMax-Hailuo-02";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const VIDEO_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const VIDEO_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Endpoint, credentials, and output location carried by `ToolContext`.
+#[derive(Debug, Clone)]
+pub struct MediaConfig {
+    /// API root including `/v1`, e.g. `https://api.axiom.io/v1`
+    pub api_base: String,
+    pub api_key: Option<String>,
+    /// Where generated files are saved
+    pub output_dir: PathBuf,
+    pub image_model: String,
+    pub audio_model: String,
+    pub music_model: String,
+    pub video_model: String,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            api_base: DEFAULT_API_BASE.to_string(),
+            api_key: None,
+            output_dir: PathBuf::from("outputs"),
+            image_model: DEFAULT_IMAGE_MODEL.to_string(),
+            audio_model: DEFAULT_AUDIO_MODEL.to_string(),
+            music_model: DEFAULT_MUSIC_MODEL.to_string(),
+            video_model: DEFAULT_VIDEO_MODEL.to_string(),
+        }
+    }
+}
+
+// === HTTP helpers ===
+
+struct MediaClient<'a> {
+    config: &'a MediaConfig,
+    http: reqwest::Client,
+}
+
+impl<'a> MediaClient<'a> {
+    fn new(config: &'a MediaConfig) -> Result<Self, ToolError> {
+        if config
+            .api_key
+            .as_deref()
+            .is_none_or(|key| key.trim().is_empty())
+        {
+            return Err(ToolError::not_available(
+                "Multimedia tools require an API key (api_key or AXIOM_API_KEY)",
+            ));
+        }
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                ToolError::execution_failed(format!("Failed to build HTTP client: {e}"))
+            })?;
+        Ok(Self { config, http })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.config.api_base.trim_end_matches('/'))
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> Result<Value, ToolError> {
+        let request = self.http.post(self.url(path)).json(body);
+        self.send(request).await
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value, ToolError> {
+        let mut url = reqwest::Url::parse(&self.url(path))
+            .map_err(|e| ToolError::execution_failed(format!("Invalid media URL: {e}")))?;
+        url.query_pairs_mut().extend_pairs(query);
+        self.send(self.http.get(url)).await
+    }
+
+    /// Send an authorized request; API-level failures arrive as HTTP 200 with
+    /// a non-zero `base_resp.status_code`.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, ToolError> {
+        let resp = request
+            .bearer_auth(self.config.api_key.as_deref().unwrap_or_default())
+            .send()
+            .await
+            .map_err(|e| ToolError::execution_failed(format!("Media request failed: {e}")))?;
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| ToolError::execution_failed(format!("Failed to read response: {e}")))?;
+        if !status.is_success() {
+            return Err(ToolError::execution_failed(format!(
+                "Media request failed: HTTP {}: {}",
+                status.as_u16(),
+                body.trim()
+            )));
+        }
+        let value: Value = serde_json::from_str(&body).map_err(|e| {
+            ToolError::execution_failed(format!("Failed to parse media response: {e}"))
+        })?;
+        if let Some(base) = value.get("base_resp")
+            && base
+                .get("status_code")
+                .and_then(Value::as_i64)
+                .is_some_and(|code| code != 0)
+        {
+            let message = base
+                .get("status_msg")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(ToolError::execution_failed(format!(
+                "Media request failed: {message}"
+            )));
+        }
+        Ok(value)
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, ToolError> {
+        let resp =
+            self.http.get(url).send().await.map_err(|e| {
+                ToolError::execution_failed(format!("Failed to download {url}: {e}"))
+            })?;
+        if !resp.status().is_success() {
+            return Err(ToolError::execution_failed(format!(
+                "Failed to download {url}: HTTP {}",
+                resp.status().as_u16()
+            )));
+        }
+        resp.bytes()
+            .await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|e| ToolError::execution_failed(format!("Failed to download {url}: {e}")))
+    }
+}
+
+/// Write `bytes` to a fresh timestamped file in the output directory.
+fn save_output(
+    context: &ToolContext,
+    prefix: &str,
+    extension: &str,
+    bytes: &[u8],
+) -> Result<PathBuf, ToolError> {
+    let dir = if context.media.output_dir.is_absolute() {
+        context.media.output_dir.clone()
+    } else {
+        context.workspace.join(&context.media.output_dir)
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        ToolError::execution_failed(format!("Failed to create {}: {e}", dir.display()))
+    })?;
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f");
+    let mut path = output_path(&dir, &format!("{prefix}_{stamp}.{extension}"));
+    let mut suffix = 1;
+    while path.exists() {
+        suffix += 1;
+        path = output_path(&dir, &format!("{prefix}_{stamp}_{suffix}.{extension}"));
+    }
+    std::fs::write(&path, bytes).map_err(|e| {
+        ToolError::execution_failed(format!("Failed to write {}: {e}", path.display()))
+    })?;
+    Ok(path)
+}
+
+/// Decode the hex-encoded audio returned by the speech and music APIs.
+fn decode_hex(hex: &str) -> Result<Vec<u8>, ToolError> {
+    let hex = hex.trim();
+    if !hex.len().is_multiple_of(2) {
+        return Err(ToolError::execution_failed(
+            "Failed to decode audio: odd-length hex string",
+        ));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| ToolError::execution_failed("Failed to decode audio: invalid hex"))
+        })
+        .collect()
+}
+
+fn hex_audio(response: &Value) -> Result<Vec<u8>, ToolError> {
+    let audio = response
+        .pointer("/data/audio")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ToolError::execution_failed("Media response contained no audio"))?;
+    decode_hex(audio)
+}
+
+fn saved_result(kind: &str, paths: &[PathBuf]) -> ToolResult {
+    let list: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    let mut content = format!("Saved {} {kind} file(s):", list.len());
+    for path in &list {
+        content.push_str("\n- ");
+        content.push_str(path);
+    }
+    ToolResult::success(content).with_metadata(json!({ "files": list }))
+}
+
+fn media_capabilities() -> Vec<ToolCapability> {
+    vec![
+        ToolCapability::Network,
+        ToolCapability::WritesFiles,
+        ToolCapability::RequiresApproval,
+    ]
+}
+
+fn required_prompt<'a>(input: &'a Value, field: &str) -> Result<&'a str, ToolError> {
+    let value = required_str(input, field)?.trim();
+    if value.is_empty() {
+        return Err(ToolError::invalid_input(format!("{field} cannot be empty")));
+    }
+    Ok(value)
+}
+
+// === generate_image ===
+
+pub struct GenerateImageTool;
+
+#[async_trait]
+impl ToolSpec for GenerateImageTool {
+    fn name(&self) -> &'static str {
+        "generate_image"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate images from a text prompt (paid). Saves the images to the output directory and returns their paths."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "Description of the image"
+                },
+                "aspect_ratio": {
+                    "type": "string",
+                    "enum": ["1:1", "16:9", "4:3", "3:2", "2:3", "3:4", "9:16", "21:9"],
+                    "description": "Aspect ratio (default: 1:1)"
+                },
+                "n": {
+                    "type": "integer",
+                    "description": "Number of images, 1-9 (default: 1)"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        media_capabilities()
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Required
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let prompt = required_prompt(&input, "prompt")?;
+        let aspect_ratio = optional_str(&input, "aspect_ratio").unwrap_or("1:1");
+        let n = optional_u64(&input, "n", 1).clamp(1, 9);
+
+        let client = MediaClient::new(&context.media)?;
+        let response = client
+            .post(
+                "image_generation",
+                &json!({
+                    "model": context.media.image_model,
+                    "prompt": prompt,
+                    "aspect_ratio": aspect_ratio,
+                    "n": n,
+                    "response_format": "url",
+                }),
+            )
+            .await?;
+        let urls: Vec<&str> = response
+            .pointer("/data/image_urls")
+            .and_then(Value::as_array)
+            .map(|urls| urls.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        if urls.is_empty() {
+            return Err(ToolError::execution_failed(
+                "Image response contained no images",
+            ));
+        }
+
+        let mut paths = Vec::with_capacity(urls.len());
+        for url in urls {
+            let bytes = client.download(url).await?;
+            paths.push(save_output(context, "image", "jpeg", &bytes)?);
+        }
+        Ok(saved_result("image", &paths))
+    }
+}
+
+// === tts ===
+
+pub struct TtsTool;
+
+#[async_trait]
+impl ToolSpec for TtsTool {
+    fn name(&self) -> &'static str {
+        "tts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Convert text to speech (paid, billed per character). Saves an MP3 to the output directory and returns its path."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "Text to speak"
+                },
+                "voice_id": {
+                    "type": "string",
+                    "description": "Voice to use (default: male-qn-qingse)"
+                },
+                "model": {
+                    "type": "string",
+                    "enum": ["speech-02-hd", "speech-02-turbo"],
+                    "description": "HD is higher quality, turbo is cheaper (default: default_audio_model, else speech-02-hd)"
+                }
+            },
+            "required": ["text"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        media_capabilities()
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Required
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let text = required_prompt(&input, "text")?;
+        let voice_id = optional_str(&input, "voice_id").unwrap_or("male-qn-qingse");
+        let model = optional_str(&input, "model").unwrap_or(&context.media.audio_model);
+
+        let client = MediaClient::new(&context.media)?;
+        let response = client
+            .post(
+                "t2a_v2",
+                &json!({
+                    "model": model,
+                    "text": text,
+                    "stream": false,
+                    "voice_setting": { "voice_id": voice_id, "speed": 1.0, "vol": 1.0, "pitch": 0 },
+                    "audio_setting": { "format": "mp3", "sample_rate": 32000, "bitrate": 128_000, "channel": 1 },
+                }),
+            )
+            .await?;
+        let path = save_output(context, "speech", "mp3", &hex_audio(&response)?)?;
+        Ok(saved_result("audio", &[path]))
+    }
+}
+
+// === generate_music ===
+
+pub struct GenerateMusicTool;
+
+#[async_trait]
+impl ToolSpec for GenerateMusicTool {
+    fn name(&self) -> &'static str {
+        "generate_music"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compose a song from a style prompt and lyrics (paid). Saves an MP3 to the output directory and returns its path."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "Style, mood, and instrumentation"
+                },
+                "lyrics": {
+                    "type": "string",
+                    "description": "Song lyrics; use [Verse]/[Chorus] tags and newlines between lines"
+                }
+            },
+            "required": ["prompt", "lyrics"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        media_capabilities()
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Required
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let prompt = required_prompt(&input, "prompt")?;
+        let lyrics = required_prompt(&input, "lyrics")?;
+
+        let client = MediaClient::new(&context.media)?;
+        let response = client
+            .post(
+                "music_generation",
+                &json!({
+                    "model": context.media.music_model,
+                    "prompt": prompt,
+                    "lyrics": lyrics,
+                    "audio_setting": { "format": "mp3", "sample_rate": 44100, "bitrate": 256_000 },
+                }),
+            )
+            .await?;
+        let path = save_output(context, "music", "mp3", &hex_audio(&response)?)?;
+        Ok(saved_result("audio", &[path]))
+    }
+}
+
+// === generate_video ===
+
+/// Video generation is asynchronous: submit a task, poll it, then download
+/// the finished file.
+pub struct GenerateVideoTool {
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl GenerateVideoTool {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            poll_interval: VIDEO_POLL_INTERVAL,
+            timeout: VIDEO_TIMEOUT,
+        }
+    }
+}
+
+impl Default for GenerateVideoTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ToolSpec for GenerateVideoTool {
+    fn name(&self) -> &'static str {
+        "generate_video"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate a short video from a text prompt (paid; takes several minutes). Saves an MP4 to the output directory and returns its path."
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "Description of the video"
+                },
+                "duration": {
+                    "type": "integer",
+                    "enum": [6, 10],
+                    "description": "Length in seconds (default: 6)"
+                },
+                "resolution": {
+                    "type": "string",
+                    "enum": ["768P", "1080P"],
+                    "description": "Resolution (default: 768P; 1080P supports 6s only)"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    fn capabilities(&self) -> Vec<ToolCapability> {
+        media_capabilities()
+    }
+
+    fn approval_requirement(&self) -> ApprovalRequirement {
+        ApprovalRequirement::Required
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
+        let prompt = required_prompt(&input, "prompt")?;
+        let duration = if optional_u64(&input, "duration", 6) > 6 {
+            10
+        } else {
+            6
+        };
+        let resolution = match optional_str(&input, "resolution") {
+            Some(res) if res.eq_ignore_ascii_case("1080P") => "1080P",
+            _ => "768P",
+        };
+
+        let client = MediaClient::new(&context.media)?;
+        let submitted = client
+            .post(
+                "video_generation",
+                &json!({
+                    "model": context.media.video_model,
+                    "prompt": prompt,
+                    "duration": duration,
+                    "resolution": resolution,
+                }),
+            )
+            .await?;
+        let task_id = submitted
+            .get("task_id")
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ToolError::execution_failed("Video response contained no task_id"))?
+            .to_string();
+
+        let started = std::time::Instant::now();
+        let file_id = loop {
+            let status = client
+                .get("query/video_generation", &[("task_id", &task_id)])
+                .await?;
+            match status.get("status").and_then(Value::as_str) {
+                Some("Success") => match status.get("file_id") {
+                    Some(Value::String(id)) if !id.is_empty() => break id.clone(),
+                    Some(Value::Number(id)) => break id.to_string(),
+                    _ => {
+                        return Err(ToolError::execution_failed("Finished video has no file_id"));
+                    }
+                },
+                Some("Fail") => {
+                    return Err(ToolError::execution_failed(format!(
+                        "Video task {task_id} failed"
+                    )));
+                }
+                _ => {}
+            }
+            if started.elapsed() >= self.timeout {
+                return Err(ToolError::Timeout {
+                    seconds: self.timeout.as_secs(),
+                });
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        };
+
+        let file = client
+            .get("files/retrieve", &[("file_id", &file_id)])
+            .await?;
+        let url = file
+            .pointer("/file/download_url")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ToolError::execution_failed("Video file has no download_url"))?;
+        let bytes = client.download(url).await?;
+        let path = save_output(context, "video", "mp4", &bytes)?;
+        Ok(saved_result("video", &[path]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn context(workspace: &std::path::Path, server: &MockServer) -> ToolContext {
+        ToolContext::new(workspace).with_media(MediaConfig {
+            api_base: format!("{}/v1", server.uri()),
+            api_key: Some("test-key".to_string()),
+            output_dir: PathBuf::from("outputs"),
+            ..MediaConfig::default()
+        })
+    }
+
+    #[test]
+    fn media_tools_require_approval() {
+        let tools: [&dyn ToolSpec; 4] = [
+            &GenerateImageTool,
+            &TtsTool,
+            &GenerateMusicTool,
+            &GenerateVideoTool::new(),
+        ];
+        for tool in tools {
+            assert_eq!(
+                tool.approval_requirement(),
+                ApprovalRequirement::Required,
+                "{}",
+                tool.name()
+            );
+            assert!(
+                crate::pricing::estimate_tool_cost(tool.name(), &json!({})).is_some(),
+                "{} has no cost estimate",
+                tool.name()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_hex_rejects_bad_input() {
+        assert_eq!(decode_hex("48690a").unwrap(), b"Hi\n");
+        assert!(decode_hex("abc").is_err());
+        assert!(decode_hex("zz").is_err());
+    }
+
+    #[tokio::test]
+    async fn generate_image_downloads_each_image() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/image_generation"))
+            .and(header("authorization", "Bearer test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "image_urls": [format!("{}/img/1.jpeg", server.uri())] },
+                "base_resp": { "status_code": 0, "status_msg": "success" },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/img/1.jpeg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg-bytes".to_vec()))
+            .mount(&server)
+            .await;
+
+        let tmp = tempdir().expect("tempdir");
+        let result = GenerateImageTool
+            .execute(
+                json!({"prompt": "a lighthouse"}),
+                &context(tmp.path(), &server),
+            )
+            .await
+            .expect("generate image");
+        let files = result.metadata.expect("metadata")["files"].clone();
+        let saved = PathBuf::from(files[0].as_str().expect("path"));
+        assert!(saved.starts_with(tmp.path().join("outputs")));
+        assert_eq!(std::fs::read(saved).expect("read"), b"jpeg-bytes");
+    }
+
+    #[tokio::test]
+    async fn api_errors_and_missing_keys_are_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/t2a_v2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "base_resp": { "status_code": 1008, "status_msg": "insufficient balance" },
+            })))
+            .mount(&server)
+            .await;
+
+        let tmp = tempdir().expect("tempdir");
+        let err = TtsTool
+            .execute(json!({"text": "hello"}), &context(tmp.path(), &server))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("insufficient balance"));
+
+        let no_key = ToolContext::new(tmp.path());
+        let err = TtsTool
+            .execute(json!({"text": "hello"}), &no_key)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("require an API key"));
+    }
+
+    #[tokio::test]
+    async fn generate_video_polls_until_the_file_is_ready() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/video_generation"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "task_id": "task-1",
+                "base_resp": { "status_code": 0 },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/query/video_generation"))
+            .and(query_param("task_id", "task-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "Processing"})))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/query/video_generation"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"status": "Success", "file_id": 42})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/files/retrieve"))
+            .and(query_param("file_id", "42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "file": { "download_url": format!("{}/video.mp4", server.uri()) },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/video.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"mp4".to_vec()))
+            .mount(&server)
+            .await;
+
+        let tool = GenerateVideoTool {
+            poll_interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+        let tmp = tempdir().expect("tempdir");
+        let result = tool
+            .execute(json!({"prompt": "waves"}), &context(tmp.path(), &server))
+            .await
+            .expect("generate video");
+        assert!(result.content.starts_with("Saved 1 video file(s):"));
+        assert!(result.content.contains(".mp4"));
+    }
+}
@@ -10,7 +10,9 @@ pub mod duo;
 pub mod execution;
 pub mod file;
 pub mod git;
+pub mod index;
 pub mod investigator;
+pub mod media;
 pub mod memory;
 pub mod patch;
 pub mod plan;
@@ -34,7 +36,10 @@ pub use spec::ToolContext;
 pub use coding::{CodingCompleteTool, CodingReviewTool};
 
 // Re-export git tools
-pub use git::{GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitStatusTool};
+pub use git::{
+    GitBlameTool, GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitStashTool,
+    GitStatusTool,
+};
 
 // Re-export memory tools
 pub use memory::{GetMemoryTool, SaveMemoryTool};
@@ -61,7 +66,7 @@ pub use web_search::{WebFetchTool, WebSearchTool};
 pub use patch::ApplyPatchTool;
 
 // Re-export file tools
-pub use file::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
+pub use file::{EditFileTool, ListDirTool, ReadFileTool, ReadManyFilesTool, WriteFileTool};
 
 // Re-export shell types
 pub use shell::{ExecShellInteractTool, ExecShellKillTool, ExecShellTool, ExecShellWaitTool};
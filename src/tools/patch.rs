@@ -18,6 +18,13 @@ use super::spec::{
 /// Maximum lines of context for fuzzy matching
 const MAX_FUZZ: usize = 50;
 
+/// Unchanged lines shown around each change by [`unified_diff`]
+const DIFF_CONTEXT: usize = 3;
+
+/// Changed regions larger than this (old lines × new lines) are diffed as a
+/// single remove/add block instead of line by line
+const DIFF_LCS_LIMIT: usize = 1_000_000;
+
 // === Types ===
 
 /// Result of applying a patch
@@ -437,6 +444,134 @@ fn matches_at_position(lines: &[String], old_lines: &[&str], pos: usize) -> bool
     true
 }
 
+// === Diff generation ===
+
+/// Render a unified diff of `old` → `new` for `path`, with a few lines of
+/// context around each change. Returns an empty string when nothing changed.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    // Merge each change's context window into hunk ranges over `ops`.
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (idx, op) in ops.iter().enumerate() {
+        if matches!(op, HunkLine::Context(_)) {
+            continue;
+        }
+        let start = idx.saturating_sub(DIFF_CONTEXT);
+        let end = (idx + DIFF_CONTEXT + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    if ranges.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let (mut old_line, mut new_line, mut cursor) = (0, 0, 0);
+    for (start, end) in ranges {
+        for op in &ops[cursor..start] {
+            old_line += usize::from(!matches!(op, HunkLine::Add(_)));
+            new_line += usize::from(!matches!(op, HunkLine::Remove(_)));
+        }
+        let hunk = &ops[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, HunkLine::Add(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, HunkLine::Remove(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            old_line + usize::from(old_count > 0),
+            new_line + usize::from(new_count > 0),
+        ));
+        for op in hunk {
+            let (prefix, text) = match op {
+                HunkLine::Context(text) => (' ', text),
+                HunkLine::Remove(text) => ('-', text),
+                HunkLine::Add(text) => ('+', text),
+            };
+            out.push(prefix);
+            out.push_str(text);
+            out.push('\n');
+        }
+        old_line += old_count;
+        new_line += new_count;
+        cursor = end;
+    }
+    out
+}
+
+/// Line-level diff: common prefix/suffix are kept as context and the middle is
+/// aligned by longest common subsequence when small enough.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<HunkLine> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<HunkLine> = old[..prefix]
+        .iter()
+        .map(|line| HunkLine::Context((*line).to_string()))
+        .collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > DIFF_LCS_LIMIT {
+        ops.extend(
+            old_mid
+                .iter()
+                .map(|line| HunkLine::Remove((*line).to_string())),
+        );
+        ops.extend(
+            new_mid
+                .iter()
+                .map(|line| HunkLine::Add((*line).to_string())),
+        );
+    } else {
+        // lcs[i][j] = length of the LCS of old_mid[i..] and new_mid[j..]
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                ops.push(HunkLine::Context(old_mid[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push(HunkLine::Remove(old_mid[i].to_string()));
+                i += 1;
+            } else {
+                ops.push(HunkLine::Add(new_mid[j].to_string()));
+                j += 1;
+            }
+        }
+    }
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| HunkLine::Context((*line).to_string())),
+    );
+    ops
+}
+
 // === Unit Tests ===
 
 #[cfg(test)]
@@ -444,6 +579,31 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_unified_diff_hunks_round_trip() {
+        let old: String = (1..=20).map(|n| format!("line{n}\n")).collect();
+        let new = old
+            .replace("line2\n", "line2 changed\n")
+            .replace("line18\n", "");
+
+        let diff = unified_diff("f.txt", &old, &new);
+        assert!(diff.starts_with(
+            "--- a/f.txt\n+++ b/f.txt\n@@ -1,5 +1,5 @@\n line1\n-line2\n+line2 changed\n"
+        ));
+        assert!(diff.contains("@@ -15,6 +15,5 @@\n"));
+        assert!(diff.contains("\n-line18\n"));
+
+        let hunks = parse_unified_diff(&diff).unwrap();
+        let mut lines: Vec<String> = old.lines().map(String::from).collect();
+        let mut offset = 0isize;
+        for hunk in &hunks {
+            apply_hunk(&mut lines, hunk, 0, &mut offset).unwrap();
+        }
+        assert_eq!(lines.join("\n") + "\n", new);
+
+        assert!(unified_diff("f.txt", &old, &old).is_empty());
+    }
+
     #[test]
     fn test_parse_range() {
         assert_eq!(parse_range("10,5").unwrap(), (10, 5));
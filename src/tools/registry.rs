@@ -211,6 +211,11 @@ impl ToolRegistry {
         self.tools.remove(name)
     }
 
+    /// Keep only the tools whose names match `keep`.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.tools.retain(|name, _| keep(name));
+    }
+
     /// Clear all tools from the registry.
     pub fn clear(&mut self) {
         self.tools.clear();
@@ -243,21 +248,25 @@ impl ToolRegistryBuilder {
         self
     }
 
-    /// Include file tools (read, write, edit, list).
+    /// Include file tools (read, read many, write, edit, list).
     #[must_use]
     pub fn with_file_tools(self) -> Self {
-        use super::file::{EditFileTool, ListDirTool, ReadFileTool, WriteFileTool};
+        use super::file::{
+            EditFileTool, ListDirTool, ReadFileTool, ReadManyFilesTool, WriteFileTool,
+        };
         self.with_tool(Arc::new(ReadFileTool))
+            .with_tool(Arc::new(ReadManyFilesTool))
             .with_tool(Arc::new(WriteFileTool))
             .with_tool(Arc::new(EditFileTool))
             .with_tool(Arc::new(ListDirTool))
     }
 
-    /// Include only read-only file tools (read, list).
+    /// Include only read-only file tools (read, read many, list).
     #[must_use]
     pub fn with_read_only_file_tools(self) -> Self {
-        use super::file::{ListDirTool, ReadFileTool};
+        use super::file::{ListDirTool, ReadFileTool, ReadManyFilesTool};
         self.with_tool(Arc::new(ReadFileTool))
+            .with_tool(Arc::new(ReadManyFilesTool))
             .with_tool(Arc::new(ListDirTool))
     }
 
@@ -298,6 +307,16 @@ impl ToolRegistryBuilder {
             .with_tool(Arc::new(WebFetchTool))
     }
 
+    /// Include paid multimedia tools (image, speech, music, video).
+    #[must_use]
+    pub fn with_media_tools(self) -> Self {
+        use super::media::{GenerateImageTool, GenerateMusicTool, GenerateVideoTool, TtsTool};
+        self.with_tool(Arc::new(GenerateImageTool))
+            .with_tool(Arc::new(TtsTool))
+            .with_tool(Arc::new(GenerateMusicTool))
+            .with_tool(Arc::new(GenerateVideoTool::new()))
+    }
+
     /// Include patch tools (`apply_patch`).
     #[must_use]
     pub fn with_patch_tools(self) -> Self {
@@ -308,11 +327,16 @@ impl ToolRegistryBuilder {
     /// Include git tools.
     #[must_use]
     pub fn with_git_tools(self) -> Self {
-        use super::git::{GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitStatusTool};
+        use super::git::{
+            GitBlameTool, GitBranchTool, GitCommitTool, GitDiffTool, GitLogTool, GitStashTool,
+            GitStatusTool,
+        };
         self.with_tool(Arc::new(GitDiffTool))
             .with_tool(Arc::new(GitStatusTool))
             .with_tool(Arc::new(GitCommitTool))
             .with_tool(Arc::new(GitLogTool))
+            .with_tool(Arc::new(GitBlameTool))
+            .with_tool(Arc::new(GitStashTool))
             .with_tool(Arc::new(GitBranchTool))
     }
 
@@ -354,6 +378,9 @@ impl ToolRegistryBuilder {
         runtime: super::subagent::SubAgentRuntime,
     ) -> Self {
         use super::investigator::CodebaseInvestigatorTool;
+        if !subagents_enabled(&manager) {
+            return self;
+        }
         self.with_tool(Arc::new(CodebaseInvestigatorTool::new(manager, runtime)))
     }
 
@@ -365,6 +392,9 @@ impl ToolRegistryBuilder {
         runtime: super::subagent::SubAgentRuntime,
     ) -> Self {
         use super::security::SecurityAnalyzeTool;
+        if !subagents_enabled(&manager) {
+            return self;
+        }
         self.with_tool(Arc::new(SecurityAnalyzeTool::new(manager, runtime)))
     }
 
@@ -463,7 +493,8 @@ impl ToolRegistryBuilder {
             .with_tool(Arc::new(DuoListDirTool::new(workspace)))
     }
 
-    /// Include sub-agent management tools.
+    /// Include sub-agent management tools. Skipped when the manager allows no
+    /// sub-agents (`--max-subagents 0`).
     #[must_use]
     pub fn with_subagent_tools(
         self,
@@ -471,6 +502,9 @@ impl ToolRegistryBuilder {
         runtime: super::subagent::SubAgentRuntime,
     ) -> Self {
         use super::subagent::{AgentCancelTool, AgentListTool, AgentResultTool, AgentSpawnTool};
+        if !subagents_enabled(&manager) {
+            return self;
+        }
 
         self.with_tool(Arc::new(AgentSpawnTool::new(manager.clone(), runtime)))
             .with_tool(Arc::new(AgentResultTool::new(manager.clone())))
@@ -493,6 +527,13 @@ impl Default for ToolRegistryBuilder {
     }
 }
 
+/// Whether `manager` may run any sub-agents; `--max-subagents 0` disables them.
+fn subagents_enabled(manager: &super::subagent::SharedSubAgentManager) -> bool {
+    manager
+        .lock()
+        .map_or(true, |manager| manager.max_agents() > 0)
+}
+
 // === Unit Tests ===
 
 #[cfg(test)]
@@ -556,6 +597,35 @@ mod tests {
         })
     }
 
+    #[tokio::test]
+    async fn test_subagent_tools_omitted_when_max_subagents_is_zero() {
+        use crate::tools::subagent::{SubAgentRuntime, new_shared_subagent_manager};
+
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        let config = crate::config::Config {
+            api_key: Some("test-key".to_string()),
+            ..Default::default()
+        };
+        let client = crate::client::AnthropicClient::new(&config).expect("client");
+        let runtime = SubAgentRuntime::new(client, "model".to_string(), ctx.clone(), false, None);
+
+        let build = |max_agents: usize| {
+            let manager = new_shared_subagent_manager(tmp.path().to_path_buf(), max_agents);
+            ToolRegistryBuilder::new()
+                .with_subagent_tools(manager.clone(), runtime.clone())
+                .with_investigator_tool(manager, runtime.clone())
+                .build(ctx.clone())
+        };
+
+        let enabled = build(2);
+        assert!(enabled.contains("agent_spawn"));
+        assert!(enabled.contains("agent_list"));
+
+        let disabled = build(0);
+        assert!(disabled.is_empty());
+    }
+
     #[test]
     fn test_registry_register_and_get() {
         let tmp = tempdir().expect("tempdir");
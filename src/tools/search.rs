@@ -111,29 +111,14 @@ impl ToolSpec for GrepFilesTool {
             .unwrap_or_default();
 
         // Parse exclude patterns
-        let exclude_patterns: Vec<String> =
-            input.get("exclude").and_then(|v| v.as_array()).map_or_else(
-                || {
-                    // Default exclusions for common non-code directories
-                    vec![
-                        "node_modules/*".to_string(),
-                        ".git/*".to_string(),
-                        "target/*".to_string(),
-                        "*.min.js".to_string(),
-                        "*.min.css".to_string(),
-                        "dist/*".to_string(),
-                        "build/*".to_string(),
-                        "__pycache__/*".to_string(),
-                        ".venv/*".to_string(),
-                        "venv/*".to_string(),
-                    ]
-                },
-                |arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(String::from))
-                        .collect()
-                },
-            );
+        let exclude_patterns: Vec<String> = input
+            .get("exclude")
+            .and_then(|v| v.as_array())
+            .map_or_else(default_excludes, |arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
 
         // Build regex
         let regex_pattern = if case_insensitive {
@@ -225,8 +210,27 @@ impl ToolSpec for GrepFilesTool {
     }
 }
 
+/// Default exclusions for common non-code directories
+pub(super) fn default_excludes() -> Vec<String> {
+    [
+        "node_modules/*",
+        ".git/*",
+        "target/*",
+        "*.min.js",
+        "*.min.css",
+        "dist/*",
+        "build/*",
+        "__pycache__/*",
+        ".venv/*",
+        "venv/*",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
 /// Collect files to search based on include/exclude patterns
-fn collect_files(
+pub(super) fn collect_files(
     root: &Path,
     include_patterns: &[String],
     exclude_patterns: &[String],
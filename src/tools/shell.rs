@@ -2,10 +2,12 @@
 //!
 //! Provides:
 //! - Synchronous command execution with timeout
-//! - Background process execution
+//! - Background process execution, with output readable while it runs
 //! - Process output retrieval
 //! - Process termination
-//! - Sandbox support (macOS Seatbelt)
+//! - Sandboxing via `SandboxManager` (workspace-write unless trust mode is on)
+//! - Output capping: oversized stdout/stderr keep their head and tail, with the
+//!   full output saved to a temp file the agent can grep
 //! - Streaming output (future)
 
 use anyhow::{Context, Result, anyhow};
@@ -27,8 +29,8 @@ use crate::sandbox::{
     SandboxType,
 };
 
-/// Maximum output size before truncation (30KB like Claude Code)
-const MAX_OUTPUT_SIZE: usize = 30_000;
+/// Default per-stream output cap before truncation (`shell_max_output_bytes`).
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 100_000;
 
 static SHELL_MANAGERS: OnceLock<Mutex<HashMap<PathBuf, SharedShellManager>>> = OnceLock::new();
 
@@ -60,6 +62,9 @@ pub struct ShellResult {
     /// Whether the command was blocked by sandbox restrictions.
     #[serde(default)]
     pub sandbox_denied: bool,
+    /// Temp file holding the untruncated output, set when either stream was capped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_output_path: Option<PathBuf>,
 }
 
 /// A background shell process being tracked
@@ -73,9 +78,27 @@ pub struct BackgroundShell {
     pub stderr: String,
     pub started_at: Instant,
     pub sandbox_type: SandboxType,
+    max_output_bytes: usize,
+    full_output_path: Option<PathBuf>,
     child: Option<Child>,
-    stdout_thread: Option<std::thread::JoinHandle<Vec<u8>>>,
-    stderr_thread: Option<std::thread::JoinHandle<Vec<u8>>>,
+    stdout_buf: OutputBuffer,
+    stderr_buf: OutputBuffer,
+    /// Byte offsets into the buffers already returned by `read_new_output`
+    streamed: (usize, usize),
+    stdout_thread: Option<std::thread::JoinHandle<()>>,
+    stderr_thread: Option<std::thread::JoinHandle<()>>,
+}
+
+/// Output captured from a background process as it is produced.
+type OutputBuffer = Arc<Mutex<Vec<u8>>>;
+
+/// Output a background task produced since the previous `read_new_output` call.
+#[derive(Debug, Clone)]
+pub struct ShellOutputDelta {
+    pub stdout: String,
+    pub stderr: String,
+    pub status: ShellStatus,
+    pub exit_code: Option<i32>,
 }
 
 impl BackgroundShell {
@@ -110,16 +133,15 @@ impl BackgroundShell {
 
     /// Collect output from the background threads
     fn collect_output(&mut self) {
-        if let Some(handle) = self.stdout_thread.take()
-            && let Ok(data) = handle.join()
-        {
-            self.stdout = String::from_utf8_lossy(&data).to_string();
+        if let Some(handle) = self.stdout_thread.take() {
+            let _ = handle.join();
         }
-        if let Some(handle) = self.stderr_thread.take()
-            && let Ok(data) = handle.join()
-        {
-            self.stderr = String::from_utf8_lossy(&data).to_string();
+        if let Some(handle) = self.stderr_thread.take() {
+            let _ = handle.join();
         }
+        self.stdout = buffer_text(&self.stdout_buf);
+        self.stderr = buffer_text(&self.stderr_buf);
+        self.full_output_path = save_full_output(&self.stdout, &self.stderr, self.max_output_bytes);
     }
 
     /// Kill the process
@@ -133,15 +155,48 @@ impl BackgroundShell {
         Ok(())
     }
 
+    /// Output produced since the last call, holding back a trailing partial
+    /// UTF-8 sequence until the rest of it arrives.
+    fn take_new_output(&mut self) -> ShellOutputDelta {
+        // Only reap once the readers hit EOF, so a grandchild holding the pipes
+        // open can't block the caller on the thread joins in `collect_output`.
+        let readers_done = [&self.stdout_thread, &self.stderr_thread]
+            .into_iter()
+            .all(|thread| {
+                thread
+                    .as_ref()
+                    .is_none_or(std::thread::JoinHandle::is_finished)
+            });
+        if readers_done {
+            self.poll();
+        }
+        let finished = self.status != ShellStatus::Running;
+        let (stdout, stdout_end) = utf8_since(&self.stdout_buf, self.streamed.0, finished);
+        let (stderr, stderr_end) = utf8_since(&self.stderr_buf, self.streamed.1, finished);
+        self.streamed = (stdout_end, stderr_end);
+        ShellOutputDelta {
+            stdout,
+            stderr,
+            status: self.status.clone(),
+            exit_code: self.exit_code,
+        }
+    }
+
     /// Get a snapshot of the current state
     pub fn snapshot(&self) -> ShellResult {
         let sandboxed = !matches!(self.sandbox_type, SandboxType::None);
+        let sandbox_denied = self.status == ShellStatus::Failed
+            && SandboxManager::was_denied(
+                self.sandbox_type,
+                self.exit_code.unwrap_or(-1),
+                &self.stderr,
+            );
         ShellResult {
             task_id: Some(self.id.clone()),
             status: self.status.clone(),
             exit_code: self.exit_code,
-            stdout: truncate_output(&self.stdout),
-            stderr: truncate_output(&self.stderr),
+            stdout: truncate_output(&self.stdout, self.max_output_bytes),
+            stderr: truncate_output(&self.stderr, self.max_output_bytes),
             duration_ms: u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
             sandboxed,
             sandbox_type: if sandboxed {
@@ -149,7 +204,8 @@ impl BackgroundShell {
             } else {
                 None
             },
-            sandbox_denied: false, // Determined after completion
+            sandbox_denied,
+            full_output_path: self.full_output_path.clone(),
         }
     }
 }
@@ -160,6 +216,7 @@ pub struct ShellManager {
     default_workspace: PathBuf,
     sandbox_manager: SandboxManager,
     sandbox_policy: ExecutionSandboxPolicy,
+    max_output_bytes: usize,
 }
 
 impl ShellManager {
@@ -170,6 +227,7 @@ impl ShellManager {
             default_workspace: workspace,
             sandbox_manager: SandboxManager::new(),
             sandbox_policy: ExecutionSandboxPolicy::default(),
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
         }
     }
 
@@ -180,6 +238,7 @@ impl ShellManager {
             default_workspace: workspace,
             sandbox_manager: SandboxManager::new(),
             sandbox_policy: policy,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
         }
     }
 
@@ -193,11 +252,31 @@ impl ShellManager {
         &self.sandbox_policy
     }
 
+    /// Set the per-stream output cap for future commands.
+    pub fn set_max_output_bytes(&mut self, max_bytes: usize) {
+        self.max_output_bytes = max_bytes;
+    }
+
     /// Check if sandboxing is available on this platform.
     pub fn is_sandbox_available(&mut self) -> bool {
         self.sandbox_manager.is_available()
     }
 
+    /// Resolve the policy for a command, keeping the workspace writable even
+    /// when the command runs from a different directory.
+    fn effective_policy(
+        &self,
+        policy_override: Option<ExecutionSandboxPolicy>,
+    ) -> ExecutionSandboxPolicy {
+        let mut policy = policy_override.unwrap_or_else(|| self.sandbox_policy.clone());
+        if let ExecutionSandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut policy
+            && !writable_roots.contains(&self.default_workspace)
+        {
+            writable_roots.push(self.default_workspace.clone());
+        }
+        policy
+    }
+
     /// Execute a shell command with the configured sandbox policy.
     pub fn execute(
         &mut self,
@@ -224,7 +303,7 @@ impl ShellManager {
         let timeout_ms = timeout_ms.clamp(1000, 600_000);
 
         // Use override policy if provided, otherwise use the manager's policy
-        let policy = policy_override.unwrap_or_else(|| self.sandbox_policy.clone());
+        let policy = self.effective_policy(policy_override);
 
         // Create command spec and prepare sandboxed environment
         let spec = CommandSpec::shell(command, work_dir.clone(), Duration::from_millis(timeout_ms))
@@ -234,7 +313,13 @@ impl ShellManager {
         if background {
             self.spawn_background_sandboxed(command, &work_dir, &exec_env)
         } else {
-            Self::execute_sync_sandboxed(command, &work_dir, timeout_ms, &exec_env)
+            Self::execute_sync_sandboxed(
+                command,
+                &work_dir,
+                timeout_ms,
+                &exec_env,
+                self.max_output_bytes,
+            )
         }
     }
 
@@ -259,7 +344,7 @@ impl ShellManager {
         let work_dir = working_dir.map_or_else(|| self.default_workspace.clone(), PathBuf::from);
 
         let timeout_ms = timeout_ms.clamp(1000, 600_000);
-        let policy = policy_override.unwrap_or_else(|| self.sandbox_policy.clone());
+        let policy = self.effective_policy(policy_override);
 
         let spec = CommandSpec::shell(command, work_dir.clone(), Duration::from_millis(timeout_ms))
             .with_policy(policy);
@@ -274,6 +359,7 @@ impl ShellManager {
         working_dir: &std::path::Path,
         timeout_ms: u64,
         exec_env: &ExecEnv,
+        max_output_bytes: usize,
     ) -> Result<ShellResult> {
         let started = Instant::now();
         let timeout = Duration::from_millis(timeout_ms);
@@ -321,6 +407,7 @@ impl ShellManager {
         if let Some(status) = child.wait_timeout(timeout)? {
             let stdout = stdout_thread.join().unwrap_or_default();
             let stderr = stderr_thread.join().unwrap_or_default();
+            let stdout_str = String::from_utf8_lossy(&stdout);
             let stderr_str = String::from_utf8_lossy(&stderr);
             let exit_code = status.code().unwrap_or(-1);
 
@@ -335,8 +422,8 @@ impl ShellManager {
                     ShellStatus::Failed
                 },
                 exit_code: status.code(),
-                stdout: truncate_output(&String::from_utf8_lossy(&stdout)),
-                stderr: truncate_output(&stderr_str),
+                stdout: truncate_output(&stdout_str, max_output_bytes),
+                stderr: truncate_output(&stderr_str, max_output_bytes),
                 duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
                 sandboxed,
                 sandbox_type: if sandboxed {
@@ -345,6 +432,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied,
+                full_output_path: save_full_output(&stdout_str, &stderr_str, max_output_bytes),
             })
         } else {
             // Timeout - kill the process
@@ -352,13 +440,15 @@ impl ShellManager {
             let status = child.wait().ok();
             let stdout = stdout_thread.join().unwrap_or_default();
             let stderr = stderr_thread.join().unwrap_or_default();
+            let stdout_str = String::from_utf8_lossy(&stdout);
+            let stderr_str = String::from_utf8_lossy(&stderr);
 
             Ok(ShellResult {
                 task_id: None,
                 status: ShellStatus::TimedOut,
                 exit_code: status.and_then(|s| s.code()),
-                stdout: truncate_output(&String::from_utf8_lossy(&stdout)),
-                stderr: truncate_output(&String::from_utf8_lossy(&stderr)),
+                stdout: truncate_output(&stdout_str, max_output_bytes),
+                stderr: truncate_output(&stderr_str, max_output_bytes),
                 duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
                 sandboxed,
                 sandbox_type: if sandboxed {
@@ -367,6 +457,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                full_output_path: save_full_output(&stdout_str, &stderr_str, max_output_bytes),
             })
         }
     }
@@ -420,6 +511,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                full_output_path: None,
             })
         } else {
             let _ = child.kill();
@@ -439,6 +531,7 @@ impl ShellManager {
                     None
                 },
                 sandbox_denied: false,
+                full_output_path: None,
             })
         }
     }
@@ -477,24 +570,13 @@ impl ShellManager {
         let stdout_handle = child.stdout.take();
         let stderr_handle = child.stderr.take();
 
-        // Spawn threads to collect output
-        let stdout_thread = stdout_handle.map(|handle| {
-            std::thread::spawn(move || {
-                let mut reader = handle;
-                let mut buf = Vec::new();
-                let _ = reader.read_to_end(&mut buf);
-                buf
-            })
-        });
-
-        let stderr_thread = stderr_handle.map(|handle| {
-            std::thread::spawn(move || {
-                let mut reader = handle;
-                let mut buf = Vec::new();
-                let _ = reader.read_to_end(&mut buf);
-                buf
-            })
-        });
+        // Spawn threads that append output as it arrives
+        let stdout_buf = OutputBuffer::default();
+        let stderr_buf = OutputBuffer::default();
+        let stdout_thread =
+            stdout_handle.map(|handle| spawn_output_reader(handle, Arc::clone(&stdout_buf)));
+        let stderr_thread =
+            stderr_handle.map(|handle| spawn_output_reader(handle, Arc::clone(&stderr_buf)));
 
         let bg_shell = BackgroundShell {
             id: task_id.clone(),
@@ -506,7 +588,12 @@ impl ShellManager {
             stderr: String::new(),
             started_at: started,
             sandbox_type,
+            max_output_bytes: self.max_output_bytes,
+            full_output_path: None,
             child: Some(child),
+            stdout_buf,
+            stderr_buf,
+            streamed: (0, 0),
             stdout_thread,
             stderr_thread,
         };
@@ -527,6 +614,7 @@ impl ShellManager {
                 None
             },
             sandbox_denied: false,
+            full_output_path: None,
         })
     }
 
@@ -564,6 +652,16 @@ impl ShellManager {
         Ok(shell.snapshot())
     }
 
+    /// Output a background process produced since the previous call, for
+    /// streaming it while the process runs.
+    pub fn read_new_output(&mut self, task_id: &str) -> Result<ShellOutputDelta> {
+        let shell = self
+            .processes
+            .get_mut(task_id)
+            .ok_or_else(|| anyhow!("Task {task_id} not found"))?;
+        Ok(shell.take_new_output())
+    }
+
     /// Kill a running background process
     pub fn kill(&mut self, task_id: &str) -> Result<ShellResult> {
         let shell = self
@@ -601,18 +699,125 @@ impl ShellManager {
     }
 }
 
-/// Truncate output to `MAX_OUTPUT_SIZE`
-fn truncate_output(output: &str) -> String {
-    if output.len() <= MAX_OUTPUT_SIZE {
-        output.to_string()
-    } else {
-        let truncated = truncate_to_boundary(output, MAX_OUTPUT_SIZE);
-        format!(
-            "{}...\n\n[Output truncated at {} characters. {} characters omitted.]",
-            truncated,
-            MAX_OUTPUT_SIZE,
-            output.len() - MAX_OUTPUT_SIZE
-        )
+/// Read `reader` to EOF on a thread, appending each chunk to `buf`.
+fn spawn_output_reader(
+    mut reader: impl Read + Send + 'static,
+    buf: OutputBuffer,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut chunk = [0u8; 8192];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => {
+                    if let Ok(mut buf) = buf.lock() {
+                        buf.extend_from_slice(&chunk[..n]);
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+    })
+}
+
+fn buffer_text(buf: &OutputBuffer) -> String {
+    buf.lock()
+        .map(|data| String::from_utf8_lossy(&data).into_owned())
+        .unwrap_or_default()
+}
+
+/// Text in `buf` from byte `from`, and the offset to resume from. An incomplete
+/// trailing UTF-8 sequence is left for the next read unless the stream is done.
+fn utf8_since(buf: &OutputBuffer, from: usize, finished: bool) -> (String, usize) {
+    let Ok(data) = buf.lock() else {
+        return (String::new(), from);
+    };
+    let bytes = data.get(from..).unwrap_or_default();
+    let end = match std::str::from_utf8(bytes) {
+        Err(err) if err.error_len().is_none() && !finished => err.valid_up_to(),
+        _ => bytes.len(),
+    };
+    (
+        String::from_utf8_lossy(&bytes[..end]).into_owned(),
+        from + end,
+    )
+}
+
+/// Cap output at `max_bytes`, keeping the head and tail and noting how much of
+/// the middle was dropped.
+fn truncate_output(output: &str, max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return output.to_string();
+    }
+    let head = truncate_to_boundary(output, max_bytes / 2);
+    let mut tail_start = output.len() - max_bytes / 2;
+    while !output.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+    let tail = &output[tail_start..];
+    let omitted = output.len() - head.len() - tail.len();
+    format!("{head}\n… [{omitted} bytes truncated] …\n{tail}")
+}
+
+/// Prefix of the temp files holding untruncated shell output.
+const FULL_OUTPUT_PREFIX: &str = "axiom-shell-";
+
+/// Full-output files older than this are removed the next time one is written.
+const FULL_OUTPUT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Write the untruncated output to a temp file when either stream exceeds
+/// `max_bytes`, returning its path. The file is readable only by the user,
+/// since command output can carry secrets.
+fn save_full_output(stdout: &str, stderr: &str, max_bytes: usize) -> Option<PathBuf> {
+    if stdout.len() <= max_bytes && stderr.len() <= max_bytes {
+        return None;
+    }
+    static PRUNED: OnceLock<()> = OnceLock::new();
+    PRUNED.get_or_init(|| prune_full_outputs(&std::env::temp_dir(), FULL_OUTPUT_MAX_AGE));
+
+    let mut contents = stdout.to_string();
+    if !stderr.is_empty() {
+        contents.push_str("\n\nSTDERR:\n");
+        contents.push_str(stderr);
+    }
+    let saved = tempfile::Builder::new()
+        .prefix(FULL_OUTPUT_PREFIX)
+        .suffix(".log")
+        .tempfile()
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.keep().map_err(|err| err.error)
+        });
+    match saved {
+        Ok((_, path)) => Some(path),
+        Err(err) => {
+            tracing::warn!("Failed to save full shell output: {err}");
+            None
+        }
+    }
+}
+
+/// Remove full-output files in `dir` last modified more than `max_age` ago.
+fn prune_full_outputs(dir: &std::path::Path, max_age: Duration) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with(FULL_OUTPUT_PREFIX) || !name.ends_with(".log") {
+            continue;
+        }
+        let stale = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > max_age);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
     }
 }
 
@@ -638,7 +843,9 @@ fn shell_manager_key(workspace: &std::path::Path) -> PathBuf {
         .unwrap_or_else(|_| workspace.to_path_buf())
 }
 
-fn shared_shell_manager(workspace: &std::path::Path) -> SharedShellManager {
+/// The per-workspace manager that owns background tasks, shared by the shell
+/// tools and the TUI shell mode so task ids work across both.
+pub fn shared_shell_manager(workspace: &std::path::Path) -> SharedShellManager {
     let key = shell_manager_key(workspace);
     let managers = SHELL_MANAGERS.get_or_init(|| Mutex::new(HashMap::new()));
     let mut managers = managers.lock().expect("lock shell manager registry");
@@ -660,6 +867,10 @@ use async_trait::async_trait;
 use serde_json::json;
 
 fn policy_override_from_context(context: &ToolContext) -> Option<ExecutionSandboxPolicy> {
+    // Trust (YOLO) mode opts out of sandboxing entirely.
+    if context.trust_mode {
+        return Some(ExecutionSandboxPolicy::DangerFullAccess);
+    }
     match &context.sandbox_policy {
         ToolSandboxPolicy::None => None,
         ToolSandboxPolicy::Standard {
@@ -668,6 +879,7 @@ fn policy_override_from_context(context: &ToolContext) -> Option<ExecutionSandbo
         } => Some(ExecutionSandboxPolicy::WorkspaceWrite {
             writable_roots: writable_roots.clone(),
             network_access: *allow_network,
+            allowed_hosts: vec![],
             exclude_tmpdir: false,
             exclude_slash_tmp: false,
         }),
@@ -675,7 +887,7 @@ fn policy_override_from_context(context: &ToolContext) -> Option<ExecutionSandbo
 }
 
 fn format_shell_result(result: &ShellResult, interactive: bool, wait_mode: bool) -> (String, bool) {
-    let output = if interactive {
+    let mut output = if interactive {
         format!(
             "Interactive command completed (exit code: {:?})",
             result.exit_code
@@ -696,16 +908,38 @@ fn format_shell_result(result: &ShellResult, interactive: bool, wait_mode: bool)
             format!("Background task started: {task_id_str}")
         }
     } else {
-        format!(
+        let mut output = format!(
             "Command failed (exit code: {:?})\n\nSTDOUT:\n{}\n\nSTDERR:\n{}",
             result.exit_code, result.stdout, result.stderr
-        )
+        );
+        if result.sandbox_denied {
+            output.push_str("\n\nSANDBOX: ");
+            output.push_str(&sandbox_denial_hint(result));
+        }
+        output
     };
+    if let Some(path) = &result.full_output_path {
+        output.push_str(&format!(
+            "\n\n[Output truncated; full output saved to {}]",
+            path.display()
+        ));
+    }
 
     let success = result.status == ShellStatus::Completed || result.status == ShellStatus::Running;
     (output, success)
 }
 
+fn sandbox_denial_hint(result: &ShellResult) -> String {
+    let sandbox_type = match result.sandbox_type.as_deref() {
+        #[cfg(target_os = "macos")]
+        Some("macos-seatbelt") => SandboxType::MacosSeatbelt,
+        #[cfg(target_os = "linux")]
+        Some("linux-landlock") => SandboxType::LinuxLandlock,
+        _ => SandboxType::None,
+    };
+    SandboxManager::denial_message(sandbox_type, &result.stderr)
+}
+
 /// Tool for executing shell commands.
 pub struct ExecShellTool;
 
@@ -806,9 +1040,11 @@ impl ToolSpec for ExecShellTool {
             let mut manager = manager
                 .lock()
                 .map_err(|_| ToolError::execution_failed("Failed to lock shell manager"))?;
+            manager.set_max_output_bytes(context.shell_max_output_bytes);
             manager.execute_with_policy(command, None, timeout_ms, true, policy_override)
         } else {
             let mut manager = ShellManager::new(context.workspace.clone());
+            manager.set_max_output_bytes(context.shell_max_output_bytes);
             if interactive {
                 manager.execute_interactive_with_policy(command, None, timeout_ms, policy_override)
             } else {
@@ -828,10 +1064,12 @@ impl ToolSpec for ExecShellTool {
                         "status": format!("{:?}", result.status),
                         "duration_ms": result.duration_ms,
                         "sandboxed": result.sandboxed,
+                        "sandbox_type": result.sandbox_type,
                         "sandbox_denied": result.sandbox_denied,
                         "task_id": result.task_id,
                         "safety_level": format!("{:?}", safety.level),
                         "interactive": interactive,
+                        "full_output_path": result.full_output_path,
                     })),
                 })
             }
@@ -917,6 +1155,7 @@ impl ToolSpec for ExecShellWaitTool {
                 "status": format!("{:?}", result.status),
                 "duration_ms": result.duration_ms,
                 "sandboxed": result.sandboxed,
+                "sandbox_type": result.sandbox_type,
                 "sandbox_denied": result.sandbox_denied,
                 "task_id": result.task_id,
             })),
@@ -991,6 +1230,7 @@ impl ToolSpec for ExecShellKillTool {
                 "status": format!("{:?}", result.status),
                 "duration_ms": result.duration_ms,
                 "sandboxed": result.sandboxed,
+                "sandbox_type": result.sandbox_type,
                 "sandbox_denied": result.sandbox_denied,
                 "task_id": result.task_id,
             })),
@@ -1248,13 +1488,151 @@ mod tests {
         assert_eq!(killed.status, ShellStatus::Killed);
     }
 
+    #[test]
+    fn test_effective_policy_keeps_workspace_writable() {
+        let tmp = tempdir().expect("tempdir");
+        let manager = ShellManager::new(tmp.path().to_path_buf());
+
+        match manager.effective_policy(None) {
+            ExecutionSandboxPolicy::WorkspaceWrite { writable_roots, .. } => {
+                assert_eq!(writable_roots, vec![tmp.path().to_path_buf()]);
+            }
+            other => panic!("unexpected policy: {other:?}"),
+        }
+
+        let full = manager.effective_policy(Some(ExecutionSandboxPolicy::DangerFullAccess));
+        assert_eq!(full, ExecutionSandboxPolicy::DangerFullAccess);
+    }
+
+    #[test]
+    fn test_trust_mode_disables_sandbox() {
+        let tmp = tempdir().expect("tempdir");
+        let ctx = ToolContext::new(tmp.path().to_path_buf());
+        assert!(policy_override_from_context(&ctx).is_none());
+
+        let trusted = ctx.with_trust_mode(true);
+        assert_eq!(
+            policy_override_from_context(&trusted),
+            Some(ExecutionSandboxPolicy::DangerFullAccess)
+        );
+
+        let mut manager = ShellManager::new(tmp.path().to_path_buf());
+        let result = manager
+            .execute_with_policy(
+                &echo_command("trusted"),
+                None,
+                5000,
+                false,
+                policy_override_from_context(&trusted),
+            )
+            .expect("execute");
+        assert!(!result.sandboxed);
+        assert!(result.sandbox_type.is_none());
+    }
+
     #[test]
     fn test_output_truncation() {
-        let long_output = "x".repeat(50_000);
-        let truncated = truncate_output(&long_output);
+        let long_output = format!("head{}tail", "x".repeat(200_000));
+        let truncated = truncate_output(&long_output, DEFAULT_MAX_OUTPUT_BYTES);
 
         assert!(truncated.len() < long_output.len());
-        assert!(truncated.contains("truncated"));
+        assert!(truncated.starts_with("head"));
+        assert!(truncated.ends_with("tail"));
+        assert!(truncated.contains("… [100008 bytes truncated] …"));
+        assert_eq!(truncate_output("short", 10), "short");
+    }
+
+    #[test]
+    fn test_background_output_streams_incrementally() {
+        let tmp = tempdir().expect("tempdir");
+        let mut manager = ShellManager::new(tmp.path().to_path_buf());
+        let task_id = manager
+            .execute(&echo_command("streamed"), None, 5000, true)
+            .expect("execute")
+            .task_id
+            .expect("task id");
+
+        let mut stdout = String::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let delta = manager.read_new_output(&task_id).expect("read output");
+            stdout.push_str(&delta.stdout);
+            if delta.status != ShellStatus::Running || Instant::now() > deadline {
+                assert_eq!(delta.status, ShellStatus::Completed);
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(stdout.contains("streamed"));
+        let again = manager.read_new_output(&task_id).expect("read output");
+        assert!(again.stdout.is_empty());
+    }
+
+    #[test]
+    fn test_partial_utf8_is_held_until_complete() {
+        let buf = OutputBuffer::default();
+        buf.lock().unwrap().extend_from_slice(&"é".as_bytes()[..1]);
+        assert_eq!(utf8_since(&buf, 0, false), (String::new(), 0));
+        buf.lock().unwrap().extend_from_slice(&"é".as_bytes()[1..]);
+        assert_eq!(utf8_since(&buf, 0, false), ("é".to_string(), 2));
+    }
+
+    #[test]
+    fn test_capped_output_is_saved_in_full() {
+        let tmp = tempdir().expect("tempdir");
+        let mut manager = ShellManager::new(tmp.path().to_path_buf());
+        manager.set_max_output_bytes(8);
+
+        let result = manager
+            .execute(&echo_command("0123456789abcdef"), None, 5000, false)
+            .expect("execute");
+
+        assert!(result.stdout.contains("bytes truncated"));
+        let path = result.full_output_path.clone().expect("full output path");
+        let full = std::fs::read_to_string(&path).expect("read full output");
+        assert!(full.contains("0123456789abcdef"));
+        let (formatted, _) = format_shell_result(&result, false, false);
+        assert!(formatted.contains(&path.display().to_string()));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_full_output_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = save_full_output("secret", "", 0).expect("save full output");
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_prune_full_outputs_removes_only_stale_logs() {
+        let tmp = tempdir().expect("tempdir");
+        let stale = tmp.path().join(format!("{FULL_OUTPUT_PREFIX}old.log"));
+        let fresh = tmp.path().join(format!("{FULL_OUTPUT_PREFIX}new.log"));
+        let other = tmp.path().join("unrelated.log");
+        for path in [&stale, &fresh, &other] {
+            std::fs::write(path, "output").expect("write");
+        }
+        let two_days_ago = std::time::SystemTime::now() - Duration::from_secs(48 * 60 * 60);
+        for path in [&stale, &other] {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_modified(two_days_ago))
+                .expect("set mtime");
+        }
+
+        prune_full_outputs(tmp.path(), FULL_OUTPUT_MAX_AGE);
+
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(other.exists());
     }
 
     #[tokio::test]
@@ -13,6 +13,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+use super::media::MediaConfig;
+use super::shell::DEFAULT_MAX_OUTPUT_BYTES;
+use super::web_search::WebSearchConfig;
+
 /// Capabilities that a tool may have or require.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ToolCapability {
@@ -162,7 +166,8 @@ impl ToolResult {
 /// Sandbox policy for command execution.
 #[derive(Debug, Clone, Default)]
 pub enum SandboxPolicy {
-    /// No sandboxing (dangerous but sometimes needed)
+    /// No explicit policy: the shell tool falls back to its default
+    /// workspace-write sandbox (or full access in trust mode)
     #[default]
     None,
     /// Standard sandbox with workspace write access
@@ -185,6 +190,12 @@ pub struct ToolContext {
     pub notes_path: PathBuf,
     /// MCP configuration path
     pub mcp_config_path: PathBuf,
+    /// Backend and credentials for `web_search`
+    pub web_search: WebSearchConfig,
+    /// Per-stream byte cap for `exec_shell` output
+    pub shell_max_output_bytes: usize,
+    /// Endpoint, credentials, and output directory for multimedia tools
+    pub media: MediaConfig,
 }
 
 impl ToolContext {
@@ -200,6 +211,9 @@ impl ToolContext {
             sandbox_policy: SandboxPolicy::None,
             notes_path,
             mcp_config_path,
+            web_search: WebSearchConfig::default(),
+            shell_max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            media: MediaConfig::default(),
         }
     }
 
@@ -216,6 +230,9 @@ impl ToolContext {
             sandbox_policy: SandboxPolicy::None,
             notes_path: notes_path.into(),
             mcp_config_path: mcp_config_path.into(),
+            web_search: WebSearchConfig::default(),
+            shell_max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            media: MediaConfig::default(),
         }
     }
 
@@ -346,6 +363,27 @@ impl ToolContext {
         self.sandbox_policy = policy;
         self
     }
+
+    /// Set the `web_search` backend.
+    #[must_use]
+    pub fn with_web_search(mut self, web_search: WebSearchConfig) -> Self {
+        self.web_search = web_search;
+        self
+    }
+
+    /// Set the `exec_shell` output cap.
+    #[must_use]
+    pub fn with_shell_max_output_bytes(mut self, max_bytes: usize) -> Self {
+        self.shell_max_output_bytes = max_bytes;
+        self
+    }
+
+    /// Set the multimedia endpoint, credentials, and output directory.
+    #[must_use]
+    pub fn with_media(mut self, media: MediaConfig) -> Self {
+        self.media = media;
+        self
+    }
 }
 
 fn normalize_path(path: &Path) -> PathBuf {
@@ -437,6 +475,12 @@ pub trait ToolSpec: Send + Sync {
         false
     }
 
+    /// Returns a preview of the tool's effect to show in the approval prompt
+    /// (e.g. a unified diff of a pending edit), if it can be computed cheaply.
+    fn approval_preview(&self, _input: &Value, _context: &ToolContext) -> Option<String> {
+        None
+    }
+
     /// Execute the tool with the given input and context.
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError>;
 }
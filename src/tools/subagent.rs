@@ -17,8 +17,10 @@ use tokio::{sync::mpsc, task::JoinHandle};
 use uuid::Uuid;
 
 use crate::client::AnthropicClient;
+use crate::compaction::estimate_request_tokens;
 use crate::core::events::Event;
 use crate::models::{ContentBlock, Message, MessageRequest, SystemPrompt, Tool};
+use crate::pricing::{self, SharedCostBudget};
 use crate::tools::plan::{PlanState, SharedPlanState};
 use crate::tools::registry::{ToolRegistry, ToolRegistryBuilder};
 use crate::tools::spec::{
@@ -97,10 +99,12 @@ impl SubAgentType {
             Self::Explore => vec![
                 "list_dir",
                 "read_file",
+                "read_many_files",
                 "grep_files",
                 "exec_shell",
                 "git_status",
                 "git_log",
+                "git_blame",
                 "web_search",
                 "web_fetch",
             ],
@@ -146,6 +150,8 @@ pub struct SubAgentRuntime {
     pub context: ToolContext,
     pub allow_shell: bool,
     pub event_tx: Option<mpsc::Sender<Event>>,
+    /// Session budget that sub-agent calls are checked and charged against
+    pub cost_budget: Option<SharedCostBudget>,
 }
 
 impl SubAgentRuntime {
@@ -164,8 +170,16 @@ impl SubAgentRuntime {
             context,
             allow_shell,
             event_tx,
+            cost_budget: None,
         }
     }
+
+    /// Check and charge sub-agent calls against the session budget.
+    #[must_use]
+    pub fn with_cost_budget(mut self, cost_budget: SharedCostBudget) -> Self {
+        self.cost_budget = Some(cost_budget);
+        self
+    }
 }
 
 /// A running sub-agent instance.
@@ -179,6 +193,8 @@ pub struct SubAgent {
     pub started_at: Instant,
     pub allowed_tools: Vec<String>,
     task_handle: Option<JoinHandle<()>>,
+    /// Where to report the outcome if the agent is cancelled before it finishes
+    event_tx: Option<mpsc::Sender<Event>>,
 }
 
 impl SubAgent {
@@ -196,6 +212,7 @@ impl SubAgent {
             started_at: Instant::now(),
             allowed_tools,
             task_handle: None,
+            event_tx: None,
         }
     }
 
@@ -233,6 +250,12 @@ impl SubAgentManager {
         }
     }
 
+    /// Maximum number of concurrently running agents; 0 means sub-agents are disabled.
+    #[must_use]
+    pub fn max_agents(&self) -> usize {
+        self.max_agents
+    }
+
     /// Count running agents.
     fn running_count(&self) -> usize {
         self.agents
@@ -280,6 +303,7 @@ impl SubAgentManager {
             started_at,
             max_steps,
         };
+        agent.event_tx.clone_from(&task.runtime.event_tx);
         let handle = tokio::spawn(run_subagent_task(task));
         agent.task_handle = Some(handle);
         self.agents.insert(agent_id.clone(), agent);
@@ -312,6 +336,14 @@ impl SubAgentManager {
             if let Some(handle) = agent.task_handle.take() {
                 handle.abort();
             }
+            // The aborted task never reports back, so report for it
+            if let Some(event_tx) = &agent.event_tx {
+                let _ = event_tx.try_send(Event::AgentComplete {
+                    id: agent.id.clone(),
+                    status: SubAgentStatus::Cancelled,
+                    result: "Cancelled".to_string(),
+                });
+            }
         }
 
         Ok(agent.snapshot())
@@ -354,10 +386,10 @@ impl SubAgentManager {
 /// Thread-safe wrapper for `SubAgentManager`.
 pub type SharedSubAgentManager = Arc<Mutex<SubAgentManager>>;
 
-/// Create a shared sub-agent manager with a configurable limit.
+/// Create a shared sub-agent manager with a configurable limit (0 disables spawning).
 #[must_use]
 pub fn new_shared_subagent_manager(workspace: PathBuf, max_agents: usize) -> SharedSubAgentManager {
-    let max_agents = max_agents.clamp(1, 5);
+    let max_agents = max_agents.min(5);
     Arc::new(Mutex::new(SubAgentManager::new(workspace, max_agents)))
 }
 
@@ -683,13 +715,17 @@ async fn run_subagent_task(task: SubAgentTask) {
     }
 
     if let Some(event_tx) = task.runtime.event_tx {
-        let status = match &result {
-            Ok(res) => summarize_subagent_result(res),
-            Err(err) => format!("Failed: {err}"),
+        let (status, summary) = match &result {
+            Ok(res) => (res.status.clone(), summarize_subagent_result(res)),
+            Err(err) => (
+                SubAgentStatus::Failed(err.to_string()),
+                format!("Failed: {err}"),
+            ),
         };
         let _ = event_tx.try_send(Event::AgentComplete {
             id: task.agent_id,
-            result: status,
+            status,
+            result: summary,
         });
     }
 }
@@ -749,7 +785,28 @@ async fn run_subagent(
             top_p: None,
         };
 
+        // Stop before a call that could push spend past the session budget
+        if let Some(budget) = &runtime.cost_budget {
+            let input_tokens =
+                estimate_request_tokens(&request.messages, &request.system, &request.tools);
+            let estimate_usd = pricing::token_cost(
+                u32::try_from(input_tokens).unwrap_or(u32::MAX),
+                request.max_tokens,
+            );
+            let exceeded = budget
+                .lock()
+                .ok()
+                .filter(|budget| budget.would_exceed(estimate_usd))
+                .map(|budget| budget.summary());
+            if let Some(summary) = exceeded {
+                return Err(anyhow!("Session budget reached ({summary})"));
+            }
+        }
+
         let response = runtime.client.create_message(request).await?;
+        if let Some(mut budget) = runtime.cost_budget.as_ref().and_then(|b| b.lock().ok()) {
+            budget.record(pricing::usage_cost(&response.usage));
+        }
 
         let mut tool_uses = Vec::new();
         for block in &response.content {
@@ -1072,4 +1129,67 @@ mod tests {
 
         assert_eq!(manager.running_count(), 1);
     }
+
+    #[test]
+    fn test_cancel_reports_completion() {
+        let (event_tx, mut event_rx) = mpsc::channel(4);
+        let mut manager = SubAgentManager::new(PathBuf::from("."), 1);
+        let mut agent = SubAgent::new(
+            SubAgentType::Explore,
+            "prompt".to_string(),
+            vec!["read_file".to_string()],
+        );
+        agent.event_tx = Some(event_tx);
+        let id = agent.id.clone();
+        manager.agents.insert(id.clone(), agent);
+
+        manager.cancel(&id).expect("cancel");
+        let Ok(Event::AgentComplete {
+            id: reported,
+            status,
+            ..
+        }) = event_rx.try_recv()
+        else {
+            panic!("expected a completion event");
+        };
+        assert_eq!(reported, id);
+        assert_eq!(status, SubAgentStatus::Cancelled);
+
+        // Cancelling again reports nothing new
+        manager.cancel(&id).expect("cancel");
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_spent_budget_stops_before_calling_the_api() {
+        let config = crate::config::Config {
+            api_key: Some("test-key".to_string()),
+            ..Default::default()
+        };
+        let client = AnthropicClient::new(&config).expect("client");
+        let budget = crate::pricing::new_shared_cost_budget(Some(0.01));
+        budget.lock().unwrap().record(0.01);
+        let runtime = SubAgentRuntime::new(
+            client,
+            "model".to_string(),
+            ToolContext::new(PathBuf::from(".")),
+            false,
+            None,
+        )
+        .with_cost_budget(budget.clone());
+
+        let err = run_subagent(
+            &runtime,
+            "agent_test".to_string(),
+            SubAgentType::Explore,
+            "prompt".to_string(),
+            vec!["read_file".to_string()],
+            Instant::now(),
+            3,
+        )
+        .await
+        .expect_err("budget should stop the agent");
+        assert!(err.to_string().contains("Session budget reached"), "{err}");
+        assert!((budget.lock().unwrap().spent_usd - 0.01).abs() < f64::EPSILON);
+    }
 }
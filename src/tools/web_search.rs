@@ -1,4 +1,12 @@
-//! Web search tool backed by DuckDuckGo HTML results.
+//! Web search tool with pluggable backends (DuckDuckGo, Brave, SearXNG).
+//!
+//! The backend comes from `search_backend` in the config and reaches the tool
+//! through `ToolContext::web_search`. Every provider normalizes its results
+//! into `WebSearchEntry`, so the transcript rendering is backend-agnostic.
+//!
+//! `web_fetch` keeps a small process-wide LRU of fetched pages (see
+//! `FetchCache`), so repeated fetches of the same URL within the TTL skip the
+//! network. `/clearcache` flushes it.
 
 use super::spec::{
     ApprovalRequirement, ToolCapability, ToolContext, ToolError, ToolResult, ToolSpec,
@@ -8,12 +16,20 @@ use async_trait::async_trait;
 use regex::Regex;
 use serde::Serialize;
 use serde_json::{Value, json};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 const DEFAULT_MAX_RESULTS: usize = 5;
 const MAX_RESULTS: usize = 10;
 const DEFAULT_TIMEOUT_MS: u64 = 15_000;
 const USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+const DUCKDUCKGO_BASE_URL: &str = "https://html.duckduckgo.com";
+const BRAVE_BASE_URL: &str = "https://api.search.brave.com";
+const DEFAULT_FETCH_CACHE_TTL_SECS: u64 = 300;
+const FETCH_CACHE_CAPACITY: usize = 32;
 
 #[derive(Debug, Clone, Serialize)]
 struct WebSearchEntry {
@@ -31,6 +47,246 @@ struct WebSearchResponse {
     results: Vec<WebSearchEntry>,
 }
 
+// === Backend configuration ===
+
+/// Search service used by `web_search`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchBackend {
+    /// DuckDuckGo HTML results; no key required.
+    #[default]
+    DuckDuckGo,
+    /// Brave Search API; requires an API key.
+    Brave,
+    /// A SearXNG instance's JSON API; requires a base URL.
+    Searxng,
+}
+
+impl SearchBackend {
+    /// Config value for this backend.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DuckDuckGo => "duckduckgo",
+            Self::Brave => "brave",
+            Self::Searxng => "searxng",
+        }
+    }
+}
+
+impl fmt::Display for SearchBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SearchBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "duckduckgo" | "ddg" => Ok(Self::DuckDuckGo),
+            "brave" => Ok(Self::Brave),
+            "searxng" | "searx" => Ok(Self::Searxng),
+            other => Err(format!(
+                "Unknown search backend '{other}' (expected duckduckgo, brave, or searxng)"
+            )),
+        }
+    }
+}
+
+/// Resolved web search settings carried by `ToolContext`.
+#[derive(Debug, Clone, Default)]
+pub struct WebSearchConfig {
+    pub backend: SearchBackend,
+    pub api_key: Option<String>,
+    pub base_url: Option<String>,
+    /// How long `web_fetch` reuses a fetched page; `None` means the default
+    /// (5 minutes) and `Some(0)` disables the cache.
+    pub fetch_cache_ttl_secs: Option<u64>,
+}
+
+impl WebSearchConfig {
+    fn fetch_cache_ttl(&self) -> Duration {
+        Duration::from_secs(
+            self.fetch_cache_ttl_secs
+                .unwrap_or(DEFAULT_FETCH_CACHE_TTL_SECS),
+        )
+    }
+}
+
+// === Providers ===
+
+/// A search service that turns a query into normalized results.
+#[async_trait]
+trait SearchProvider: Send + Sync {
+    /// Name reported as the response `source`.
+    fn name(&self) -> &'static str;
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<WebSearchEntry>, ToolError>;
+}
+
+fn provider_for(config: &WebSearchConfig) -> Result<Box<dyn SearchProvider>, ToolError> {
+    let base_url = config
+        .base_url
+        .as_deref()
+        .map(|url| url.trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty());
+    let api_key = config.api_key.clone().filter(|key| !key.trim().is_empty());
+    match config.backend {
+        SearchBackend::DuckDuckGo => Ok(Box::new(DuckDuckGoProvider {
+            base_url: base_url.unwrap_or_else(|| DUCKDUCKGO_BASE_URL.to_string()),
+        })),
+        SearchBackend::Brave => {
+            let api_key = api_key.ok_or_else(|| {
+                ToolError::execution_failed(
+                    "Brave search requires search_api_key (or AXIOM_SEARCH_API_KEY)",
+                )
+            })?;
+            Ok(Box::new(BraveProvider {
+                base_url: base_url.unwrap_or_else(|| BRAVE_BASE_URL.to_string()),
+                api_key,
+            }))
+        }
+        SearchBackend::Searxng => {
+            let base_url = base_url.ok_or_else(|| {
+                ToolError::execution_failed(
+                    "SearXNG search requires search_base_url (or AXIOM_SEARCH_BASE_URL)",
+                )
+            })?;
+            Ok(Box::new(SearxngProvider { base_url, api_key }))
+        }
+    }
+}
+
+/// Send `request` and return the body, failing on non-2xx responses.
+async fn fetch_body(request: reqwest::RequestBuilder) -> Result<String, ToolError> {
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| ToolError::execution_failed(format!("Web search request failed: {e}")))?;
+
+    let status = resp.status();
+    let body = resp
+        .text()
+        .await
+        .map_err(|e| ToolError::execution_failed(format!("Failed to read response: {e}")))?;
+
+    if !status.is_success() {
+        return Err(ToolError::execution_failed(format!(
+            "Web search failed: HTTP {}",
+            status.as_u16()
+        )));
+    }
+    Ok(body)
+}
+
+fn parse_json_body(body: &str) -> Result<Value, ToolError> {
+    serde_json::from_str(body)
+        .map_err(|e| ToolError::execution_failed(format!("Failed to parse search response: {e}")))
+}
+
+struct DuckDuckGoProvider {
+    base_url: String,
+}
+
+#[async_trait]
+impl SearchProvider for DuckDuckGoProvider {
+    fn name(&self) -> &'static str {
+        "duckduckgo"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<WebSearchEntry>, ToolError> {
+        let url = format!("{}/html/?q={}", self.base_url, url_encode(query));
+        let body = fetch_body(
+            client
+                .get(&url)
+                .header(
+                    "Accept",
+                    "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+                )
+                .header("Accept-Language", "en-US,en;q=0.5"),
+        )
+        .await?;
+        Ok(parse_duckduckgo_results(&body, max_results))
+    }
+}
+
+struct BraveProvider {
+    base_url: String,
+    api_key: String,
+}
+
+#[async_trait]
+impl SearchProvider for BraveProvider {
+    fn name(&self) -> &'static str {
+        "brave"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<WebSearchEntry>, ToolError> {
+        let url = format!(
+            "{}/res/v1/web/search?q={}&count={max_results}",
+            self.base_url,
+            url_encode(query)
+        );
+        let body = fetch_body(
+            client
+                .get(&url)
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", &self.api_key),
+        )
+        .await?;
+        Ok(parse_brave_results(&parse_json_body(&body)?, max_results))
+    }
+}
+
+struct SearxngProvider {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl SearchProvider for SearxngProvider {
+    fn name(&self) -> &'static str {
+        "searxng"
+    }
+
+    async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<WebSearchEntry>, ToolError> {
+        let url = format!(
+            "{}/search?q={}&format=json",
+            self.base_url,
+            url_encode(query)
+        );
+        let mut request = client.get(&url).header("Accept", "application/json");
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let body = fetch_body(request).await?;
+        Ok(parse_searxng_results(&parse_json_body(&body)?, max_results))
+    }
+}
+
+// === WebSearchTool ===
+
 pub struct WebSearchTool;
 
 #[async_trait]
@@ -72,7 +328,7 @@ impl ToolSpec for WebSearchTool {
         ApprovalRequirement::Auto
     }
 
-    async fn execute(&self, input: Value, _context: &ToolContext) -> Result<ToolResult, ToolError> {
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
         let query = required_str(&input, "query")?.trim().to_string();
         if query.is_empty() {
             return Err(ToolError::invalid_input("Query cannot be empty"));
@@ -86,6 +342,7 @@ impl ToolSpec for WebSearchTool {
         .clamp(1, MAX_RESULTS);
         let timeout_ms = optional_u64(&input, "timeout_ms", DEFAULT_TIMEOUT_MS).min(60_000);
 
+        let provider = provider_for(&context.web_search)?;
         let client = reqwest::Client::builder()
             .timeout(Duration::from_millis(timeout_ms))
             .user_agent(USER_AGENT)
@@ -94,33 +351,7 @@ impl ToolSpec for WebSearchTool {
                 ToolError::execution_failed(format!("Failed to build HTTP client: {e}"))
             })?;
 
-        let encoded = url_encode(&query);
-        let url = format!("https://html.duckduckgo.com/html/?q={encoded}");
-        let resp = client
-            .get(&url)
-            .header(
-                "Accept",
-                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
-            )
-            .header("Accept-Language", "en-US,en;q=0.5")
-            .send()
-            .await
-            .map_err(|e| ToolError::execution_failed(format!("Web search request failed: {e}")))?;
-
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| ToolError::execution_failed(format!("Failed to read response: {e}")))?;
-
-        if !status.is_success() {
-            return Err(ToolError::execution_failed(format!(
-                "Web search failed: HTTP {}",
-                status.as_u16()
-            )));
-        }
-
-        let results = parse_duckduckgo_results(&body, max_results);
+        let results = provider.search(&client, &query, max_results).await?;
         let message = if results.is_empty() {
             "No results found".to_string()
         } else {
@@ -129,7 +360,7 @@ impl ToolSpec for WebSearchTool {
 
         let response = WebSearchResponse {
             query,
-            source: "duckduckgo".to_string(),
+            source: provider.name().to_string(),
             count: results.len(),
             message,
             results,
@@ -139,6 +370,89 @@ impl ToolSpec for WebSearchTool {
     }
 }
 
+// === Fetch cache ===
+
+static FETCH_CACHE: OnceLock<Mutex<FetchCache>> = OnceLock::new();
+
+/// Fetched pages keyed by URL, evicting the least recently used entry once
+/// `FETCH_CACHE_CAPACITY` is reached.
+#[derive(Debug, Default)]
+struct FetchCache {
+    entries: HashMap<String, CachedPage>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CachedPage {
+    text: String,
+    fetched_at: Instant,
+    last_used: u64,
+}
+
+impl FetchCache {
+    /// Cached text for `url` if it is younger than `ttl`; stale entries are dropped.
+    fn get(&mut self, url: &str, ttl: Duration, now: Instant) -> Option<String> {
+        let fresh = self
+            .entries
+            .get(url)
+            .is_some_and(|page| now.saturating_duration_since(page.fetched_at) < ttl);
+        if !fresh {
+            self.entries.remove(url);
+            return None;
+        }
+        self.tick += 1;
+        let page = self.entries.get_mut(url)?;
+        page.last_used = self.tick;
+        Some(page.text.clone())
+    }
+
+    fn insert(&mut self, url: &str, text: String, now: Instant) {
+        if !self.entries.contains_key(url)
+            && self.entries.len() >= FETCH_CACHE_CAPACITY
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, page)| page.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.entries.insert(
+            url.to_string(),
+            CachedPage {
+                text,
+                fetched_at: now,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        count
+    }
+}
+
+fn fetch_cache() -> &'static Mutex<FetchCache> {
+    FETCH_CACHE.get_or_init(|| Mutex::new(FetchCache::default()))
+}
+
+/// Drop every cached `web_fetch` page, returning how many were removed.
+pub fn clear_fetch_cache() -> usize {
+    fetch_cache().lock().map_or(0, |mut cache| cache.clear())
+}
+
+/// Whether a response's `Cache-Control` header lets us keep it.
+fn cacheable(cache_control: Option<&str>) -> bool {
+    cache_control.is_none_or(|value| {
+        !value
+            .split(',')
+            .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+    })
+}
+
 // === WebFetchTool ===
 
 pub struct WebFetchTool;
@@ -178,8 +492,18 @@ impl ToolSpec for WebFetchTool {
         true
     }
 
-    async fn execute(&self, input: Value, _context: &ToolContext) -> Result<ToolResult, ToolError> {
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolResult, ToolError> {
         let url = required_str(&input, "url")?;
+        let ttl = context.web_search.fetch_cache_ttl();
+
+        if !ttl.is_zero()
+            && let Some(text) = fetch_cache()
+                .lock()
+                .ok()
+                .and_then(|mut cache| cache.get(url, ttl, Instant::now()))
+        {
+            return Ok(ToolResult::success(format!("(cached)\n{text}")));
+        }
 
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(30))
@@ -196,6 +520,11 @@ impl ToolSpec for WebFetchTool {
             .map_err(|e| ToolError::execution_failed(format!("Request failed: {e}")))?;
 
         let status = resp.status();
+        let store = cacheable(
+            resp.headers()
+                .get(reqwest::header::CACHE_CONTROL)
+                .and_then(|value| value.to_str().ok()),
+        );
         let body = resp
             .text()
             .await
@@ -208,6 +537,12 @@ impl ToolSpec for WebFetchTool {
         }
 
         let clean_text = normalize_text(&body);
+        if store
+            && !ttl.is_zero()
+            && let Ok(mut cache) = fetch_cache().lock()
+        {
+            cache.insert(url, clean_text.clone(), Instant::now());
+        }
         Ok(ToolResult::success(clean_text))
     }
 }
@@ -252,6 +587,54 @@ fn parse_duckduckgo_results(html: &str, max_results: usize) -> Vec<WebSearchEntr
     results
 }
 
+/// Brave Search API: `web.results[]` with `title`, `url`, `description`.
+fn parse_brave_results(body: &Value, max_results: usize) -> Vec<WebSearchEntry> {
+    let items = body
+        .pointer("/web/results")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    parse_json_results(items, "description", max_results)
+}
+
+/// SearXNG JSON API: `results[]` with `title`, `url`, `content`.
+fn parse_searxng_results(body: &Value, max_results: usize) -> Vec<WebSearchEntry> {
+    let items = body
+        .get("results")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    parse_json_results(items, "content", max_results)
+}
+
+fn parse_json_results(
+    items: &[Value],
+    snippet_key: &str,
+    max_results: usize,
+) -> Vec<WebSearchEntry> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let title = normalize_text(item.get("title")?.as_str()?);
+            let url = item.get("url")?.as_str()?.to_string();
+            if title.is_empty() || url.is_empty() {
+                return None;
+            }
+            let snippet = item
+                .get(snippet_key)
+                .and_then(Value::as_str)
+                .map(normalize_text)
+                .filter(|s| !s.is_empty());
+            Some(WebSearchEntry {
+                title,
+                url,
+                snippet,
+            })
+        })
+        .take(max_results)
+        .collect()
+}
+
 fn normalize_url(href: &str) -> String {
     if let Some(uddg) = extract_query_param(href, "uddg") {
         let decoded = percent_decode(&uddg);
@@ -337,3 +720,95 @@ fn extract_query_param(url: &str, key: &str) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetch_cache_expires_and_evicts_least_recently_used() {
+        let ttl = Duration::from_secs(300);
+        let start = Instant::now();
+        let mut cache = FetchCache::default();
+        cache.insert("https://a", "a".to_string(), start);
+        assert_eq!(cache.get("https://a", ttl, start), Some("a".to_string()));
+        assert_eq!(cache.get("https://a", ttl, start + ttl), None);
+        assert!(cache.entries.is_empty());
+
+        for i in 0..FETCH_CACHE_CAPACITY {
+            cache.insert(&format!("https://{i}"), i.to_string(), start);
+        }
+        assert!(cache.get("https://0", ttl, start).is_some());
+        cache.insert("https://new", "new".to_string(), start);
+        assert_eq!(cache.entries.len(), FETCH_CACHE_CAPACITY);
+        assert!(cache.entries.contains_key("https://0"));
+        assert!(!cache.entries.contains_key("https://1"));
+        assert_eq!(cache.clear(), FETCH_CACHE_CAPACITY);
+    }
+
+    #[test]
+    fn no_store_responses_are_not_cached() {
+        assert!(cacheable(None));
+        assert!(cacheable(Some("max-age=60, public")));
+        assert!(!cacheable(Some("private, No-Store")));
+        let config = WebSearchConfig::default();
+        assert_eq!(config.fetch_cache_ttl(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn search_backend_parses_config_values() {
+        assert_eq!("DuckDuckGo".parse(), Ok(SearchBackend::DuckDuckGo));
+        assert_eq!("brave".parse(), Ok(SearchBackend::Brave));
+        assert_eq!(" searxng ".parse(), Ok(SearchBackend::Searxng));
+        assert!("bing".parse::<SearchBackend>().is_err());
+        assert_eq!(SearchBackend::default(), SearchBackend::DuckDuckGo);
+    }
+
+    #[test]
+    fn providers_require_their_credentials() {
+        let ddg = provider_for(&WebSearchConfig::default()).expect("duckduckgo");
+        assert_eq!(ddg.name(), "duckduckgo");
+
+        let brave = WebSearchConfig {
+            backend: SearchBackend::Brave,
+            ..WebSearchConfig::default()
+        };
+        assert!(provider_for(&brave).is_err());
+        let brave = WebSearchConfig {
+            api_key: Some("key".to_string()),
+            ..brave
+        };
+        assert_eq!(provider_for(&brave).expect("brave").name(), "brave");
+
+        let searxng = WebSearchConfig {
+            backend: SearchBackend::Searxng,
+            ..WebSearchConfig::default()
+        };
+        assert!(provider_for(&searxng).is_err());
+    }
+
+    #[test]
+    fn json_results_are_normalized() {
+        let brave = json!({"web": {"results": [
+            {"title": "Rust <strong>Lang</strong>", "url": "https://rust-lang.org", "description": "A &amp; B"},
+            {"title": "", "url": "https://skip.example"},
+            {"title": "Second", "url": "https://two.example"}
+        ]}});
+        let results = parse_brave_results(&brave, 5);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Rust Lang");
+        assert_eq!(results[0].snippet.as_deref(), Some("A & B"));
+        assert_eq!(results[1].snippet, None);
+
+        let searxng = json!({"results": [
+            {"title": "One", "url": "https://one.example", "content": "first"},
+            {"title": "Two", "url": "https://two.example", "content": "second"}
+        ]});
+        let results = parse_searxng_results(&searxng, 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].url, "https://one.example");
+        assert_eq!(results[0].snippet.as_deref(), Some("first"));
+
+        assert!(parse_searxng_results(&json!({}), 5).is_empty());
+    }
+}
@@ -1,28 +1,33 @@
 //! Application state for the TUI.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
-use std::time::{Instant, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::client::Backend;
 use crate::config::{Config, has_api_key};
 use crate::duo::{SharedDuoSession, new_shared_duo_session};
 use crate::hooks::{HookContext, HookEvent, HookExecutor, HookResult};
-use crate::models::{Message, SystemPrompt};
+use crate::models::{ContentBlock, Message, SystemPrompt};
 use crate::palette::{self, UiTheme};
+use crate::pricing::{SharedCostBudget, new_shared_cost_budget};
 use crate::rlm::{RlmSession, SharedRlmSession};
+use crate::session_manager::SessionManager;
 use crate::settings::Settings;
+use crate::skills::{SkillChanges, SkillRegistry, SkillSource, SkillWatcher, skill_search_path};
 use crate::tools::plan::{SharedPlanState, new_shared_plan_state};
+use crate::tools::subagent::SubAgentStatus;
 use crate::tools::todo::{SharedTodoList, new_shared_todo_list};
-use crate::tui::approval::ApprovalMode;
+use crate::tui::approval::{ApprovalMode, ApprovalPolicy, ToolCategory, get_tool_category};
 use crate::tui::clipboard::{ClipboardContent, ClipboardHandler};
 use crate::tui::fuzzy_picker::FuzzyPicker;
 use crate::tui::history::{HistoryCell, TranscriptRenderOptions};
-use crate::tui::paste_burst::{FlushResult, PasteBurst};
+use crate::tui::paste_burst::{FlushResult, PasteBurst, PasteBurstConfig};
 use crate::tui::scrolling::{MouseScrollState, TranscriptScroll};
 use crate::tui::search_view::SearchResult;
 use crate::tui::selection::TranscriptSelection;
@@ -196,6 +201,36 @@ pub struct TuiOptions {
     pub yolo: bool,
     /// Resume a previous session by ID
     pub resume_session_id: Option<String>,
+    /// Skip auto-save and crash-recovery snapshots (`--no-save`)
+    pub no_save: bool,
+    /// Config file given with `--config` (None: default location)
+    pub config_path: Option<PathBuf>,
+    /// Profile the session started with (`--profile` or `AXIOM_PROFILE`)
+    pub profile: Option<String>,
+}
+
+#[cfg(test)]
+impl TuiOptions {
+    /// Options for tests: `test-model` in `workspace`, nothing enabled.
+    pub fn for_test(workspace: impl Into<PathBuf>) -> Self {
+        Self {
+            model: "test-model".to_string(),
+            workspace: workspace.into(),
+            allow_shell: false,
+            max_subagents: 1,
+            skills_dir: PathBuf::from("."),
+            memory_path: PathBuf::from("memory.md"),
+            notes_path: PathBuf::from("notes.txt"),
+            mcp_config_path: PathBuf::from("mcp.json"),
+            use_memory: false,
+            start_in_agent_mode: false,
+            yolo: false,
+            resume_session_id: None,
+            no_save: false,
+            config_path: None,
+            profile: None,
+        }
+    }
 }
 
 /// Global UI state for the TUI.
@@ -219,11 +254,17 @@ pub struct App {
     pub last_transcript_visible: usize,
     pub last_transcript_total: usize,
     pub last_transcript_padding_top: usize,
+    /// Outline layout regions and show scroll state (`/debug render`)
+    pub debug_render: bool,
     pub is_loading: bool,
     pub status_message: Option<String>,
     pub model: String,
     pub workspace: PathBuf,
+    /// Global skills directory; the workspace `skills/` folder is searched too
     pub skills_dir: PathBuf,
+    /// Skills loaded from the search path; refreshed by `/skills reload`
+    pub skills: SkillRegistry,
+    skill_watcher: SkillWatcher,
     #[allow(dead_code)]
     pub system_prompt: Option<SystemPrompt>,
     pub input_history: Vec<String>,
@@ -231,9 +272,24 @@ pub struct App {
     pub auto_compact: bool,
     pub show_thinking: bool,
     pub show_tool_details: bool,
-    #[allow(dead_code)]
-    pub compact_threshold: usize,
+    /// Truncate long tool output; toggled with Ctrl+O or `/collapse`
+    pub collapse_tool_output: bool,
+    /// Percent of the context window at which auto-compaction kicks in
+    pub auto_compact_threshold: u8,
+    /// Recent exchanges a compaction keeps verbatim
+    pub compact_keep_pairs: usize,
     pub max_input_history: usize,
+    /// Composer height cap in rows; longer input scrolls with the cursor
+    pub max_composer_lines: usize,
+    /// j/k, g/G, Ctrl-d/Ctrl-u and `/` navigate the transcript when the composer is empty
+    pub vim_mode: bool,
+    /// Hold sub-agent completions until the batch finishes, then show one summary
+    pub aggregate_subagents: bool,
+    /// Sub-agents spawned while aggregating, with their outcome once complete
+    pub subagent_batch: Vec<(String, Option<(SubAgentStatus, String)>)>,
+    /// Config file and profile from startup, reused by `/reload` and `/config edit`
+    pub config_path: Option<PathBuf>,
+    pub config_profile: Option<String>,
     pub total_tokens: u32,
     /// Estimated tokens currently in context (reset on clear/load)
     pub total_conversation_tokens: u32,
@@ -254,15 +310,27 @@ pub struct App {
     pub yolo: bool,
     /// Shell mode - when true, input is executed as shell commands
     pub shell_mode: bool,
+    /// Background shell-mode commands (`cmd &`) whose output is still streaming
+    pub shell_jobs: Vec<ShellJob>,
     // Clipboard handler
     pub clipboard: ClipboardHandler,
     // Tool approval session allowlist
     pub approval_session_approved: HashSet<String>,
+    /// Tool categories approved as a whole ("approve all safe tools")
+    pub approval_session_categories: HashSet<ToolCategory>,
     pub approval_mode: ApprovalMode,
     // Modal view stack (approval/help/etc.)
     pub view_stack: ViewStack,
     /// Current session ID for auto-save updates
     pub current_session_id: Option<String>,
+    /// Save the session after each turn and snapshot turns for crash recovery
+    pub auto_save: bool,
+    /// Directory sessions are saved in (`session_dir`)
+    pub session_dir: PathBuf,
+    /// Redact secrets from sessions before writing them (`redact_on_save`)
+    pub redact_on_save: bool,
+    /// Session recovered at startup from an unfinished run, for `/recover`
+    pub recoverable_session_id: Option<String>,
     /// Trust mode - allow access outside workspace
     pub trust_mode: bool,
     /// Project documentation (AGENTS.md or CLAUDE.md)
@@ -280,10 +348,14 @@ pub struct App {
     pub todos: SharedTodoList,
     /// Tool execution log
     pub tool_log: Vec<String>,
-    /// Session cost tracking
-    pub session_cost: f64,
+    /// Session spend and budget, shared with the engine
+    pub cost_budget: SharedCostBudget,
+    /// Last turn stopped at the budget; `/budget raise` resumes it
+    pub budget_blocked: bool,
     /// Active skill to apply to next user message
     pub active_skill: Option<String>,
+    /// Tools the active skill restricts the next turn to
+    pub active_skill_tools: Option<Vec<String>>,
     /// Tool call cells by tool id
     pub tool_cells: HashMap<String, usize>,
     /// Active exploring cell index
@@ -308,8 +380,13 @@ pub struct App {
     pub queued_messages: VecDeque<QueuedMessage>,
     /// Draft queued message being edited
     pub queued_draft: Option<QueuedMessage>,
+    /// User prompt (1-based) being rewritten with `/edit`; sending cuts the
+    /// conversation back to it
+    pub editing_prompt: Option<usize>,
     /// Start time for current turn
     pub turn_started_at: Option<Instant>,
+    /// Session duration, tool-call, and response-time counters
+    pub stats: SessionStats,
     /// Last prompt token usage
     pub last_prompt_tokens: Option<u32>,
     /// Last completion token usage
@@ -335,6 +412,8 @@ pub struct App {
     pub pinned_messages: Vec<PinnedMessage>,
     /// Custom model context windows from config
     pub custom_context_windows: std::collections::HashMap<String, u32>,
+    /// Replies come from the scripted mock backend, not the API
+    pub mock_backend: bool,
     /// Cached search results
     pub search_results: Vec<SearchResult>,
     /// Current search result index
@@ -348,6 +427,99 @@ pub struct App {
 pub struct QueuedMessage {
     pub display: String,
     pub skill_instruction: Option<String>,
+    /// Tool restriction from the skill, applied to this message's turn
+    pub skill_tools: Option<Vec<String>>,
+}
+
+/// Counters behind `/stats`.
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    pub started_at: Instant,
+    /// Tool calls started, by tool name
+    pub tool_calls: BTreeMap<String, usize>,
+    /// Execution time of completed tool calls, by tool name
+    pub tool_timings: BTreeMap<String, ToolTiming>,
+    /// Completed turns and their combined wall-clock time
+    pub turns: u32,
+    pub turn_time: Duration,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            tool_calls: BTreeMap::new(),
+            tool_timings: BTreeMap::new(),
+            turns: 0,
+            turn_time: Duration::ZERO,
+        }
+    }
+
+    pub fn record_tool_call(&mut self, name: &str) {
+        *self.tool_calls.entry(name.to_string()).or_default() += 1;
+    }
+
+    pub fn record_tool_duration(&mut self, name: &str, elapsed: Duration) {
+        let timing = self.tool_timings.entry(name.to_string()).or_default();
+        timing.completed += 1;
+        timing.total += elapsed;
+        timing.slowest = timing.slowest.max(elapsed);
+    }
+
+    /// Tools ordered by mean execution time, slowest first
+    pub fn slowest_tools(&self) -> Vec<(&str, &ToolTiming)> {
+        let mut tools: Vec<(&str, &ToolTiming)> = self
+            .tool_timings
+            .iter()
+            .map(|(name, timing)| (name.as_str(), timing))
+            .collect();
+        tools.sort_by(|a, b| b.1.average().cmp(&a.1.average()).then_with(|| a.0.cmp(b.0)));
+        tools
+    }
+
+    pub fn record_turn(&mut self, elapsed: Duration) {
+        self.turns += 1;
+        self.turn_time += elapsed;
+    }
+
+    /// Mean time from sending a message to the turn completing
+    pub fn average_turn_time(&self) -> Option<Duration> {
+        (self.turns > 0).then(|| self.turn_time / self.turns)
+    }
+}
+
+/// Accumulated execution time for one tool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ToolTiming {
+    pub completed: u32,
+    pub total: Duration,
+    pub slowest: Duration,
+}
+
+impl ToolTiming {
+    pub fn average(&self) -> Duration {
+        if self.completed == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.completed
+        }
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A background shell-mode command streaming into a transcript cell.
+#[derive(Debug, Clone)]
+pub struct ShellJob {
+    pub task_id: String,
+    /// First line of the job's cell, used to find it again in the history
+    pub header: String,
+    /// History index of the cell receiving output
+    pub cell: usize,
 }
 
 /// Source of a pinned message.
@@ -395,6 +567,7 @@ impl QueuedMessage {
         Self {
             display,
             skill_instruction,
+            skill_tools: None,
         }
     }
 
@@ -449,14 +622,25 @@ impl App {
             start_in_agent_mode,
             yolo,
             resume_session_id: _,
+            no_save,
+            config_path,
+            profile,
         } = options;
-        // Check if API key exists
-        let needs_onboarding = !has_api_key(config);
+        // Check if API key exists; the mock backend doesn't need one
+        let mock_backend = config.backend() == Backend::Mock;
+        let needs_onboarding = !has_api_key(config) && !mock_backend;
         let settings = Settings::load().unwrap_or_else(|_| Settings::default());
         let auto_compact = settings.auto_compact;
+        let auto_compact_threshold = settings.auto_compact_threshold;
+        let compact_keep_pairs = settings.compact_keep_pairs;
         let show_thinking = settings.show_thinking;
         let show_tool_details = settings.show_tool_details;
+        let collapse_tool_output = settings.collapse_tool_output;
         let max_input_history = settings.max_input_history;
+        let max_composer_lines = settings.max_composer_lines;
+        let vim_mode = settings.vim_mode;
+        let aggregate_subagents = settings.aggregate_subagents;
+        let paste_burst = PasteBurst::new(PasteBurstConfig::from_settings(&settings));
         let ui_theme = palette::ui_theme(&settings.theme);
         let model = settings.default_model.clone().unwrap_or(model);
 
@@ -485,16 +669,26 @@ impl App {
             } else {
                 ""
             };
+            let save_msg = if no_save {
+                " | not saving (--no-save)"
+            } else {
+                ""
+            };
             vec![HistoryCell::System {
                 content: format!(
-                    "Welcome to Axiom! Model: {} | Workspace: {}{}",
+                    "Welcome to Axiom! Model: {} | Workspace: {}{}{}",
                     model,
                     workspace.display(),
-                    mode_msg
+                    mode_msg,
+                    save_msg
                 ),
             }]
         };
 
+        let skill_path = skill_search_path(&workspace, global_skills_dir.clone());
+        let skills = SkillRegistry::discover_path(&skill_path);
+        let skill_watcher = SkillWatcher::new(&skill_path);
+
         // Initialize hooks executor from config
         let hooks_config = config.hooks_config();
         let hooks = HookExecutor::new(hooks_config, workspace.clone());
@@ -504,13 +698,6 @@ impl App {
 
         let history_len = history.len() as u64;
 
-        let local_skills_dir = workspace.join("skills");
-        let skills_dir = if local_skills_dir.exists() {
-            local_skills_dir
-        } else {
-            global_skills_dir
-        };
-
         // Load input history from disk
         let input_history = Self::load_input_history_static(&settings);
 
@@ -518,7 +705,7 @@ impl App {
             mode: initial_mode,
             input: String::new(),
             cursor_position: 0,
-            paste_burst: PasteBurst::default(),
+            paste_burst,
             history,
             history_version: history_len,
             api_messages: Vec::new(),
@@ -533,19 +720,30 @@ impl App {
             last_transcript_visible: 0,
             last_transcript_total: 0,
             last_transcript_padding_top: 0,
+            debug_render: false,
             is_loading: false,
             status_message: None,
             model,
             workspace: workspace.clone(),
-            skills_dir,
+            skills_dir: global_skills_dir,
+            skills,
+            skill_watcher,
             system_prompt: None,
             input_history,
             history_index: None,
             auto_compact,
             show_thinking,
             show_tool_details,
-            compact_threshold: 50000,
+            collapse_tool_output,
+            auto_compact_threshold,
+            compact_keep_pairs,
             max_input_history,
+            max_composer_lines,
+            vim_mode,
+            aggregate_subagents,
+            subagent_batch: Vec::new(),
+            config_path,
+            config_profile: profile,
             total_tokens: 0,
             total_conversation_tokens: 0,
             allow_shell: true,
@@ -565,8 +763,10 @@ impl App {
             hooks,
             yolo: initial_mode == AppMode::Yolo,
             shell_mode: false,
+            shell_jobs: Vec::new(),
             clipboard: ClipboardHandler::new(),
             approval_session_approved: HashSet::new(),
+            approval_session_categories: HashSet::new(),
             approval_mode: if matches!(initial_mode, AppMode::Yolo | AppMode::Rlm | AppMode::Duo) {
                 ApprovalMode::Auto
             } else {
@@ -574,6 +774,10 @@ impl App {
             },
             view_stack: ViewStack::new(),
             current_session_id: None,
+            auto_save: !no_save,
+            session_dir: config.session_dir(),
+            redact_on_save: settings.redact_on_save,
+            recoverable_session_id: None,
             trust_mode: initial_mode == AppMode::Yolo,
             project_doc: None,
             plan_state,
@@ -582,8 +786,10 @@ impl App {
             rlm_repl_active: false,
             todos: new_shared_todo_list(),
             tool_log: Vec::new(),
-            session_cost: 0.0,
+            cost_budget: new_shared_cost_budget(config.max_session_cost_usd()),
+            budget_blocked: false,
             active_skill: None,
+            active_skill_tools: None,
             tool_cells: HashMap::new(),
             exploring_cell: None,
             exploring_entries: HashMap::new(),
@@ -596,7 +802,9 @@ impl App {
             pending_tool_uses: Vec::new(),
             queued_messages: VecDeque::new(),
             queued_draft: None,
+            editing_prompt: None,
             turn_started_at: None,
+            stats: SessionStats::new(),
             last_prompt_tokens: None,
             last_completion_tokens: None,
             last_usage_at: None,
@@ -609,6 +817,7 @@ impl App {
             suggestion_engine: SuggestionEngine::new(),
             pinned_messages: Vec::new(),
             custom_context_windows: config.model_context_windows(),
+            mock_backend,
             search_results: Vec::new(),
             current_search_idx: None,
             search_query: String::new(),
@@ -656,6 +865,53 @@ impl App {
         self.onboarding = OnboardingState::None;
     }
 
+    /// Approval policy in effect, derived from `approval_mode` and category approvals
+    pub fn approval_policy(&self) -> ApprovalPolicy {
+        match self.approval_mode {
+            ApprovalMode::Auto => ApprovalPolicy::Yolo,
+            ApprovalMode::Never => ApprovalPolicy::Deny,
+            ApprovalMode::Suggest
+                if self
+                    .approval_session_categories
+                    .contains(&ToolCategory::Safe) =>
+            {
+                ApprovalPolicy::AutoSafe
+            }
+            ApprovalMode::Suggest => ApprovalPolicy::Manual,
+        }
+    }
+
+    /// Decide a tool approval without prompting: `Some(false)` to refuse,
+    /// `Some(true)` to approve, `None` to ask. Deny wins over approvals
+    /// granted earlier in the session.
+    pub fn auto_approval(&self, tool_name: &str) -> Option<bool> {
+        match self.approval_mode {
+            ApprovalMode::Never => Some(false),
+            ApprovalMode::Auto => Some(true),
+            ApprovalMode::Suggest => {
+                let approved = self.approval_session_approved.contains(tool_name)
+                    || self
+                        .approval_session_categories
+                        .contains(&get_tool_category(tool_name));
+                approved.then_some(true)
+            }
+        }
+    }
+
+    /// Switch approval policy; per-tool session approvals are left alone
+    pub fn set_approval_policy(&mut self, policy: ApprovalPolicy) {
+        self.approval_mode = match policy {
+            ApprovalPolicy::Manual | ApprovalPolicy::AutoSafe => ApprovalMode::Suggest,
+            ApprovalPolicy::Yolo => ApprovalMode::Auto,
+            ApprovalPolicy::Deny => ApprovalMode::Never,
+        };
+        if policy == ApprovalPolicy::AutoSafe {
+            self.approval_session_categories.insert(ToolCategory::Safe);
+        } else {
+            self.approval_session_categories.remove(&ToolCategory::Safe);
+        }
+    }
+
     pub fn set_mode(&mut self, mode: AppMode) {
         let previous_mode = self.mode;
         self.mode = mode;
@@ -729,6 +985,51 @@ impl App {
             .with_tokens(self.total_tokens)
     }
 
+    /// Estimated USD spent this session.
+    pub fn session_cost(&self) -> f64 {
+        self.cost_budget
+            .lock()
+            .map_or(0.0, |budget| budget.spent_usd)
+    }
+
+    /// Context window of the current model, when known.
+    pub fn context_window(&self) -> Option<u32> {
+        crate::models::context_window_for_model(&self.model, Some(&self.custom_context_windows))
+    }
+
+    /// Estimated context size and window when the conversation no longer fits.
+    pub fn context_overflow(&self) -> Option<(u32, u32)> {
+        let window = self.context_window()?;
+        (self.total_conversation_tokens > window)
+            .then_some((self.total_conversation_tokens, window))
+    }
+
+    /// Context size at which auto-compaction kicks in, when the window is known.
+    pub fn auto_compact_threshold_tokens(&self) -> Option<u32> {
+        let window = u64::from(self.context_window()?);
+        let tokens = window * u64::from(self.auto_compact_threshold) / 100;
+        Some(u32::try_from(tokens).unwrap_or(u32::MAX))
+    }
+
+    /// Pinned messages and recent exchanges that compaction must keep, and
+    /// the context size that compacts before the next request.
+    pub fn keep_policy(&self) -> crate::compaction::KeepPolicy {
+        let auto_compact_tokens = self.auto_compact.then(|| {
+            self.auto_compact_threshold_tokens()
+                .and_then(|tokens| usize::try_from(tokens).ok())
+                .unwrap_or(crate::compaction::DEFAULT_AUTO_COMPACT_TOKENS)
+        });
+        crate::compaction::KeepPolicy {
+            recent_pairs: self.compact_keep_pairs,
+            pinned: self
+                .pinned_messages
+                .iter()
+                .map(|pin| pin.content.clone())
+                .collect(),
+            auto_compact_tokens,
+        }
+    }
+
     /// Recalculate estimated tokens currently in context for the header meter.
     pub fn recalculate_context_tokens(&mut self) {
         let tool_tokens = self.estimate_tool_tokens();
@@ -781,14 +1082,52 @@ impl App {
         }
     }
 
+    /// Directories skills are loaded from, lowest precedence first
+    pub fn skill_search_path(&self) -> Vec<(SkillSource, PathBuf)> {
+        skill_search_path(&self.workspace, self.skills_dir.clone())
+    }
+
+    /// Session store at the configured location, redacting on save when enabled
+    pub fn session_manager(&self) -> std::io::Result<SessionManager> {
+        SessionManager::new(self.session_dir.clone())
+            .map(|manager| manager.with_redaction(self.redact_on_save))
+    }
+
+    /// Rescan the skill search path, returning what changed.
+    pub fn reload_skills(&mut self) -> SkillChanges {
+        let search_path = self.skill_search_path();
+        let skills = SkillRegistry::discover_path(&search_path);
+        let changes = self.skills.diff(&skills);
+        self.skills = skills;
+        self.skill_watcher.reset(&search_path);
+        changes
+    }
+
+    /// Reload skills when SKILL.md files changed on disk since the last poll.
+    pub fn poll_skill_files(&mut self, now: Instant) -> Option<SkillChanges> {
+        if !self.skill_watcher.poll(&self.skill_search_path(), now) {
+            return None;
+        }
+        let changes = self.reload_skills();
+        (!changes.is_empty()).then_some(changes)
+    }
+
     pub fn mark_history_updated(&mut self) {
         self.history_version = self.history_version.wrapping_add(1);
     }
 
+    /// Flip every tool cell between truncated and full output for this session.
+    pub fn toggle_tool_output_collapse(&mut self) -> bool {
+        self.collapse_tool_output = !self.collapse_tool_output;
+        self.mark_history_updated();
+        self.collapse_tool_output
+    }
+
     pub fn transcript_render_options(&self) -> TranscriptRenderOptions {
         TranscriptRenderOptions {
             show_thinking: self.show_thinking,
             show_tool_details: self.show_tool_details,
+            collapse_tool_output: self.collapse_tool_output,
         }
     }
 
@@ -1274,6 +1613,71 @@ impl App {
 
     const MAX_PINS: usize = 5;
 
+    /// Text of each user prompt in the transcript, oldest first.
+    pub fn user_prompts(&self) -> Vec<&str> {
+        self.history
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::User { content } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Transcript and API-message positions of user prompt `n` (1-based).
+    /// Prompts are matched counting back from the newest, since compaction
+    /// and `/trim` drop the oldest API messages while the transcript keeps
+    /// them. `None` when there is no such prompt or it has left the context.
+    fn prompt_positions(&self, n: usize) -> Option<(usize, usize)> {
+        let history: Vec<usize> = self
+            .history
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| matches!(cell, HistoryCell::User { .. }))
+            .map(|(pos, _)| pos)
+            .collect();
+        let api: Vec<usize> = self
+            .api_messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| {
+                message.role == "user"
+                    && message
+                        .content
+                        .iter()
+                        .any(|block| matches!(block, ContentBlock::Text { .. }))
+            })
+            .map(|(pos, _)| pos)
+            .collect();
+        let history_pos = *history.get(n.checked_sub(1)?)?;
+        let from_newest = history.len() - n;
+        let api_pos = *api.get(api.len().checked_sub(from_newest + 1)?)?;
+        Some((history_pos, api_pos))
+    }
+
+    /// Whether user prompt `n` (1-based) is still in the model's context.
+    pub fn prompt_in_context(&self, n: usize) -> bool {
+        self.prompt_positions(n).is_some()
+    }
+
+    /// Drop user prompt `n` (1-based) and everything after it from both the
+    /// transcript and the API messages. Returns false when there is no such
+    /// prompt or it is no longer in the model's context.
+    pub fn rewind_to_prompt(&mut self, n: usize) -> bool {
+        let Some((history_pos, api_pos)) = self.prompt_positions(n) else {
+            return false;
+        };
+
+        self.history.truncate(history_pos);
+        self.api_messages.truncate(api_pos);
+        self.tool_cells.clear();
+        self.exploring_entries.clear();
+        self.transcript_selection.clear();
+        self.mark_history_updated();
+        self.recalculate_context_tokens();
+        true
+    }
+
     /// Pin a message from history by cell index.
     /// Returns true if a message was pinned, false if invalid index or not pinnable.
     pub fn pin_message(&mut self, cell_idx: usize) -> bool {
@@ -1344,8 +1748,14 @@ pub enum AppAction {
     },
     SendMessage(String),
     ListSubAgents,
-    /// Trigger manual context compaction
-    CompactContext,
+    /// Trigger manual context compaction, optionally down to a token target
+    CompactContext {
+        target_tokens: Option<usize>,
+    },
+    /// Show what a manual compaction would summarize without applying it
+    PreviewCompaction,
+    /// Rebuild the investigator's file index without blocking the UI
+    RebuildIndex,
     /// Open the session picker modal
     OpenSessionPicker,
     /// Open the model picker modal
@@ -1354,8 +1764,16 @@ pub enum AppAction {
     OpenHistoryPicker,
     /// Reload configuration from disk
     ReloadConfig,
-    /// Set the input text (for snippet insertion)
-    SetInput(String),
+    /// Open the config file in an external editor, then reload it
+    EditConfig {
+        editor: String,
+        path: PathBuf,
+    },
+    /// Set the input text and cursor (char offset), for snippet insertion
+    SetInput {
+        text: String,
+        cursor: usize,
+    },
     /// Open the search modal with optional query
     OpenSearch(String),
 }
@@ -1368,18 +1786,10 @@ mod tests {
 
     fn test_options(yolo: bool) -> TuiOptions {
         TuiOptions {
-            model: "test-model".to_string(),
-            workspace: PathBuf::from("."),
             allow_shell: yolo,
-            max_subagents: 1,
-            skills_dir: PathBuf::from("."),
-            memory_path: PathBuf::from("memory.md"),
-            notes_path: PathBuf::from("notes.txt"),
-            mcp_config_path: PathBuf::from("mcp.json"),
-            use_memory: false,
             start_in_agent_mode: yolo,
             yolo,
-            resume_session_id: None,
+            ..TuiOptions::for_test(".")
         }
     }
 
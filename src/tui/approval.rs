@@ -32,6 +32,42 @@ impl ApprovalMode {
     }
 }
 
+/// Overall approval policy, derived from `ApprovalMode` and category approvals
+///
+/// This is what the header shows: how much the agent may do without asking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalPolicy {
+    /// Every tool that needs approval prompts
+    Manual,
+    /// Safe (read-only) tools run without asking; everything else prompts
+    AutoSafe,
+    /// Everything runs without asking
+    Yolo,
+    /// Tools that need approval are refused
+    Deny,
+}
+
+impl ApprovalPolicy {
+    pub fn label(self) -> &'static str {
+        match self {
+            ApprovalPolicy::Manual => "manual",
+            ApprovalPolicy::AutoSafe => "auto-safe",
+            ApprovalPolicy::Yolo => "yolo",
+            ApprovalPolicy::Deny => "deny",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "manual" => Some(ApprovalPolicy::Manual),
+            "auto-safe" | "auto_safe" | "autosafe" => Some(ApprovalPolicy::AutoSafe),
+            "yolo" | "auto" => Some(ApprovalPolicy::Yolo),
+            "deny" | "never" => Some(ApprovalPolicy::Deny),
+            _ => None,
+        }
+    }
+}
+
 /// User's decision for a pending approval
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReviewDecision {
@@ -39,6 +75,8 @@ pub enum ReviewDecision {
     Approved,
     /// Approve and don't ask again for this tool type this session
     ApprovedForSession,
+    /// Approve and don't ask again for any tool in this category this session
+    ApprovedCategoryForSession,
     /// Reject the tool execution
     Denied,
     /// Abort the entire turn
@@ -46,16 +84,58 @@ pub enum ReviewDecision {
 }
 
 /// Categorizes tools by cost/risk level
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ToolCategory {
     /// Free, read-only operations (`list_dir`, `read_file`, todo_*)
     Safe,
-    /// File modifications (`write_file`, `edit_file`)
+    /// File modifications (`write_file`, `edit_file`, `git_stash`)
     FileWrite,
     /// Shell execution (`exec_shell`)
     Shell,
     /// Paid multimedia APIs (`generate_image`, `tts`, etc.)
     PaidMultimedia,
+    /// Anything else with side effects, including MCP and unknown tools
+    /// (`git_commit`, `agent_spawn`, `save_memory`, `mcp_*`)
+    SideEffects,
+}
+
+impl ToolCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            ToolCategory::Safe => "Safe",
+            ToolCategory::FileWrite => "File Write",
+            ToolCategory::Shell => "Shell Command",
+            ToolCategory::PaidMultimedia => "Paid API",
+            ToolCategory::SideEffects => "Side Effects",
+        }
+    }
+}
+
+/// Options offered in the approval popup, in display order: key, label, decision.
+///
+/// Only read-only tools get the category-wide option; blanket approval of
+/// writes, shell, or paid APIs is what `--yolo` is for.
+pub fn approval_options(
+    category: ToolCategory,
+) -> Vec<(&'static str, &'static str, ReviewDecision)> {
+    let mut options = vec![
+        ("y", "Approve (this time)", ReviewDecision::Approved),
+        (
+            "a",
+            "Approve for session",
+            ReviewDecision::ApprovedForSession,
+        ),
+    ];
+    if category == ToolCategory::Safe {
+        options.push((
+            "s",
+            "Approve all safe tools for session",
+            ReviewDecision::ApprovedCategoryForSession,
+        ));
+    }
+    options.push(("n", "Deny", ReviewDecision::Denied));
+    options.push(("Esc", "Abort turn", ReviewDecision::Abort));
+    options
 }
 
 /// Request for user approval of a tool execution
@@ -71,6 +151,8 @@ pub struct ApprovalRequest {
     pub params: Value,
     /// Estimated cost (for paid tools)
     pub estimated_cost: Option<CostEstimate>,
+    /// Unified diff of the proposed change (for file edits)
+    pub diff: Option<String>,
 }
 
 impl ApprovalRequest {
@@ -84,9 +166,17 @@ impl ApprovalRequest {
             category,
             params: params.clone(),
             estimated_cost,
+            diff: None,
         }
     }
 
+    /// Attach a diff preview; empty diffs are dropped
+    #[must_use]
+    pub fn with_diff(mut self, diff: Option<String>) -> Self {
+        self.diff = diff.filter(|diff| !diff.is_empty());
+        self
+    }
+
     /// Format parameters for display (truncated)
     pub fn params_display(&self) -> String {
         let truncated = truncate_params_value(&self.params, 200);
@@ -96,7 +186,10 @@ impl ApprovalRequest {
 
 /// Get the category for a tool by name
 pub fn get_tool_category(name: &str) -> ToolCategory {
-    if matches!(name, "write_file" | "edit_file" | "apply_patch") {
+    if matches!(
+        name,
+        "write_file" | "edit_file" | "apply_patch" | "git_stash"
+    ) {
         ToolCategory::FileWrite
     } else if name == "exec_shell" {
         ToolCategory::Shell
@@ -123,12 +216,47 @@ pub fn get_tool_category(name: &str) -> ToolCategory {
             | "query_video_template"
     ) {
         ToolCategory::PaidMultimedia
-    } else {
-        // Default to safe (includes read/list/todo/note/update_plan and unknown tools)
+    } else if is_read_only_tool(name) {
         ToolCategory::Safe
+    } else {
+        // Unknown and MCP tools may do anything, so they are never Safe
+        ToolCategory::SideEffects
     }
 }
 
+/// Built-in tools that only read the workspace or the agent's own planning
+/// state. Anything not listed here is treated as having side effects.
+fn is_read_only_tool(name: &str) -> bool {
+    matches!(
+        name,
+        "list_dir"
+            | "read_file"
+            | "read_many_files"
+            | "grep_files"
+            | "git_status"
+            | "git_diff"
+            | "git_log"
+            | "git_blame"
+            | "get_memory"
+            | "web_search"
+            | "web_fetch"
+            | "think"
+            | "todo_list"
+            | "todo_add"
+            | "todo_update"
+            | "todo_write"
+            | "update_plan"
+            | "agent_list"
+            | "agent_result"
+            | "artifact_list"
+            | "rlm_status"
+            | "duo_status"
+            | "duo_list_dir"
+            | "duo_read_file"
+            | "exec_shell_wait"
+    )
+}
+
 /// Approval overlay state managed by the modal view stack
 #[derive(Debug, Clone)]
 pub struct ApprovalView {
@@ -169,16 +297,15 @@ impl ApprovalView {
     }
 
     fn select_next(&mut self) {
-        self.selected = (self.selected + 1).min(3);
+        let last = approval_options(self.request.category).len() - 1;
+        self.selected = (self.selected + 1).min(last);
     }
 
     fn current_decision(&self) -> ReviewDecision {
-        match self.selected {
-            0 => ReviewDecision::Approved,
-            1 => ReviewDecision::ApprovedForSession,
-            2 => ReviewDecision::Denied,
-            _ => ReviewDecision::Abort,
-        }
+        approval_options(self.request.category)
+            .into_iter()
+            .nth(self.selected)
+            .map_or(ReviewDecision::Abort, |(_, _, decision)| decision)
     }
 
     fn emit_decision(&self, decision: ReviewDecision, timed_out: bool) -> ViewAction {
@@ -224,6 +351,9 @@ impl ModalView for ApprovalView {
             KeyCode::Enter => self.emit_decision(self.current_decision(), false),
             KeyCode::Char('y') => self.emit_decision(ReviewDecision::Approved, false),
             KeyCode::Char('a') => self.emit_decision(ReviewDecision::ApprovedForSession, false),
+            KeyCode::Char('s') if self.request.category == ToolCategory::Safe => {
+                self.emit_decision(ReviewDecision::ApprovedCategoryForSession, false)
+            }
             KeyCode::Char('n') => self.emit_decision(ReviewDecision::Denied, false),
             KeyCode::Esc => self.emit_decision(ReviewDecision::Abort, false),
             KeyCode::Char('e')
@@ -244,6 +374,7 @@ impl ModalView for ApprovalView {
             &self.request,
             self.selected,
             &params_display,
+            self.expanded,
         );
         approval_widget.render(area, buf);
     }
@@ -299,3 +430,77 @@ fn truncate_string_value(value: &str, max_len: usize) -> String {
     let truncated: String = value.chars().take(max_len).collect();
     format!("{truncated}...")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn decision_for(view: &mut ApprovalView, code: KeyCode) -> Option<ReviewDecision> {
+        match view.handle_key(KeyEvent::new(code, KeyModifiers::NONE)) {
+            ViewAction::EmitAndClose(ViewEvent::ApprovalDecision { decision, .. }) => {
+                Some(decision)
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn safe_tools_offer_category_approval() {
+        let request = ApprovalRequest::new("id", "read_file", &serde_json::json!({}));
+        let mut view = ApprovalView::new(request);
+        assert_eq!(
+            decision_for(&mut view, KeyCode::Char('s')),
+            Some(ReviewDecision::ApprovedCategoryForSession)
+        );
+
+        view.select_next();
+        view.select_next();
+        assert_eq!(
+            view.current_decision(),
+            ReviewDecision::ApprovedCategoryForSession
+        );
+        for _ in 0..5 {
+            view.select_next();
+        }
+        assert_eq!(view.current_decision(), ReviewDecision::Abort);
+    }
+
+    #[test]
+    fn risky_tools_do_not_offer_category_approval() {
+        let request = ApprovalRequest::new("id", "exec_shell", &serde_json::json!({}));
+        let mut view = ApprovalView::new(request);
+        assert_eq!(decision_for(&mut view, KeyCode::Char('s')), None);
+
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.current_decision(), ReviewDecision::Denied);
+    }
+
+    #[test]
+    fn only_allowlisted_read_only_tools_are_safe() {
+        for name in ["read_file", "grep_files", "git_diff", "todo_write"] {
+            assert_eq!(get_tool_category(name), ToolCategory::Safe, "{name}");
+        }
+        for name in [
+            "git_commit",
+            "git_branch",
+            "exec_shell_interact",
+            "exec_shell_kill",
+            "agent_spawn",
+            "save_memory",
+            "rlm_query",
+            "codebase_investigator",
+            "mcp_github_create_issue",
+            "some_unknown_tool",
+        ] {
+            assert_eq!(get_tool_category(name), ToolCategory::SideEffects, "{name}");
+        }
+        let request = ApprovalRequest::new("id", "git_commit", &serde_json::json!({}));
+        assert!(
+            !approval_options(request.category)
+                .iter()
+                .any(|(_, _, decision)| *decision == ReviewDecision::ApprovedCategoryForSession)
+        );
+    }
+}
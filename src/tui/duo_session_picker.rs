@@ -395,11 +395,8 @@ impl ModalView for DuoSessionPicker {
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let popup_width = (area.width * 4 / 5).clamp(50, 85);
-        let popup_height = (MAX_VISIBLE_SESSIONS as u16 * 4 + 8).min(area.height - 4);
-        let popup_x = (area.width - popup_width) / 2;
-        let popup_y = (area.height - popup_height) / 2;
-        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+        let popup_height = (MAX_VISIBLE_SESSIONS as u16 * 4 + 8).min(area.height.saturating_sub(4));
+        let popup_area = crate::tui::layout::centered_rect_percent(area, 80, 50..=85, popup_height);
 
         Clear.render(popup_area, buf);
 
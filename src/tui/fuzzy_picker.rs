@@ -219,11 +219,8 @@ pub fn render(f: &mut Frame, picker: &FuzzyPicker, area: Rect) {
     }
 
     // Create a centered popup
-    let popup_width = (area.width * 4 / 5).clamp(40, 80);
-    let popup_height = (MAX_VISIBLE_MATCHES as u16 + 5).min(area.height - 4);
-    let popup_x = (area.width - popup_width) / 2;
-    let popup_y = (area.height - popup_height) / 2;
-    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+    let popup_height = (MAX_VISIBLE_MATCHES as u16 + 5).min(area.height.saturating_sub(4));
+    let popup_area = crate::tui::layout::centered_rect_percent(area, 80, 40..=80, popup_height);
 
     // Clear the background
     f.render_widget(Clear, popup_area);
@@ -317,7 +314,7 @@ pub fn render(f: &mut Frame, picker: &FuzzyPicker, area: Rect) {
 }
 
 /// Simple fuzzy matching algorithm
-fn fuzzy_match(haystack: &str, needle: &str) -> Option<(i64, Vec<usize>)> {
+pub(crate) fn fuzzy_match(haystack: &str, needle: &str) -> Option<(i64, Vec<usize>)> {
     if needle.is_empty() {
         return Some((0, Vec::new()));
     }
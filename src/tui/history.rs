@@ -6,7 +6,7 @@ use std::time::Instant;
 use ratatui::style::{Modifier, Style, Stylize};
 use ratatui::text::{Line, Span};
 use serde_json::Value;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::models::{ContentBlock, Message};
 use crate::palette;
@@ -17,6 +17,8 @@ use crate::tui::syntax;
 const TOOL_COMMAND_LINE_LIMIT: usize = 5;
 const TOOL_OUTPUT_LINE_LIMIT: usize = 12;
 const TOOL_TEXT_LIMIT: usize = 240;
+const TOOL_DIFF_LINE_LIMIT: usize = 40;
+const EXPAND_HINT: &str = "press Ctrl+O to expand";
 
 // === History Cells ===
 
@@ -48,6 +50,8 @@ pub enum HistoryCell {
 pub struct TranscriptRenderOptions {
     pub show_thinking: bool,
     pub show_tool_details: bool,
+    /// Truncate long tool output to a few lines with an expand marker.
+    pub collapse_tool_output: bool,
 }
 
 impl Default for TranscriptRenderOptions {
@@ -55,6 +59,7 @@ impl Default for TranscriptRenderOptions {
         Self {
             show_thinking: true,
             show_tool_details: true,
+            collapse_tool_output: true,
         }
     }
 }
@@ -89,7 +94,7 @@ impl HistoryCell {
         match self {
             HistoryCell::ThinkingSummary { .. } if !options.show_thinking => Vec::new(),
             HistoryCell::Tool(cell) if !options.show_tool_details => {
-                let mut lines = cell.lines(width);
+                let mut lines = cell.render(width, options.collapse_tool_output);
                 if lines.len() > 2 {
                     lines.truncate(2);
                     lines.push(Line::from(Span::styled(
@@ -99,6 +104,7 @@ impl HistoryCell {
                 }
                 lines
             }
+            HistoryCell::Tool(cell) => cell.render(width, options.collapse_tool_output),
             _ => self.lines(width),
         }
     }
@@ -163,6 +169,7 @@ pub enum ToolCell {
     Exploring(ExploringCell),
     PlanUpdate(PlanUpdateCell),
     PatchSummary(PatchSummaryCell),
+    Diff(DiffCell),
     Mcp(McpToolCell),
     ViewImage(ViewImageCell),
     WebSearch(WebSearchCell),
@@ -172,19 +179,30 @@ pub enum ToolCell {
 impl ToolCell {
     /// Render the tool cell into lines.
     pub fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.render(width, true)
+    }
+
+    /// Render the tool cell, truncating long output only when `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
         match self {
-            ToolCell::Exec(cell) => cell.lines(width),
+            ToolCell::Exec(cell) => cell.render(width, collapsed),
             ToolCell::Exploring(cell) => cell.lines(width),
             ToolCell::PlanUpdate(cell) => cell.lines(width),
-            ToolCell::PatchSummary(cell) => cell.lines(width),
-            ToolCell::Mcp(cell) => cell.lines(width),
+            ToolCell::PatchSummary(cell) => cell.render(width, collapsed),
+            ToolCell::Diff(cell) => cell.render(width, collapsed),
+            ToolCell::Mcp(cell) => cell.render(width, collapsed),
             ToolCell::ViewImage(cell) => cell.lines(width),
             ToolCell::WebSearch(cell) => cell.lines(width),
-            ToolCell::Generic(cell) => cell.lines(width),
+            ToolCell::Generic(cell) => cell.render(width, collapsed),
         }
     }
 }
 
+/// Line limit for tool output: `limit` when collapsed, unbounded when expanded.
+fn output_limit(limit: usize, collapsed: bool) -> usize {
+    if collapsed { limit } else { usize::MAX }
+}
+
 /// Overall status for a tool execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToolStatus {
@@ -206,8 +224,8 @@ pub struct ExecCell {
 }
 
 impl ExecCell {
-    /// Render the execution cell into lines.
-    pub fn lines(&self, width: u16) -> Vec<Line<'static>> {
+    /// Render the execution cell, showing the full output unless `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
         let (label, color) = match self.status {
             ToolStatus::Running => ("Running", palette::STATUS_WARNING),
@@ -238,7 +256,11 @@ impl ExecCell {
 
         if self.interaction.is_none() {
             if let Some(output) = self.output.as_ref() {
-                lines.extend(render_exec_output(output, width, TOOL_OUTPUT_LINE_LIMIT));
+                lines.extend(render_exec_output(
+                    output,
+                    width,
+                    output_limit(TOOL_OUTPUT_LINE_LIMIT, collapsed),
+                ));
             } else if self.status != ToolStatus::Running {
                 lines.push(Line::from(Span::styled(
                     "  (no output)",
@@ -259,6 +281,28 @@ impl ExecCell {
     }
 }
 
+/// Header line for a tool cell, followed by the execution time once known.
+fn tool_header(header: String, style: Style, duration_ms: Option<u64>) -> Line<'static> {
+    let mut spans = vec![Span::styled(header, style)];
+    if let Some(duration_ms) = duration_ms {
+        spans.push(Span::styled(
+            format!(" ({})", format_tool_duration(duration_ms)),
+            Style::default().fg(palette::TEXT_MUTED),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Format a tool's execution time, e.g. `45ms` or `1.2s`.
+pub fn format_tool_duration(duration_ms: u64) -> String {
+    if duration_ms < 1000 {
+        format!("{duration_ms}ms")
+    } else {
+        let seconds = f64::from(u32::try_from(duration_ms).unwrap_or(u32::MAX)) / 1000.0;
+        format!("{seconds:.1}s")
+    }
+}
+
 /// Source of a shell command execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExecSource {
@@ -377,13 +421,17 @@ pub struct PlanStep {
 pub struct PatchSummaryCell {
     pub path: String,
     pub summary: String,
+    /// Unified diff sent to the tool, shown colorized under the summary
+    pub diff: String,
     pub status: ToolStatus,
     pub error: Option<String>,
+    pub duration_ms: Option<u64>,
 }
 
 impl PatchSummaryCell {
-    /// Render the patch summary cell into lines.
-    pub fn lines(&self, width: u16) -> Vec<Line<'static>> {
+    /// Render the patch summary cell, showing the whole diff unless `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
+        let limit = output_limit(TOOL_COMMAND_LINE_LIMIT, collapsed);
         let mut lines = Vec::new();
         let header = match self.status {
             ToolStatus::Running => "Applying Patch",
@@ -395,27 +443,57 @@ impl PatchSummaryCell {
             ToolStatus::Success => palette::STATUS_SUCCESS,
             ToolStatus::Failed => palette::STATUS_ERROR,
         };
-        lines.push(Line::from(Span::styled(
-            header,
+        lines.push(tool_header(
+            header.to_string(),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
-        )));
+            self.duration_ms,
+        ));
         lines.extend(wrap_plain_line(
             &format!("  {}", self.path),
             Style::default().fg(palette::TEXT_MUTED),
             width,
         ));
-        lines.extend(render_tool_output(
-            &self.summary,
-            width,
-            TOOL_COMMAND_LINE_LIMIT,
-        ));
+        lines.extend(render_tool_output(&self.summary, width, limit));
+        if syntax::is_unified_diff(&self.diff) {
+            lines.extend(render_diff(
+                &self.diff,
+                width,
+                output_limit(TOOL_DIFF_LINE_LIMIT, collapsed),
+            ));
+        }
         if let Some(error) = self.error.as_ref() {
-            lines.extend(render_tool_output(error, width, TOOL_COMMAND_LINE_LIMIT));
+            lines.extend(render_tool_output(error, width, limit));
         }
         lines
     }
 }
 
+/// Cell for a git diff shown by the `/diff` command.
+#[derive(Debug, Clone)]
+pub struct DiffCell {
+    /// Header describing what was diffed, e.g. "Staged Diff: src"
+    pub title: String,
+    pub diff: String,
+}
+
+impl DiffCell {
+    /// Render the diff cell, showing the whole diff unless `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(Span::styled(
+            self.title.clone(),
+            Style::default()
+                .fg(palette::BLUE)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        lines.extend(render_diff(
+            &self.diff,
+            width,
+            output_limit(TOOL_DIFF_LINE_LIMIT, collapsed),
+        ));
+        lines
+    }
+}
+
 /// Cell representing an MCP tool execution.
 #[derive(Debug, Clone)]
 pub struct McpToolCell {
@@ -423,11 +501,12 @@ pub struct McpToolCell {
     pub status: ToolStatus,
     pub content: Option<String>,
     pub is_image: bool,
+    pub duration_ms: Option<u64>,
 }
 
 impl McpToolCell {
-    /// Render the MCP tool cell into lines.
-    pub fn lines(&self, width: u16) -> Vec<Line<'static>> {
+    /// Render the MCP tool cell, showing the full content unless `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
         let header = match self.status {
             ToolStatus::Running => format!("Calling {}", self.tool),
@@ -438,10 +517,11 @@ impl McpToolCell {
         } else {
             palette::BLUE
         };
-        lines.push(Line::from(Span::styled(
+        lines.push(tool_header(
             header,
             Style::default().fg(color).add_modifier(Modifier::BOLD),
-        )));
+            self.duration_ms,
+        ));
 
         if self.is_image {
             lines.push(Line::from(Span::styled(
@@ -451,7 +531,11 @@ impl McpToolCell {
         }
 
         if let Some(content) = self.content.as_ref() {
-            lines.extend(render_tool_output(content, width, TOOL_COMMAND_LINE_LIMIT));
+            lines.extend(render_tool_output(
+                content,
+                width,
+                output_limit(TOOL_COMMAND_LINE_LIMIT, collapsed),
+            ));
         }
         lines
     }
@@ -477,6 +561,7 @@ pub struct WebSearchCell {
     pub query: String,
     pub status: ToolStatus,
     pub summary: Option<String>,
+    pub duration_ms: Option<u64>,
 }
 
 impl WebSearchCell {
@@ -487,12 +572,13 @@ impl WebSearchCell {
             ToolStatus::Running => "Searching",
             _ => "Searched",
         };
-        lines.push(Line::from(Span::styled(
-            header,
+        lines.push(tool_header(
+            header.to_string(),
             Style::default()
                 .fg(palette::BLUE)
                 .add_modifier(Modifier::BOLD),
-        )));
+            self.duration_ms,
+        ));
         lines.extend(wrap_plain_line(
             &format!("  {}", self.query),
             Style::default().fg(palette::TEXT_MUTED),
@@ -516,12 +602,14 @@ pub struct GenericToolCell {
     pub name: String,
     pub status: ToolStatus,
     pub input_summary: Option<String>,
+    /// Raw tool output; summarized to one line while collapsed.
     pub output: Option<String>,
+    pub duration_ms: Option<u64>,
 }
 
 impl GenericToolCell {
-    /// Render the generic tool cell into lines.
-    pub fn lines(&self, width: u16) -> Vec<Line<'static>> {
+    /// Render the generic tool cell, showing the raw output unless `collapsed`.
+    pub fn render(&self, width: u16, collapsed: bool) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
         let header = match self.status {
             ToolStatus::Running => format!("Calling {}", self.name),
@@ -532,10 +620,11 @@ impl GenericToolCell {
         } else {
             palette::BLUE
         };
-        lines.push(Line::from(Span::styled(
+        lines.push(tool_header(
             header,
             Style::default().fg(color).add_modifier(Modifier::BOLD),
-        )));
+            self.duration_ms,
+        ));
         let show_args = matches!(self.status, ToolStatus::Running) || self.output.is_none();
         if show_args && let Some(summary) = self.input_summary.as_ref() {
             lines.extend(render_compact_kv(
@@ -551,7 +640,18 @@ impl GenericToolCell {
             } else {
                 Style::default().fg(palette::TEXT_MUTED)
             };
-            lines.extend(render_compact_kv("result:", output, style, width));
+            if collapsed {
+                let summary = summarize_tool_output(output);
+                lines.extend(render_compact_kv("result:", &summary, style, width));
+                if summary != *output {
+                    lines.push(Line::from(Span::styled(
+                        format!("  … ({EXPAND_HINT})"),
+                        Style::default().fg(palette::TEXT_MUTED),
+                    )));
+                }
+            } else {
+                lines.extend(render_tool_output(output, width, usize::MAX));
+            }
         }
         lines
     }
@@ -1092,10 +1192,7 @@ fn render_tool_output(output: &str, width: u16, line_limit: usize) -> Vec<Line<'
         if idx >= line_limit {
             let omitted = total.saturating_sub(line_limit);
             if omitted > 0 {
-                lines.push(Line::from(Span::styled(
-                    format!("  ... +{omitted} lines"),
-                    Style::default().fg(palette::TEXT_MUTED),
-                )));
+                lines.push(collapsed_marker(omitted));
             }
             break;
         }
@@ -1107,6 +1204,41 @@ fn render_tool_output(output: &str, width: u16, line_limit: usize) -> Vec<Line<'
     lines
 }
 
+/// Unified diff with per-line colors. Lines are hard-wrapped by display width
+/// (not at word boundaries) so code indentation survives.
+fn render_diff(diff: &str, width: u16, line_limit: usize) -> Vec<Line<'static>> {
+    let content_width = width.saturating_sub(4).max(1) as usize;
+    let mut all_lines = Vec::new();
+    for line in diff.lines() {
+        let style = syntax::diff_line_style(line);
+        for part in wrap_chars(&line.replace('\t', "    "), content_width) {
+            all_lines.push((part, style));
+        }
+    }
+
+    let total = all_lines.len();
+    let mut lines = Vec::new();
+    for (part, style) in all_lines.into_iter().take(line_limit) {
+        lines.push(Line::from(vec![
+            Span::styled("  | ", Style::default().fg(palette::TEXT_MUTED)),
+            Span::styled(part, style),
+        ]));
+    }
+    if total > line_limit {
+        lines.push(collapsed_marker(total - line_limit));
+    }
+    lines
+}
+
+/// Marker for output hidden while a tool cell is collapsed.
+fn collapsed_marker(omitted: usize) -> Line<'static> {
+    let noun = if omitted == 1 { "line" } else { "lines" };
+    Line::from(Span::styled(
+        format!("  … ({omitted} more {noun}, {EXPAND_HINT})"),
+        Style::default().fg(palette::TEXT_MUTED),
+    ))
+}
+
 fn render_exec_output(output: &str, width: u16, line_limit: usize) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     if output.trim().is_empty() {
@@ -1131,12 +1263,9 @@ fn render_exec_output(output: &str, width: u16, line_limit: usize) -> Vec<Line<'
         ]));
     }
 
-    if total > 2 * line_limit {
+    if total > line_limit.saturating_mul(2) {
         let omitted = total.saturating_sub(2 * line_limit);
-        lines.push(Line::from(Span::styled(
-            format!("  ... +{omitted} lines"),
-            Style::default().fg(palette::TEXT_MUTED),
-        )));
+        lines.push(collapsed_marker(omitted));
         let tail_start = total.saturating_sub(line_limit);
         for line in &all_lines[tail_start..] {
             lines.push(Line::from(vec![
@@ -1164,6 +1293,26 @@ fn wrap_plain_line(line: &str, style: Style, width: u16) -> Vec<Line<'static>> {
     lines
 }
 
+/// Break `text` into chunks of at most `width` display columns, keeping whitespace.
+fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if current_width + ch_width > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current.push(ch);
+        current_width += ch_width;
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
 fn wrap_text(text: &str, width: usize) -> Vec<String> {
     if width == 0 {
         return vec![text.to_string()];
@@ -1308,7 +1457,116 @@ fn render_error(message: &str, suggestion: Option<&str>, width: u16) -> Vec<Line
 
 #[cfg(test)]
 mod tests {
-    use super::extract_reasoning_summary;
+    use super::{
+        ExecCell, ExecSource, GenericToolCell, HistoryCell, PatchSummaryCell, ToolCell, ToolStatus,
+        TranscriptRenderOptions, extract_reasoning_summary, format_tool_duration,
+    };
+    use crate::palette;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn patch_summary_colors_diff_lines_within_width() {
+        let cell = PatchSummaryCell {
+            path: "src/lib.rs".to_string(),
+            summary: "Changes: +1 / -1".to_string(),
+            diff: "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-    let old = 1;\n+    let new_value = compute_something_long(argument_one, argument_two);\n"
+                .to_string(),
+            status: ToolStatus::Success,
+            error: None,
+            duration_ms: None,
+        };
+        let width = 30;
+        let lines = cell.render(width, true);
+
+        for line in &lines {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            assert!(
+                text.width() <= usize::from(width),
+                "line too wide: {text:?}"
+            );
+        }
+        let fg_of = |needle: &str| {
+            lines
+                .iter()
+                .flat_map(|line| &line.spans)
+                .find(|span| span.content.contains(needle))
+                .and_then(|span| span.style.fg)
+        };
+        assert_eq!(fg_of("-    let old"), Some(palette::RED));
+        assert_eq!(fg_of("+    let new_value"), Some(palette::GREEN));
+        assert_eq!(fg_of("@@ -1 +1 @@"), Some(palette::BLUE));
+        // Wrapped continuation keeps the addition color
+        assert_eq!(fg_of("argument_two);"), Some(palette::GREEN));
+    }
+
+    #[test]
+    fn completed_tool_header_shows_duration() {
+        let mut cell = GenericToolCell {
+            name: "note".to_string(),
+            status: ToolStatus::Running,
+            input_summary: None,
+            output: None,
+            duration_ms: None,
+        };
+        let header = |cell: &GenericToolCell| {
+            cell.render(80, true)[0]
+                .spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect::<String>()
+        };
+        assert_eq!(header(&cell), "Calling note");
+
+        cell.status = ToolStatus::Success;
+        cell.duration_ms = Some(1234);
+        assert_eq!(header(&cell), "Called note (1.2s)");
+
+        assert_eq!(format_tool_duration(45), "45ms");
+        assert_eq!(format_tool_duration(60_000), "60.0s");
+    }
+
+    #[test]
+    fn collapsed_exec_output_shows_expand_marker() {
+        let output = (1..=40)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cell = HistoryCell::Tool(ToolCell::Exec(ExecCell {
+            command: "seq 40".to_string(),
+            status: ToolStatus::Success,
+            output: Some(output),
+            started_at: None,
+            duration_ms: None,
+            source: ExecSource::Assistant,
+            interaction: None,
+        }));
+        let text = |options| {
+            cell.lines_with_options(80, options)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let collapsed = text(TranscriptRenderOptions::default());
+        assert!(
+            collapsed
+                .iter()
+                .any(|l| l.contains("16 more lines, press Ctrl+O to expand"))
+        );
+        assert!(!collapsed.iter().any(|l| l.ends_with("line 20")));
+
+        let expanded = text(TranscriptRenderOptions {
+            collapse_tool_output: false,
+            ..TranscriptRenderOptions::default()
+        });
+        assert!(expanded.iter().any(|l| l.ends_with("line 20")));
+        assert!(!expanded.iter().any(|l| l.contains("more lines")));
+    }
 
     #[test]
     fn extract_reasoning_summary_prefers_summary_block() {
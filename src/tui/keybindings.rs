@@ -0,0 +1,182 @@
+//! Keybinding registry shared by the event loop and the help overlay.
+//!
+//! The vim table drives `vim_motion` directly; the default table documents the
+//! composer keys handled in `run_event_loop`. `HelpView` renders whichever set
+//! is active so help never drifts from behavior.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A key and what it does, as shown in help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+const fn binding(keys: &'static str, description: &'static str) -> KeyBinding {
+    KeyBinding { keys, description }
+}
+
+/// Default (emacs-style) composer bindings.
+pub const DEFAULT_BINDINGS: &[KeyBinding] = &[
+    binding("Enter", "Send message / Execute shell command"),
+    binding("Esc", "Cancel request / Clear input"),
+    binding("Tab", "Cycle modes"),
+    binding("Ctrl+C", "Exit"),
+    binding("Ctrl+X", "Toggle shell mode"),
+    binding("Ctrl+J", "Insert newline (multiline input)"),
+    binding("Alt+Enter", "Insert newline (multiline input)"),
+    binding("Ctrl+D", "Exit when input is empty"),
+    binding("Ctrl+U", "Clear input"),
+    binding("Ctrl+W", "Delete word backward"),
+    binding("Ctrl+K", "Delete to end of line"),
+    binding("Ctrl+V", "Paste from clipboard"),
+    binding("Ctrl+O", "Collapse/expand tool output"),
+    binding("Ctrl+F", "Search transcript"),
+    binding("PgUp/PgDn", "Scroll transcript"),
+    binding("Ctrl+/", "Show this help"),
+    binding("F1", "Show this help"),
+];
+
+/// Transcript navigation remapped by `vim_mode` while the composer is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimMotion {
+    LineDown,
+    LineUp,
+    HalfPageDown,
+    HalfPageUp,
+    Top,
+    Bottom,
+    Search,
+    Command,
+}
+
+/// A vim key: the key it matches, the motion it triggers, and its help text.
+#[derive(Debug, Clone, Copy)]
+pub struct VimBinding {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub motion: VimMotion,
+    pub binding: KeyBinding,
+}
+
+const fn vim(code: char, ctrl: bool, motion: VimMotion, binding: KeyBinding) -> VimBinding {
+    VimBinding {
+        code: KeyCode::Char(code),
+        ctrl,
+        motion,
+        binding,
+    }
+}
+
+pub const VIM_BINDINGS: &[VimBinding] = &[
+    vim(
+        'j',
+        false,
+        VimMotion::LineDown,
+        binding("j", "Scroll transcript down"),
+    ),
+    vim(
+        'k',
+        false,
+        VimMotion::LineUp,
+        binding("k", "Scroll transcript up"),
+    ),
+    vim('g', false, VimMotion::Top, binding("g", "Jump to top")),
+    vim(
+        'G',
+        false,
+        VimMotion::Bottom,
+        binding("G", "Jump to bottom"),
+    ),
+    vim(
+        'd',
+        true,
+        VimMotion::HalfPageDown,
+        binding("Ctrl+D", "Half page down"),
+    ),
+    vim(
+        'u',
+        true,
+        VimMotion::HalfPageUp,
+        binding("Ctrl+U", "Half page up"),
+    ),
+    vim(
+        '/',
+        false,
+        VimMotion::Search,
+        binding("/", "Search transcript"),
+    ),
+    vim(
+        ':',
+        false,
+        VimMotion::Command,
+        binding(":", "Start a slash command"),
+    ),
+];
+
+/// Vim motion for `key`, ignoring Alt chords. Callers check that vim mode is
+/// on and the composer is empty.
+#[must_use]
+pub fn vim_motion_for(key: &KeyEvent) -> Option<VimMotion> {
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        return None;
+    }
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    VIM_BINDINGS
+        .iter()
+        .find(|b| b.code == key.code && b.ctrl == ctrl)
+        .map(|b| b.motion)
+}
+
+/// Help sections for the active keybinding mode. In vim mode, default bindings
+/// on the same key are dropped in favour of the vim ones.
+#[must_use]
+pub fn help_sections(vim_mode: bool) -> Vec<(&'static str, Vec<KeyBinding>)> {
+    if !vim_mode {
+        return vec![("Keys:", DEFAULT_BINDINGS.to_vec())];
+    }
+    let vim_keys: Vec<KeyBinding> = VIM_BINDINGS.iter().map(|b| b.binding).collect();
+    let defaults = DEFAULT_BINDINGS
+        .iter()
+        .filter(|b| !vim_keys.iter().any(|v| v.keys == b.keys))
+        .copied()
+        .collect();
+    vec![
+        ("Vim keys (composer empty):", vim_keys),
+        ("Keys:", defaults),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vim_help_replaces_overridden_default_keys() {
+        let default = help_sections(false);
+        assert_eq!(default.len(), 1);
+        assert!(default[0].1.iter().any(|b| b.keys == "Ctrl+D"));
+
+        let vim = help_sections(true);
+        assert_eq!(vim.len(), 2);
+        assert_eq!(vim[0].1.len(), VIM_BINDINGS.len());
+        let ctrl_d: Vec<_> = vim
+            .iter()
+            .flat_map(|(_, bindings)| bindings)
+            .filter(|b| b.keys == "Ctrl+D")
+            .collect();
+        assert_eq!(ctrl_d.len(), 1);
+        assert_eq!(ctrl_d[0].description, "Half page down");
+    }
+
+    #[test]
+    fn vim_motion_matches_registry_keys() {
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(vim_motion_for(&ctrl_d), Some(VimMotion::HalfPageDown));
+        let d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(vim_motion_for(&d), None);
+        let alt_j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::ALT);
+        assert_eq!(vim_motion_for(&alt_j), None);
+    }
+}
@@ -0,0 +1,65 @@
+//! Popup geometry shared by modal views.
+//!
+//! Every helper clamps the popup to the available area and uses saturating
+//! math, so modals render (shrunk) instead of panicking on tiny terminals.
+
+use std::ops::RangeInclusive;
+
+use ratatui::layout::Rect;
+
+/// Center a `width` x `height` popup in `area`, shrinking it to fit.
+#[must_use]
+pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x.saturating_add(area.width.saturating_sub(width) / 2),
+        y: area
+            .y
+            .saturating_add(area.height.saturating_sub(height) / 2),
+        width,
+        height,
+    }
+}
+
+/// Center a popup `percent_x`% of `area` wide (kept within `width`) and
+/// `height` rows tall, shrinking it to fit.
+#[must_use]
+pub fn centered_rect_percent(
+    area: Rect,
+    percent_x: u16,
+    width: RangeInclusive<u16>,
+    height: u16,
+) -> Rect {
+    let scaled = u32::from(area.width) * u32::from(percent_x.min(100)) / 100;
+    let scaled = u16::try_from(scaled).unwrap_or(u16::MAX);
+    centered_rect(area, scaled.clamp(*width.start(), *width.end()), height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centered_rect_shrinks_to_fit_the_area() {
+        let area = Rect::new(2, 1, 10, 4);
+        assert_eq!(centered_rect(area, 6, 2), Rect::new(4, 2, 6, 2));
+        assert_eq!(centered_rect(area, 80, 24), area);
+        assert_eq!(centered_rect(Rect::default(), 80, 24), Rect::default());
+    }
+
+    #[test]
+    fn centered_rect_percent_scales_within_bounds() {
+        let wide = Rect::new(0, 0, 200, 50);
+        assert_eq!(centered_rect_percent(wide, 80, 50..=80, 10).width, 80);
+        let medium = Rect::new(0, 0, 90, 50);
+        assert_eq!(centered_rect_percent(medium, 80, 50..=80, 10).width, 72);
+        let narrow = Rect::new(0, 0, 55, 50);
+        assert_eq!(centered_rect_percent(narrow, 80, 50..=80, 10).width, 50);
+        let tiny = Rect::new(0, 0, 20, 3);
+        assert_eq!(
+            centered_rect_percent(tiny, 80, 50..=80, 10),
+            Rect::new(0, 0, 20, 3)
+        );
+    }
+}
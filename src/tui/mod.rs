@@ -11,6 +11,8 @@ pub mod event_broker;
 pub mod fuzzy_picker;
 pub mod history;
 pub mod history_picker;
+pub mod keybindings;
+pub mod layout;
 pub mod model_picker;
 pub mod paste_burst;
 pub mod scrolling;
@@ -20,6 +22,7 @@ pub mod session_picker;
 pub mod streaming;
 pub mod suggestions;
 pub mod syntax;
+pub mod terminal_guard;
 pub mod transcript;
 pub mod tutorial;
 pub mod ui;
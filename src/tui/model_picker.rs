@@ -284,11 +284,8 @@ impl ModalView for ModelPicker {
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
         // Create a centered popup
-        let popup_width = (area.width * 3 / 5).clamp(50, 70);
-        let popup_height = (self.models.len() as u16 * 5 + 6).min(area.height - 4);
-        let popup_x = (area.width - popup_width) / 2;
-        let popup_y = (area.height - popup_height) / 2;
-        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+        let popup_height = (self.models.len() as u16 * 5 + 6).min(area.height.saturating_sub(4));
+        let popup_area = crate::tui::layout::centered_rect_percent(area, 60, 50..=70, popup_height);
 
         // Clear the background
         Clear.render(popup_area, buf);
@@ -2,13 +2,49 @@
 
 use std::time::{Duration, Instant};
 
-const PASTE_BURST_MIN_CHARS: u16 = 3;
-const PASTE_BURST_CHAR_INTERVAL: Duration = Duration::from_millis(8);
-const PASTE_ENTER_SUPPRESS_WINDOW: Duration = Duration::from_millis(120);
+use crate::settings::Settings;
+
+pub(crate) const DEFAULT_PASTE_BURST_MIN_CHARS: u16 = 3;
+pub(crate) const DEFAULT_PASTE_BURST_INTERVAL_MS: u64 = 8;
 #[cfg(not(windows))]
-const PASTE_BURST_ACTIVE_IDLE_TIMEOUT: Duration = Duration::from_millis(8);
+pub(crate) const DEFAULT_PASTE_BURST_IDLE_MS: u64 = 8;
 #[cfg(windows)]
-const PASTE_BURST_ACTIVE_IDLE_TIMEOUT: Duration = Duration::from_millis(60);
+pub(crate) const DEFAULT_PASTE_BURST_IDLE_MS: u64 = 60;
+const PASTE_ENTER_SUPPRESS_WINDOW: Duration = Duration::from_millis(120);
+/// Idle gap that ends a paste forced with `/paste`; generous so slow
+/// terminals do not split it.
+const FORCED_PASTE_IDLE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Timing thresholds for burst detection, from the `paste_burst_*` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PasteBurstConfig {
+    /// Consecutive fast keystrokes before input is treated as a paste
+    pub min_chars: u16,
+    /// Longest gap between keystrokes that still counts as one burst
+    pub char_interval: Duration,
+    /// Idle time after which an active burst is flushed as a paste
+    pub active_idle_timeout: Duration,
+}
+
+impl Default for PasteBurstConfig {
+    fn default() -> Self {
+        Self {
+            min_chars: DEFAULT_PASTE_BURST_MIN_CHARS,
+            char_interval: Duration::from_millis(DEFAULT_PASTE_BURST_INTERVAL_MS),
+            active_idle_timeout: Duration::from_millis(DEFAULT_PASTE_BURST_IDLE_MS),
+        }
+    }
+}
+
+impl PasteBurstConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            min_chars: settings.paste_burst_min_chars,
+            char_interval: Duration::from_millis(settings.paste_burst_interval_ms),
+            active_idle_timeout: Duration::from_millis(settings.paste_burst_idle_ms),
+        }
+    }
+}
 
 #[derive(Default)]
 pub(crate) struct PasteBurst {
@@ -18,6 +54,9 @@ pub(crate) struct PasteBurst {
     buffer: String,
     active: bool,
     pending_first_char: Option<(char, Instant)>,
+    /// Set by `/paste`: buffer the next input as a paste regardless of speed
+    forced: bool,
+    config: PasteBurstConfig,
 }
 
 pub(crate) enum CharDecision {
@@ -39,18 +78,42 @@ pub(crate) enum FlushResult {
 }
 
 impl PasteBurst {
+    pub fn new(config: PasteBurstConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_config(&mut self, config: PasteBurstConfig) {
+        self.config = config;
+    }
+
     #[cfg(test)]
     pub fn recommended_flush_delay() -> Duration {
-        PASTE_BURST_CHAR_INTERVAL + Duration::from_millis(1)
+        PasteBurstConfig::default().char_interval + Duration::from_millis(1)
     }
 
     #[cfg(test)]
     pub(crate) fn recommended_active_flush_delay() -> Duration {
-        PASTE_BURST_ACTIVE_IDLE_TIMEOUT + Duration::from_millis(1)
+        PasteBurstConfig::default().active_idle_timeout + Duration::from_millis(1)
+    }
+
+    /// Treat the next input as a paste: every key, Enter included, is
+    /// buffered until input goes idle.
+    pub fn force_next_paste(&mut self) {
+        self.clear_after_explicit_paste();
+        self.forced = true;
+    }
+
+    #[cfg(test)]
+    pub fn is_forced(&self) -> bool {
+        self.forced
     }
 
     pub fn on_plain_char(&mut self, ch: char, now: Instant) -> CharDecision {
         self.note_plain_char(now);
+        self.active |= self.forced;
 
         if self.active {
             self.burst_window_until = Some(now + PASTE_ENTER_SUPPRESS_WINDOW);
@@ -58,7 +121,7 @@ impl PasteBurst {
         }
 
         if let Some((held, held_at)) = self.pending_first_char
-            && now.duration_since(held_at) <= PASTE_BURST_CHAR_INTERVAL
+            && now.duration_since(held_at) <= self.config.char_interval
         {
             self.active = true;
             let _ = self.pending_first_char.take();
@@ -67,7 +130,7 @@ impl PasteBurst {
             return CharDecision::BeginBufferFromPending;
         }
 
-        if self.consecutive_plain_char_burst >= PASTE_BURST_MIN_CHARS {
+        if self.consecutive_plain_char_burst >= self.config.min_chars {
             return CharDecision::BeginBuffer {
                 retro_chars: self.consecutive_plain_char_burst.saturating_sub(1),
             };
@@ -79,13 +142,14 @@ impl PasteBurst {
 
     pub fn on_plain_char_no_hold(&mut self, now: Instant) -> Option<CharDecision> {
         self.note_plain_char(now);
+        self.active |= self.forced;
 
         if self.active {
             self.burst_window_until = Some(now + PASTE_ENTER_SUPPRESS_WINDOW);
             return Some(CharDecision::BufferAppend);
         }
 
-        if self.consecutive_plain_char_burst >= PASTE_BURST_MIN_CHARS {
+        if self.consecutive_plain_char_burst >= self.config.min_chars {
             return Some(CharDecision::BeginBuffer {
                 retro_chars: self.consecutive_plain_char_burst.saturating_sub(1),
             });
@@ -96,7 +160,7 @@ impl PasteBurst {
 
     fn note_plain_char(&mut self, now: Instant) {
         match self.last_plain_char_time {
-            Some(prev) if now.duration_since(prev) <= PASTE_BURST_CHAR_INTERVAL => {
+            Some(prev) if now.duration_since(prev) <= self.config.char_interval => {
                 self.consecutive_plain_char_burst =
                     self.consecutive_plain_char_burst.saturating_add(1);
             }
@@ -106,10 +170,12 @@ impl PasteBurst {
     }
 
     pub fn flush_if_due(&mut self, now: Instant) -> FlushResult {
-        let timeout = if self.is_active_internal() {
-            PASTE_BURST_ACTIVE_IDLE_TIMEOUT
+        let timeout = if self.forced {
+            FORCED_PASTE_IDLE_TIMEOUT
+        } else if self.is_active_internal() {
+            self.config.active_idle_timeout
         } else {
-            PASTE_BURST_CHAR_INTERVAL
+            self.config.char_interval
         };
         let timed_out = self
             .last_plain_char_time
@@ -117,6 +183,7 @@ impl PasteBurst {
 
         if timed_out && self.is_active_internal() {
             self.active = false;
+            self.forced = false;
             let out = std::mem::take(&mut self.buffer);
             FlushResult::Paste(out)
         } else if timed_out {
@@ -132,6 +199,11 @@ impl PasteBurst {
 
     pub fn append_newline_if_active(&mut self, now: Instant) -> bool {
         if self.is_active() {
+            if self.forced {
+                // A forced paste may start with newlines; they count as input
+                self.active = true;
+                self.last_plain_char_time = Some(now);
+            }
             self.buffer.push('\n');
             self.burst_window_until = Some(now + PASTE_ENTER_SUPPRESS_WINDOW);
             true
@@ -197,6 +269,7 @@ impl PasteBurst {
             return None;
         }
         self.active = false;
+        self.forced = false;
         let mut out = std::mem::take(&mut self.buffer);
         if let Some((ch, _)) = self.pending_first_char.take() {
             out.push(ch);
@@ -210,10 +283,11 @@ impl PasteBurst {
         self.burst_window_until = None;
         self.active = false;
         self.pending_first_char = None;
+        self.forced = false;
     }
 
     pub fn is_active(&self) -> bool {
-        self.is_active_internal() || self.pending_first_char.is_some()
+        self.is_active_internal() || self.pending_first_char.is_some() || self.forced
     }
 
     fn is_active_internal(&self) -> bool {
@@ -227,6 +301,7 @@ impl PasteBurst {
         self.active = false;
         self.buffer.clear();
         self.pending_first_char = None;
+        self.forced = false;
     }
 }
 
@@ -295,4 +370,59 @@ mod tests {
         assert_eq!(burst.flush_before_modified_input(), Some("a".to_string()));
         assert!(!burst.is_active());
     }
+
+    #[test]
+    fn configured_thresholds_change_what_counts_as_a_burst() {
+        let mut burst = PasteBurst::new(PasteBurstConfig {
+            min_chars: 3,
+            char_interval: Duration::from_millis(40),
+            active_idle_timeout: Duration::from_millis(40),
+        });
+        let t0 = Instant::now();
+        assert!(matches!(
+            burst.on_plain_char('a', t0),
+            CharDecision::RetainFirstChar
+        ));
+        // 20ms apart is typing by default but a burst with a 40ms interval
+        let t1 = t0 + Duration::from_millis(20);
+        assert!(matches!(
+            burst.on_plain_char('b', t1),
+            CharDecision::BeginBufferFromPending
+        ));
+        burst.append_char_to_buffer('b', t1);
+        assert!(matches!(
+            burst.flush_if_due(t1 + Duration::from_millis(30)),
+            FlushResult::None
+        ));
+        assert!(matches!(
+            burst.flush_if_due(t1 + Duration::from_millis(41)),
+            FlushResult::Paste(ref s) if s == "ab"
+        ));
+    }
+
+    #[test]
+    fn forced_paste_buffers_slow_input_and_newlines() {
+        let mut burst = PasteBurst::default();
+        burst.force_next_paste();
+        let t0 = Instant::now();
+        assert!(burst.append_newline_if_active(t0));
+        let t1 = t0 + Duration::from_millis(100);
+        assert!(matches!(
+            burst.on_plain_char('a', t1),
+            CharDecision::BufferAppend
+        ));
+        burst.append_char_to_buffer('a', t1);
+        let t2 = t1 + Duration::from_millis(100);
+        assert!(burst.newline_should_insert_instead_of_submit(t2));
+        assert!(burst.append_newline_if_active(t2));
+        assert!(matches!(burst.flush_if_due(t2), FlushResult::None));
+
+        let t3 = t2 + FORCED_PASTE_IDLE_TIMEOUT + Duration::from_millis(1);
+        assert!(matches!(
+            burst.flush_if_due(t3),
+            FlushResult::Paste(ref s) if s == "\na\n"
+        ));
+        assert!(!burst.is_forced());
+        assert!(!burst.is_active());
+    }
 }
@@ -2,6 +2,8 @@
 
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+
 // === Transcript Line Metadata ===
 
 /// Metadata describing how rendered transcript lines map to history cells.
@@ -31,7 +33,10 @@ impl TranscriptLineMeta {
 // === Scroll Anchors ===
 
 /// Scroll anchor for the transcript view.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Saved with each session so resuming returns to the same place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum TranscriptScroll {
     #[default]
     ToBottom,
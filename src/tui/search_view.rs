@@ -40,6 +40,7 @@ pub struct SearchView {
     selected_idx: usize,
     scroll_offset: usize,
     last_results: Vec<SearchResult>,
+    error: Option<String>,
 }
 
 impl SearchView {
@@ -55,6 +56,7 @@ impl SearchView {
             selected_idx: 0,
             scroll_offset: 0,
             last_results: Vec::new(),
+            error: None,
         }
     }
 
@@ -78,94 +80,73 @@ impl SearchView {
         self.selected_idx
     }
 
-    /// Search through history cells and return results
+    /// Error from the last regex compile, shown under the query
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Compile the query for the current mode. Literal queries are escaped so
+    /// both modes share one matcher.
+    fn matcher(&self) -> Result<Regex, String> {
+        let pattern = if self.regex_mode {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        let flags = if self.case_sensitive { "(?m)" } else { "(?im)" };
+        Regex::new(&format!("{flags}{pattern}")).map_err(|e| {
+            let message = e.to_string();
+            let detail = message.lines().last().unwrap_or(&message).trim();
+            detail.trim_start_matches("error: ").to_string()
+        })
+    }
+
+    /// Search through history cells and return results.
+    ///
+    /// An invalid regex leaves the previous results in place and records the
+    /// error for display instead.
     pub fn search(&mut self, history: &[HistoryCell]) -> Vec<SearchResult> {
         if self.query.is_empty() {
+            self.error = None;
             self.last_results.clear();
             return Vec::new();
         }
 
+        let re = match self.matcher() {
+            Ok(re) => {
+                self.error = None;
+                re
+            }
+            Err(e) => {
+                self.error = Some(e);
+                return self.last_results.clone();
+            }
+        };
+
         let mut results = Vec::new();
 
         for (cell_idx, cell) in history.iter().enumerate() {
             let (content, source) = match cell {
-                HistoryCell::User { content } => (content.clone(), "You"),
-                HistoryCell::Assistant { content, .. } => (content.clone(), "Assistant"),
-                HistoryCell::System { content } => (content.clone(), "System"),
-                HistoryCell::ThinkingSummary { summary } => (summary.clone(), "Thinking"),
+                HistoryCell::User { content } => (content.as_str(), "You"),
+                HistoryCell::Assistant { content, .. } => (content.as_str(), "Assistant"),
+                HistoryCell::System { content } => (content.as_str(), "System"),
+                HistoryCell::ThinkingSummary { summary } => (summary.as_str(), "Thinking"),
                 _ => continue,
             };
 
             let timestamp = format!("#{}", cell_idx + 1);
 
-            if self.regex_mode {
-                // Regex search
-                let pattern = if self.case_sensitive {
-                    format!("(?m){}", regex::escape(&self.query))
-                } else {
-                    format!("(?im){}", regex::escape(&self.query))
-                };
-
-                if let Ok(re) = Regex::new(&pattern) {
-                    for mat in re.find_iter(&content) {
-                        let preview = Self::create_preview(&content, mat.start(), mat.end());
-                        let match_start =
-                            preview.find(&content[mat.start()..mat.end()]).unwrap_or(0);
-                        let match_end = match_start + (mat.end() - mat.start());
-
-                        results.push(SearchResult {
-                            cell_index: cell_idx,
-                            preview,
-                            match_start,
-                            match_end,
-                            source: source.to_string(),
-                            timestamp: timestamp.clone(),
-                        });
-                    }
-                }
-            } else {
-                // Literal search
-                let search_in = if self.case_sensitive {
-                    content.clone()
-                } else {
-                    content.to_lowercase()
-                };
-                let search_for = if self.case_sensitive {
-                    self.query.clone()
-                } else {
-                    self.query.to_lowercase()
-                };
-
-                let mut start = 0;
-                while let Some(pos) = search_in[start..].find(&search_for) {
-                    let actual_pos = start + pos;
-                    let match_end = actual_pos + search_for.len();
-
-                    let preview = Self::create_preview(&content, actual_pos, match_end);
-
-                    // Calculate match position in preview
-                    let match_text = if self.case_sensitive {
-                        &content[actual_pos..match_end]
-                    } else {
-                        &search_in[actual_pos..match_end]
-                    };
-                    let match_start_in_preview = preview.find(match_text).unwrap_or(0);
-                    let match_end_in_preview = match_start_in_preview + match_text.len();
-
-                    results.push(SearchResult {
-                        cell_index: cell_idx,
-                        preview,
-                        match_start: match_start_in_preview,
-                        match_end: match_end_in_preview,
-                        source: source.to_string(),
-                        timestamp: timestamp.clone(),
-                    });
-
-                    start = actual_pos + 1;
-                    if start >= content.len() {
-                        break;
-                    }
-                }
+            // Empty matches (e.g. `a*`) would flag every position; skip them.
+            for mat in re.find_iter(content).filter(|m| !m.is_empty()) {
+                let (preview, match_start) = Self::create_preview(content, mat.start(), mat.end());
+                results.push(SearchResult {
+                    cell_index: cell_idx,
+                    preview,
+                    match_start,
+                    match_end: match_start + mat.len(),
+                    source: source.to_string(),
+                    timestamp: timestamp.clone(),
+                });
             }
         }
 
@@ -181,8 +162,9 @@ impl SearchView {
         results
     }
 
-    /// Create a preview string with context around the match
-    fn create_preview(content: &str, match_start: usize, match_end: usize) -> String {
+    /// Create a preview string with context around the match, returning it
+    /// with the byte offset of the match inside the preview
+    fn create_preview(content: &str, match_start: usize, match_end: usize) -> (String, usize) {
         const PREVIEW_CHARS: usize = 60;
 
         let content_len = content.len();
@@ -200,21 +182,23 @@ impl SearchView {
             .char_indices()
             .find(|(i, _)| *i >= context_start)
             .map(|(i, _)| i)
-            .unwrap_or(0);
+            .unwrap_or(0)
+            .min(match_start);
         let end_idx = content
             .char_indices()
             .find(|(i, _)| *i >= context_end)
             .map(|(i, _)| i)
             .unwrap_or(content_len);
 
+        let offset = preview.len() + (match_start - start_idx);
         preview.push_str(&content[start_idx..end_idx]);
 
         if context_end < content_len {
             preview.push_str("...");
         }
 
-        // Replace newlines with spaces for single-line preview
-        preview.replace('\n', " ")
+        // Replace newlines with spaces for single-line preview (same byte length)
+        (preview.replace('\n', " "), offset)
     }
 
     /// Move to next result
@@ -236,6 +220,13 @@ impl SearchView {
         }
     }
 
+    /// Jump back to the first result after the query or mode changes.
+    /// Results are kept so an invalid regex doesn't blank the list.
+    fn reset_selection(&mut self) {
+        self.selected_idx = 0;
+        self.scroll_offset = 0;
+    }
+
     /// Adjust scroll offset to keep selected item visible
     fn adjust_scroll(&mut self, total_results: usize) {
         const VISIBLE_ITEMS: usize = 8;
@@ -297,30 +288,12 @@ impl ModalView for SearchView {
             }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.case_sensitive = !self.case_sensitive;
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
+                self.reset_selection();
                 ViewAction::None
             }
             KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.regex_mode = !self.regex_mode;
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
-                ViewAction::None
-            }
-            KeyCode::Char('c') if key.modifiers.is_empty() => {
-                self.case_sensitive = !self.case_sensitive;
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
-                ViewAction::None
-            }
-            KeyCode::Char('r') if key.modifiers.is_empty() => {
-                self.regex_mode = !self.regex_mode;
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
+                self.reset_selection();
                 ViewAction::None
             }
             KeyCode::Backspace => {
@@ -334,9 +307,7 @@ impl ModalView for SearchView {
                     self.query.remove(byte_pos - 1);
                     self.cursor_position -= 1;
                 }
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
+                self.reset_selection();
                 ViewAction::None
             }
             KeyCode::Char(c) => {
@@ -348,9 +319,7 @@ impl ModalView for SearchView {
                     .unwrap_or(self.query.len());
                 self.query.insert(byte_pos, c);
                 self.cursor_position += 1;
-                self.selected_idx = 0;
-                self.scroll_offset = 0;
-                self.last_results.clear();
+                self.reset_selection();
                 ViewAction::None
             }
             _ => ViewAction::None,
@@ -362,12 +331,7 @@ impl ModalView for SearchView {
         let popup_width = 80.min(area.width.saturating_sub(4));
         let popup_height = 20.min(area.height.saturating_sub(4));
 
-        let popup_area = Rect {
-            x: (area.width - popup_width) / 2,
-            y: (area.height - popup_height) / 2,
-            width: popup_width,
-            height: popup_height,
-        };
+        let popup_area = crate::tui::layout::centered_rect(area, popup_width, popup_height);
 
         Clear.render(popup_area, buf);
 
@@ -391,14 +355,29 @@ impl ModalView for SearchView {
             .border_style(Style::default().fg(palette::ORANGE))
             .render(popup_area, buf);
 
-        // Query input line
-        let _query_line = format!("> {}", self.query);
+        // Query input line, tagged with the active mode, plus any regex error
         let query_style = Style::default().fg(palette::TEXT_PRIMARY);
-        Paragraph::new(Line::from(vec![
+        let mode = match (self.regex_mode, self.case_sensitive) {
+            (false, false) => "text",
+            (false, true) => "text, match case",
+            (true, false) => "regex",
+            (true, true) => "regex, match case",
+        };
+        let mut query_lines = vec![Line::from(vec![
             Span::styled("> ", Style::default().fg(palette::BLUE).bold()),
             Span::styled(&self.query, query_style),
-        ]))
-        .render(chunks[0], buf);
+            Span::styled(
+                format!("  ({mode})"),
+                Style::default().fg(palette::TEXT_MUTED),
+            ),
+        ])];
+        if let Some(error) = self.error() {
+            query_lines.push(Line::from(Span::styled(
+                format!("  Invalid regex: {error}"),
+                Style::default().fg(palette::RED),
+            )));
+        }
+        Paragraph::new(query_lines).render(chunks[0], buf);
 
         // Options bar
         let case_style = if self.case_sensitive() {
@@ -417,9 +396,9 @@ impl ModalView for SearchView {
         };
 
         let options_line = Line::from(vec![
-            Span::styled("[c]", case_style),
-            Span::styled(" Case ", Style::default().fg(palette::TEXT_MUTED)),
-            Span::styled("[r]", regex_style),
+            Span::styled("^C", case_style),
+            Span::styled(" Match case  ", Style::default().fg(palette::TEXT_MUTED)),
+            Span::styled("^R", regex_style),
             Span::styled(" Regex", Style::default().fg(palette::TEXT_MUTED)),
         ]);
         Paragraph::new(options_line).render(chunks[1], buf);
@@ -568,3 +547,60 @@ pub fn render_search_results(
 
     Paragraph::new(lines).render(results_area, buf);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> Vec<HistoryCell> {
+        vec![
+            HistoryCell::User {
+                content: "Why does Shutdown panic?".to_string(),
+            },
+            HistoryCell::Assistant {
+                content: "The panic at shutdown comes from drop order.".to_string(),
+                streaming: false,
+            },
+        ]
+    }
+
+    fn type_query(view: &mut SearchView, text: &str) {
+        for c in text.chars() {
+            view.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn ctrl(view: &mut SearchView, c: char) {
+        view.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn literal_search_toggles_case_sensitivity() {
+        let mut view = SearchView::new(None);
+        type_query(&mut view, "shutdown");
+        assert_eq!(view.query(), "shutdown");
+        assert_eq!(view.search(&history()).len(), 2);
+
+        ctrl(&mut view, 'c');
+        let results = view.search(&history());
+        assert_eq!(results.len(), 1);
+        let hit = &results[0];
+        assert_eq!(&hit.preview[hit.match_start..hit.match_end], "shutdown");
+    }
+
+    #[test]
+    fn regex_mode_matches_patterns_and_reports_errors() {
+        let mut view = SearchView::new(Some("pa.ic"));
+        assert!(view.search(&history()).is_empty());
+
+        ctrl(&mut view, 'r');
+        assert!(view.regex_mode());
+        let results = view.search(&history());
+        assert_eq!(results.len(), 2);
+        assert!(view.error().is_none());
+
+        type_query(&mut view, "(");
+        assert_eq!(view.search(&history()).len(), 2);
+        assert!(view.error().is_some_and(|e| e.contains("unclosed")));
+    }
+}
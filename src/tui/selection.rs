@@ -1,5 +1,7 @@
 //! Text selection state for the transcript view.
 
+use unicode_width::UnicodeWidthChar;
+
 // === Types ===
 
 /// A selection endpoint in the transcript (line/column).
@@ -44,4 +46,80 @@ impl TranscriptSelection {
             Some((anchor, head))
         }
     }
+
+    /// Selected column range `[start, end)` on `line_index`, in terminal cells.
+    ///
+    /// Interior lines of a multi-line selection are fully selected (`end` is
+    /// `usize::MAX`); lines outside the selection return `None`.
+    #[must_use]
+    pub fn columns_for_line(&self, line_index: usize) -> Option<(usize, usize)> {
+        let (start, end) = self.ordered_endpoints()?;
+        if line_index < start.line_index || line_index > end.line_index {
+            return None;
+        }
+        let col_start = if line_index == start.line_index {
+            start.column
+        } else {
+            0
+        };
+        let col_end = if line_index == end.line_index {
+            end.column
+        } else {
+            usize::MAX
+        };
+        Some((col_start, col_end))
+    }
+}
+
+/// Characters of `text` whose first cell falls in `[start, end)`.
+///
+/// Columns are terminal cells, so a wide character counts as two columns and
+/// is included when the selection starts on its left half.
+#[must_use]
+pub fn slice_columns(text: &str, start: usize, end: usize) -> String {
+    let mut out = String::new();
+    let mut col = 0usize;
+    for ch in text.chars() {
+        if col >= end {
+            break;
+        }
+        if col >= start {
+            out.push(ch);
+        }
+        col += ch.width().unwrap_or(0);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(line_index: usize, column: usize) -> TranscriptSelectionPoint {
+        TranscriptSelectionPoint { line_index, column }
+    }
+
+    #[test]
+    fn multi_line_selection_column_ranges() {
+        // Dragged upwards: head before anchor
+        let selection = TranscriptSelection {
+            anchor: Some(point(4, 3)),
+            head: Some(point(2, 5)),
+            dragging: false,
+        };
+        assert_eq!(selection.columns_for_line(1), None);
+        assert_eq!(selection.columns_for_line(2), Some((5, usize::MAX)));
+        assert_eq!(selection.columns_for_line(3), Some((0, usize::MAX)));
+        assert_eq!(selection.columns_for_line(4), Some((0, 3)));
+        assert_eq!(selection.columns_for_line(5), None);
+    }
+
+    #[test]
+    fn slice_columns_counts_cells() {
+        assert_eq!(slice_columns("hello world", 6, 11), "world");
+        assert_eq!(slice_columns("hello", 2, usize::MAX), "llo");
+        // Each CJK character is two cells wide
+        assert_eq!(slice_columns("日本語 text", 2, 6), "本語");
+        assert_eq!(slice_columns("日本語 text", 7, 9), "te");
+    }
 }
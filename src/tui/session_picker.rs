@@ -2,6 +2,8 @@
 //!
 //! Provides a fuzzy-searchable list of sessions with metadata display.
 
+use std::path::Path;
+
 use crate::palette;
 use crate::session_manager::{SessionManager, SessionMetadata};
 use crate::tui::views::{ModalKind, ModalView, ViewAction, ViewEvent};
@@ -53,8 +55,8 @@ pub struct SessionPicker {
 
 impl SessionPicker {
     /// Create a new session picker
-    pub fn new(current_session_id: Option<String>) -> Self {
-        let sessions = load_sessions();
+    pub fn new(current_session_id: Option<String>, session_dir: &Path) -> Self {
+        let sessions = load_sessions(session_dir);
         let matches = sessions
             .iter()
             .map(|s| SessionMatch {
@@ -216,12 +218,16 @@ impl SessionPicker {
 
         // Format metadata line
         let age = self.format_age(&meta.updated_at);
-        let meta_text = format!(
+        let mut meta_text = format!(
             "{} | {} msgs | {}",
             &meta.id[..8.min(meta.id.len())],
             meta.message_count,
             age
         );
+        if !meta.tags.is_empty() {
+            meta_text.push_str(" | ");
+            meta_text.push_str(&crate::session_manager::format_tags(&meta.tags));
+        }
         let meta_style = if is_selected {
             Style::default().fg(palette::SILVER)
         } else {
@@ -365,11 +371,8 @@ impl ModalView for SessionPicker {
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
         // Create a centered popup
-        let popup_width = (area.width * 4 / 5).clamp(50, 80);
-        let popup_height = (MAX_VISIBLE_SESSIONS as u16 * 3 + 8).min(area.height - 4);
-        let popup_x = (area.width - popup_width) / 2;
-        let popup_y = (area.height - popup_height) / 2;
-        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+        let popup_height = (MAX_VISIBLE_SESSIONS as u16 * 3 + 8).min(area.height.saturating_sub(4));
+        let popup_area = crate::tui::layout::centered_rect_percent(area, 80, 50..=80, popup_height);
 
         // Clear the background
         Clear.render(popup_area, buf);
@@ -436,9 +439,9 @@ impl ModalView for SessionPicker {
     }
 }
 
-/// Load all sessions from the default location
-fn load_sessions() -> Vec<SessionMetadata> {
-    SessionManager::default_location()
+/// Load all sessions saved in `session_dir`
+fn load_sessions(session_dir: &Path) -> Vec<SessionMetadata> {
+    SessionManager::new(session_dir.to_path_buf())
         .and_then(|m| m.list_sessions())
         .unwrap_or_default()
 }
@@ -535,7 +538,8 @@ mod tests {
 
     #[test]
     fn test_format_age() {
-        let picker = SessionPicker::new(None);
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let picker = SessionPicker::new(None, tmp.path());
         let now = chrono::Utc::now();
         assert_eq!(picker.format_age(&now), "just now");
 
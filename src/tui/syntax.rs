@@ -1,6 +1,6 @@
 //! Lightweight regex-based syntax highlighter for code blocks.
 
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use regex::Regex;
 
@@ -22,6 +22,7 @@ pub enum Language {
     Yaml,
     Toml,
     Markdown,
+    Diff,
 }
 
 impl Language {
@@ -41,6 +42,7 @@ impl Language {
             "yaml" | "yml" => Some(Self::Yaml),
             "toml" => Some(Self::Toml),
             "markdown" | "md" => Some(Self::Markdown),
+            "diff" | "patch" => Some(Self::Diff),
             _ => None,
         }
     }
@@ -85,11 +87,40 @@ pub fn highlight_code(code: &str, language: &str) -> Vec<Line<'static>> {
     }
 
     let lang = lang.unwrap();
+    if lang == Language::Diff {
+        return code
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), diff_line_style(line))))
+            .collect();
+    }
     code.lines()
         .map(|line| highlight_line(line, lang))
         .collect()
 }
 
+/// Style for one line of a unified diff: additions green, removals red, hunk
+/// headers blue, file headers bold.
+pub fn diff_line_style(line: &str) -> Style {
+    if line.starts_with("+++") || line.starts_with("---") {
+        Style::default()
+            .fg(palette::TEXT_PRIMARY)
+            .add_modifier(Modifier::BOLD)
+    } else if line.starts_with("@@") {
+        Style::default().fg(palette::BLUE)
+    } else if line.starts_with('+') {
+        Style::default().fg(palette::GREEN)
+    } else if line.starts_with('-') {
+        Style::default().fg(palette::RED)
+    } else {
+        Style::default().fg(palette::TEXT_MUTED)
+    }
+}
+
+/// Whether `text` contains a unified-diff hunk header.
+pub fn is_unified_diff(text: &str) -> bool {
+    text.lines().any(|line| line.starts_with("@@"))
+}
+
 /// Highlight a single line of code.
 fn highlight_line(line: &str, lang: Language) -> Line<'static> {
     if line.is_empty() {
@@ -635,7 +666,7 @@ fn get_keywords_and_types(lang: Language) -> (Vec<&'static str>, Vec<&'static st
             keywords = vec!["true", "false", "null", "yes", "no", "on", "off"];
             types = vec![];
         }
-        Language::Markdown => {
+        Language::Markdown | Language::Diff => {
             keywords = vec![];
             types = vec![];
         }
@@ -850,4 +881,17 @@ mod tests {
         let type_span = spans.iter().find(|s| s.content == "i32");
         assert!(type_span.is_some(), "Should find type 'i32' in output");
     }
+
+    #[test]
+    fn test_diff_highlighting() {
+        assert_eq!(Language::from_str("diff"), Some(Language::Diff));
+        let lines = highlight_code("@@ -1 +1 @@\n-old\n+new\n context", "diff");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].spans[0].style.fg, Some(palette::BLUE));
+        assert_eq!(lines[1].spans[0].style.fg, Some(palette::RED));
+        assert_eq!(lines[2].spans[0].style.fg, Some(palette::GREEN));
+        assert_eq!(lines[3].spans[0].style.fg, Some(palette::TEXT_MUTED));
+        assert!(is_unified_diff("--- a\n+++ b\n@@ -1 +1 @@"));
+        assert!(!is_unified_diff("+just a plus line"));
+    }
 }
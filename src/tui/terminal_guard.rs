@@ -0,0 +1,176 @@
+//! Terminal setup and teardown for the TUI.
+//!
+//! `TerminalGuard` puts the terminal into raw/alternate-screen mode and puts it
+//! back when dropped, so an early `?` return or a panic never leaves the shell
+//! in raw mode. While it is alive a panic hook restores the terminal before the
+//! panic message prints; the previous hook comes back when the guard goes.
+//! Mouse capture and bracketed paste are optional: consoles that reject them
+//! (legacy Windows consoles in particular) get a degraded TUI instead of a
+//! startup error.
+
+use std::io::{self, Write};
+use std::panic::{self, PanicHookInfo};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+
+/// Optional terminal features that were successfully turned on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalModes {
+    pub mouse_capture: bool,
+    pub bracketed_paste: bool,
+}
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Send + Sync + 'static;
+
+/// Restores the terminal when dropped.
+pub struct TerminalGuard {
+    modes: TerminalModes,
+    active: bool,
+    /// Hook that was installed before ours, put back on drop
+    previous_hook: Option<Arc<PanicHook>>,
+}
+
+/// Thread running the TUI; only a panic on it restores the terminal, since
+/// panics in engine tasks are caught and the TUI keeps running.
+static TUI_THREAD: Mutex<Option<ThreadId>> = Mutex::new(None);
+
+impl TerminalGuard {
+    /// Enter raw mode and the alternate screen, then try the optional modes.
+    pub fn enter() -> Result<Self> {
+        if let Ok(mut tui_thread) = TUI_THREAD.lock() {
+            *tui_thread = Some(thread::current().id());
+        }
+        let previous_hook = install_panic_hook();
+        let mut guard = Self {
+            modes: TerminalModes {
+                mouse_capture: false,
+                bracketed_paste: false,
+            },
+            active: false,
+            previous_hook: Some(previous_hook),
+        };
+        enable_raw_mode()?;
+        guard.active = true;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        guard.modes = enable_optional_modes(!is_legacy_windows_console());
+        Ok(guard)
+    }
+
+    pub fn modes(&self) -> TerminalModes {
+        self.modes
+    }
+
+    /// Hand the terminal to a child process (editor, interactive shell).
+    pub fn suspend(&mut self) -> Result<()> {
+        disable_raw_mode()?;
+        leave_screen(self.modes)?;
+        self.active = false;
+        Ok(())
+    }
+
+    /// Take the terminal back after `suspend`.
+    pub fn resume(&mut self) -> Result<()> {
+        enable_raw_mode()?;
+        self.active = true;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        self.modes = enable_optional_modes(self.modes.mouse_capture);
+        Ok(())
+    }
+
+    /// Restore the terminal, reporting the first error instead of ignoring it.
+    pub fn restore(mut self) -> Result<()> {
+        self.active = false;
+        disable_raw_mode()?;
+        leave_screen(self.modes)?;
+        execute!(io::stdout(), Show)?;
+        Ok(())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if self.active {
+            restore_best_effort();
+        }
+        if let Ok(mut tui_thread) = TUI_THREAD.lock() {
+            *tui_thread = None;
+        }
+        // `set_hook` panics when called while unwinding; leave ours in place then.
+        if let Some(previous) = self.previous_hook.take()
+            && !thread::panicking()
+        {
+            let _ = panic::take_hook();
+            panic::set_hook(Box::new(move |info| previous(info)));
+        }
+    }
+}
+
+/// Turn on bracketed paste and (when `mouse` is set) mouse capture, keeping
+/// whichever the console accepts.
+fn enable_optional_modes(mouse: bool) -> TerminalModes {
+    let mut stdout = io::stdout();
+    TerminalModes {
+        bracketed_paste: execute!(stdout, EnableBracketedPaste).is_ok(),
+        mouse_capture: mouse && execute!(stdout, EnableMouseCapture).is_ok(),
+    }
+}
+
+fn leave_screen(modes: TerminalModes) -> Result<()> {
+    let mut stdout = io::stdout();
+    if modes.mouse_capture {
+        execute!(stdout, DisableMouseCapture)?;
+    }
+    if modes.bracketed_paste {
+        execute!(stdout, DisableBracketedPaste)?;
+    }
+    execute!(stdout, LeaveAlternateScreen)?;
+    Ok(())
+}
+
+/// Undo every mode the TUI may have set, ignoring failures of each step.
+fn restore_best_effort() {
+    let _ = disable_raw_mode();
+    let mut stdout = io::stdout();
+    let _ = execute!(stdout, DisableMouseCapture);
+    let _ = execute!(stdout, DisableBracketedPaste);
+    let _ = execute!(stdout, LeaveAlternateScreen, Show);
+    let _ = stdout.flush();
+}
+
+/// Restore the terminal before the panic message prints, so it lands on the
+/// normal screen instead of vanishing with the alternate one. Returns the hook
+/// that was replaced.
+fn install_panic_hook() -> Arc<PanicHook> {
+    let previous: Arc<PanicHook> = Arc::from(panic::take_hook());
+    let chained = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        let on_tui_thread = TUI_THREAD
+            .lock()
+            .is_ok_and(|tui_thread| *tui_thread == Some(thread::current().id()));
+        if on_tui_thread {
+            restore_best_effort();
+        }
+        chained(info);
+    }));
+    previous
+}
+
+/// True for Windows consoles without virtual-terminal support (conhost before
+/// Windows 10), where mouse capture fights the console's own selection.
+#[cfg(windows)]
+pub fn is_legacy_windows_console() -> bool {
+    !crossterm::ansi_support::supports_ansi()
+}
+
+#[cfg(not(windows))]
+pub fn is_legacy_windows_console() -> bool {
+    false
+}
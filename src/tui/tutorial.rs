@@ -176,12 +176,7 @@ pub fn render_tutorial(f: &mut ratatui::Frame, area: Rect, tutorial: &Tutorial)
     let popup_width = 65.min(area.width.saturating_sub(4));
     let popup_height = 20.min(area.height.saturating_sub(4));
 
-    let popup_area = Rect {
-        x: (area.width - popup_width) / 2,
-        y: (area.height - popup_height) / 2,
-        width: popup_width,
-        height: popup_height,
-    };
+    let popup_area = crate::tui::layout::centered_rect(area, popup_width, popup_height);
 
     // Get current step
     let step = match tutorial.current() {
@@ -7,14 +7,9 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use crossterm::{
-    event::{
-        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
-        MouseEventKind,
-    },
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
 };
 use ratatui::{
     Frame, Terminal,
@@ -32,26 +27,32 @@ use crate::core::engine::{EngineConfig, EngineHandle, spawn_engine};
 use crate::core::events::Event as EngineEvent;
 use crate::core::ops::Op;
 use crate::hooks::HookEvent;
+use crate::lifetime_usage::UsageLedger;
 use crate::models::{ContentBlock, Message, SystemPrompt};
 use crate::palette;
 use crate::prompts;
 use crate::rlm;
-use crate::session_manager::{SessionManager, create_saved_session, update_session};
+use crate::session_manager::{create_saved_session, drop_unanswered_tool_uses, update_session};
+use crate::tools::shell::{ShellResult, ShellStatus};
 use crate::tools::spec::{ToolError, ToolResult};
 use crate::tools::subagent::{SubAgentResult, SubAgentStatus};
 use crate::tui::command_completer::CommandCompleter;
 use crate::tui::event_broker::EventBroker;
 use crate::tui::fuzzy_picker;
+use crate::tui::keybindings::{VimMotion, vim_motion_for};
 use crate::tui::paste_burst::CharDecision;
 use crate::tui::scrolling::{ScrollDirection, TranscriptScroll};
-use crate::tui::selection::TranscriptSelectionPoint;
+use crate::tui::selection::{TranscriptSelectionPoint, slice_columns};
+use crate::tui::terminal_guard::{TerminalGuard, TerminalModes, is_legacy_windows_console};
 use crate::tui::tutorial::{handle_tutorial_key, render_tutorial};
 
 use super::app::{
-    App, AppAction, AppMode, OnboardingField, OnboardingState, QueuedMessage, TestResult,
+    App, AppAction, AppMode, OnboardingField, OnboardingState, QueuedMessage, ShellJob, TestResult,
     TuiOptions,
 };
-use super::approval::{ApprovalMode, ApprovalRequest, ApprovalView, ReviewDecision};
+use super::approval::{
+    ApprovalRequest, ApprovalView, ReviewDecision, ToolCategory, get_tool_category,
+};
 use super::history::{
     ExecCell, ExecSource, ExploringCell, ExploringEntry, GenericToolCell, HistoryCell, McpToolCell,
     PatchSummaryCell, PlanStep, PlanUpdateCell, ToolCell, ToolStatus, ViewImageCell, WebSearchCell,
@@ -108,15 +109,6 @@ const RLM_BUDGET_HARD_QUERIES: u32 = 16;
 const RLM_BUDGET_HARD_INPUT_TOKENS: u64 = 120_000;
 const RLM_BUDGET_HARD_OUTPUT_TOKENS: u64 = 40_000;
 const AUTO_RLM_MAX_SCAN_ENTRIES: usize = 50_000;
-const AUTO_RLM_EXCLUDED_DIRS: &[&str] = &[
-    ".git",
-    "target",
-    "node_modules",
-    ".codex",
-    ".aleph",
-    "dist",
-    "build",
-];
 
 // ASCII logo for onboarding screen only
 const LOGO: &str = r"
@@ -139,23 +131,39 @@ const LOGO: &str = r"
 /// # }
 /// ```
 pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(
-        stdout,
-        EnterAlternateScreen,
-        EnableBracketedPaste,
-        EnableMouseCapture
-    )?;
-    let backend = CrosstermBackend::new(stdout);
+    // Also installs a panic hook that restores the terminal; the previous hook
+    // is reinstated when the guard is dropped.
+    let mut terminal_guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
     let event_broker = EventBroker::new();
 
     let mut app = App::new(options.clone(), config);
 
+    // A recovery snapshot left behind means the last run died mid-turn
+    if let Ok(manager) = app.session_manager()
+        && let Ok(Some(recovered)) = manager.promote_recovery_snapshot()
+    {
+        app.add_message(HistoryCell::System {
+            content: format!(
+                "Found an unfinished session from {} (\"{}\", {} messages). \
+                 Run /recover to continue it; it is also saved as session {}.",
+                recovered
+                    .updated_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                recovered.title,
+                recovered.message_count,
+                &recovered.id[..8]
+            ),
+        });
+        app.recoverable_session_id = Some(recovered.id);
+    }
+
     // Load existing session if resuming
+    let mut compact_resumed_session = false;
     if let Some(ref session_id) = options.resume_session_id
-        && let Ok(manager) = SessionManager::default_location()
+        && let Ok(manager) = app.session_manager()
     {
         // Try to load by prefix or full ID
         let load_result: std::io::Result<Option<crate::session_manager::SavedSession>> =
@@ -194,6 +202,8 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
                     app.history.extend(history_cells_from_message(msg));
                 }
                 app.mark_history_updated();
+                compact_resumed_session = warn_on_context_overflow(&mut app);
+                app.transcript_scroll = saved.metadata.transcript_scroll;
                 app.status_message = Some(format!("Resumed session: {}", &saved.metadata.id[..8]));
             }
             Ok(None) => {
@@ -205,6 +215,8 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
         }
     }
 
+    warn_on_limited_terminal(&mut app, terminal_guard.modes());
+
     // Create the Engine with configuration from TuiOptions
     let engine_config = EngineConfig {
         model: app.model.clone(),
@@ -223,7 +235,10 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
         memory_path: options.memory_path.clone(),
         cache_system: true,
         cache_tools: true,
-        auto_compact: app.auto_compact,
+        web_search: config.web_search_config(),
+        shell_max_output_bytes: config.shell_max_output_bytes(),
+        cost_budget: app.cost_budget.clone(),
+        media: config.media_config(),
     };
 
     // Spawn the Engine - it will handle all API communication
@@ -238,6 +253,14 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
                 workspace: app.workspace.clone(),
             })
             .await;
+        if compact_resumed_session {
+            let _ = engine_handle
+                .send(Op::CompactContext {
+                    target_tokens: None,
+                    keep: app.keep_policy(),
+                })
+                .await;
+        }
     }
 
     // Fire session start hook
@@ -248,6 +271,7 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
 
     let result = run_event_loop(
         &mut terminal,
+        &mut terminal_guard,
         &mut app,
         config,
         engine_handle,
@@ -255,20 +279,24 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
     )
     .await;
 
+    if app.auto_save
+        && let Ok(manager) = app.session_manager()
+    {
+        // Remember where the transcript was scrolled so resuming lands there
+        if let Some(id) = &app.current_session_id {
+            let _ = manager.set_transcript_scroll(id, app.transcript_scroll);
+        }
+        // A clean exit leaves nothing to recover
+        let _ = manager.clear_recovery_snapshot();
+    }
+
     // Fire session end hook
     {
         let context = app.base_hook_context();
         let _ = app.execute_hooks(HookEvent::SessionEnd, &context);
     }
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableBracketedPaste,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    terminal_guard.restore()?;
 
     result
 }
@@ -276,6 +304,7 @@ pub async fn run_tui(config: &Config, options: TuiOptions) -> Result<()> {
 #[allow(clippy::too_many_lines)]
 async fn run_event_loop(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    terminal_guard: &mut TerminalGuard,
     app: &mut App,
     _config: &Config,
     engine_handle: EngineHandle,
@@ -283,6 +312,8 @@ async fn run_event_loop(
 ) -> Result<()> {
     // Track streaming state
     let mut current_streaming_text = String::new();
+    // When and at which history version the in-progress turn was last snapshotted
+    let mut last_recovery_snapshot: Option<(Instant, u64)> = None;
 
     loop {
         // First, poll for engine events (non-blocking)
@@ -369,12 +400,25 @@ async fn run_event_loop(
                         }
                         app.reasoning_buffer.clear();
                     }
+                    EngineEvent::ToolCallPreparing { name, .. } => {
+                        app.status_message = Some(format!("Preparing {name}..."));
+                    }
                     EngineEvent::ToolCallStarted { id, name, input } => {
+                        if app.status_message.as_deref() == Some(&format!("Preparing {name}...")) {
+                            app.status_message = None;
+                        }
+                        app.stats.record_tool_call(&name);
                         app.pending_tool_uses
                             .push((id.clone(), name.clone(), input.clone()));
                         handle_tool_call_started(app, &id, &name, &input);
                     }
-                    EngineEvent::ToolCallComplete { id, name, result } => {
+                    EngineEvent::ToolCallComplete {
+                        id,
+                        name,
+                        result,
+                        duration,
+                    } => {
+                        app.stats.record_tool_duration(&name, duration);
                         let tool_content = match &result {
                             Ok(output) => output.content.clone(),
                             Err(err) => format!("Error: {err}"),
@@ -386,10 +430,11 @@ async fn run_event_loop(
                                 content: tool_content,
                             }],
                         });
-                        handle_tool_call_complete(app, &id, &name, &result);
+                        handle_tool_call_complete(app, &id, &result, duration);
                     }
                     EngineEvent::TurnStarted => {
                         app.is_loading = true;
+                        app.budget_blocked = false;
                         current_streaming_text.clear();
                         app.turn_started_at = Some(Instant::now());
                         app.reasoning_buffer.clear();
@@ -399,7 +444,9 @@ async fn run_event_loop(
                     }
                     EngineEvent::TurnComplete { usage } => {
                         app.is_loading = false;
-                        app.turn_started_at = None;
+                        if let Some(started) = app.turn_started_at.take() {
+                            app.stats.record_turn(started.elapsed());
+                        }
                         let turn_tokens = usage.input_tokens + usage.output_tokens;
                         app.total_tokens = app.total_tokens.saturating_add(turn_tokens);
                         app.recalculate_context_tokens();
@@ -409,9 +456,20 @@ async fn run_event_loop(
                         app.last_completion_tokens = Some(usage.output_tokens);
                         app.last_usage_at = Some(Instant::now());
 
+                        if let Err(err) = UsageLedger::default_location()
+                            .and_then(|ledger| ledger.record(&app.model, &usage))
+                        {
+                            crate::logging::warn(format!(
+                                "Failed to update lifetime usage: {err:#}"
+                            ));
+                        }
+
                         // Auto-save session after each turn
-                        if let Ok(manager) = SessionManager::default_location() {
-                            let session = if let Some(ref existing_id) = app.current_session_id {
+                        if app.auto_save
+                            && let Ok(manager) = app.session_manager()
+                        {
+                            let mut session = if let Some(ref existing_id) = app.current_session_id
+                            {
                                 // Update existing session
                                 if let Ok(existing) = manager.load_session(existing_id) {
                                     update_session(
@@ -443,13 +501,16 @@ async fn run_event_loop(
                                     app.pinned_messages.clone(),
                                 )
                             };
+                            session.metadata.transcript_scroll = app.transcript_scroll;
 
                             if let Err(e) = manager.save_session(&session) {
                                 eprintln!("Failed to save session: {e}");
                             } else {
                                 app.current_session_id = Some(session.metadata.id.clone());
+                                let _ = manager.clear_recovery_snapshot();
                             }
                         }
+                        last_recovery_snapshot = None;
 
                         if queued_to_send.is_none() {
                             queued_to_send = app.pop_queued_message();
@@ -469,30 +530,75 @@ async fn run_event_loop(
                         });
                         app.is_loading = false;
                     }
+                    EngineEvent::BudgetExceeded {
+                        spent_usd,
+                        limit_usd,
+                        estimate_usd,
+                    } => {
+                        app.budget_blocked = true;
+                        app.add_message(HistoryCell::Error {
+                            message: format!(
+                                "Session budget reached: ${spent_usd:.4} of ${limit_usd:.2} spent; \
+                                 the next request could cost up to ${estimate_usd:.4}. Turn stopped."
+                            ),
+                            suggestion: Some(
+                                "Use /budget raise <amount> to continue, or /budget off to remove the cap."
+                                    .to_string(),
+                            ),
+                        });
+                    }
                     EngineEvent::SessionUpdated {
                         messages,
                         system_prompt,
                     } => {
+                        // The engine only replaces the session when it compacts
+                        let before = app.total_conversation_tokens;
                         app.api_messages = messages;
                         app.system_prompt = system_prompt;
                         app.recalculate_context_tokens();
+                        app.add_message(HistoryCell::System {
+                            content: format!(
+                                "Context compacted: ~{before} -> ~{} tokens",
+                                app.total_conversation_tokens
+                            ),
+                        });
+                    }
+                    EngineEvent::CompactionPreview {
+                        summarized_messages,
+                        kept_messages,
+                        summarized_tokens,
+                        summary_tokens,
+                        summary,
+                    } => {
+                        app.add_message(HistoryCell::System {
+                            content: format!(
+                                "Compaction preview: would summarize {summarized_messages} \
+                                 messages (~{summarized_tokens} tokens) into a \
+                                 ~{summary_tokens}-token summary and keep {kept_messages}.\n\n\
+                                 Proposed summary:\n{summary}\n\n\
+                                 Nothing has changed yet; run /compact now to apply it."
+                            ),
+                        });
                     }
                     EngineEvent::Status { message } => {
                         app.status_message = Some(message);
                     }
                     EngineEvent::PauseEvents => {
                         if !event_broker.is_paused() {
-                            pause_terminal(terminal)?;
+                            terminal_guard.suspend()?;
                             event_broker.pause_events();
                         }
                     }
                     EngineEvent::ResumeEvents => {
                         if event_broker.is_paused() {
-                            resume_terminal(terminal)?;
+                            resume_terminal(terminal, terminal_guard)?;
                             event_broker.resume_events();
                         }
                     }
                     EngineEvent::AgentSpawned { id, prompt } => {
+                        if app.aggregate_subagents {
+                            app.subagent_batch.push((id.clone(), None));
+                        }
                         app.add_message(HistoryCell::System {
                             content: format!(
                                 "Sub-agent {id} spawned: {}",
@@ -503,11 +609,41 @@ async fn run_event_loop(
                     EngineEvent::AgentProgress { id, status } => {
                         app.status_message = Some(format!("Sub-agent {id}: {status}"));
                     }
-                    EngineEvent::AgentComplete { id, result } => {
+                    EngineEvent::AgentComplete { id, status, result } => {
+                        if let Some(entry) = app
+                            .subagent_batch
+                            .iter_mut()
+                            .find(|(agent_id, outcome)| *agent_id == id && outcome.is_none())
+                        {
+                            entry.1 = Some((status, result));
+                            if app
+                                .subagent_batch
+                                .iter()
+                                .all(|(_, outcome)| outcome.is_some())
+                            {
+                                let batch = std::mem::take(&mut app.subagent_batch);
+                                app.add_message(HistoryCell::System {
+                                    content: format_subagent_batch(&batch),
+                                });
+                            }
+                        } else {
+                            app.add_message(HistoryCell::System {
+                                content: format!(
+                                    "Sub-agent {id} completed: {}",
+                                    summarize_tool_output(&result)
+                                ),
+                            });
+                        }
+                    }
+                    EngineEvent::IndexRebuilt {
+                        indexed,
+                        total,
+                        path,
+                    } => {
                         app.add_message(HistoryCell::System {
                             content: format!(
-                                "Sub-agent {id} completed: {}",
-                                summarize_tool_output(&result)
+                                "Rebuilt file index: indexed {indexed} of {total} files at {}",
+                                path.display()
                             ),
                         });
                     }
@@ -520,27 +656,32 @@ async fn run_event_loop(
                         id,
                         tool_name,
                         description,
+                        params,
+                        preview,
                     } => {
-                        let session_approved = app.approval_session_approved.contains(&tool_name);
-                        if session_approved || app.approval_mode == ApprovalMode::Auto {
-                            let _ = engine_handle.approve_tool_call(id.clone()).await;
-                        } else if app.approval_mode == ApprovalMode::Never {
-                            let _ = engine_handle.deny_tool_call(id.clone()).await;
-                            app.add_message(HistoryCell::System {
-                                content: format!(
-                                    "Blocked tool '{tool_name}' (approval_mode=never)"
-                                ),
-                            });
-                        } else {
-                            // Create approval request and show overlay
-                            let request =
-                                ApprovalRequest::new(&id, &tool_name, &serde_json::json!({}));
-                            app.view_stack.push(ApprovalView::new(request));
-                            app.add_message(HistoryCell::System {
-                                content: format!(
-                                    "Approval required for tool '{tool_name}': {description}"
-                                ),
-                            });
+                        match app.auto_approval(&tool_name) {
+                            Some(true) => {
+                                let _ = engine_handle.approve_tool_call(id.clone()).await;
+                            }
+                            Some(false) => {
+                                let _ = engine_handle.deny_tool_call(id.clone()).await;
+                                app.add_message(HistoryCell::System {
+                                    content: format!(
+                                        "Blocked tool '{tool_name}' (approval_mode=never)"
+                                    ),
+                                });
+                            }
+                            None => {
+                                // Create approval request and show overlay
+                                let request = ApprovalRequest::new(&id, &tool_name, &params)
+                                    .with_diff(preview);
+                                app.view_stack.push(ApprovalView::new(request));
+                                app.add_message(HistoryCell::System {
+                                    content: format!(
+                                        "Approval required for tool '{tool_name}': {description}"
+                                    ),
+                                });
+                            }
                         }
                     }
                     EngineEvent::ToolCallProgress { id, output } => {
@@ -555,6 +696,21 @@ async fn run_event_loop(
             dispatch_user_message(app, &engine_handle, next).await?;
         }
 
+        // Snapshot the in-progress turn so a crash before TurnComplete loses little
+        if app.is_loading
+            && app.auto_save
+            && last_recovery_snapshot.is_none_or(|(at, version)| {
+                version != app.history_version && at.elapsed() >= RECOVERY_SNAPSHOT_INTERVAL
+            })
+        {
+            let partial = app
+                .streaming_message_index
+                .is_some()
+                .then_some(current_streaming_text.as_str());
+            save_recovery_snapshot(app, partial);
+            last_recovery_snapshot = Some((Instant::now(), app.history_version));
+        }
+
         if !app.view_stack.is_empty() {
             let events = app.view_stack.tick();
             handle_view_events(app, &engine_handle, events).await;
@@ -566,6 +722,12 @@ async fn run_event_loop(
         }
 
         app.flush_paste_burst_if_due(Instant::now());
+        poll_shell_jobs(app);
+        if let Some(changes) = app.poll_skill_files(Instant::now()) {
+            app.add_message(HistoryCell::System {
+                content: format!("Skills updated on disk:\n{}", changes.summary()),
+            });
+        }
 
         // Update suggestion engine (auto-hide expired suggestions)
         app.suggestion_engine.tick();
@@ -617,7 +779,7 @@ async fn run_event_loop(
                 continue;
             }
 
-            let Event::Key(key) = evt else {
+            let Event::Key(mut key) = evt else {
                 continue;
             };
 
@@ -730,7 +892,7 @@ async fn run_event_loop(
                 if app.view_stack.top_kind() == Some(ModalKind::Help) {
                     app.view_stack.pop();
                 } else {
-                    app.view_stack.push(HelpView::new());
+                    app.view_stack.push(HelpView::new(app.mode, app.vim_mode));
                 }
                 continue;
             }
@@ -812,6 +974,18 @@ async fn run_event_loop(
                 continue;
             }
 
+            match vim_motion(app, &key) {
+                // `/` searches in vim mode, so `:` starts slash commands instead
+                Some(VimMotion::Command) => {
+                    key = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)
+                }
+                Some(motion) => {
+                    apply_vim_motion(app, motion);
+                    continue;
+                }
+                None => {}
+            }
+
             let now = Instant::now();
             app.flush_paste_burst_if_due(now);
 
@@ -959,16 +1133,40 @@ async fn run_event_loop(
                                     AppAction::ListSubAgents => {
                                         let _ = engine_handle.send(Op::ListSubAgents).await;
                                     }
-                                    AppAction::CompactContext => {
-                                        let _ = engine_handle.send(Op::CompactContext).await;
+                                    AppAction::CompactContext { target_tokens } => {
+                                        let _ = engine_handle
+                                            .send(Op::CompactContext {
+                                                target_tokens,
+                                                keep: app.keep_policy(),
+                                            })
+                                            .await;
                                         app.add_message(HistoryCell::System {
                                             content: "Compacting context...".to_string(),
                                         });
                                     }
+                                    AppAction::RebuildIndex => {
+                                        let _ = engine_handle
+                                            .send(Op::RebuildIndex {
+                                                workspace: app.workspace.clone(),
+                                            })
+                                            .await;
+                                    }
+                                    AppAction::PreviewCompaction => {
+                                        let _ = engine_handle
+                                            .send(Op::PreviewCompaction {
+                                                keep: app.keep_policy(),
+                                            })
+                                            .await;
+                                        app.add_message(HistoryCell::System {
+                                            content: "Planning compaction (preview only)..."
+                                                .to_string(),
+                                        });
+                                    }
                                     AppAction::OpenSessionPicker => {
                                         app.view_stack.push(
                                             crate::tui::session_picker::SessionPicker::new(
                                                 app.current_session_id.clone(),
+                                                &app.session_dir,
                                             ),
                                         );
                                     }
@@ -998,15 +1196,11 @@ async fn run_event_loop(
                                         );
                                     }
                                     AppAction::ReloadConfig => {
-                                        // Reload configuration from disk
-                                        let profile = std::env::var("AXIOM_PROFILE").ok();
-                                        let config_path = std::env::var("AXIOM_CONFIG_PATH")
-                                            .ok()
-                                            .map(std::path::PathBuf::from);
-
-                                        match crate::config::Config::load(
-                                            config_path,
-                                            profile.as_deref(),
+                                        // Reload with the config file and profile from startup
+                                        match crate::config::Config::load_for_workspace(
+                                            app.config_path.clone(),
+                                            app.config_profile.as_deref(),
+                                            Some(&app.workspace),
                                         ) {
                                             Ok(config) => {
                                                 // Apply relevant config changes to the app
@@ -1024,6 +1218,9 @@ async fn run_event_loop(
                                                         app.show_thinking = settings.show_thinking;
                                                         app.show_tool_details =
                                                             settings.show_tool_details;
+                                                        app.collapse_tool_output =
+                                                            settings.collapse_tool_output;
+                                                        app.vim_mode = settings.vim_mode;
                                                         app.max_input_history =
                                                             settings.max_input_history;
                                                         app.ui_theme = crate::palette::ui_theme(
@@ -1070,10 +1267,32 @@ async fn run_event_loop(
                                             }
                                         }
                                     }
-                                    AppAction::SetInput(text) => {
+                                    AppAction::EditConfig { editor, path } => {
+                                        terminal_guard.suspend()?;
+                                        event_broker.pause_events();
+                                        let status = run_editor(&editor, &path);
+                                        resume_terminal(terminal, terminal_guard)?;
+                                        event_broker.resume_events();
+
+                                        let content = match status {
+                                            Ok(status) if status.success() => {
+                                                let result = commands::reload::reload(app);
+                                                result.message.unwrap_or_default()
+                                            }
+                                            Ok(status) => format!(
+                                                "Editor exited with {status}; config not reloaded."
+                                            ),
+                                            Err(e) => format!(
+                                                "Failed to open {} with '{editor}': {e}",
+                                                path.display()
+                                            ),
+                                        };
+                                        app.add_message(HistoryCell::System { content });
+                                    }
+                                    AppAction::SetInput { text, cursor } => {
                                         // Insert snippet text into input field
                                         app.input = text;
-                                        app.cursor_position = app.input.chars().count();
+                                        app.cursor_position = cursor.min(app.input.chars().count());
                                     }
                                     AppAction::OpenSearch(query) => {
                                         // Open search view with optional query
@@ -1121,6 +1340,7 @@ async fn run_event_loop(
                                 } else {
                                     build_queued_message(app, input)
                                 };
+                                let rewind = app.editing_prompt.take();
                                 if app.is_loading {
                                     app.queue_message(queued);
                                     app.status_message = Some(format!(
@@ -1128,7 +1348,40 @@ async fn run_event_loop(
                                         app.queued_message_count()
                                     ));
                                 } else {
-                                    dispatch_user_message(app, &engine_handle, queued).await?;
+                                    // `/edit` resends from an earlier prompt: cut the
+                                    // conversation back to it before sending
+                                    let rewound = match rewind {
+                                        Some(prompt) if app.rewind_to_prompt(prompt) => {
+                                            let _ = engine_handle
+                                                .send(Op::SyncSession {
+                                                    messages: app.api_messages.clone(),
+                                                    system_prompt: app.system_prompt.clone(),
+                                                    model: app.model.clone(),
+                                                    workspace: app.workspace.clone(),
+                                                })
+                                                .await;
+                                            true
+                                        }
+                                        Some(prompt) => {
+                                            // Give the text back rather than send it as a
+                                            // new prompt the user did not ask for
+                                            app.input.clone_from(&queued.display);
+                                            app.cursor_position = app.input.chars().count();
+                                            app.add_message(HistoryCell::System {
+                                                content: format!(
+                                                    "Prompt {prompt} is no longer in the \
+                                                     model's context, so the edit was not \
+                                                     sent. Press Enter to send it as a new \
+                                                     prompt."
+                                                ),
+                                            });
+                                            false
+                                        }
+                                        None => true,
+                                    };
+                                    if rewound {
+                                        dispatch_user_message(app, &engine_handle, queued).await?;
+                                    }
                                 }
                             }
                         }
@@ -1187,6 +1440,15 @@ async fn run_event_loop(
                 KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     app.clear_input();
                 }
+                KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    // Ctrl+O: Collapse/expand long tool output
+                    let message = if app.toggle_tool_output_collapse() {
+                        "Ctrl+O: Tool output collapsed"
+                    } else {
+                        "Ctrl+O: Tool output expanded"
+                    };
+                    app.status_message = Some(message.to_string());
+                }
                 KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     app.paste_from_clipboard();
                 }
@@ -1196,7 +1458,7 @@ async fn run_event_loop(
                 }
                 KeyCode::Char('/') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     // Ctrl+/: Open HelpView (same as F1)
-                    app.view_stack.push(HelpView::new());
+                    app.view_stack.push(HelpView::new(app.mode, app.vim_mode));
                     app.status_message = Some("Ctrl+/: Help opened".to_string());
                 }
                 KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -1392,7 +1654,45 @@ fn in_command_context(app: &App) -> bool {
 
 fn build_queued_message(app: &mut App, input: String) -> QueuedMessage {
     let skill_instruction = app.active_skill.take();
-    QueuedMessage::new(input, skill_instruction)
+    let mut message = QueuedMessage::new(input, skill_instruction);
+    message.skill_tools = app.active_skill_tools.take();
+    message
+}
+
+/// Minimum time between recovery snapshots while a turn is running
+const RECOVERY_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Save the conversation so far, including a partially streamed reply, where
+/// the next startup will find it if this process dies before the turn ends.
+fn save_recovery_snapshot(app: &App, partial_reply: Option<&str>) {
+    let Ok(manager) = app.session_manager() else {
+        return;
+    };
+    let mut messages = app.api_messages.clone();
+    drop_unanswered_tool_uses(&mut messages);
+    if let Some(text) = partial_reply.filter(|text| !text.is_empty()) {
+        messages.push(Message {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::Text {
+                text: format!("{text}\n\n[interrupted]"),
+                cache_control: None,
+            }],
+        });
+    }
+    let mut session = create_saved_session(
+        &messages,
+        &app.model,
+        &app.workspace,
+        u64::from(app.total_tokens),
+        app.system_prompt.as_ref(),
+        app.pinned_messages.clone(),
+    );
+    if let Some(id) = &app.current_session_id {
+        session.metadata.id.clone_from(id);
+    }
+    if let Err(err) = manager.save_recovery_snapshot(&session) {
+        crate::logging::warn(format!("Failed to save recovery snapshot: {err}"));
+    }
 }
 
 async fn dispatch_user_message(
@@ -1448,6 +1748,8 @@ async fn dispatch_user_message(
             model: app.model.clone(),
             allow_shell: app.allow_shell,
             trust_mode: app.trust_mode,
+            allowed_tools: message.skill_tools,
+            keep: app.keep_policy(),
         })
         .await?;
 
@@ -1489,6 +1791,7 @@ fn handle_rlm_input(app: &mut App, input: String) {
 /// Execute a shell command in shell mode (Ctrl-X)
 async fn execute_shell_command(app: &mut App, command: &str) {
     use crate::command_safety::{SafetyLevel, analyze_command};
+    use crate::sandbox::SandboxPolicy;
     use crate::tools::shell::ShellManager;
 
     let trimmed = command.trim();
@@ -1526,13 +1829,23 @@ async fn execute_shell_command(app: &mut App, command: &str) {
         SafetyLevel::Safe | SafetyLevel::WorkspaceSafe => {}
     }
 
+    if let Some(background) = strip_background_suffix(trimmed) {
+        start_shell_job(app, background);
+        return;
+    }
+
     app.status_message = Some(format!("Running shell command: {trimmed}"));
 
     let command_owned = trimmed.to_string();
     let workspace = app.workspace.clone();
+    let trust_mode = app.trust_mode;
 
     let result = tokio::task::spawn_blocking(move || {
-        let mut manager = ShellManager::new(workspace);
+        let mut manager = if trust_mode {
+            ShellManager::with_sandbox(workspace, SandboxPolicy::DangerFullAccess)
+        } else {
+            ShellManager::new(workspace)
+        };
         manager.execute(&command_owned, None, 120_000, false)
     })
     .await;
@@ -1581,6 +1894,147 @@ async fn execute_shell_command(app: &mut App, command: &str) {
     }
 }
 
+/// The command without a trailing `&` (but not `&&`), if it asks to run in the
+/// background.
+fn strip_background_suffix(command: &str) -> Option<&str> {
+    let rest = command.strip_suffix('&')?;
+    if rest.ends_with('&') {
+        return None;
+    }
+    let rest = rest.trim_end();
+    (!rest.is_empty()).then_some(rest)
+}
+
+/// Start a shell-mode command on the shared shell manager and stream its
+/// output into a transcript cell until it exits.
+fn start_shell_job(app: &mut App, command: &str) {
+    use crate::sandbox::SandboxPolicy;
+    use crate::tools::shell::shared_shell_manager;
+
+    let policy = app.trust_mode.then_some(SandboxPolicy::DangerFullAccess);
+    let manager = shared_shell_manager(&app.workspace);
+    let result = match manager.try_lock() {
+        Ok(mut manager) => manager.execute_with_policy(command, None, 120_000, true, policy),
+        Err(std::sync::TryLockError::WouldBlock) => Err(anyhow::anyhow!(
+            "a tool is waiting on a shell job; try again in a moment"
+        )),
+        Err(std::sync::TryLockError::Poisoned(_)) => {
+            Err(anyhow::anyhow!("Failed to lock shell manager"))
+        }
+    };
+
+    match result {
+        Ok(ShellResult {
+            task_id: Some(task_id),
+            ..
+        }) => {
+            let header = format!("[{task_id}] running in background: {command}");
+            app.add_message(HistoryCell::System {
+                content: header.clone(),
+            });
+            app.shell_jobs.push(ShellJob {
+                task_id,
+                header,
+                cell: app.history.len().saturating_sub(1),
+            });
+        }
+        Ok(_) => app.add_message(HistoryCell::System {
+            content: "Shell error: background task did not return an id".to_string(),
+        }),
+        Err(err) => app.add_message(HistoryCell::System {
+            content: format!("Shell error: {err}"),
+        }),
+    }
+}
+
+/// Append output from background shell-mode jobs to their transcript cells,
+/// dropping jobs once they exit.
+fn poll_shell_jobs(app: &mut App) {
+    use crate::tools::shell::shared_shell_manager;
+
+    if app.shell_jobs.is_empty() {
+        return;
+    }
+    let manager = shared_shell_manager(&app.workspace);
+    // `exec_shell_wait` can hold the manager for minutes; try again next tick
+    // rather than freezing the UI
+    let Ok(mut manager) = manager.try_lock() else {
+        return;
+    };
+    let mut jobs = std::mem::take(&mut app.shell_jobs);
+    jobs.retain_mut(|job| {
+        let Ok(delta) = manager.read_new_output(&job.task_id) else {
+            return false;
+        };
+        let mut text = delta.stdout;
+        text.push_str(&delta.stderr);
+        let running = delta.status == ShellStatus::Running;
+        if !running {
+            let exit = delta
+                .exit_code
+                .map_or_else(|| format!("{:?}", delta.status), |code| code.to_string());
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&format!("[{} exited: {exit}]", job.task_id));
+        }
+        if !text.is_empty() {
+            append_shell_job_output(app, job, &text);
+        }
+        running
+    });
+    app.shell_jobs = jobs;
+}
+
+fn append_shell_job_output(app: &mut App, job: &mut ShellJob, text: &str) {
+    if let Some(HistoryCell::System { content }) = app.history.get_mut(job.cell)
+        && content.starts_with(&job.header)
+    {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(text);
+        cap_shell_job_output(content, &job.header);
+        app.mark_history_updated();
+        if matches!(app.transcript_scroll, TranscriptScroll::ToBottom) {
+            app.scroll_to_bottom();
+        }
+    } else {
+        // The cell is gone (e.g. after /clear); continue in a fresh one.
+        app.add_message(HistoryCell::System {
+            content: format!("{}\n{text}", job.header),
+        });
+        job.cell = app.history.len().saturating_sub(1);
+    }
+}
+
+/// Most output kept in a background job's cell; older lines are dropped.
+const MAX_SHELL_JOB_OUTPUT: usize = 64 * 1024;
+
+const SHELL_JOB_TRIMMED: &str = "[earlier output trimmed]";
+
+/// Keep the header and roughly the last `MAX_SHELL_JOB_OUTPUT` bytes of a job
+/// cell, cutting at a line break.
+fn cap_shell_job_output(content: &mut String, header: &str) {
+    let limit = header.len() + SHELL_JOB_TRIMMED.len() + 2 + MAX_SHELL_JOB_OUTPUT;
+    if content.len() <= limit {
+        return;
+    }
+    let mut cut = content.len() - MAX_SHELL_JOB_OUTPUT;
+    while !content.is_char_boundary(cut) {
+        cut += 1;
+    }
+    let cut = content[cut..]
+        .find('\n')
+        .map_or(cut, |newline| cut + newline + 1);
+    let tail = content.split_off(cut);
+    content.truncate(header.len());
+    content.push('\n');
+    content.push_str(SHELL_JOB_TRIMMED);
+    content.push('\n');
+    content.push_str(&tail);
+}
+
 struct AutoRlmDecision {
     source: AutoRlmSource,
     reason: String,
@@ -1949,7 +2403,7 @@ fn find_largest_file(workspace: &Path) -> Option<(PathBuf, u64)> {
             let path = entry.path();
             if path.is_dir() {
                 if let Some(name) = path.file_name().and_then(|s| s.to_str())
-                    && AUTO_RLM_EXCLUDED_DIRS.contains(&name)
+                    && crate::tools::investigator::SCAN_EXCLUDED_DIRS.contains(&name)
                 {
                     continue;
                 }
@@ -2086,6 +2540,8 @@ fn render(f: &mut Frame, app: &mut App) {
             app.custom_context_windows.clone(),
         )
         .with_shell_mode(app.shell_mode)
+        .with_mock_backend(app.mock_backend)
+        .with_approval_policy(app.approval_policy())
         .with_pins(app.list_pins());
         let header_widget = HeaderWidget::new(header_data);
         let buf = f.buffer_mut();
@@ -2159,6 +2615,58 @@ fn render(f: &mut Frame, app: &mut App) {
     if app.fuzzy_picker.is_active() {
         fuzzy_picker::render(f, &app.fuzzy_picker, size);
     }
+
+    if app.debug_render {
+        let regions = [
+            ("header", chunks[0]),
+            ("transcript", chunks[1]),
+            ("status", chunks[2]),
+            ("composer", chunks[3]),
+            ("footer", chunks[4]),
+        ];
+        render_layout_debug(f, &regions, app);
+    }
+}
+
+/// Outline each layout region with its name and geometry (`/debug render`).
+fn render_layout_debug(f: &mut Frame, regions: &[(&str, Rect)], app: &App) {
+    let colors = [
+        palette::MAGENTA,
+        palette::BLUE,
+        palette::YELLOW,
+        palette::GREEN,
+        palette::ORANGE,
+    ];
+    for (&(name, rect), color) in regions.iter().zip(colors.iter().cycle()) {
+        if rect.area() == 0 {
+            continue;
+        }
+        let style = Style::default().fg(*color);
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(style)
+            .title(Span::styled(layout_debug_label(name, rect), style));
+        if name == "transcript" {
+            block = block.title_bottom(Span::styled(
+                format!(
+                    " scroll top={} visible={} total={} pad={} ",
+                    app.last_transcript_top,
+                    app.last_transcript_visible,
+                    app.last_transcript_total,
+                    app.last_transcript_padding_top
+                ),
+                style,
+            ));
+        }
+        f.render_widget(block, rect);
+    }
+}
+
+fn layout_debug_label(name: &str, rect: Rect) -> String {
+    format!(
+        " {name} {}x{} @{},{} ",
+        rect.width, rect.height, rect.x, rect.y
+    )
 }
 
 async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events: Vec<ViewEvent>) {
@@ -2170,12 +2678,24 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
                 decision,
                 timed_out,
             } => {
-                if decision == ReviewDecision::ApprovedForSession {
-                    app.approval_session_approved.insert(tool_name);
+                match decision {
+                    ReviewDecision::ApprovedForSession => {
+                        app.approval_session_approved.insert(tool_name);
+                    }
+                    ReviewDecision::ApprovedCategoryForSession => {
+                        // Blanket approval is only ever granted for read-only tools
+                        let category = get_tool_category(&tool_name);
+                        if category == ToolCategory::Safe {
+                            app.approval_session_categories.insert(category);
+                        }
+                    }
+                    _ => {}
                 }
 
                 match decision {
-                    ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+                    ReviewDecision::Approved
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedCategoryForSession => {
                         let _ = engine_handle.approve_tool_call(tool_id).await;
                     }
                     ReviewDecision::Denied | ReviewDecision::Abort => {
@@ -2193,9 +2713,7 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
                 match result {
                     crate::tui::session_picker::SessionPickerResult::Selected(session_id) => {
                         // Load the session directly
-                        if let Ok(manager) =
-                            crate::session_manager::SessionManager::default_location()
-                        {
+                        if let Ok(manager) = app.session_manager() {
                             if let Ok(session) = manager.load_session(&session_id) {
                                 app.api_messages.clone_from(&session.messages);
                                 app.history.clear();
@@ -2214,6 +2732,7 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
                                 }
                                 app.recalculate_context_tokens();
                                 app.scroll_to_bottom();
+                                app.transcript_scroll = session.metadata.transcript_scroll;
 
                                 // Sync with the engine
                                 let _ = engine_handle
@@ -2232,6 +2751,14 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
                                         app.api_messages.len()
                                     ),
                                 });
+                                if warn_on_context_overflow(app) {
+                                    let _ = engine_handle
+                                        .send(Op::CompactContext {
+                                            target_tokens: None,
+                                            keep: app.keep_policy(),
+                                        })
+                                        .await;
+                                }
                             } else {
                                 app.add_message(HistoryCell::System {
                                     content: format!(
@@ -2301,41 +2828,26 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
             }
             ViewEvent::DuoSessionPickerResult { result } => match result {
                 crate::tui::duo_session_picker::DuoSessionPickerResult::Selected(session_id) => {
-                    let rt = tokio::runtime::Runtime::new().unwrap();
-                    if let Ok(session) =
-                        rt.block_on(async { crate::duo::load_session(&session_id).await })
-                    {
-                        let state_loaded = {
-                            let mut duo_session = app.duo_session.lock().unwrap();
-                            if let Some(state) = session.active_state {
+                    let short_id = session_id.get(..8).unwrap_or(&session_id);
+                    let content = match crate::duo::load_session(&session_id).await {
+                        Ok(session) => match (session.active_state, app.duo_session.lock()) {
+                            (Some(state), Ok(mut duo_session)) => {
                                 duo_session.start_session(
                                     state.requirements.clone(),
                                     state.session_name.clone(),
                                     Some(state.max_turns),
                                     Some(state.approval_threshold),
                                 );
-                                true
-                            } else {
-                                false
+                                format!("Loaded Duo session: {short_id}")
                             }
-                        };
-                        if state_loaded {
-                            app.add_message(HistoryCell::System {
-                                content: format!("Loaded Duo session: {}", &session_id[..8]),
-                            });
-                        } else {
-                            app.add_message(HistoryCell::System {
-                                content: format!(
-                                    "No active state in Duo session: {}",
-                                    &session_id[..8]
-                                ),
-                            });
-                        }
-                    } else {
-                        app.add_message(HistoryCell::System {
-                            content: format!("Failed to load Duo session: {}", &session_id[..8]),
-                        });
-                    }
+                            (Some(_), Err(_)) => format!(
+                                "Failed to load Duo session {short_id}: Duo state is unavailable"
+                            ),
+                            (None, _) => format!("No active state in Duo session: {short_id}"),
+                        },
+                        Err(e) => format!("Failed to load Duo session {short_id}: {e}"),
+                    };
+                    app.add_message(HistoryCell::System { content });
                 }
                 crate::tui::duo_session_picker::DuoSessionPickerResult::Cancelled => {}
             },
@@ -2343,25 +2855,87 @@ async fn handle_view_events(app: &mut App, engine_handle: &EngineHandle, events:
     }
 }
 
-fn pause_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture,
-        DisableBracketedPaste
-    )?;
-    Ok(())
+/// Run `editor` (which may carry its own arguments, e.g. `code --wait`) on `path`
+fn run_editor(editor: &str, path: &Path) -> Result<std::process::ExitStatus> {
+    let mut parts = shlex::split(editor)
+        .filter(|parts| !parts.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("could not parse editor command"))?;
+    let program = parts.remove(0);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()?;
+    Ok(status)
+}
+
+/// Explain features this console could not turn on, and that Windows runs
+/// shell commands without a sandbox.
+fn warn_on_limited_terminal(app: &mut App, modes: TerminalModes) {
+    let mut notes = Vec::new();
+    if !modes.mouse_capture {
+        let reason = if is_legacy_windows_console() {
+            "this is a legacy Windows console"
+        } else {
+            "the terminal rejected it"
+        };
+        notes.push(format!(
+            "Mouse capture is off because {reason}; scroll with PageUp/PageDown \
+             and select text with the terminal's own selection."
+        ));
+    }
+    if !modes.bracketed_paste {
+        notes.push(
+            "Bracketed paste is unavailable; multi-line pastes are detected from typing speed. \
+             If a paste submits early, run /paste before pasting."
+                .to_string(),
+        );
+    }
+    if cfg!(windows) && app.allow_shell && !crate::sandbox::is_sandbox_available() {
+        notes.push(
+            "Shell commands run without a sandbox on Windows; review each approval carefully."
+                .to_string(),
+        );
+    }
+    if !notes.is_empty() {
+        app.add_message(HistoryCell::System {
+            content: notes.join("\n"),
+        });
+    }
+}
+
+/// Warn when a resumed conversation no longer fits the model's context window.
+/// Returns true when auto-compact is on and the session should be compacted
+/// before the first turn.
+fn warn_on_context_overflow(app: &mut App) -> bool {
+    let Some((used, window)) = app.context_overflow() else {
+        return false;
+    };
+    let content = if app.auto_compact {
+        format!(
+            "This session is ~{used} tokens, more than the {window}-token context window of {}. \
+             Compacting it before the first turn...",
+            app.model
+        )
+    } else {
+        format!(
+            "This session is ~{used} tokens, more than the {window}-token context window of {}, \
+             so the next request would fail. Run /compact now to summarize older messages \
+             or /trim to drop the oldest ones.",
+            app.model
+        )
+    };
+    app.add_message(HistoryCell::System { content });
+    app.auto_compact
 }
 
-fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
-    enable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        EnterAlternateScreen,
-        EnableMouseCapture,
-        EnableBracketedPaste
-    )?;
+fn resume_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    terminal_guard: &mut TerminalGuard,
+) -> Result<()> {
+    terminal_guard.resume()?;
     terminal.clear()?;
     Ok(())
 }
@@ -2719,7 +3293,7 @@ fn render_command_footer(f: &mut Frame, area: Rect, app: &App) {
 
     if app.transcript_selection.is_active() {
         let span = Span::styled(
-            copy_selection_hint(),
+            copy_selection_hint(app),
             Style::default().fg(palette::TEXT_MUTED),
         );
         push_footer_span(
@@ -3056,7 +3630,7 @@ fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
             }
 
             if app.transcript_selection.dragging
-                && let Some(point) = selection_point_from_mouse(app, mouse)
+                && let Some(point) = selection_point_from_drag(app, mouse)
             {
                 app.transcript_selection.head = Some(point);
             }
@@ -3066,6 +3640,10 @@ fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
                 app.transcript_selection.dragging = false;
                 if selection_has_content(app) {
                     copy_active_selection(app);
+                } else {
+                    // A plain click shouldn't leave an empty selection that
+                    // captures Ctrl+C.
+                    app.transcript_selection.clear();
                 }
             }
         }
@@ -3121,6 +3699,38 @@ fn selection_point_from_position(
     })
 }
 
+/// Like `selection_point_from_mouse`, but a drag past the transcript edges (or
+/// into the top padding) extends the selection to the nearest line instead of
+/// dropping the update.
+fn selection_point_from_drag(app: &App, mouse: MouseEvent) -> Option<TranscriptSelectionPoint> {
+    let area = app.last_transcript_area?;
+    let (column, row) = clamp_to_transcript(
+        area,
+        mouse.column,
+        mouse.row,
+        app.last_transcript_padding_top,
+    );
+    selection_point_from_position(
+        area,
+        column,
+        row,
+        app.last_transcript_top,
+        app.last_transcript_total,
+        app.last_transcript_padding_top,
+    )
+}
+
+fn clamp_to_transcript(area: Rect, column: u16, row: u16, padding_top: usize) -> (u16, u16) {
+    let max_column = area.x + area.width.saturating_sub(1);
+    let max_row = area.y + area.height.saturating_sub(1);
+    let padding = u16::try_from(padding_top).unwrap_or(u16::MAX);
+    let min_row = area.y.saturating_add(padding).min(max_row);
+    (
+        column.clamp(area.x, max_column),
+        row.clamp(min_row, max_row),
+    )
+}
+
 fn is_inside_scrollbar(app: &App, mouse: MouseEvent) -> bool {
     let Some(area) = app.last_scrollbar_area else {
         return false;
@@ -3180,25 +3790,17 @@ fn selection_to_text(app: &App) -> Option<String> {
     let end_index = end.line_index.min(lines.len().saturating_sub(1));
     let start_index = start.line_index.min(end_index);
 
-    let mut out = String::new();
-    #[allow(clippy::needless_range_loop)]
-    for line_index in start_index..=end_index {
-        let line_text = line_to_plain(&lines[line_index]);
-        let slice = if start_index == end_index {
-            slice_text(&line_text, start.column, end.column)
-        } else if line_index == start_index {
-            slice_text(&line_text, start.column, line_text.chars().count())
-        } else if line_index == end_index {
-            slice_text(&line_text, 0, end.column)
-        } else {
-            line_text
-        };
-        out.push_str(&slice);
-        if line_index != end_index {
-            out.push('\n');
-        }
-    }
-    Some(out)
+    let text = (start_index..=end_index)
+        .map(|line_index| {
+            let line_text = line_to_plain(&lines[line_index]);
+            match app.transcript_selection.columns_for_line(line_index) {
+                Some((col_start, col_end)) => slice_columns(&line_text, col_start, col_end),
+                None => line_text,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(text)
 }
 
 fn is_copy_shortcut(key: &KeyEvent) -> bool {
@@ -3214,14 +3816,46 @@ fn is_copy_shortcut(key: &KeyEvent) -> bool {
     key.modifiers.contains(KeyModifiers::CONTROL) && key.modifiers.contains(KeyModifiers::SHIFT)
 }
 
-fn copy_selection_hint() -> &'static str {
-    "Release to copy selection"
+fn copy_selection_hint(app: &App) -> &'static str {
+    if app.transcript_selection.dragging {
+        "Release to copy selection"
+    } else {
+        "Ctrl+C to copy selection"
+    }
 }
 
 fn should_scroll_with_arrows(_app: &App) -> bool {
     false
 }
 
+/// Vim keys only apply while the composer is empty, so typing is never stolen.
+fn vim_motion(app: &App, key: &KeyEvent) -> Option<VimMotion> {
+    if !app.vim_mode || !app.input.is_empty() {
+        return None;
+    }
+    vim_motion_for(key)
+}
+
+fn apply_vim_motion(app: &mut App, motion: VimMotion) {
+    let half_page = (app.last_transcript_visible / 2).max(1);
+    match motion {
+        VimMotion::LineDown => app.scroll_down(1),
+        VimMotion::LineUp => app.scroll_up(1),
+        VimMotion::HalfPageDown => app.scroll_down(half_page),
+        VimMotion::HalfPageUp => app.scroll_up(half_page),
+        VimMotion::Top => {
+            if let Some(anchor) = TranscriptScroll::anchor_for(app.transcript_cache.line_meta(), 0)
+            {
+                app.transcript_scroll = anchor;
+                app.pending_scroll_delta = 0;
+            }
+        }
+        VimMotion::Bottom => app.scroll_to_bottom(),
+        VimMotion::Search => app.view_stack.push(SearchView::new(None)),
+        VimMotion::Command => {}
+    }
+}
+
 fn line_to_plain(line: &Line<'static>) -> String {
     line.spans
         .iter()
@@ -3229,21 +3863,6 @@ fn line_to_plain(line: &Line<'static>) -> String {
         .collect::<String>()
 }
 
-fn slice_text(text: &str, start: usize, end: usize) -> String {
-    let mut out = String::new();
-    let mut idx = 0usize;
-    for ch in text.chars() {
-        if idx >= start && idx < end {
-            out.push(ch);
-        }
-        idx += 1;
-        if idx >= end {
-            break;
-        }
-    }
-    out
-}
-
 #[allow(clippy::too_many_lines)]
 fn render_onboarding(f: &mut Frame, area: Rect, app: &App) {
     // Clear the entire screen with a dark background
@@ -3253,12 +3872,7 @@ fn render_onboarding(f: &mut Frame, area: Rect, app: &App) {
     // Center the content
     let content_width = 70.min(area.width.saturating_sub(4));
     let content_height = 24.min(area.height.saturating_sub(4));
-    let content_area = Rect {
-        x: (area.width - content_width) / 2,
-        y: (area.height - content_height) / 2,
-        width: content_width,
-        height: content_height,
-    };
+    let content_area = crate::tui::layout::centered_rect(area, content_width, content_height);
 
     match app.onboarding {
         OnboardingState::Welcome => {
@@ -3557,17 +4171,39 @@ fn format_subagent_list(agents: &[SubAgentResult]) -> String {
             "  {} ({:?}) - {} | steps: {} | {}ms",
             agent.agent_id, agent.agent_type, status, agent.steps_taken, agent.duration_ms
         );
-        if matches!(agent.status, SubAgentStatus::Completed)
-            && let Some(result) = agent.result.as_ref()
-        {
-            let _ = write!(line, "\n    Result: {}", summarize_tool_output(result));
-        }
+        push_subagent_result(&mut line, &agent.status, agent.result.as_deref());
         lines.push(line);
     }
 
     lines.join("\n")
 }
 
+/// One summary cell for a finished batch of sub-agents (`aggregate_subagents`).
+fn format_subagent_batch(batch: &[(String, Option<(SubAgentStatus, String)>)]) -> String {
+    let mut lines = Vec::new();
+    lines.push(format!("Sub-agents finished ({}):", batch.len()));
+    lines.push("----------------------------------------".to_string());
+
+    for (id, outcome) in batch {
+        let Some((status, result)) = outcome else {
+            continue;
+        };
+        let mut line = format!("  {id} - {}", format_subagent_status(status));
+        push_subagent_result(&mut line, status, Some(result));
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+fn push_subagent_result(line: &mut String, status: &SubAgentStatus, result: Option<&str>) {
+    if matches!(status, SubAgentStatus::Completed)
+        && let Some(result) = result
+    {
+        let _ = write!(line, "\n    Result: {}", summarize_tool_output(result));
+    }
+}
+
 fn format_subagent_status(status: &SubAgentStatus) -> String {
     match status {
         SubAgentStatus::Running => "running".to_string(),
@@ -3686,12 +4322,19 @@ fn handle_tool_call_started(app: &mut App, id: &str, name: &str, input: &serde_j
 
     if name == "apply_patch" {
         let (path, summary) = parse_patch_summary(input);
+        let diff = input
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
         app.add_message(HistoryCell::Tool(ToolCell::PatchSummary(
             PatchSummaryCell {
                 path,
                 summary,
+                diff,
                 status: ToolStatus::Running,
                 error: None,
+                duration_ms: None,
             },
         )));
         app.tool_cells
@@ -3705,6 +4348,7 @@ fn handle_tool_call_started(app: &mut App, id: &str, name: &str, input: &serde_j
             status: ToolStatus::Running,
             content: None,
             is_image: false,
+            duration_ms: None,
         })));
         app.tool_cells
             .insert(id, app.history.len().saturating_sub(1));
@@ -3733,6 +4377,7 @@ fn handle_tool_call_started(app: &mut App, id: &str, name: &str, input: &serde_j
             query,
             status: ToolStatus::Running,
             summary: None,
+            duration_ms: None,
         })));
         app.tool_cells
             .insert(id, app.history.len().saturating_sub(1));
@@ -3745,6 +4390,7 @@ fn handle_tool_call_started(app: &mut App, id: &str, name: &str, input: &serde_j
         status: ToolStatus::Running,
         input_summary,
         output: None,
+        duration_ms: None,
     })));
     app.tool_cells
         .insert(id, app.history.len().saturating_sub(1));
@@ -3754,8 +4400,8 @@ fn handle_tool_call_started(app: &mut App, id: &str, name: &str, input: &serde_j
 fn handle_tool_call_complete(
     app: &mut App,
     id: &str,
-    _name: &str,
     result: &Result<ToolResult, ToolError>,
+    duration: Duration,
 ) {
     if app.ignored_tool_calls.remove(id) {
         return;
@@ -3798,6 +4444,9 @@ fn handle_tool_call_complete(
         },
         Err(_) => ToolStatus::Failed,
     };
+    // Background tools report again when they finish; only time the final result
+    let duration_ms = (status != ToolStatus::Running)
+        .then(|| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX));
 
     if let Some(cell) = app.history.get_mut(cell_index) {
         match cell {
@@ -3808,7 +4457,8 @@ fn handle_tool_call_complete(
                         .metadata
                         .as_ref()
                         .and_then(|m| m.get("duration_ms"))
-                        .and_then(serde_json::Value::as_u64);
+                        .and_then(serde_json::Value::as_u64)
+                        .or(duration_ms);
                     if status != ToolStatus::Running && exec.interaction.is_none() {
                         exec.output = Some(tool_result.content.clone());
                     }
@@ -3825,6 +4475,7 @@ fn handle_tool_call_complete(
             }
             HistoryCell::Tool(ToolCell::PatchSummary(patch)) => {
                 patch.status = status;
+                patch.duration_ms = duration_ms;
                 match result.as_ref() {
                     Ok(tool_result) => {
                         if let Ok(json) =
@@ -3841,6 +4492,7 @@ fn handle_tool_call_complete(
                 app.mark_history_updated();
             }
             HistoryCell::Tool(ToolCell::Mcp(mcp)) => {
+                mcp.duration_ms = duration_ms;
                 match result.as_ref() {
                     Ok(tool_result) => {
                         let summary = summarize_mcp_output(&tool_result.content);
@@ -3861,6 +4513,7 @@ fn handle_tool_call_complete(
             }
             HistoryCell::Tool(ToolCell::WebSearch(search)) => {
                 search.status = status;
+                search.duration_ms = duration_ms;
                 match result.as_ref() {
                     Ok(tool_result) => {
                         search.summary = Some(summarize_tool_output(&tool_result.content));
@@ -3873,9 +4526,10 @@ fn handle_tool_call_complete(
             }
             HistoryCell::Tool(ToolCell::Generic(generic)) => {
                 generic.status = status;
+                generic.duration_ms = duration_ms;
                 match result.as_ref() {
                     Ok(tool_result) => {
-                        generic.output = Some(summarize_tool_output(&tool_result.content));
+                        generic.output = Some(tool_result.content.clone());
                     }
                     Err(err) => {
                         generic.output = Some(err.to_string());
@@ -4110,6 +4764,41 @@ mod tests {
     use std::path::PathBuf;
     use tempfile::tempdir;
 
+    #[test]
+    fn trailing_ampersand_runs_in_background() {
+        assert_eq!(
+            strip_background_suffix("cargo watch &"),
+            Some("cargo watch")
+        );
+        assert_eq!(strip_background_suffix("make 2>&1 &"), Some("make 2>&1"));
+        assert_eq!(strip_background_suffix("cargo build && cargo test"), None);
+        assert_eq!(strip_background_suffix("true &&"), None);
+        assert_eq!(strip_background_suffix("&"), None);
+        assert_eq!(strip_background_suffix("ls"), None);
+    }
+
+    #[test]
+    fn subagent_batch_summarizes_each_outcome() {
+        let batch = vec![
+            (
+                "agent_a".to_string(),
+                Some((SubAgentStatus::Completed, "found 3 call sites".to_string())),
+            ),
+            (
+                "agent_b".to_string(),
+                Some((
+                    SubAgentStatus::Failed("timed out".to_string()),
+                    "Failed: timed out".to_string(),
+                )),
+            ),
+        ];
+        let summary = format_subagent_batch(&batch);
+        assert!(summary.starts_with("Sub-agents finished (2):"));
+        assert!(summary.contains("  agent_a - completed\n    Result: found 3 call sites"));
+        assert!(summary.contains("  agent_b - failed: timed out"));
+        assert!(!summary.contains("Result: Failed"));
+    }
+
     #[test]
     fn selection_point_from_position_ignores_top_padding() {
         let area = Rect {
@@ -4164,22 +4853,208 @@ mod tests {
         assert_eq!(p1.column, 0);
     }
 
+    #[test]
+    fn drag_outside_transcript_clamps_to_nearest_line() {
+        let area = Rect {
+            x: 10,
+            y: 20,
+            width: 30,
+            height: 5,
+        };
+        // Above the area and into the padding -> first content row
+        assert_eq!(clamp_to_transcript(area, 5, 0, 3), (10, 23));
+        // Below and right of the area -> last cell
+        assert_eq!(clamp_to_transcript(area, 200, 200, 0), (39, 24));
+        // Inside stays put
+        assert_eq!(clamp_to_transcript(area, 12, 21, 0), (12, 21));
+    }
+
     fn make_test_app_with_workspace(workspace: PathBuf) -> App {
-        let options = TuiOptions {
-            model: "test-model".to_string(),
-            workspace,
-            allow_shell: false,
-            max_subagents: 1,
-            skills_dir: PathBuf::from("."),
-            memory_path: PathBuf::from("memory.md"),
-            notes_path: PathBuf::from("notes.txt"),
-            mcp_config_path: PathBuf::from("mcp.json"),
-            use_memory: false,
-            start_in_agent_mode: false,
-            yolo: false,
-            resume_session_id: None,
+        App::new(TuiOptions::for_test(workspace), &Config::default())
+    }
+
+    #[test]
+    fn limited_terminal_modes_are_explained() {
+        let mut app = make_test_app_with_workspace(PathBuf::from("."));
+        let before = app.history.len();
+        warn_on_limited_terminal(
+            &mut app,
+            TerminalModes {
+                mouse_capture: true,
+                bracketed_paste: true,
+            },
+        );
+        if !cfg!(windows) {
+            assert_eq!(app.history.len(), before);
+        }
+
+        warn_on_limited_terminal(
+            &mut app,
+            TerminalModes {
+                mouse_capture: false,
+                bracketed_paste: false,
+            },
+        );
+        let Some(HistoryCell::System { content }) = app.history.last() else {
+            panic!("expected a system note");
+        };
+        assert!(content.contains("PageUp/PageDown"), "{content}");
+        assert!(content.contains("/paste"), "{content}");
+    }
+
+    #[test]
+    fn oversized_resume_warns_and_trim_fits_the_window() {
+        let mut app = make_test_app_with_workspace(PathBuf::from("."));
+        app.custom_context_windows
+            .insert("test-model".to_string(), 1000);
+        let text = |role: &str, len: usize| Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: "a".repeat(len),
+                cache_control: None,
+            }],
+        };
+        app.api_messages = vec![
+            text("user", 2000),
+            text("assistant", 2000),
+            text("user", 2000),
+            text("assistant", 40),
+        ];
+        app.recalculate_context_tokens();
+        assert!(app.context_overflow().is_some());
+
+        app.auto_compact = false;
+        assert!(!warn_on_context_overflow(&mut app));
+        let HistoryCell::System { content } = app.history.last().expect("notice") else {
+            panic!("expected a system notice");
+        };
+        assert!(content.contains("/trim"), "{content}");
+
+        let result = crate::commands::execute("/trim", &mut app);
+        assert!(matches!(result.action, Some(AppAction::SyncSession { .. })));
+        assert_eq!(app.api_messages.len(), 2);
+        assert_eq!(app.api_messages[0].role, "user");
+        assert!(app.context_overflow().is_none());
+
+        app.auto_compact = true;
+        app.api_messages.push(text("user", 8000));
+        app.recalculate_context_tokens();
+        assert!(warn_on_context_overflow(&mut app));
+    }
+
+    #[test]
+    fn compact_target_and_auto_threshold_use_the_window() {
+        let mut app = make_test_app_with_workspace(PathBuf::from("."));
+        app.custom_context_windows
+            .insert("test-model".to_string(), 10_000);
+        let text = |role: &str, len: usize| Message {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: "a".repeat(len),
+                cache_control: None,
+            }],
+        };
+        app.api_messages = (0..8)
+            .map(|i| text(if i % 2 == 0 { "user" } else { "assistant" }, 4000))
+            .collect();
+        app.recalculate_context_tokens();
+
+        assert_eq!(app.keep_policy().auto_compact_tokens, None);
+        app.auto_compact = true;
+        app.auto_compact_threshold = 90;
+        assert_eq!(app.keep_policy().auto_compact_tokens, Some(9_000));
+        app.auto_compact_threshold = 50;
+        assert_eq!(app.keep_policy().auto_compact_tokens, Some(5_000));
+
+        let result = crate::commands::execute("/compact --target 30", &mut app);
+        let Some(AppAction::CompactContext {
+            target_tokens: Some(budget),
+        }) = result.action
+        else {
+            panic!("expected a targeted compaction");
         };
-        App::new(options, &Config::default())
+        assert!(budget < 3000, "{budget}");
+        assert!(
+            crate::commands::execute("/compact --target 99", &mut app)
+                .action
+                .is_none()
+        );
+        let result = crate::commands::execute("/compact --target x", &mut app);
+        assert!(result.message.unwrap().contains("Usage"));
+
+        // A preview goes to the engine and leaves the conversation alone
+        let result = crate::commands::execute("/compact --preview", &mut app);
+        assert!(matches!(result.action, Some(AppAction::PreviewCompaction)));
+        assert_eq!(app.api_messages.len(), 8);
+    }
+
+    #[test]
+    fn shell_job_output_is_capped_at_a_line_break() {
+        let header = "[shell_1] running in background: yes";
+        let mut content = format!("{header}\n");
+        for i in 0..20_000 {
+            content.push_str(&format!("line {i}\n"));
+        }
+        cap_shell_job_output(&mut content, header);
+        let expected_start = format!("{header}\n{SHELL_JOB_TRIMMED}\nline ");
+        assert!(content.starts_with(&expected_start));
+        assert!(content.len() <= header.len() + SHELL_JOB_TRIMMED.len() + 2 + MAX_SHELL_JOB_OUTPUT);
+        assert!(content.ends_with("line 19999\n"));
+
+        let before = content.clone();
+        cap_shell_job_output(&mut content, header);
+        assert_eq!(content, before);
+    }
+
+    #[test]
+    fn layout_debug_label_reports_geometry() {
+        let rect = Rect {
+            x: 0,
+            y: 3,
+            width: 80,
+            height: 20,
+        };
+        assert_eq!(
+            layout_debug_label("transcript", rect),
+            " transcript 80x20 @0,3 "
+        );
+    }
+
+    #[test]
+    fn vim_motion_only_applies_to_empty_composer_when_enabled() {
+        let mut app = make_test_app_with_workspace(PathBuf::from("."));
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        app.vim_mode = false;
+        assert_eq!(vim_motion(&app, &j), None);
+
+        app.vim_mode = true;
+        assert_eq!(vim_motion(&app, &j), Some(VimMotion::LineDown));
+        assert_eq!(
+            vim_motion(&app, &key(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Some(VimMotion::Bottom)
+        );
+        assert_eq!(
+            vim_motion(&app, &key(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(VimMotion::HalfPageDown)
+        );
+        assert_eq!(
+            vim_motion(&app, &key(KeyCode::Char('/'), KeyModifiers::NONE)),
+            Some(VimMotion::Search)
+        );
+        assert_eq!(
+            vim_motion(&app, &key(KeyCode::Char('x'), KeyModifiers::CONTROL)),
+            None
+        );
+
+        assert_eq!(
+            vim_motion(&app, &key(KeyCode::Char(':'), KeyModifiers::NONE)),
+            Some(VimMotion::Command)
+        );
+
+        app.insert_char('h');
+        assert_eq!(vim_motion(&app, &j), None);
     }
 
     #[test]
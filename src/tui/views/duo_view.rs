@@ -357,12 +357,7 @@ impl ModalView for DuoView {
         let popup_width = 70.min(area.width.saturating_sub(4));
         let popup_height = 32.min(area.height.saturating_sub(4));
 
-        let popup_area = Rect {
-            x: (area.width - popup_width) / 2,
-            y: (area.height - popup_height) / 2,
-            width: popup_width,
-            height: popup_height,
-        };
+        let popup_area = crate::tui::layout::centered_rect(area, popup_width, popup_height);
 
         Clear.render(popup_area, buf);
 
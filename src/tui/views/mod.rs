@@ -3,8 +3,10 @@ use ratatui::{buffer::Buffer, layout::Rect};
 use std::fmt;
 
 use crate::palette;
+use crate::tui::app::AppMode;
 use crate::tui::approval::ReviewDecision;
 use crate::tui::duo_session_picker::DuoSessionPickerResult;
+use crate::tui::fuzzy_picker::fuzzy_match;
 use crate::tui::history_picker::HistoryPickerResult;
 use crate::tui::model_picker::ModelPickerResult;
 use crate::tui::session_picker::SessionPickerResult;
@@ -173,12 +175,141 @@ impl fmt::Debug for ViewStack {
 
 pub struct HelpView {
     scroll: usize,
+    /// Mode whose commands and tips are listed first
+    mode: AppMode,
+    /// Show the vim bindings that are live when `vim_mode` is on
+    vim_mode: bool,
+    /// Typed text narrowing the command, tool, and key entries
+    filter: String,
 }
 
+/// A help section: heading and `(name, description)` entries.
+type HelpSection = (&'static str, Vec<(String, String)>);
+
 impl HelpView {
-    pub fn new() -> Self {
-        Self { scroll: 0 }
+    pub fn new(mode: AppMode, vim_mode: bool) -> Self {
+        Self {
+            scroll: 0,
+            mode,
+            vim_mode,
+            filter: String::new(),
+        }
+    }
+
+    fn sections(&self) -> Vec<HelpSection> {
+        let commands = crate::commands::COMMANDS
+            .iter()
+            .map(|cmd| (format!("/{}", cmd.name), cmd.description.to_string()))
+            .collect();
+        let tools = vec![
+            (
+                "web_search".to_string(),
+                "Search the web (DuckDuckGo, Brave, or SearXNG; MCP optional)".to_string(),
+            ),
+            (
+                "mcp_*".to_string(),
+                "Tools exposed by MCP servers".to_string(),
+            ),
+        ];
+        let mut sections: Vec<HelpSection> = mode_section(self.mode).into_iter().collect();
+        sections.push(("Commands:", commands));
+        sections.push(("Tools:", tools));
+        for (title, bindings) in crate::tui::keybindings::help_sections(self.vim_mode) {
+            let entries = bindings
+                .into_iter()
+                .map(|b| (b.keys.to_string(), b.description.to_string()))
+                .collect();
+            sections.push((title, entries));
+        }
+        sections
     }
+
+    /// Sections with only the entries fuzzy-matching the filter; empty
+    /// sections are dropped.
+    fn filtered_sections(&self) -> Vec<HelpSection> {
+        let needle = self.filter.trim().to_lowercase();
+        if needle.is_empty() {
+            return self.sections();
+        }
+        self.sections()
+            .into_iter()
+            .filter_map(|(title, entries)| {
+                let entries: Vec<_> = entries
+                    .into_iter()
+                    .filter(|(name, description)| {
+                        let haystack = format!("{name} {description}").to_lowercase();
+                        fuzzy_match(&haystack, &needle).is_some()
+                    })
+                    .collect();
+                (!entries.is_empty()).then_some((title, entries))
+            })
+            .collect()
+    }
+}
+
+/// Commands and tips for the current mode, shown above the full lists.
+fn mode_section(mode: AppMode) -> Option<HelpSection> {
+    let (title, entries): (&'static str, &[(&str, &str)]) = match mode {
+        AppMode::Normal => return None,
+        AppMode::Agent => (
+            "Agent mode:",
+            &[
+                ("/subagents", "List sub-agent status"),
+                (
+                    "/queue",
+                    "View or edit messages queued while the agent works",
+                ),
+                ("Esc", "Cancel the running turn"),
+            ],
+        ),
+        AppMode::Yolo => (
+            "YOLO mode:",
+            &[
+                (
+                    "git_stash",
+                    "Ask the agent to snapshot work before risky edits",
+                ),
+                ("/yolo", "Leave YOLO mode and restore approvals"),
+                ("Esc", "Cancel the running turn"),
+            ],
+        ),
+        AppMode::Plan => (
+            "Plan mode:",
+            &[
+                ("Tab", "Switch to Agent mode to carry out the plan"),
+                ("/queue", "View or edit queued messages"),
+            ],
+        ),
+        AppMode::Rlm => (
+            "RLM mode:",
+            &[
+                ("/load @path", "Load a file as the RLM context"),
+                ("/repl", "Toggle the expression REPL"),
+                ("lines(a, b)", "Show context lines a..b"),
+                ("search(\"re\")", "Search the loaded context"),
+                ("chunk_auto()", "Split the context into chunks"),
+                ("vars() get set", "Inspect and set REPL variables"),
+                ("/status", "Show RLM context status"),
+                ("/save-session", "Save the RLM session to a file"),
+            ],
+        ),
+        AppMode::Duo => (
+            "Duo mode:",
+            &[
+                ("Tab", "Entering Duo opens the session view"),
+                ("duo_init", "Start a player/coach session for a task"),
+                ("duo_player", "Player implements the current round"),
+                ("duo_coach", "Coach reviews and gives feedback"),
+                ("duo_advance", "Move to the next round"),
+                ("duo_status", "Show session state"),
+            ],
+        ),
+    };
+    let entries = entries
+        .iter()
+        .map(|(name, description)| (name.to_string(), description.to_string()))
+        .collect();
+    Some((title, entries))
 }
 
 impl ModalView for HelpView {
@@ -195,18 +326,37 @@ impl ModalView for HelpView {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> ViewAction {
-        use crossterm::event::KeyCode;
+        use crossterm::event::{KeyCode, KeyModifiers};
 
         match key.code {
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => ViewAction::Close,
-            KeyCode::Up | KeyCode::Char('k') => {
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.scroll = 0;
+                ViewAction::None
+            }
+            KeyCode::Esc | KeyCode::Enter => ViewAction::Close,
+            KeyCode::Up => {
                 self.scroll = self.scroll.saturating_sub(1);
                 ViewAction::None
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            KeyCode::Down => {
                 self.scroll = self.scroll.saturating_add(1);
                 ViewAction::None
             }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.scroll = 0;
+                ViewAction::None
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.filter.push(c);
+                self.scroll = 0;
+                ViewAction::None
+            }
             _ => ViewAction::None,
         }
     }
@@ -222,12 +372,7 @@ impl ModalView for HelpView {
         let popup_width = 65.min(area.width.saturating_sub(4));
         let popup_height = 24.min(area.height.saturating_sub(4));
 
-        let popup_area = Rect {
-            x: (area.width - popup_width) / 2,
-            y: (area.height - popup_height) / 2,
-            width: popup_width,
-            height: popup_height,
-        };
+        let popup_area = crate::tui::layout::centered_rect(area, popup_width, popup_height);
 
         Clear.render(popup_area, buf);
 
@@ -236,61 +381,42 @@ impl ModalView for HelpView {
                 "Axiom CLI Help",
                 Style::default().fg(palette::BLUE).bold(),
             )]),
+            Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(palette::TEXT_MUTED)),
+                Span::raw(self.filter.clone()),
+                Span::styled("_", Style::default().fg(palette::BLUE)),
+            ]),
             Line::from(""),
-            Line::from(vec![Span::styled(
+        ];
+
+        if self.filter.is_empty() {
+            help_lines.push(Line::from(vec![Span::styled(
                 "Modes:",
                 Style::default().fg(palette::ORANGE).bold(),
-            )]),
-            Line::from("  Tab cycles modes: Normal → Plan → Agent → Yolo → RLM"),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Commands:",
-                Style::default().fg(palette::ORANGE).bold(),
-            )]),
-        ];
+            )]));
+            help_lines.push(Line::from(
+                "  Tab cycles modes: Normal → Plan → Agent → Yolo → RLM",
+            ));
+            help_lines.push(Line::from(""));
+        }
 
-        for cmd in crate::commands::COMMANDS.iter() {
-            help_lines.push(Line::from(format!(
-                "  /{:<12} - {}",
-                cmd.name, cmd.description
+        let sections = self.filtered_sections();
+        if sections.is_empty() {
+            help_lines.push(Line::from(Span::styled(
+                format!("  No help entries match '{}'", self.filter),
+                Style::default().fg(palette::TEXT_MUTED),
             )));
         }
-
-        help_lines.push(Line::from(""));
-        help_lines.push(Line::from(vec![Span::styled(
-            "Tools:",
-            Style::default().fg(palette::ORANGE).bold(),
-        )]));
-        help_lines.push(Line::from(
-            "  web_search   - Search the web (DuckDuckGo; MCP optional)",
-        ));
-        help_lines.push(Line::from("  mcp_*        - Tools exposed by MCP servers"));
-        help_lines.push(Line::from(""));
-        help_lines.push(Line::from(vec![Span::styled(
-            "Keys:",
-            Style::default().fg(palette::ORANGE).bold(),
-        )]));
-        help_lines.push(Line::from(
-            "  Enter        - Send message / Execute shell command",
-        ));
-        help_lines.push(Line::from("  Esc          - Cancel request / Clear input"));
-        help_lines.push(Line::from("  Tab          - Cycle modes"));
-        help_lines.push(Line::from("  Ctrl+C       - Exit"));
-        help_lines.push(Line::from("  Ctrl+X       - Toggle shell mode"));
-        help_lines.push(Line::from(
-            "  Ctrl+J       - Insert newline (multiline input)",
-        ));
-        help_lines.push(Line::from(
-            "  Alt+Enter    - Insert newline (multiline input)",
-        ));
-        help_lines.push(Line::from("  Ctrl+D       - Exit when input is empty"));
-        help_lines.push(Line::from("  Ctrl+W       - Delete word backward"));
-        help_lines.push(Line::from("  Ctrl+K       - Delete to end of line"));
-        help_lines.push(Line::from("  Ctrl+V       - Paste from clipboard"));
-        help_lines.push(Line::from("  Ctrl+/       - Show this help"));
-        help_lines.push(Line::from("  F1           - Show this help"));
-        help_lines.push(Line::from("  Up/Down      - Scroll this help"));
-        help_lines.push(Line::from(""));
+        for (title, entries) in sections {
+            help_lines.push(Line::from(vec![Span::styled(
+                title,
+                Style::default().fg(palette::ORANGE).bold(),
+            )]));
+            for (name, description) in entries {
+                help_lines.push(Line::from(format!("  {name:<12} - {description}")));
+            }
+            help_lines.push(Line::from(""));
+        }
 
         let total_lines = help_lines.len();
         let visible_lines = (popup_height as usize).saturating_sub(3);
@@ -311,7 +437,10 @@ impl ModalView for HelpView {
                         Style::default().fg(palette::BLUE).bold(),
                     )]))
                     .title_bottom(Line::from(vec![
-                        Span::styled(" Esc to close ", Style::default().fg(palette::TEXT_MUTED)),
+                        Span::styled(
+                            " Type to filter · Esc to close ",
+                            Style::default().fg(palette::TEXT_MUTED),
+                        ),
                         Span::styled(scroll_indicator, Style::default().fg(palette::ORANGE)),
                     ]))
                     .borders(Borders::ALL)
@@ -328,3 +457,94 @@ pub use crate::tui::search_view::SearchResult;
 
 pub mod duo_view;
 pub use duo_view::DuoView;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn type_filter(view: &mut HelpView, text: &str) {
+        for c in text.chars() {
+            view.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn help_filter_narrows_entries_and_esc_clears_it() {
+        let mut view = HelpView::new(AppMode::Normal, false);
+        type_filter(&mut view, "theme");
+
+        let sections = view.filtered_sections();
+        assert!(!sections.is_empty());
+        assert!(
+            sections
+                .iter()
+                .flat_map(|(_, entries)| entries)
+                .any(|(name, _)| name == "/theme")
+        );
+        assert!(sections.iter().all(|(title, _)| *title != "Tools:"));
+
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            ViewAction::None
+        ));
+        assert!(view.filter.is_empty());
+        assert!(matches!(
+            view.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            ViewAction::Close
+        ));
+    }
+
+    #[test]
+    fn popups_render_on_tiny_terminals() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let request = crate::tui::approval::ApprovalRequest::new(
+            "id",
+            "edit_file",
+            &serde_json::json!({"path": "a.txt"}),
+        )
+        .with_diff(Some("@@ -1 +1 @@\n-a\n+b\n".to_string()));
+        let views: Vec<Box<dyn ModalView>> = vec![
+            Box::new(HelpView::new(AppMode::Normal, false)),
+            Box::new(crate::tui::search_view::SearchView::new(Some("query"))),
+            Box::new(crate::tui::history_picker::HistoryPicker::new(&[
+                "one".to_string()
+            ])),
+            Box::new(crate::tui::session_picker::SessionPicker::new(
+                None,
+                tmp.path(),
+            )),
+            Box::new(crate::tui::duo_session_picker::DuoSessionPicker::new(None)),
+            Box::new(crate::tui::approval::ApprovalView::new(request)),
+        ];
+        for (width, height) in [(0, 0), (1, 1), (3, 2), (10, 4), (30, 6)] {
+            let area = Rect::new(0, 0, width, height);
+            let mut buf = Buffer::empty(area);
+            for view in &views {
+                view.render(area, &mut buf);
+            }
+        }
+    }
+
+    #[test]
+    fn help_lists_current_mode_first() {
+        let rlm = HelpView::new(AppMode::Rlm, false).sections();
+        assert_eq!(rlm[0].0, "RLM mode:");
+        assert!(rlm[0].1.iter().any(|(name, _)| name == "/repl"));
+
+        let duo = HelpView::new(AppMode::Duo, false).sections();
+        assert_eq!(duo[0].0, "Duo mode:");
+
+        let normal = HelpView::new(AppMode::Normal, false).sections();
+        assert_eq!(normal[0].0, "Commands:");
+    }
+
+    #[test]
+    fn help_filter_matches_key_descriptions() {
+        let mut view = HelpView::new(AppMode::Normal, true);
+        type_filter(&mut view, "half page");
+        let sections = view.filtered_sections();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].0, "Vim keys (composer empty):");
+    }
+}
@@ -12,6 +12,7 @@ use unicode_width::UnicodeWidthStr;
 use crate::models::context_window_for_model;
 use crate::palette;
 use crate::tui::app::{AppMode, PinnedMessage};
+use crate::tui::approval::ApprovalPolicy;
 
 use super::Renderable;
 
@@ -24,6 +25,9 @@ pub struct HeaderData<'a> {
     pub is_streaming: bool,
     pub background: ratatui::style::Color,
     pub shell_mode: bool,
+    /// Replies come from the scripted mock backend
+    pub mock_backend: bool,
+    pub approval_policy: ApprovalPolicy,
     pub pins: Vec<&'a PinnedMessage>,
     pub custom_context_windows: std::collections::HashMap<String, u32>,
 }
@@ -48,6 +52,8 @@ impl<'a> HeaderData<'a> {
             is_streaming,
             background,
             shell_mode: false,
+            mock_backend: false,
+            approval_policy: ApprovalPolicy::Manual,
             pins: Vec::new(),
             custom_context_windows,
         }
@@ -60,6 +66,20 @@ impl<'a> HeaderData<'a> {
         self
     }
 
+    /// Set mock backend status.
+    #[must_use]
+    pub fn with_mock_backend(mut self, mock_backend: bool) -> Self {
+        self.mock_backend = mock_backend;
+        self
+    }
+
+    /// Set the approval policy shown after the model name.
+    #[must_use]
+    pub fn with_approval_policy(mut self, approval_policy: ApprovalPolicy) -> Self {
+        self.approval_policy = approval_policy;
+        self
+    }
+
     /// Set pinned messages.
     #[must_use]
     pub fn with_pins(mut self, pins: Vec<&'a PinnedMessage>) -> Self {
@@ -88,7 +108,7 @@ impl<'a> HeaderData<'a> {
 
 /// Header bar widget (1-2 lines height).
 ///
-/// Layout: `[MODE] | model-name | Context: XX% | [streaming indicator]`
+/// Layout: `[MODE] [MOCK] | model-name | Approvals: policy | Context: XX% | [streaming indicator]`
 /// If pins exist, a second line shows: `📌 [source] preview`
 pub struct HeaderWidget<'a> {
     data: HeaderData<'a>,
@@ -131,6 +151,19 @@ impl<'a> HeaderWidget<'a> {
         )
     }
 
+    /// Build the badge marking the mock backend, if active.
+    fn mock_badge(&self) -> Option<Span<'static>> {
+        self.data.mock_backend.then(|| {
+            Span::styled(
+                " MOCK ",
+                Style::default()
+                    .fg(palette::INK)
+                    .bg(palette::YELLOW)
+                    .add_modifier(Modifier::BOLD),
+            )
+        })
+    }
+
     /// Build the model name span.
     fn model_span(&self) -> Span<'static> {
         let display_name = if self.data.model.len() > 20 {
@@ -142,6 +175,20 @@ impl<'a> HeaderWidget<'a> {
         Span::styled(display_name, Style::default().fg(palette::TEXT_MUTED))
     }
 
+    /// Build the approval policy span, colored by how much runs unsupervised.
+    fn approval_span(&self) -> Span<'static> {
+        let color = match self.data.approval_policy {
+            ApprovalPolicy::Manual => palette::TEXT_MUTED,
+            ApprovalPolicy::AutoSafe => palette::STATUS_INFO,
+            ApprovalPolicy::Yolo => palette::STATUS_ERROR,
+            ApprovalPolicy::Deny => palette::STATUS_WARNING,
+        };
+        Span::styled(
+            format!("Approvals: {}", self.data.approval_policy.label()),
+            Style::default().fg(color),
+        )
+    }
+
     /// Build the context meter span with color based on usage.
     fn context_meter(&self) -> Span<'static> {
         let remaining = self.data.context_remaining_percent();
@@ -235,11 +282,21 @@ impl Renderable for HeaderWidget<'_> {
         }
 
         // Render main header line
-        let mut left_spans = vec![
-            self.mode_badge(),
-            Span::styled(" | ", Style::default().fg(palette::TEXT_MUTED)),
-            self.model_span(),
-        ];
+        let mut left_spans = vec![self.mode_badge()];
+        if let Some(mock) = self.mock_badge() {
+            left_spans.push(Span::raw(" "));
+            left_spans.push(mock);
+        }
+        left_spans.push(Span::styled(
+            " | ",
+            Style::default().fg(palette::TEXT_MUTED),
+        ));
+        left_spans.push(self.model_span());
+        left_spans.push(Span::styled(
+            " | ",
+            Style::default().fg(palette::TEXT_MUTED),
+        ));
+        left_spans.push(self.approval_span());
 
         let context_span = self.context_meter();
         let streaming_span = self.streaming_indicator();
@@ -330,6 +387,8 @@ mod tests {
             is_streaming: false,
             background: palette::INK,
             shell_mode: false,
+            mock_backend: false,
+            approval_policy: ApprovalPolicy::Manual,
             pins: Vec::new(),
             custom_context_windows: custom_windows,
         };
@@ -348,6 +407,8 @@ mod tests {
             is_streaming: false,
             background: palette::INK,
             shell_mode: false,
+            mock_backend: false,
+            approval_policy: ApprovalPolicy::Manual,
             pins: Vec::new(),
             custom_context_windows: custom_windows,
         };
@@ -366,10 +427,55 @@ mod tests {
             is_streaming: false,
             background: palette::INK,
             shell_mode: false,
+            mock_backend: false,
+            approval_policy: ApprovalPolicy::Manual,
             pins: Vec::new(),
             custom_context_windows: custom_windows,
         };
         assert_eq!(data.context_percent(), 0);
         assert_eq!(data.context_remaining_percent(), 100);
     }
+
+    #[test]
+    fn mock_backend_badge_is_shown() {
+        let data = HeaderData::new(
+            AppMode::Agent,
+            "mock-model",
+            0,
+            false,
+            palette::INK,
+            std::collections::HashMap::new(),
+        )
+        .with_mock_backend(true);
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+        HeaderWidget::new(data).render(area, &mut buf);
+        let text: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(text.starts_with(" AGENT   MOCK  | mock-model"), "{text}");
+    }
+
+    #[test]
+    fn approval_policy_is_shown_after_model() {
+        let data = HeaderData::new(
+            AppMode::Yolo,
+            "test-model",
+            0,
+            false,
+            palette::INK,
+            std::collections::HashMap::new(),
+        )
+        .with_approval_policy(ApprovalPolicy::Yolo);
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        HeaderWidget::new(data).render(area, &mut buf);
+        let text: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(
+            text.starts_with(" YOLO  | test-model | Approvals: yolo | "),
+            "{text}"
+        );
+    }
 }
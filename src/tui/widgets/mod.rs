@@ -6,7 +6,7 @@ pub use renderable::Renderable;
 
 use crate::palette;
 use crate::tui::app::{App, AppMode};
-use crate::tui::approval::{ApprovalRequest, ToolCategory};
+use crate::tui::approval::{ApprovalRequest, ToolCategory, approval_options};
 use crate::tui::scrolling::TranscriptScroll;
 use ratatui::{
     buffer::Buffer,
@@ -20,7 +20,7 @@ use ratatui::{
     },
 };
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub struct ChatWidget {
     content_area: Rect,
@@ -207,7 +207,13 @@ impl Renderable for ComposerWidget<'_> {
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        composer_height(&self.app.input, width, self.max_height, self.prompt)
+        composer_height(
+            &self.app.input,
+            width,
+            self.max_height,
+            self.app.max_composer_lines,
+            self.prompt,
+        )
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
@@ -242,32 +248,81 @@ pub struct ApprovalWidget<'a> {
     request: &'a ApprovalRequest,
     selected: usize,
     params_display: Option<String>,
+    expanded: bool,
 }
 
+/// Diff lines shown in the approval popup before Ctrl+E expands it.
+const APPROVAL_DIFF_LINES: usize = 12;
+
 impl<'a> ApprovalWidget<'a> {
     pub fn with_expanded(
         request: &'a ApprovalRequest,
         selected: usize,
         params_display: &str,
+        expanded: bool,
     ) -> Self {
         Self {
             request,
             selected,
             params_display: Some(params_display.to_string()),
+            expanded,
         }
     }
+
+    /// Diff lines to show, styled, plus how many were left out.
+    fn diff_lines(&self, width: usize, max_lines: usize) -> (Vec<Line<'static>>, usize) {
+        let Some(diff) = self.request.diff.as_deref() else {
+            return (Vec::new(), 0);
+        };
+        // Skip the ---/+++ header; the tool name and params already name the file.
+        let body: Vec<&str> = diff
+            .lines()
+            .skip_while(|line| line.starts_with("--- ") || line.starts_with("+++ "))
+            .collect();
+        let shown = body.len().min(max_lines);
+        let lines = body[..shown]
+            .iter()
+            .map(|line| {
+                let text = crate::utils::truncate_with_ellipsis(
+                    &line.replace('\t', "    "),
+                    width.saturating_sub(2),
+                    "...",
+                );
+                Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(text, crate::tui::syntax::diff_line_style(line)),
+                ])
+            })
+            .collect();
+        (lines, body.len() - shown)
+    }
 }
 
 impl Renderable for ApprovalWidget<'_> {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let popup_width = 65.min(area.width.saturating_sub(4));
-        let popup_height = 18.min(area.height.saturating_sub(4));
-        let popup_area = Rect {
-            x: (area.width.saturating_sub(popup_width)) / 2,
-            y: (area.height.saturating_sub(popup_height)) / 2,
-            width: popup_width,
-            height: popup_height,
+        let has_diff = self.request.diff.is_some();
+        let popup_width = if has_diff { 100 } else { 65 }.min(area.width.saturating_sub(4));
+        let max_height = area.height.saturating_sub(4);
+        // Fixed rows (tool, type, cost, params, options, borders) plus the diff.
+        let diff_budget = if self.expanded {
+            usize::from(max_height.saturating_sub(20))
+        } else {
+            APPROVAL_DIFF_LINES
+        };
+        let (diff_lines, diff_omitted) =
+            self.diff_lines(usize::from(popup_width.saturating_sub(2)), diff_budget);
+        let diff_height = if has_diff {
+            u16::try_from(diff_lines.len() + 3).unwrap_or(u16::MAX)
+        } else {
+            0
         };
+        let extra_options =
+            u16::try_from(approval_options(self.request.category).len() - 4).unwrap_or(0);
+        let popup_height = 18u16
+            .saturating_add(extra_options)
+            .saturating_add(diff_height)
+            .min(max_height);
+        let popup_area = crate::tui::layout::centered_rect(area, popup_width, popup_height);
 
         Clear.render(popup_area, buf);
 
@@ -284,18 +339,19 @@ impl Renderable for ApprovalWidget<'_> {
             ]),
         ];
 
-        let category_label = match self.request.category {
-            ToolCategory::Safe => ("Safe", palette::STATUS_SUCCESS),
-            ToolCategory::FileWrite => ("File Write", palette::STATUS_WARNING),
-            ToolCategory::Shell => ("Shell Command", palette::STATUS_ERROR),
-            ToolCategory::PaidMultimedia => ("Paid API", palette::MAGENTA),
+        let category_color = match self.request.category {
+            ToolCategory::Safe => palette::STATUS_SUCCESS,
+            ToolCategory::FileWrite => palette::STATUS_WARNING,
+            ToolCategory::Shell => palette::STATUS_ERROR,
+            ToolCategory::PaidMultimedia => palette::MAGENTA,
+            ToolCategory::SideEffects => palette::STATUS_WARNING,
         };
         lines.push(Line::from(vec![
             Span::raw("  Type: "),
             Span::styled(
-                category_label.0,
+                self.request.category.label(),
                 Style::default()
-                    .fg(category_label.1)
+                    .fg(category_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
@@ -332,16 +388,29 @@ impl Renderable for ApprovalWidget<'_> {
             Style::default().fg(palette::TEXT_MUTED),
         )));
 
-        lines.push(Line::from(""));
+        if has_diff {
+            lines.push(Line::from(""));
+            lines.push(Line::from("  Changes:"));
+            lines.extend(diff_lines);
+            if diff_omitted > 0 {
+                let hint = if self.expanded {
+                    "do not fit"
+                } else {
+                    "press Ctrl+E to expand"
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("  … ({diff_omitted} more lines, {hint})"),
+                    Style::default().fg(palette::TEXT_MUTED),
+                )));
+            }
+        }
 
-        let options = [
-            ("y", "Approve (this time)"),
-            ("a", "Approve for session"),
-            ("n", "Deny"),
-            ("Esc", "Abort turn"),
-        ];
+        lines.push(Line::from(""));
 
-        for (i, (key, label)) in options.iter().enumerate() {
+        for (i, (key, label, _)) in approval_options(self.request.category)
+            .into_iter()
+            .enumerate()
+        {
             let is_selected = i == self.selected;
             let style = if is_selected {
                 Style::default()
@@ -357,7 +426,7 @@ impl Renderable for ApprovalWidget<'_> {
                     format!("[{key}] "),
                     Style::default().fg(palette::STATUS_SUCCESS),
                 ),
-                Span::styled(*label, style),
+                Span::styled(label, style),
             ]));
         }
 
@@ -395,28 +464,13 @@ pub(crate) fn pad_lines_to_bottom(lines: &mut Vec<Line<'static>>, height: usize)
 }
 
 fn apply_selection(lines: &mut [Line<'static>], top: usize, app: &App) {
-    let Some((start, end)) = app.transcript_selection.ordered_endpoints() else {
-        return;
-    };
-
     let selection_style = Style::default().bg(app.ui_theme.selection_bg);
 
     for (idx, line) in lines.iter_mut().enumerate() {
-        let line_index = top + idx;
-        if line_index < start.line_index || line_index > end.line_index {
+        let Some((col_start, col_end)) = app.transcript_selection.columns_for_line(top + idx)
+        else {
             continue;
-        }
-
-        let (col_start, col_end) = if start.line_index == end.line_index {
-            (start.column, end.column)
-        } else if line_index == start.line_index {
-            (start.column, usize::MAX)
-        } else if line_index == end.line_index {
-            (0, end.column)
-        } else {
-            (0, usize::MAX)
         };
-
         let new_spans = apply_selection_to_line(line, col_start, col_end, selection_style);
         line.spans = new_spans;
     }
@@ -433,8 +487,7 @@ fn apply_selection_to_line(
 
     for span in &line.spans {
         let span_text: &str = span.content.as_ref();
-        let span_len = span_text.chars().count();
-        let span_end = current_col + span_len;
+        let span_end = current_col + span_text.width();
 
         if span_end <= col_start || current_col >= col_end {
             result.push(span.clone());
@@ -444,13 +497,12 @@ fn apply_selection_to_line(
                 span.style.patch(selection_style),
             ));
         } else {
-            let chars: Vec<char> = span_text.chars().collect();
             let mut before = String::new();
             let mut selected = String::new();
             let mut after = String::new();
 
-            for (i, &ch) in chars.iter().enumerate() {
-                let char_col = current_col + i;
+            let mut char_col = current_col;
+            for ch in span_text.chars() {
                 if char_col < col_start {
                     before.push(ch);
                 } else if char_col < col_end {
@@ -458,6 +510,7 @@ fn apply_selection_to_line(
                 } else {
                     after.push(ch);
                 }
+                char_col += ch.width().unwrap_or(0);
             }
 
             if !before.is_empty() {
@@ -489,7 +542,14 @@ fn render_scrollbar(buf: &mut Buffer, area: Rect, top: usize, visible: usize, to
     scrollbar.render(area, buf, &mut state);
 }
 
-fn composer_height(input: &str, width: u16, available_height: u16, prompt: &str) -> u16 {
+/// Rows needed for `input`, capped by `max_lines` and the space available.
+fn composer_height(
+    input: &str,
+    width: u16,
+    available_height: u16,
+    max_lines: usize,
+    prompt: &str,
+) -> u16 {
     let prompt_width = prompt.width();
     let prompt_width_u16 = u16::try_from(prompt_width).unwrap_or(u16::MAX);
     let content_width = usize::from(width.saturating_sub(prompt_width_u16).max(1));
@@ -497,7 +557,7 @@ fn composer_height(input: &str, width: u16, available_height: u16, prompt: &str)
     if line_count == 0 {
         line_count = 1;
     }
-    let max_height = usize::from(available_height.clamp(1, 8));
+    let max_height = usize::from(available_height).min(max_lines).max(1);
     line_count.clamp(1, max_height).try_into().unwrap_or(1)
 }
 
@@ -646,8 +706,72 @@ fn wrap_text(text: &str, width: usize) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::pad_lines_to_bottom;
-    use ratatui::text::Line;
+    use super::{ApprovalWidget, Renderable, composer_height, layout_input, pad_lines_to_bottom};
+    use crate::tui::approval::ApprovalRequest;
+    use ratatui::{buffer::Buffer, layout::Rect, text::Line};
+
+    fn render_approval(request: &ApprovalRequest, expanded: bool) -> String {
+        let area = Rect::new(0, 0, 110, 80);
+        let mut buf = Buffer::empty(area);
+        ApprovalWidget::with_expanded(request, 0, "{}", expanded).render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn approval_widget_truncates_diff_until_expanded() {
+        let mut diff = String::from("--- a/f.txt\n+++ b/f.txt\n@@ -1,20 +1,20 @@\n");
+        for n in 1..=20 {
+            diff.push_str(&format!("-old{n}\n+new{n}\n"));
+        }
+        let request =
+            ApprovalRequest::new("id", "edit_file", &serde_json::json!({})).with_diff(Some(diff));
+
+        let collapsed = render_approval(&request, false);
+        assert!(collapsed.contains("Changes:"));
+        assert!(collapsed.contains("+new1 "));
+        assert!(!collapsed.contains("+new20"));
+        assert!(collapsed.contains("(29 more lines, press Ctrl+E to expand)"));
+
+        let expanded = render_approval(&request, true);
+        assert!(expanded.contains("+new20"));
+        assert!(!expanded.contains("more lines"));
+    }
+
+    #[test]
+    fn composer_height_respects_line_cap_and_available_space() {
+        let input = (1..=20)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(composer_height(&input, 40, 30, 8, "> "), 8);
+        assert_eq!(composer_height(&input, 40, 30, 12, "> "), 12);
+        assert_eq!(composer_height(&input, 40, 5, 12, "> "), 5);
+        assert_eq!(composer_height("short", 40, 30, 12, "> "), 1);
+    }
+
+    #[test]
+    fn layout_input_scrolls_to_keep_cursor_visible() {
+        let input = (1..=20)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cursor = input.chars().count();
+        let (visible, row, _) = layout_input(&input, cursor, 10, 5);
+        assert_eq!(visible.len(), 5);
+        assert_eq!(visible.last().map(String::as_str), Some("20"));
+        assert_eq!(row, 4);
+
+        let (visible, row, _) = layout_input(&input, 0, 10, 5);
+        assert_eq!(visible.first().map(String::as_str), Some("1"));
+        assert_eq!(row, 0);
+    }
 
     #[test]
     fn pad_lines_to_bottom_noop_when_already_filled() {
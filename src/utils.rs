@@ -83,6 +83,63 @@ pub fn truncate_to_boundary(s: &str, max_len: usize) -> &str {
     &s[..idx]
 }
 
+/// Top-level directory of the git repository or worktree containing `dir`, or
+/// `None` outside a repository or when git is unavailable.
+#[must_use]
+pub fn git_toplevel(dir: &Path) -> Option<PathBuf> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!root.is_empty()).then(|| PathBuf::from(root))
+}
+
+/// Run `git diff` in `cwd`: the index when `staged`, `base...HEAD` when a
+/// base is given, optionally limited to `path`.
+pub fn git_diff(
+    cwd: &Path,
+    staged: bool,
+    base: Option<&str>,
+    path: Option<&Path>,
+) -> Result<String> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.arg("diff").current_dir(cwd);
+    if staged {
+        cmd.arg("--cached");
+    }
+    if let Some(base) = base {
+        cmd.arg(format!("{base}...HEAD"));
+    }
+    if let Some(path) = path {
+        cmd.arg("--").arg(path);
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run git diff. Is git installed? ({e})"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git diff failed: {}", stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Cut a diff to at most `max_chars` bytes, marking where it was truncated.
+#[must_use]
+pub fn truncate_diff(mut diff: String, max_chars: usize) -> String {
+    if diff.len() > max_chars {
+        let keep = truncate_to_boundary(&diff, max_chars).len();
+        diff.truncate(keep);
+        diff.push_str("\n...[truncated]\n");
+    }
+    diff
+}
+
 /// Estimate the total character count across message content blocks.
 #[must_use]
 pub fn estimate_message_chars(messages: &[Message]) -> usize {
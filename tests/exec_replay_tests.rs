@@ -0,0 +1,38 @@
+//! Offline `axiom exec` runs against recorded API responses (`AXIOM_REPLAY`).
+
+#[cfg(test)]
+mod exec_replay_tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn exec_replays_cassette_without_network() -> Result<(), std::io::Error> {
+        let home = tempfile::tempdir()?;
+        let config = home.path().join("config.toml");
+        std::fs::write(&config, "base_url = \"http://127.0.0.1:9\"\n")?;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_axiom"))
+            .arg("--config")
+            .arg(&config)
+            .args(["exec", "Say hello"])
+            .env("HOME", home.path())
+            .env("AXIOM_REPLAY", fixture("exec_hello.jsonl"))
+            .env_remove("AXIOM_API_KEY")
+            .env_remove("MINIMAX_API_KEY")
+            .current_dir(home.path())
+            .output()?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "stderr: {stderr}");
+        assert_eq!(stdout.trim(), "Hello from a recorded response.");
+        Ok(())
+    }
+}